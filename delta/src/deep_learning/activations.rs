@@ -81,6 +81,75 @@ fn name(&self) -> &str {
     fn initialize(&self, input_units: Ix) -> f32;
 }
 
+/// A struct representing the Exponential Linear Unit (ELU) activation function.
+#[derive(Debug)]
+pub struct EluActivation {
+    alpha: f32,
+}
+
+impl Default for EluActivation {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl EluActivation {
+    /// Creates a new instance of `EluActivation` with the given alpha value.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The saturation value for negative inputs.
+    #[inline(always)]
+    pub fn new(alpha: f32) -> Self {
+        Self { alpha }
+    }
+}
+
+impl Activation for EluActivation {
+    /// Applies ELU activation to the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor after applying ELU activation.
+    #[inline(always)]
+    fn activate(&self, input: &Tensor) -> Tensor {
+        let alpha = self.alpha;
+        input.map(|x| if x > 0.0 { x } else { alpha * x.exp_m1() })
+    }
+
+    /// Computes the derivative of ELU activation for the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// A tensor representing the derivative of ELU activation.
+    #[inline(always)]
+    fn derivative(&self, input: &Tensor) -> Tensor {
+        let alpha = self.alpha;
+        input.map(|x| if x > 0.0 { 1.0 } else { alpha * x.exp() })
+    }
+
+    /// Initializes the activation function with the given input units.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_units` - The number of input units.
+    ///
+    /// # Returns
+    ///
+    /// The standard deviation to use for weight initialization.
+    fn initialize(&self, input_units: Ix) -> f32 {
+        (2.0 / input_units as f32).sqrt()
+    }
+}
+
 /// A struct representing the Gaussian Error Linear Unit (GeLU) activation function.
 #[derive(Debug)]
 pub struct GeluActivation;
@@ -173,6 +242,160 @@ fn initialize(&self, input_units: Ix) -> f32 {
     }
 }
 
+/// A struct representing the Hard Sigmoid activation function.
+#[derive(Debug)]
+pub struct HardSigmoidActivation;
+
+impl Default for HardSigmoidActivation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HardSigmoidActivation {
+    /// Creates a new instance of `HardSigmoidActivation`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Applies the piecewise linear approximation `clamp(x / 6 + 0.5, 0, 1)` of the sigmoid.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The input value.
+    ///
+    /// # Returns
+    ///
+    /// The output value after applying Hard Sigmoid.
+    fn hard_sigmoid(x: f32) -> f32 {
+        (x / 6.0 + 0.5).clamp(0.0, 1.0)
+    }
+}
+
+impl Activation for HardSigmoidActivation {
+    /// Applies Hard Sigmoid activation to the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor after applying Hard Sigmoid activation.
+    #[inline(always)]
+    fn activate(&self, input: &Tensor) -> Tensor {
+        input.map(Self::hard_sigmoid)
+    }
+
+    /// Computes the derivative of Hard Sigmoid activation for the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// A tensor representing the derivative of Hard Sigmoid activation.
+    #[inline(always)]
+    fn derivative(&self, input: &Tensor) -> Tensor {
+        input.map(|x| if x > -3.0 && x < 3.0 { 1.0 / 6.0 } else { 0.0 })
+    }
+
+    /// Initializes the activation function with the given input units.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_units` - The number of input units.
+    ///
+    /// # Returns
+    ///
+    /// The standard deviation to use for weight initialization.
+    fn initialize(&self, input_units: Ix) -> f32 {
+        (1.0 / input_units as f32).sqrt()
+    }
+}
+
+/// A struct representing the Hard Swish activation function.
+#[derive(Debug)]
+pub struct HardSwishActivation;
+
+impl Default for HardSwishActivation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HardSwishActivation {
+    /// Creates a new instance of `HardSwishActivation`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Computes the derivative of Hard Swish.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The input value.
+    ///
+    /// # Returns
+    ///
+    /// The derivative of Hard Swish at the input value.
+    fn hard_swish_derivative(x: f32) -> f32 {
+        if x <= -3.0 {
+            0.0
+        } else if x >= 3.0 {
+            1.0
+        } else {
+            (2.0 * x + 3.0) / 6.0
+        }
+    }
+}
+
+impl Activation for HardSwishActivation {
+    /// Applies Hard Swish activation to the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor after applying Hard Swish activation.
+    #[inline(always)]
+    fn activate(&self, input: &Tensor) -> Tensor {
+        input.map(|x| x * HardSigmoidActivation::hard_sigmoid(x))
+    }
+
+    /// Computes the derivative of Hard Swish activation for the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// A tensor representing the derivative of Hard Swish activation.
+    #[inline(always)]
+    fn derivative(&self, input: &Tensor) -> Tensor {
+        input.map(Self::hard_swish_derivative)
+    }
+
+    /// Initializes the activation function with the given input units.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_units` - The number of input units.
+    ///
+    /// # Returns
+    ///
+    /// The standard deviation to use for weight initialization.
+    fn initialize(&self, input_units: Ix) -> f32 {
+        (2.0 / input_units as f32).sqrt()
+    }
+}
+
 /// A struct representing the Leaky Rectified Linear Unit (Leaky ReLU) activation function.
 #[derive(Debug)]
 pub struct LeakyReluActivation {
@@ -191,8 +414,276 @@ pub fn new(alpha: f32) -> Self {
     }
 }
 
-impl Activation for LeakyReluActivation {
-    /// Applies Leaky ReLU activation to the input tensor.
+impl Activation for LeakyReluActivation {
+    /// Applies Leaky ReLU activation to the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor after applying Leaky ReLU activation.
+    #[inline(always)]
+    fn activate(&self, input: &Tensor) -> Tensor {
+        let alpha = self.alpha;
+        input.map(|x| if x > 0.0 { x } else { alpha * x })
+    }
+
+    /// Computes the derivative of Leaky ReLU activation for the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// A tensor representing the derivative of Leaky ReLU activation.
+    #[inline(always)]
+    fn derivative(&self, input: &Tensor) -> Tensor {
+        let alpha = self.alpha;
+        input.map(|x| if x > 0.0 { 1.0 } else { alpha })
+    }
+
+    /// Initializes the activation function with the given input units.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_units` - The number of input units.
+    ///
+    /// # Returns
+    ///
+    /// The standard deviation to use for weight initialization.
+    fn initialize(&self, input_units: Ix) -> f32 {
+        (2.0 / input_units as f32).sqrt()
+    }
+}
+
+/// A struct representing the Mish activation function.
+#[derive(Debug)]
+pub struct MishActivation;
+
+impl Default for MishActivation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MishActivation {
+    /// Creates a new instance of `MishActivation`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Computes the derivative of Mish.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The input value.
+    ///
+    /// # Returns
+    ///
+    /// The derivative of Mish at the input value.
+    fn mish_derivative(x: f32) -> f32 {
+        let tanh_softplus = SoftplusActivation::softplus(x).tanh();
+        tanh_softplus + x * SigmoidActivation::sigmoid(x) * (1.0 - tanh_softplus * tanh_softplus)
+    }
+}
+
+impl Activation for MishActivation {
+    /// Applies Mish activation to the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor after applying Mish activation.
+    #[inline(always)]
+    fn activate(&self, input: &Tensor) -> Tensor {
+        input.map(|x| x * SoftplusActivation::softplus(x).tanh())
+    }
+
+    /// Computes the derivative of Mish activation for the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// A tensor representing the derivative of Mish activation.
+    #[inline(always)]
+    fn derivative(&self, input: &Tensor) -> Tensor {
+        input.map(Self::mish_derivative)
+    }
+
+    /// Initializes the activation function with the given input units.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_units` - The number of input units.
+    ///
+    /// # Returns
+    ///
+    /// The standard deviation to use for weight initialization.
+    fn initialize(&self, input_units: Ix) -> f32 {
+        (2.0 / input_units as f32).sqrt()
+    }
+}
+
+/// A struct representing the Parametric Rectified Linear Unit (PReLU) activation function.
+///
+/// The slope is fixed when used as an activation; use the `PReLU` layer to learn it.
+#[derive(Debug)]
+pub struct PreluActivation {
+    /// The parameter `alpha` for the PReLU activation function.
+    pub alpha: f32,
+}
+
+impl PreluActivation {
+    /// Creates a new instance of `PreluActivation` with the specified `alpha` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The parameter for the negative slope.
+    pub fn new(alpha: f32) -> Self {
+        Self { alpha }
+    }
+}
+
+impl Activation for PreluActivation {
+    /// Applies PReLU activation to the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor after applying PReLU activation.
+    fn activate(&self, input: &Tensor) -> Tensor {
+        let alpha = self.alpha;
+        input.map(|x| if x > 0.0 { x } else { alpha * x })
+    }
+
+    /// Computes the derivative of PReLU activation for the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// A tensor representing the derivative of PReLU activation.
+    fn derivative(&self, input: &Tensor) -> Tensor {
+        let alpha = self.alpha;
+        input.map(|x| if x > 0.0 { 1.0 } else { alpha })
+    }
+
+    /// Initializes the weights for the Softmax activation function.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_units` - The number of input units.
+    ///
+    /// # Returns
+    ///
+    /// The initial weight value for the Softmax activation function.
+    fn initialize(&self, input_units: Ix) -> f32 {
+        (1.0 / input_units as f32).sqrt()
+    }
+}
+
+/// A struct representing the Rectified Linear Unit (ReLU) activation function.
+#[derive(Debug)]
+pub struct ReluActivation;
+
+impl Default for ReluActivation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReluActivation {
+    /// Creates a new instance of `ReluActivation`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Activation for ReluActivation {
+    /// Applies ReLU activation to the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor after applying ReLU activation.
+    #[inline(always)]
+    fn activate(&self, input: &Tensor) -> Tensor {
+        input.map_max(0.0)
+    }
+
+    /// Computes the derivative of ReLU activation for the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// A tensor representing the derivative of ReLU activation.
+    #[inline(always)]
+    fn derivative(&self, input: &Tensor) -> Tensor {
+        input.map(|x| if x > 0.0 { 1.0 } else { 0.0 })
+    }
+
+    /// Initializes the activation function with the given input units.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_units` - The number of input units.
+    ///
+    /// # Returns
+    ///
+    /// The standard deviation to use for weight initialization.
+    fn initialize(&self, input_units: Ix) -> f32 {
+        (2.0 / input_units as f32).sqrt()
+    }
+}
+
+/// A struct representing the Scaled Exponential Linear Unit (SELU) activation function.
+#[derive(Debug)]
+pub struct SeluActivation;
+
+impl Default for SeluActivation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SeluActivation {
+    /// Creates a new instance of `SeluActivation`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// The negative saturation value from the self-normalizing networks paper.
+    const ALPHA: f32 = 1.673_263_2;
+
+    /// The output scale from the self-normalizing networks paper.
+    const SCALE: f32 = 1.050_701;
+}
+
+impl Activation for SeluActivation {
+    /// Applies SELU activation to the input tensor.
     ///
     /// # Arguments
     ///
@@ -200,14 +691,13 @@ impl Activation for LeakyReluActivation {
     ///
     /// # Returns
     ///
-    /// The output tensor after applying Leaky ReLU activation.
+    /// The output tensor after applying SELU activation.
     #[inline(always)]
     fn activate(&self, input: &Tensor) -> Tensor {
-        let alpha = self.alpha;
-        input.map(|x| if x > 0.0 { x } else { alpha * x })
+        input.map(|x| Self::SCALE * if x > 0.0 { x } else { Self::ALPHA * x.exp_m1() })
     }
 
-    /// Computes the derivative of Leaky ReLU activation for the input tensor.
+    /// Computes the derivative of SELU activation for the input tensor.
     ///
     /// # Arguments
     ///
@@ -215,11 +705,10 @@ fn activate(&self, input: &Tensor) -> Tensor {
     ///
     /// # Returns
     ///
-    /// A tensor representing the derivative of Leaky ReLU activation.
+    /// A tensor representing the derivative of SELU activation.
     #[inline(always)]
     fn derivative(&self, input: &Tensor) -> Tensor {
-        let alpha = self.alpha;
-        input.map(|x| if x > 0.0 { 1.0 } else { alpha })
+        input.map(|x| Self::SCALE * if x > 0.0 { 1.0 } else { Self::ALPHA * x.exp() })
     }
 
     /// Initializes the activation function with the given input units.
@@ -230,32 +719,50 @@ fn derivative(&self, input: &Tensor) -> Tensor {
     ///
     /// # Returns
     ///
-    /// The standard deviation to use for weight initialization.
+    /// The standard deviation to use for weight initialization (LeCun normal).
     fn initialize(&self, input_units: Ix) -> f32 {
-        (2.0 / input_units as f32).sqrt()
+        (1.0 / input_units as f32).sqrt()
     }
 }
 
-/// A struct representing the Parametric Rectified Linear Unit (PReLU) activation function.
+/// A struct representing the Sigmoid activation function.
 #[derive(Debug)]
-pub struct PreluActivation {
-    /// The learnable parameter `alpha` for the PReLU activation function.
-    pub alpha: f32,
+pub struct SigmoidActivation;
+
+impl Default for SigmoidActivation {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl PreluActivation {
-    /// Creates a new instance of `PreluActivation` with the specified `alpha` parameter.
+impl SigmoidActivation {
+    /// Creates a new instance of `SigmoidActivation`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Applies the logistic function without overflowing for large negative inputs.
     ///
     /// # Arguments
     ///
-    /// * `alpha` - The parameter for the negative slope.
-    pub fn new(alpha: f32) -> Self {
-        Self { alpha }
+    /// * `x` - The input value.
+    ///
+    /// # Returns
+    ///
+    /// The output value after applying the sigmoid.
+    pub(crate) fn sigmoid(x: f32) -> f32 {
+        if x >= 0.0 {
+            1.0 / (1.0 + (-x).exp())
+        } else {
+            let e = x.exp();
+            e / (1.0 + e)
+        }
     }
 }
 
-impl Activation for PreluActivation {
-    /// Applies PReLU activation to the input tensor.
+impl Activation for SigmoidActivation {
+    /// Applies Sigmoid activation to the input tensor.
     ///
     /// # Arguments
     ///
@@ -263,13 +770,13 @@ impl Activation for PreluActivation {
     ///
     /// # Returns
     ///
-    /// The output tensor after applying PReLU activation.
+    /// The output tensor after applying Sigmoid activation.
+    #[inline(always)]
     fn activate(&self, input: &Tensor) -> Tensor {
-        let alpha = self.alpha;
-        input.map(|x| if x > 0.0 { x } else { alpha * x })
+        input.map(Self::sigmoid)
     }
 
-    /// Computes the derivative of PReLU activation for the input tensor.
+    /// Computes the derivative of Sigmoid activation for the input tensor.
     ///
     /// # Arguments
     ///
@@ -277,13 +784,16 @@ fn activate(&self, input: &Tensor) -> Tensor {
     ///
     /// # Returns
     ///
-    /// A tensor representing the derivative of PReLU activation.
+    /// A tensor representing the derivative of Sigmoid activation.
+    #[inline(always)]
     fn derivative(&self, input: &Tensor) -> Tensor {
-        let alpha = self.alpha;
-        input.map(|x| if x > 0.0 { 1.0 } else { alpha })
+        input.map(|x| {
+            let y = Self::sigmoid(x);
+            y * (1.0 - y)
+        })
     }
 
-    /// Initializes the weights for the Softmax activation function.
+    /// Initializes the activation function with the given input units.
     ///
     /// # Arguments
     ///
@@ -291,32 +801,32 @@ fn derivative(&self, input: &Tensor) -> Tensor {
     ///
     /// # Returns
     ///
-    /// The initial weight value for the Softmax activation function.
+    /// The standard deviation to use for weight initialization.
     fn initialize(&self, input_units: Ix) -> f32 {
         (1.0 / input_units as f32).sqrt()
     }
 }
 
-/// A struct representing the Rectified Linear Unit (ReLU) activation function.
+/// A struct representing the Sigmoid Linear Unit (SiLU), also known as Swish, activation function.
 #[derive(Debug)]
-pub struct ReluActivation;
+pub struct SiluActivation;
 
-impl Default for ReluActivation {
+impl Default for SiluActivation {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl ReluActivation {
-    /// Creates a new instance of `ReluActivation`.
+impl SiluActivation {
+    /// Creates a new instance of `SiluActivation`.
     #[inline(always)]
     pub fn new() -> Self {
         Self
     }
 }
 
-impl Activation for ReluActivation {
-    /// Applies ReLU activation to the input tensor.
+impl Activation for SiluActivation {
+    /// Applies SiLU activation to the input tensor.
     ///
     /// # Arguments
     ///
@@ -324,13 +834,13 @@ impl Activation for ReluActivation {
     ///
     /// # Returns
     ///
-    /// The output tensor after applying ReLU activation.
+    /// The output tensor after applying SiLU activation.
     #[inline(always)]
     fn activate(&self, input: &Tensor) -> Tensor {
-        input.map_max(0.0)
+        input.map(|x| x * SigmoidActivation::sigmoid(x))
     }
 
-    /// Computes the derivative of ReLU activation for the input tensor.
+    /// Computes the derivative of SiLU activation for the input tensor.
     ///
     /// # Arguments
     ///
@@ -338,10 +848,13 @@ fn activate(&self, input: &Tensor) -> Tensor {
     ///
     /// # Returns
     ///
-    /// A tensor representing the derivative of ReLU activation.
+    /// A tensor representing the derivative of SiLU activation.
     #[inline(always)]
     fn derivative(&self, input: &Tensor) -> Tensor {
-        input.map(|x| if x > 0.0 { 1.0 } else { 0.0 })
+        input.map(|x| {
+            let y = SigmoidActivation::sigmoid(x);
+            y + x * y * (1.0 - y)
+        })
     }
 
     /// Initializes the activation function with the given input units.
@@ -358,6 +871,9 @@ fn initialize(&self, input_units: Ix) -> f32 {
     }
 }
 
+/// The Swish activation function, an alias of [`SiluActivation`].
+pub type SwishActivation = SiluActivation;
+
 /// A struct representing the Softmax activation function.
 #[derive(Debug)]
 pub struct SoftmaxActivation;
@@ -472,12 +988,251 @@ fn initialize(&self, input_units: Ix) -> f32 {
     }
 }
 
+/// A struct representing the Softplus activation function.
+#[derive(Debug)]
+pub struct SoftplusActivation;
+
+impl Default for SoftplusActivation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SoftplusActivation {
+    /// Creates a new instance of `SoftplusActivation`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Computes `ln(1 + e^x)` as `max(x, 0) + ln(1 + e^-|x|)` to avoid overflow.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The input value.
+    ///
+    /// # Returns
+    ///
+    /// The output value after applying Softplus.
+    fn softplus(x: f32) -> f32 {
+        x.max(0.0) + (-x.abs()).exp().ln_1p()
+    }
+}
+
+impl Activation for SoftplusActivation {
+    /// Applies Softplus activation to the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor after applying Softplus activation.
+    #[inline(always)]
+    fn activate(&self, input: &Tensor) -> Tensor {
+        input.map(Self::softplus)
+    }
+
+    /// Computes the derivative of Softplus activation for the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// A tensor representing the derivative of Softplus activation.
+    #[inline(always)]
+    fn derivative(&self, input: &Tensor) -> Tensor {
+        input.map(SigmoidActivation::sigmoid)
+    }
+
+    /// Initializes the activation function with the given input units.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_units` - The number of input units.
+    ///
+    /// # Returns
+    ///
+    /// The standard deviation to use for weight initialization.
+    fn initialize(&self, input_units: Ix) -> f32 {
+        (1.0 / input_units as f32).sqrt()
+    }
+}
+
+/// A struct representing the Softsign activation function.
+#[derive(Debug)]
+pub struct SoftsignActivation;
+
+impl Default for SoftsignActivation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SoftsignActivation {
+    /// Creates a new instance of `SoftsignActivation`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Activation for SoftsignActivation {
+    /// Applies Softsign activation to the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor after applying Softsign activation.
+    #[inline(always)]
+    fn activate(&self, input: &Tensor) -> Tensor {
+        input.map(|x| x / (1.0 + x.abs()))
+    }
+
+    /// Computes the derivative of Softsign activation for the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// A tensor representing the derivative of Softsign activation.
+    #[inline(always)]
+    fn derivative(&self, input: &Tensor) -> Tensor {
+        input.map(|x| 1.0 / (1.0 + x.abs()).powi(2))
+    }
+
+    /// Initializes the activation function with the given input units.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_units` - The number of input units.
+    ///
+    /// # Returns
+    ///
+    /// The standard deviation to use for weight initialization.
+    fn initialize(&self, input_units: Ix) -> f32 {
+        (1.0 / input_units as f32).sqrt()
+    }
+}
+
+/// A struct representing the Hyperbolic Tangent (Tanh) activation function.
+#[derive(Debug)]
+pub struct TanhActivation;
+
+impl Default for TanhActivation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TanhActivation {
+    /// Creates a new instance of `TanhActivation`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Activation for TanhActivation {
+    /// Applies Tanh activation to the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor after applying Tanh activation.
+    #[inline(always)]
+    fn activate(&self, input: &Tensor) -> Tensor {
+        input.map(|x| x.tanh())
+    }
+
+    /// Computes the derivative of Tanh activation for the input tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// A tensor representing the derivative of Tanh activation.
+    #[inline(always)]
+    fn derivative(&self, input: &Tensor) -> Tensor {
+        input.map(|x| 1.0 - x.tanh().powi(2))
+    }
+
+    /// Initializes the activation function with the given input units.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_units` - The number of input units.
+    ///
+    /// # Returns
+    ///
+    /// The standard deviation to use for weight initialization.
+    fn initialize(&self, input_units: Ix) -> f32 {
+        (1.0 / input_units as f32).sqrt()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ndarray::{Array, IxDyn, Shape};
 
     use super::*;
 
+    /// Inputs away from the kinks of the piecewise activations.
+    const FINITE_DIFFERENCE_INPUTS: [f32; 8] = [-5.0, -2.5, -1.2, -0.4, 0.3, 1.1, 2.2, 4.5];
+
+    /// Checks the derivative of an element-wise activation against central finite differences.
+    fn assert_derivative_matches(activation: &dyn Activation) {
+        let epsilon = 1e-2;
+        let shape = Shape::from(IxDyn(&[2, 4]));
+        let input = Tensor::new(FINITE_DIFFERENCE_INPUTS.to_vec(), shape.clone());
+        let plus = activation.activate(&input.add_scalar(epsilon));
+        let minus = activation.activate(&input.add_scalar(-epsilon));
+        let derivative = activation.derivative(&input);
+
+        for (i, x) in FINITE_DIFFERENCE_INPUTS.iter().enumerate() {
+            let numeric = (plus.to_vec()[i] - minus.to_vec()[i]) / (2.0 * epsilon);
+            let analytic = derivative.to_vec()[i];
+            assert!(
+                (numeric - analytic).abs() < 1e-3,
+                "{} at {}: numeric = {}, analytic = {}",
+                activation.name(),
+                x,
+                numeric,
+                analytic
+            );
+        }
+        assert_eq!(derivative.data.shape(), &[2, 4]);
+    }
+
+    /// Checks that an activation stays finite for extreme inputs.
+    fn assert_finite_for_extremes(activation: &dyn Activation) {
+        let input = Tensor::new(vec![-1000.0, -88.0, 88.0, 1000.0], Shape::from(IxDyn(&[1, 4])));
+        assert!(activation.activate(&input).data.iter().all(|x| x.is_finite()));
+        assert!(activation.derivative(&input).data.iter().all(|x| x.is_finite()));
+    }
+
+    #[test]
+    fn test_elu_derivative() {
+        let elu = EluActivation::new(1.0);
+        assert_derivative_matches(&elu);
+        assert_finite_for_extremes(&elu);
+        let input = Tensor::new(vec![-1000.0, 2.0], Shape::from(IxDyn(&[1, 2])));
+        assert_eq!(elu.activate(&input).to_vec(), vec![-1.0, 2.0]);
+    }
+
     #[test]
     fn test_gelu_activate() {
         let input = Tensor::new(vec![1.0, 2.0, 3.0], Shape::from(IxDyn(&[1, 3])));
@@ -639,4 +1394,76 @@ fn test_softmax_initialize() {
         let softmax = SoftmaxActivation::new();
         assert_eq!(softmax.initialize(3), 0.57735026);
     }
+
+    #[test]
+    fn test_hard_sigmoid_derivative() {
+        let hard_sigmoid = HardSigmoidActivation::new();
+        assert_derivative_matches(&hard_sigmoid);
+        let input = Tensor::new(vec![-4.0, 0.0, 4.0], Shape::from(IxDyn(&[1, 3])));
+        assert_eq!(hard_sigmoid.activate(&input).to_vec(), vec![0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_hard_swish_derivative() {
+        let hard_swish = HardSwishActivation::new();
+        assert_derivative_matches(&hard_swish);
+        let input = Tensor::new(vec![-4.0, 0.0, 4.0], Shape::from(IxDyn(&[1, 3])));
+        assert_eq!(hard_swish.activate(&input).to_vec(), vec![-0.0, 0.0, 4.0]);
+    }
+
+    #[test]
+    fn test_mish_derivative() {
+        let mish = MishActivation::new();
+        assert_derivative_matches(&mish);
+        assert_finite_for_extremes(&mish);
+    }
+
+    #[test]
+    fn test_selu_derivative() {
+        let selu = SeluActivation::new();
+        assert_derivative_matches(&selu);
+        assert_finite_for_extremes(&selu);
+        assert_eq!(selu.initialize(4), 0.5);
+    }
+
+    #[test]
+    fn test_sigmoid_derivative() {
+        let sigmoid = SigmoidActivation::new();
+        assert_derivative_matches(&sigmoid);
+        assert_finite_for_extremes(&sigmoid);
+        let input = Tensor::new(vec![-1000.0, 0.0, 1000.0], Shape::from(IxDyn(&[1, 3])));
+        assert_eq!(sigmoid.activate(&input).to_vec(), vec![0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_silu_derivative() {
+        let silu = SwishActivation::new();
+        assert_derivative_matches(&silu);
+        assert_finite_for_extremes(&silu);
+        assert_eq!(silu.name(), "SiluActivation");
+    }
+
+    #[test]
+    fn test_softplus_derivative() {
+        let softplus = SoftplusActivation::new();
+        assert_derivative_matches(&softplus);
+        assert_finite_for_extremes(&softplus);
+        let input = Tensor::new(vec![-1000.0, 1000.0], Shape::from(IxDyn(&[1, 2])));
+        assert_eq!(softplus.activate(&input).to_vec(), vec![0.0, 1000.0]);
+    }
+
+    #[test]
+    fn test_softsign_derivative() {
+        let softsign = SoftsignActivation::new();
+        assert_derivative_matches(&softsign);
+        assert_finite_for_extremes(&softsign);
+    }
+
+    #[test]
+    fn test_tanh_derivative() {
+        let tanh = TanhActivation::new();
+        assert_derivative_matches(&tanh);
+        assert_finite_for_extremes(&tanh);
+        assert_eq!(tanh.initialize(4), 0.5);
+    }
 }
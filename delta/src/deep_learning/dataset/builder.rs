@@ -0,0 +1,461 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use super::{Dataset, Downloader, cache};
+use crate::deep_learning::rng::rng_from_seed;
+
+/// The seed of the stratified splits made by loaders that do not take `LoadOptions`, such as
+/// `DatasetOps::load_val`.
+pub const DEFAULT_SPLIT_SEED: u64 = 42;
+
+/// The split of a dataset to load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Split {
+    #[default]
+    Train,
+    Test,
+    Val,
+}
+
+/// Options used by `DatasetBuilder` to load a dataset.
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    /// The directory the dataset files are read from and downloaded to.
+    pub root: Option<PathBuf>,
+    /// The split to load.
+    pub split: Split,
+    /// Whether missing files may be downloaded.
+    pub download: bool,
+    /// Whether downloads are disabled even if `download` is set.
+    pub offline: bool,
+    /// SHA-256 hashes by file name that override the known hashes of the dataset files.
+    pub checksums: HashMap<String, String>,
+    /// The downloader used for missing files.
+    pub downloader: Downloader,
+    /// The fraction of the training data held out for `Split::Val`.
+    pub validation_split: f32,
+    /// The seed of a stratified random validation split. Without a seed, the last samples are
+    /// held out.
+    pub split_seed: Option<u64>,
+    /// The fraction of the samples held out for `Split::Test` by datasets without a separate
+    /// test set, such as ImageNetV2.
+    pub test_split: f32,
+}
+
+impl LoadOptions {
+    /// Returns the directory of the dataset files.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The subdirectory of the cache directory used when no root is configured.
+    ///
+    /// # Returns
+    ///
+    /// The configured root, or `name` inside `cache::data_dir()`.
+    pub fn resolve_root(&self, name: &str) -> PathBuf {
+        self.root.clone().unwrap_or_else(|| cache::data_dir().join(name))
+    }
+
+    /// Returns whether downloads are disabled by these options, `cache::set_offline` or
+    /// `DELTA_OFFLINE`.
+    pub fn is_offline(&self) -> bool {
+        self.offline || cache::is_offline()
+    }
+
+    /// Returns the SHA-256 hash a dataset file is verified against.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    /// * `known` - The known hash of the file, if any.
+    ///
+    /// # Returns
+    ///
+    /// The hash configured in `checksums` for the file name, or `known`.
+    pub fn expected_checksum<'a>(&'a self, path: &Path, known: Option<&'a str>) -> Option<&'a str> {
+        path.file_name()
+            .and_then(|name| self.checksums.get(name.to_string_lossy().as_ref()))
+            .map(String::as_str)
+            .or(known)
+    }
+
+    /// Returns the index of the first sample of the training data held out for `Split::Val`.
+    ///
+    /// # Arguments
+    ///
+    /// * `total_samples` - The number of samples in the training data.
+    ///
+    /// # Returns
+    ///
+    /// The index of the first validation sample, or an error if `Split::Val` is requested without
+    /// a validation split.
+    pub fn validation_start(&self, total_samples: usize) -> Result<usize, String> {
+        if self.split == Split::Val && self.validation_split <= 0.0 {
+            return Err("Split::Val requires a validation split greater than 0".to_string());
+        }
+
+        let validation_size = (total_samples as f32 * self.validation_split).round() as usize;
+        Ok(total_samples - validation_size)
+    }
+
+    /// Selects the part of the training data that belongs to the requested split.
+    ///
+    /// The last `validation_split` fraction of the samples forms `Split::Val`, and the remaining
+    /// samples form `Split::Train`. With a `split_seed`, `Split::Val` is instead a seeded random
+    /// `validation_split` fraction of the samples of each class.
+    ///
+    /// # Arguments
+    ///
+    /// * `train_data` - The full training data.
+    ///
+    /// # Returns
+    ///
+    /// The samples of the requested split, or an error if `Split::Val` is requested without a
+    /// validation split.
+    pub fn select_training_split(&self, train_data: Dataset) -> Result<Dataset, String> {
+        let total_samples = train_data.inputs.shape().raw_dim()[0];
+        let validation_start = self.validation_start(total_samples)?;
+        let (train, val) = match self.split_seed {
+            Some(seed) => {
+                train_data.stratified_split(self.validation_split, &mut rng_from_seed(seed))
+            }
+            None => train_data.split_at(validation_start),
+        };
+
+        match self.split {
+            Split::Val => Ok(val),
+            _ => Ok(train),
+        }
+    }
+
+    /// Returns an error if files are missing and downloading is disabled or offline mode is
+    /// enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `missing` - A description of the missing files.
+    ///
+    /// # Returns
+    ///
+    /// An error if the files cannot be downloaded.
+    pub fn ensure_download_allowed(&self, missing: &str) -> Result<(), String> {
+        if self.is_offline() {
+            Err(format!(
+                "{} not found and offline mode is enabled; place the file there or disable \
+                 offline mode with `offline(false)`, `cache::set_offline` or `{}`",
+                missing,
+                cache::OFFLINE_ENV
+            ))
+        } else if self.download {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} not found and downloading is disabled; enable it with `download(true)`",
+                missing
+            ))
+        }
+    }
+}
+
+/// A dataset that can be loaded from `LoadOptions`.
+pub trait LoadDataset: Sized {
+    /// The type of future returned by `load_with`.
+    type BuildFuture: Future<Output = Result<Self, String>> + Send;
+
+    /// Loads the dataset with the given options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options describing where and which split to load.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the loaded dataset.
+    fn load_with(options: LoadOptions) -> Self::BuildFuture;
+}
+
+/// A builder for loading a single split of a dataset from a chosen directory.
+#[derive(Debug, Clone)]
+pub struct DatasetBuilder<D: LoadDataset> {
+    options: LoadOptions,
+    _dataset: PhantomData<D>,
+}
+
+impl<D: LoadDataset> Default for DatasetBuilder<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: LoadDataset> DatasetBuilder<D> {
+    /// Creates a new `DatasetBuilder` for the training split, without downloading.
+    pub fn new() -> Self {
+        Self { options: LoadOptions::default(), _dataset: PhantomData }
+    }
+
+    /// Sets the directory the dataset files are read from and downloaded to.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The dataset directory.
+    pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.options.root = Some(root.into());
+        self
+    }
+
+    /// Sets the split to load.
+    ///
+    /// # Arguments
+    ///
+    /// * `split` - The split to load.
+    pub fn split(mut self, split: Split) -> Self {
+        self.options.split = split;
+        self
+    }
+
+    /// Sets whether missing files may be downloaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `download` - Whether to download missing files.
+    pub fn download(mut self, download: bool) -> Self {
+        self.options.download = download;
+        self
+    }
+
+    /// Sets whether downloads are disabled, so that missing files are reported as errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `offline` - Whether to disable downloads.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.options.offline = offline;
+        self
+    }
+
+    /// Sets the SHA-256 hash a dataset file is verified against, replacing its known hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the file, such as `train-images-idx3-ubyte.gz`.
+    /// * `sha256` - The hexadecimal SHA-256 hash of the file.
+    pub fn checksum(mut self, file_name: impl Into<String>, sha256: impl Into<String>) -> Self {
+        self.options.checksums.insert(file_name.into(), sha256.into());
+        self
+    }
+
+    /// Sets the downloader used for missing files, to configure retries or report progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `downloader` - The downloader to use.
+    pub fn downloader(mut self, downloader: Downloader) -> Self {
+        self.options.downloader = downloader;
+        self
+    }
+
+    /// Sets the fraction of the training data held out for `Split::Val`.
+    ///
+    /// # Arguments
+    ///
+    /// * `validation_split` - The fraction of the training data, in `[0, 1)`.
+    pub fn validation_split(mut self, validation_split: f32) -> Self {
+        if !(0.0..1.0).contains(&validation_split) {
+            panic!("Validation split must be in [0, 1).");
+        }
+        self.options.validation_split = validation_split;
+        self
+    }
+
+    /// Holds out a stratified random validation split chosen with a seed, instead of the last
+    /// samples of the training data.
+    ///
+    /// The same seed selects the same samples, so the `Split::Train` and `Split::Val` datasets
+    /// loaded with it do not overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the split.
+    pub fn split_seed(mut self, seed: u64) -> Self {
+        self.options.split_seed = Some(seed);
+        self
+    }
+
+    /// Sets the fraction of the samples held out for `Split::Test` by datasets without a
+    /// separate test set, such as ImageNetV2.
+    ///
+    /// # Arguments
+    ///
+    /// * `test_split` - The fraction of the samples, in `(0, 1)`.
+    pub fn test_split(mut self, test_split: f32) -> Self {
+        if test_split <= 0.0 || test_split >= 1.0 {
+            panic!("Test split must be in (0, 1).");
+        }
+        self.options.test_split = test_split;
+        self
+    }
+
+    /// Returns the options the dataset will be loaded with.
+    pub fn options(&self) -> &LoadOptions {
+        &self.options
+    }
+
+    /// Loads the dataset.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the loaded dataset, or an error if the files are missing or
+    /// invalid.
+    pub fn build(self) -> D::BuildFuture {
+        D::load_with(self.options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{IxDyn, Shape};
+    use serial_test::serial;
+
+    use super::*;
+    use crate::deep_learning::tensor_ops::Tensor;
+
+    /// A dataset that loads nothing, for testing the builder options.
+    struct Empty;
+
+    impl LoadDataset for Empty {
+        type BuildFuture = std::future::Ready<Result<Self, String>>;
+
+        fn load_with(_options: LoadOptions) -> Self::BuildFuture {
+            std::future::ready(Ok(Empty))
+        }
+    }
+
+    fn dataset(size: usize) -> Dataset {
+        let values: Vec<f32> = (0..size).map(|x| x as f32).collect();
+        Dataset::new(
+            Tensor::new(values.clone(), Shape::from(IxDyn(&[size, 1]))),
+            Tensor::new(values, Shape::from(IxDyn(&[size, 1]))),
+        )
+    }
+
+    #[test]
+    fn test_select_training_split_holds_out_validation_tail() {
+        let options = LoadOptions { validation_split: 0.2, ..Default::default() };
+        let train = options.select_training_split(dataset(10)).unwrap();
+        let val = LoadOptions { split: Split::Val, ..options }
+            .select_training_split(dataset(10))
+            .unwrap();
+
+        assert_eq!(train.labels.to_vec(), (0..8).map(|x| x as f32).collect::<Vec<_>>());
+        assert_eq!(val.labels.to_vec(), vec![8.0, 9.0]);
+    }
+
+    #[test]
+    fn test_select_training_split_with_seed_is_stratified_and_complementary() {
+        // Ten samples of class 0 followed by ten of class 1.
+        let labels: Vec<f32> = (0..20).map(|x| (x / 10) as f32).collect();
+        let inputs: Vec<f32> = (0..20).map(|x| x as f32).collect();
+        let data = || {
+            Dataset::new(
+                Tensor::new(inputs.clone(), Shape::from(IxDyn(&[20, 1]))),
+                Tensor::new(labels.clone(), Shape::from(IxDyn(&[20, 1]))),
+            )
+        };
+        let options =
+            LoadOptions { validation_split: 0.2, split_seed: Some(7), ..Default::default() };
+
+        let train = options.select_training_split(data()).unwrap();
+        let val = LoadOptions { split: Split::Val, ..options.clone() }
+            .select_training_split(data())
+            .unwrap();
+
+        let val_inputs = val.inputs.to_vec();
+        assert_eq!(val_inputs.iter().filter(|&&x| x < 10.0).count(), 2);
+        assert_eq!(val_inputs.iter().filter(|&&x| x >= 10.0).count(), 2);
+        assert_ne!(val_inputs, vec![16.0, 17.0, 18.0, 19.0]);
+        let mut all = [train.inputs.to_vec(), val_inputs].concat();
+        all.sort_by(f32::total_cmp);
+        assert_eq!(all, inputs);
+    }
+
+    #[test]
+    fn test_select_training_split_requires_validation_split_for_val() {
+        let options = LoadOptions { split: Split::Val, ..Default::default() };
+        assert!(options.select_training_split(dataset(4)).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_ensure_download_allowed() {
+        let options = LoadOptions::default();
+        let err = options.ensure_download_allowed("mnist/train-images-idx3-ubyte.gz").unwrap_err();
+        assert!(err.contains("downloading is disabled"));
+        assert!(LoadOptions { download: true, ..options }.ensure_download_allowed("x").is_ok());
+    }
+
+    #[test]
+    #[serial]
+    fn test_ensure_download_allowed_reports_offline_mode() {
+        let options = LoadOptions { download: true, offline: true, ..Default::default() };
+        let err = options.ensure_download_allowed("mnist/train-images-idx3-ubyte.gz").unwrap_err();
+        assert!(err.contains("offline mode is enabled"));
+
+        cache::set_offline(Some(true));
+        let offline = LoadOptions { download: true, ..Default::default() }.is_offline();
+        cache::set_offline(None);
+        assert!(offline);
+    }
+
+    #[test]
+    fn test_expected_checksum_prefers_configured_hash() {
+        let mut options = LoadOptions::default();
+        options.checksums.insert("a.gz".to_string(), "abc".to_string());
+
+        assert_eq!(options.expected_checksum(Path::new("root/a.gz"), Some("def")), Some("abc"));
+        assert_eq!(options.expected_checksum(Path::new("root/b.gz"), Some("def")), Some("def"));
+        assert_eq!(options.expected_checksum(Path::new("root/b.gz"), None), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Validation split must be in [0, 1).")]
+    fn test_builder_rejects_invalid_validation_split() {
+        DatasetBuilder::<Empty>::new().validation_split(1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Test split must be in (0, 1).")]
+    fn test_builder_rejects_invalid_test_split() {
+        DatasetBuilder::<Empty>::new().test_split(0.0);
+    }
+}
@@ -0,0 +1,327 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use log::{debug, warn};
+use sha2::{Digest, Sha256};
+
+use super::LoadOptions;
+use crate::get_workspace_dir;
+
+/// The environment variable that sets the directory datasets are cached in.
+pub const DATA_DIR_ENV: &str = "DELTA_DATA_DIR";
+
+/// The environment variable that disables downloads when set to `1`, `true` or `yes`.
+pub const OFFLINE_ENV: &str = "DELTA_OFFLINE";
+
+/// The cache settings configured through the API, which take precedence over the environment.
+#[derive(Default)]
+struct CacheConfig {
+    data_dir: Option<PathBuf>,
+    offline: Option<bool>,
+}
+
+/// Returns the lazily initialized cache settings.
+fn config() -> &'static Mutex<CacheConfig> {
+    static CACHE_CONFIG: OnceLock<Mutex<CacheConfig>> = OnceLock::new();
+    CACHE_CONFIG.get_or_init(|| Mutex::new(CacheConfig::default()))
+}
+
+/// Sets the directory every dataset is cached in.
+///
+/// Each dataset uses a subdirectory named after it, unless a root is passed to its builder.
+///
+/// # Arguments
+///
+/// * `dir` - The cache directory, or `None` to fall back to `DELTA_DATA_DIR`.
+pub fn set_data_dir(dir: Option<PathBuf>) {
+    config().lock().expect("Cache config lock poisoned").data_dir = dir;
+}
+
+/// Returns the directory datasets are cached in.
+///
+/// The directory set with `set_data_dir` is used first, then `DELTA_DATA_DIR`, then
+/// `.cache/dataset` in the workspace directory.
+///
+/// # Returns
+///
+/// The cache directory.
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = config().lock().expect("Cache config lock poisoned").data_dir.clone() {
+        return dir;
+    }
+
+    match std::env::var_os(DATA_DIR_ENV) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => get_workspace_dir().join(".cache/dataset"),
+    }
+}
+
+/// Enables or disables offline mode for every dataset.
+///
+/// In offline mode, loading a dataset whose files are not cached fails instead of downloading.
+///
+/// # Arguments
+///
+/// * `offline` - Whether to disable downloads, or `None` to fall back to `DELTA_OFFLINE`.
+pub fn set_offline(offline: Option<bool>) {
+    config().lock().expect("Cache config lock poisoned").offline = offline;
+}
+
+/// Returns whether offline mode is enabled through `set_offline` or `DELTA_OFFLINE`.
+///
+/// # Returns
+///
+/// `true` if downloads are disabled.
+pub fn is_offline() -> bool {
+    if let Some(offline) = config().lock().expect("Cache config lock poisoned").offline {
+        return offline;
+    }
+
+    std::env::var(OFFLINE_ENV)
+        .map(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Computes the SHA-256 hash of a file.
+///
+/// # Arguments
+///
+/// * `path` - The file to hash.
+///
+/// # Returns
+///
+/// The lowercase hexadecimal hash, or an error if the file cannot be read.
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| format!("{}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Checks that a file has the expected SHA-256 hash.
+///
+/// # Arguments
+///
+/// * `path` - The file to check.
+/// * `expected` - The expected hexadecimal hash.
+///
+/// # Returns
+///
+/// An error describing the mismatch if the hashes differ.
+pub fn verify_sha256(path: &Path, expected: &str) -> Result<(), String> {
+    let actual = sha256_file(path)?;
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Checksum mismatch for {}: expected SHA-256 {}, got {}",
+            path.display(),
+            expected,
+            actual
+        ))
+    }
+}
+
+/// Makes sure a verified copy of a remote file is in the cache.
+///
+/// A cached file that fails verification is treated as corrupted: it is removed and downloaded
+/// again with `options.downloader`. The expected hash is taken from `options.checksums` if present, then from `known`.
+///
+/// # Arguments
+///
+/// * `url` - The URL to download the file from.
+/// * `path` - The path of the cached file.
+/// * `known` - The known SHA-256 hash of the file, if any.
+/// * `options` - The options controlling downloads and checksums.
+///
+/// # Returns
+///
+/// An error if the file is missing and cannot be downloaded, or if the download is corrupted.
+pub async fn fetch(
+    url: &str,
+    path: &Path,
+    known: Option<&str>,
+    options: &LoadOptions,
+) -> Result<(), String> {
+    let expected = options.expected_checksum(path, known);
+
+    if path.exists() {
+        match expected {
+            None => {
+                debug!("Using cached file: {}", path.display());
+                return Ok(());
+            }
+            Some(expected) => match verify_sha256(path, expected) {
+                Ok(()) => {
+                    debug!("Using verified cached file: {}", path.display());
+                    return Ok(());
+                }
+                Err(e) => {
+                    warn!("{}; removing the corrupted file", e);
+                    fs::remove_file(path).map_err(|e| e.to_string())?;
+                }
+            },
+        }
+    }
+
+    options.ensure_download_allowed(&path.display().to_string())?;
+
+    debug!("Downloading {} to {}", url, path.display());
+    options.downloader.download(url, path).await?;
+
+    if let Some(expected) = expected {
+        if let Err(e) = verify_sha256(path, expected) {
+            fs::remove_file(path).map_err(|e| e.to_string())?;
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    /// Writes an archive with the given contents into a temporary directory, which is removed
+    /// when the returned `TempDir` is dropped.
+    fn fixture(contents: &[u8]) -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("archive.tar.gz");
+        fs::write(&path, contents).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn test_sha256_file() {
+        let (_dir, path) = fixture(b"abc");
+        assert_eq!(sha256_file(&path).unwrap(), ABC_SHA256);
+        assert!(verify_sha256(&path, "00").unwrap_err().contains("Checksum mismatch"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_data_dir_prefers_api_over_environment() {
+        std::env::set_var(DATA_DIR_ENV, "/tmp/delta_env_data");
+        assert_eq!(data_dir(), PathBuf::from("/tmp/delta_env_data"));
+
+        set_data_dir(Some(PathBuf::from("/tmp/delta_api_data")));
+        assert_eq!(data_dir(), PathBuf::from("/tmp/delta_api_data"));
+
+        set_data_dir(None);
+        std::env::remove_var(DATA_DIR_ENV);
+        assert_eq!(data_dir(), get_workspace_dir().join(".cache/dataset"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_offline_prefers_api_over_environment() {
+        std::env::set_var(OFFLINE_ENV, "true");
+        assert!(is_offline());
+
+        set_offline(Some(false));
+        assert!(!is_offline());
+
+        set_offline(None);
+        std::env::remove_var(OFFLINE_ENV);
+        assert!(!is_offline());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_uses_verified_cache() {
+        let (_dir, path) = fixture(b"abc");
+        let hash = sha256_file(&path).unwrap();
+        let options = LoadOptions { offline: true, ..Default::default() };
+
+        fetch("http://127.0.0.1:9/archive.tar.gz", &path, Some(&hash), &options).await.unwrap();
+        assert!(path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_removes_corrupted_cache() {
+        let (_dir, path) = fixture(b"truncated");
+        let options = LoadOptions { offline: true, ..Default::default() };
+
+        let err = fetch("http://127.0.0.1:9/archive.tar.gz", &path, Some("00"), &options)
+            .await
+            .unwrap_err();
+        assert!(err.contains("offline mode is enabled"));
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_redownloads_corrupted_cache() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/archive.tar.gz", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\nConnection: close\r\n\r\nabc")
+                .unwrap();
+        });
+        let (_dir, path) = fixture(b"truncated");
+        let options = LoadOptions { download: true, ..Default::default() };
+
+        fetch(&url, &path, Some(ABC_SHA256), &options).await.unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"abc");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prefers_checksum_from_options() {
+        let (_dir, path) = fixture(b"abc");
+        let mut options = LoadOptions { offline: true, ..Default::default() };
+        options.checksums.insert("archive.tar.gz".to_string(), sha256_file(&path).unwrap());
+
+        fetch("http://127.0.0.1:9/archive.tar.gz", &path, Some("00"), &options).await.unwrap();
+        assert!(path.exists());
+    }
+}
@@ -0,0 +1,509 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use reqwest::{Client, StatusCode};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// The progress of a download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// The number of bytes downloaded so far, including bytes from a resumed download.
+    pub downloaded: u64,
+    /// The total size of the file, if the server reported it.
+    pub total: Option<u64>,
+}
+
+/// A callback that receives the progress of a download after every chunk.
+pub type ProgressCallback = Arc<dyn Fn(DownloadProgress) + Send + Sync>;
+
+/// The outcome of a failed download attempt.
+enum AttemptError {
+    /// The attempt failed in a way that may succeed if tried again.
+    Retry(String),
+    /// The attempt failed in a way that will not succeed if tried again.
+    Fatal(String),
+}
+
+/// Downloads files over HTTP for the datasets.
+///
+/// The file is streamed to a `.part` file next to the destination, which is moved into place once
+/// the download completes. Failed attempts are retried with exponential backoff, and resume from
+/// the bytes already downloaded using HTTP `Range` requests.
+#[derive(Clone)]
+pub struct Downloader {
+    client: Client,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    progress: Option<ProgressCallback>,
+}
+
+impl fmt::Debug for Downloader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Downloader")
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+impl Default for Downloader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Downloader {
+    /// Creates a new `Downloader` that retries 3 times, starting with a 1 second backoff.
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .connect_timeout(Duration::from_secs(30))
+            .read_timeout(Duration::from_secs(60))
+            .build()
+            .expect("Failed to build the HTTP client");
+
+        Self {
+            client,
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            progress: None,
+        }
+    }
+
+    /// Sets how many times a failed download is retried.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_retries` - The number of retries after the first attempt.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the backoff between retries, which doubles after every failed attempt.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - The delay before the first retry.
+    /// * `max` - The longest delay between two attempts.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Sets a callback that receives the progress of every download.
+    ///
+    /// Without a callback, the progress is logged at the `debug` level.
+    ///
+    /// # Arguments
+    ///
+    /// * `progress` - The callback to call after every downloaded chunk.
+    pub fn with_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(DownloadProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Returns the temporary file a download to `dest` is streamed to.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest` - The destination of the download.
+    ///
+    /// # Returns
+    ///
+    /// The path of the `.part` file.
+    pub fn part_path(dest: &Path) -> PathBuf {
+        let mut name = dest.file_name().unwrap_or_default().to_os_string();
+        name.push(".part");
+        dest.with_file_name(name)
+    }
+
+    /// Downloads a file, resuming a previous partial download if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the file.
+    /// * `dest` - The path to move the downloaded file to.
+    ///
+    /// # Returns
+    ///
+    /// The size of the file, or an error if every attempt failed.
+    pub async fn download(&self, url: &str, dest: &Path) -> Result<u64, String> {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| e.to_string())?;
+        }
+
+        let part = Self::part_path(dest);
+        let mut attempt = 0;
+
+        loop {
+            match self.attempt(url, &part).await {
+                Ok(size) => {
+                    fs::rename(&part, dest).await.map_err(|e| e.to_string())?;
+                    info!("Downloaded {} ({} bytes) to {}", url, size, dest.display());
+                    return Ok(size);
+                }
+                Err(AttemptError::Fatal(e)) => return Err(e),
+                Err(AttemptError::Retry(e)) if attempt >= self.max_retries => {
+                    return Err(format!(
+                        "Download of {} failed after {} attempts: {}",
+                        url,
+                        attempt + 1,
+                        e
+                    ));
+                }
+                Err(AttemptError::Retry(e)) => {
+                    let backoff = self
+                        .initial_backoff
+                        .saturating_mul(2u32.saturating_pow(attempt))
+                        .min(self.max_backoff);
+                    warn!("Download of {} failed: {}; retrying in {:?}", url, e, backoff);
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Makes a single attempt to download the rest of a file into its `.part` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the file.
+    /// * `part` - The `.part` file holding the bytes downloaded so far.
+    ///
+    /// # Returns
+    ///
+    /// The size of the file once it is complete.
+    async fn attempt(&self, url: &str, part: &Path) -> Result<u64, AttemptError> {
+        let offset = fs::metadata(part).await.map(|metadata| metadata.len()).unwrap_or(0);
+
+        let mut request = self.client.get(url);
+        if offset > 0 {
+            debug!("Resuming download of {} from byte {}", url, offset);
+            request = request.header(RANGE, format!("bytes={}-", offset));
+        }
+        let mut response = request.send().await.map_err(|e| AttemptError::Retry(e.to_string()))?;
+
+        let status = response.status();
+        let (mut file, mut downloaded, total) = match status {
+            StatusCode::PARTIAL_CONTENT if offset > 0 => {
+                let total = response
+                    .headers()
+                    .get(CONTENT_RANGE)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.rsplit('/').next())
+                    .and_then(|total| total.parse().ok())
+                    .or_else(|| response.content_length().map(|length| length + offset));
+                let file = fs::OpenOptions::new()
+                    .append(true)
+                    .open(part)
+                    .await
+                    .map_err(|e| AttemptError::Fatal(e.to_string()))?;
+                (file, offset, total)
+            }
+            StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => {
+                fs::remove_file(part).await.map_err(|e| AttemptError::Fatal(e.to_string()))?;
+                return Err(AttemptError::Retry(format!(
+                    "The server rejected resuming from byte {}",
+                    offset
+                )));
+            }
+            status if status.is_success() => {
+                let file =
+                    fs::File::create(part).await.map_err(|e| AttemptError::Fatal(e.to_string()))?;
+                (file, 0, response.content_length())
+            }
+            status
+                if status.is_server_error()
+                    || status == StatusCode::TOO_MANY_REQUESTS
+                    || status == StatusCode::REQUEST_TIMEOUT =>
+            {
+                return Err(AttemptError::Retry(format!("Request failed with status: {}", status)));
+            }
+            status => {
+                return Err(AttemptError::Fatal(format!(
+                    "Request failed with status: {} for URL: {}",
+                    status, url
+                )));
+            }
+        };
+
+        let mut logged_tenth = None;
+        loop {
+            let chunk = match response.chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(e) => {
+                    file.flush().await.map_err(|e| AttemptError::Fatal(e.to_string()))?;
+                    return Err(AttemptError::Retry(e.to_string()));
+                }
+            };
+            file.write_all(&chunk).await.map_err(|e| AttemptError::Fatal(e.to_string()))?;
+            downloaded += chunk.len() as u64;
+
+            let progress = DownloadProgress { downloaded, total };
+            match &self.progress {
+                Some(callback) => callback(progress),
+                None => {
+                    if let Some(total) = total.filter(|&total| total > 0) {
+                        let tenth = downloaded * 10 / total;
+                        if logged_tenth != Some(tenth) {
+                            logged_tenth = Some(tenth);
+                            debug!("Downloading {}: {}%", url, tenth * 10);
+                        }
+                    }
+                }
+            }
+        }
+        file.flush().await.map_err(|e| AttemptError::Fatal(e.to_string()))?;
+
+        match total {
+            Some(total) if downloaded < total => Err(AttemptError::Retry(format!(
+                "Connection closed after {} of {} bytes",
+                downloaded, total
+            ))),
+            _ => Ok(downloaded),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::Mutex;
+    use std::thread;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// How the stand-in server answers a request.
+    enum Reply {
+        /// Sends the file from the requested offset.
+        Serve,
+        /// Sends the headers for the file from the requested offset, but only `n` bytes of it.
+        Truncate(usize),
+        /// Sends an empty response with the status code.
+        Status(u16),
+    }
+
+    /// A local HTTP server that serves one file and records the `Range` offsets it was asked for.
+    struct StandInServer {
+        url: String,
+        offsets: Arc<Mutex<Vec<Option<u64>>>>,
+    }
+
+    impl StandInServer {
+        fn start(body: Vec<u8>, replies: Vec<Reply>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/file.bin", listener.local_addr().unwrap());
+            let offsets = Arc::new(Mutex::new(Vec::new()));
+            let recorded = Arc::clone(&offsets);
+
+            thread::spawn(move || {
+                let mut replies = replies.into_iter();
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let offset = read_range(&mut stream);
+                    recorded.lock().unwrap().push(offset);
+                    respond(&mut stream, &body, offset, replies.next().unwrap_or(Reply::Serve));
+                }
+            });
+
+            Self { url, offsets }
+        }
+
+        fn offsets(&self) -> Vec<Option<u64>> {
+            self.offsets.lock().unwrap().clone()
+        }
+    }
+
+    fn read_range(stream: &mut TcpStream) -> Option<u64> {
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let read = stream.read(&mut buffer).unwrap();
+            if read == 0 {
+                break;
+            }
+            request.extend_from_slice(&buffer[..read]);
+        }
+
+        String::from_utf8_lossy(&request).lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            if !name.eq_ignore_ascii_case("range") {
+                return None;
+            }
+            value.trim().strip_prefix("bytes=")?.trim_end_matches('-').parse().ok()
+        })
+    }
+
+    fn respond(stream: &mut TcpStream, body: &[u8], offset: Option<u64>, reply: Reply) {
+        let start = offset.unwrap_or(0) as usize;
+        let status_line = if offset.is_some() { "206 Partial Content" } else { "200 OK" };
+        let content_range = match offset {
+            Some(offset) => {
+                format!("Content-Range: bytes {}-{}/{}\r\n", offset, body.len() - 1, body.len())
+            }
+            None => String::new(),
+        };
+        let headers = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+            status_line,
+            body.len() - start,
+            content_range
+        );
+
+        let _ = match reply {
+            Reply::Serve => {
+                stream.write_all(headers.as_bytes()).and_then(|_| stream.write_all(&body[start..]))
+            }
+            Reply::Truncate(n) => stream
+                .write_all(headers.as_bytes())
+                .and_then(|_| stream.write_all(&body[start..start + n])),
+            Reply::Status(code) => stream.write_all(
+                format!(
+                    "HTTP/1.1 {} Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    code
+                )
+                .as_bytes(),
+            ),
+        };
+    }
+
+    fn body() -> Vec<u8> {
+        (0..10_000u32).map(|x| (x % 251) as u8).collect()
+    }
+
+    /// Returns a destination in a missing subdirectory of a temporary directory, which is
+    /// removed when the returned `TempDir` is dropped.
+    fn destination() -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let dest = dir.path().join("downloads").join("file.bin");
+        (dir, dest)
+    }
+
+    fn downloader() -> Downloader {
+        Downloader::new()
+            .with_max_retries(3)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(4))
+    }
+
+    #[tokio::test]
+    async fn test_download_moves_file_into_place_and_reports_progress() {
+        let server = StandInServer::start(body(), vec![Reply::Serve]);
+        let (_dir, dest) = destination();
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reported);
+
+        let size = downloader()
+            .with_progress(move |progress| sink.lock().unwrap().push(progress))
+            .download(&server.url, &dest)
+            .await
+            .unwrap();
+
+        assert_eq!(size, 10_000);
+        assert_eq!(std::fs::read(&dest).unwrap(), body());
+        assert!(!Downloader::part_path(&dest).exists());
+        let reported = reported.lock().unwrap();
+        assert_eq!(
+            reported.last(),
+            Some(&DownloadProgress { downloaded: 10_000, total: Some(10_000) })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_existing_part_file() {
+        let server = StandInServer::start(body(), vec![Reply::Serve]);
+        let (_dir, dest) = destination();
+        std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+        std::fs::write(Downloader::part_path(&dest), &body()[..4_000]).unwrap();
+
+        downloader().download(&server.url, &dest).await.unwrap();
+
+        assert_eq!(server.offsets(), vec![Some(4_000)]);
+        assert_eq!(std::fs::read(&dest).unwrap(), body());
+    }
+
+    #[tokio::test]
+    async fn test_download_retries_and_resumes_after_interruption() {
+        let server = StandInServer::start(
+            body(),
+            vec![Reply::Status(503), Reply::Truncate(3_000), Reply::Serve],
+        );
+        let (_dir, dest) = destination();
+
+        downloader().download(&server.url, &dest).await.unwrap();
+
+        assert_eq!(server.offsets(), vec![None, None, Some(3_000)]);
+        assert_eq!(std::fs::read(&dest).unwrap(), body());
+    }
+
+    #[tokio::test]
+    async fn test_download_gives_up_after_max_retries() {
+        let replies = (0..4).map(|_| Reply::Status(500)).collect();
+        let server = StandInServer::start(body(), replies);
+        let (_dir, dest) = destination();
+
+        let err = downloader().download(&server.url, &dest).await.unwrap_err();
+
+        assert!(err.contains("after 4 attempts"), "{}", err);
+        assert_eq!(server.offsets().len(), 4);
+        assert!(!dest.exists());
+    }
+
+    #[tokio::test]
+    async fn test_download_does_not_retry_client_errors() {
+        let server = StandInServer::start(body(), vec![Reply::Status(404)]);
+        let (_dir, dest) = destination();
+
+        let err = downloader().download(&server.url, &dest).await.unwrap_err();
+
+        assert!(err.contains("404"), "{}", err);
+        assert_eq!(server.offsets().len(), 1);
+        assert!(!dest.exists());
+    }
+}
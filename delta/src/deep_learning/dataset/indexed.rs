@@ -0,0 +1,496 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::sync::Arc;
+
+use ndarray::{Axis, IxDyn, Shape};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use super::Dataset;
+use crate::deep_learning::tensor_ops::Tensor;
+
+/// A dataset whose samples can be read by index.
+///
+/// Unlike `DatasetOps`, the trait is implemented by a loaded instance that holds exactly one split,
+/// so `len` and `get` never have to guess which data to use.
+pub trait IndexedDataset {
+    /// Returns the number of samples in the dataset.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the dataset contains no samples.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets a single sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the sample.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the input and the target of the sample, without a batch axis.
+    fn get(&self, index: usize) -> (Tensor, Tensor);
+
+    /// Gets the samples at the given indices as a batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - The indices of the samples, in batch order.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the inputs and targets of the batch, stacked along a new batch axis.
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        let (inputs, targets): (Vec<Tensor>, Vec<Tensor>) =
+            indices.iter().map(|&index| self.get(index)).unzip();
+
+        (
+            Tensor::stack(&inputs).expect("Failed to stack batch inputs"),
+            Tensor::stack(&targets).expect("Failed to stack batch targets"),
+        )
+    }
+
+    /// Gets the weights of the samples at the given indices.
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - The indices of the samples, in batch order.
+    ///
+    /// # Returns
+    ///
+    /// A `[batch]` tensor with the sample weights, or `None` if the dataset has no sample weights.
+    fn get_sample_weights(&self, indices: &[usize]) -> Option<Tensor> {
+        let _ = indices;
+        None
+    }
+}
+
+impl IndexedDataset for Dataset {
+    fn len(&self) -> usize {
+        self.inputs.shape().raw_dim()[0]
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        let sample = |tensor: &Tensor| Tensor {
+            data: tensor.data.index_axis(Axis(0), index).to_owned(),
+            device: tensor.device.clone(),
+        };
+        (sample(&self.inputs), sample(&self.labels))
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        (self.inputs.take(indices), self.labels.take(indices))
+    }
+
+    fn get_sample_weights(&self, indices: &[usize]) -> Option<Tensor> {
+        self.sample_weights.as_ref().map(|weights| weights.take(indices))
+    }
+}
+
+impl<D: IndexedDataset + ?Sized> IndexedDataset for Arc<D> {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        (**self).get(index)
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        (**self).get_batch(indices)
+    }
+
+    fn get_sample_weights(&self, indices: &[usize]) -> Option<Tensor> {
+        (**self).get_sample_weights(indices)
+    }
+}
+
+/// A view of a dataset restricted to a subset of its indices.
+#[derive(Debug, Clone)]
+pub struct Subset<D: IndexedDataset> {
+    dataset: Arc<D>,
+    indices: Vec<usize>,
+}
+
+impl<D: IndexedDataset> Subset<D> {
+    /// Creates a new `Subset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset` - The dataset to take samples from.
+    /// * `indices` - The indices of the dataset samples in the subset.
+    ///
+    /// # Returns
+    ///
+    /// A new `Subset` instance.
+    pub fn new(dataset: Arc<D>, indices: Vec<usize>) -> Self {
+        if let Some(&index) = indices.iter().find(|&&index| index >= dataset.len()) {
+            panic!(
+                "Subset index {} out of range for a dataset of {} samples.",
+                index,
+                dataset.len()
+            );
+        }
+
+        Self { dataset, indices }
+    }
+
+    /// Returns the indices of the dataset samples in the subset.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Returns the dataset the subset takes samples from.
+    pub fn dataset(&self) -> &Arc<D> {
+        &self.dataset
+    }
+
+    /// Maps subset indices to indices of the underlying dataset.
+    fn dataset_indices(&self, indices: &[usize]) -> Vec<usize> {
+        indices.iter().map(|&index| self.indices[index]).collect()
+    }
+}
+
+impl<D: IndexedDataset> IndexedDataset for Subset<D> {
+    fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        self.dataset.get(self.indices[index])
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        self.dataset.get_batch(&self.dataset_indices(indices))
+    }
+
+    fn get_sample_weights(&self, indices: &[usize]) -> Option<Tensor> {
+        self.dataset.get_sample_weights(&self.dataset_indices(indices))
+    }
+}
+
+/// A dataset made of several datasets placed one after the other.
+#[derive(Debug, Clone)]
+pub struct ConcatDataset<D: IndexedDataset> {
+    datasets: Vec<D>,
+    offsets: Vec<usize>,
+}
+
+impl<D: IndexedDataset> ConcatDataset<D> {
+    /// Creates a new `ConcatDataset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `datasets` - The datasets to concatenate. Their samples must have the same shapes.
+    ///
+    /// # Returns
+    ///
+    /// A new `ConcatDataset` instance.
+    pub fn new(datasets: Vec<D>) -> Self {
+        let offsets = datasets
+            .iter()
+            .scan(0, |offset, dataset| {
+                let start = *offset;
+                *offset += dataset.len();
+                Some(start)
+            })
+            .collect();
+
+        Self { datasets, offsets }
+    }
+
+    /// Returns the concatenated datasets.
+    pub fn datasets(&self) -> &[D] {
+        &self.datasets
+    }
+
+    /// Finds the dataset holding a sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the sample in the concatenated dataset.
+    ///
+    /// # Returns
+    ///
+    /// The position of the dataset and the index of the sample within it.
+    fn locate(&self, index: usize) -> (usize, usize) {
+        if index >= self.len() {
+            panic!("Index {} out of range for a dataset of {} samples.", index, self.len());
+        }
+
+        let position = self.offsets.partition_point(|&offset| offset <= index) - 1;
+        (position, index - self.offsets[position])
+    }
+}
+
+impl<D: IndexedDataset> IndexedDataset for ConcatDataset<D> {
+    fn len(&self) -> usize {
+        self.datasets.iter().map(|dataset| dataset.len()).sum()
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        let (position, index) = self.locate(index);
+        self.datasets[position].get(index)
+    }
+
+    fn get_sample_weights(&self, indices: &[usize]) -> Option<Tensor> {
+        let weights = indices
+            .iter()
+            .map(|&index| {
+                let (position, index) = self.locate(index);
+                self.datasets[position].get_sample_weights(&[index]).map(|w| w.data[[0]])
+            })
+            .collect::<Option<Vec<f32>>>()?;
+
+        Some(Tensor::new(weights, Shape::from(IxDyn(&[indices.len()]))))
+    }
+}
+
+/// Randomly splits a dataset into non-overlapping subsets.
+///
+/// # Arguments
+///
+/// * `dataset` - The dataset to split.
+/// * `lengths` - The number of samples in each subset. They must add up to the dataset length.
+/// * `rng` - The random number generator used to permute the samples.
+///
+/// # Returns
+///
+/// One `Subset` per entry of `lengths`.
+pub fn random_split<D: IndexedDataset>(
+    dataset: D,
+    lengths: &[usize],
+    rng: &mut StdRng,
+) -> Vec<Subset<D>> {
+    let total: usize = lengths.iter().sum();
+    if total != dataset.len() {
+        panic!(
+            "Sum of split lengths ({}) does not match the dataset length ({}).",
+            total,
+            dataset.len()
+        );
+    }
+
+    let mut indices: Vec<usize> = (0..dataset.len()).collect();
+    indices.shuffle(rng);
+
+    let dataset = Arc::new(dataset);
+    let mut start = 0;
+    lengths
+        .iter()
+        .map(|&length| {
+            let subset = Subset::new(Arc::clone(&dataset), indices[start..start + length].to_vec());
+            start += length;
+            subset
+        })
+        .collect()
+}
+
+/// Splits sample indices into two parts that keep the class proportions of the samples.
+///
+/// The samples of each class are shuffled, and `fraction` of them, rounded, go to the second
+/// part. Both parts are returned in ascending order.
+///
+/// # Arguments
+///
+/// * `classes` - The class of each sample.
+/// * `fraction` - The fraction of the samples of each class in the second part.
+/// * `rng` - The random number generator used to choose the samples.
+///
+/// # Returns
+///
+/// The indices of the first part and of the second part.
+pub fn stratified_split_indices(
+    classes: &[usize],
+    fraction: f32,
+    rng: &mut StdRng,
+) -> (Vec<usize>, Vec<usize>) {
+    if !(0.0..=1.0).contains(&fraction) {
+        panic!("Split fraction must be between 0 and 1.");
+    }
+
+    let num_classes = classes.iter().max().map_or(0, |&class| class + 1);
+    let mut by_class = vec![Vec::new(); num_classes];
+    for (index, &class) in classes.iter().enumerate() {
+        by_class[class].push(index);
+    }
+
+    let (mut first, mut second) = (Vec::new(), Vec::new());
+    for mut indices in by_class {
+        indices.shuffle(rng);
+        let second_size = (indices.len() as f32 * fraction).round() as usize;
+        let (kept, held_out) = indices.split_at(indices.len() - second_size);
+        first.extend_from_slice(kept);
+        second.extend_from_slice(held_out);
+    }
+
+    first.sort_unstable();
+    second.sort_unstable();
+    (first, second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deep_learning::rng::rng_from_seed;
+
+    fn range_dataset(start: usize, size: usize) -> Dataset {
+        let values: Vec<f32> = (start..start + size).map(|x| x as f32).collect();
+        let inputs = Tensor::new(
+            values.iter().flat_map(|&x| [x, -x]).collect(),
+            Shape::from(IxDyn(&[size, 2])),
+        );
+        let labels = Tensor::new(values, Shape::from(IxDyn(&[size])));
+        Dataset::new(inputs, labels)
+    }
+
+    fn targets(dataset: &impl IndexedDataset, indices: &[usize]) -> Vec<f32> {
+        dataset.get_batch(indices).1.to_vec()
+    }
+
+    #[test]
+    fn test_dataset_get_removes_batch_axis() {
+        let dataset = range_dataset(0, 4);
+        let (input, target) = dataset.get(2);
+
+        assert_eq!(input.data.shape(), &[2]);
+        assert_eq!(input.to_vec(), vec![2.0, -2.0]);
+        assert_eq!(target.data.ndim(), 0);
+        assert_eq!(target.data.iter().cloned().collect::<Vec<_>>(), vec![2.0]);
+    }
+
+    #[test]
+    fn test_default_get_batch_stacks_samples() {
+        struct Samples(Dataset);
+
+        impl IndexedDataset for Samples {
+            fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            fn get(&self, index: usize) -> (Tensor, Tensor) {
+                self.0.get(index)
+            }
+        }
+
+        let dataset = range_dataset(0, 5);
+        let samples = Samples(dataset.clone());
+
+        assert_eq!(samples.get_batch(&[4, 1]).0.data, dataset.get_batch(&[4, 1]).0.data);
+        assert_eq!(targets(&samples, &[4, 1]), vec![4.0, 1.0]);
+    }
+
+    #[test]
+    fn test_subset_maps_indices() {
+        let mut dataset = range_dataset(0, 6);
+        dataset
+            .set_sample_weights(Tensor::new(
+                vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+                Shape::from(IxDyn(&[6])),
+            ))
+            .unwrap();
+        let subset = Subset::new(Arc::new(dataset), vec![5, 3, 1]);
+
+        assert_eq!(subset.len(), 3);
+        assert_eq!(targets(&subset, &[0, 2]), vec![5.0, 1.0]);
+        assert_eq!(subset.get_sample_weights(&[1]).unwrap().to_vec(), vec![3.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Subset index 6 out of range")]
+    fn test_subset_rejects_out_of_range_indices() {
+        Subset::new(Arc::new(range_dataset(0, 6)), vec![0, 6]);
+    }
+
+    #[test]
+    fn test_concat_dataset_spans_datasets() {
+        let concat = ConcatDataset::new(vec![
+            range_dataset(0, 3),
+            range_dataset(10, 0),
+            range_dataset(20, 2),
+        ]);
+
+        assert_eq!(concat.len(), 5);
+        assert_eq!(targets(&concat, &[0, 2, 3, 4]), vec![0.0, 2.0, 20.0, 21.0]);
+        assert_eq!(concat.get(3).0.to_vec(), vec![20.0, -20.0]);
+        assert!(concat.get_sample_weights(&[0]).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 5 out of range")]
+    fn test_concat_dataset_rejects_out_of_range_index() {
+        ConcatDataset::new(vec![range_dataset(0, 3), range_dataset(3, 2)]).get(5);
+    }
+
+    #[test]
+    fn test_random_split_partitions_dataset() {
+        let subsets = random_split(range_dataset(0, 10), &[7, 3], &mut rng_from_seed(42));
+
+        assert_eq!(subsets.iter().map(|s| s.len()).collect::<Vec<_>>(), vec![7, 3]);
+        let mut all: Vec<usize> = subsets.iter().flat_map(|s| s.indices().to_vec()).collect();
+        all.sort();
+        assert_eq!(all, (0..10).collect::<Vec<_>>());
+
+        let again = random_split(range_dataset(0, 10), &[7, 3], &mut rng_from_seed(42));
+        assert_eq!(subsets[1].indices(), again[1].indices());
+    }
+
+    #[test]
+    #[should_panic(expected = "Sum of split lengths (9) does not match the dataset length (10).")]
+    fn test_random_split_rejects_mismatched_lengths() {
+        random_split(range_dataset(0, 10), &[6, 3], &mut rng_from_seed(0));
+    }
+
+    #[test]
+    fn test_stratified_split_indices_keeps_class_proportions() {
+        let classes: Vec<usize> = (0..20).map(|i| if i < 10 { 0 } else { 1 + i % 2 }).collect();
+
+        let (train, val) = stratified_split_indices(&classes, 0.2, &mut rng_from_seed(3));
+
+        assert_eq!(train.len() + val.len(), 20);
+        let count = |indices: &[usize], class| indices.iter().filter(|&&i| classes[i] == class).count();
+        assert_eq!((count(&val, 0), count(&val, 1), count(&val, 2)), (2, 1, 1));
+        assert!(train.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(val.iter().all(|index| !train.contains(index)));
+
+        let (_, again) = stratified_split_indices(&classes, 0.2, &mut rng_from_seed(3));
+        assert_eq!(val, again);
+        let (_, other) = stratified_split_indices(&classes, 0.2, &mut rng_from_seed(4));
+        assert_ne!(val, other);
+    }
+
+    #[test]
+    #[should_panic(expected = "Split fraction must be between 0 and 1.")]
+    fn test_stratified_split_indices_rejects_invalid_fraction() {
+        stratified_split_indices(&[0, 1], 1.5, &mut rng_from_seed(0));
+    }
+}
@@ -0,0 +1,516 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::Receiver;
+
+use log::warn;
+use rand::Rng;
+
+use super::Batch;
+use crate::deep_learning::rng::{fork_global_rng, rng_from_seed};
+use crate::deep_learning::tensor_ops::Tensor;
+
+/// An iterator over the `(input, target)` samples of an `IterableDataset`.
+pub type SampleIter<'a> = Box<dyn Iterator<Item = (Tensor, Tensor)> + Send + 'a>;
+
+/// A dataset whose samples can only be read in order, such as a log file or a message queue.
+///
+/// Unlike `IndexedDataset`, the number of samples does not have to be known and the source may be
+/// unbounded. Each call to `iter` starts a new pass over the source; sources that are consumed as
+/// they are read, such as a queue, continue where the previous pass stopped.
+pub trait IterableDataset: Send + Sync {
+    /// Starts a pass over the samples.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the input and the target of each sample, without a batch axis.
+    fn iter(&self) -> SampleIter<'_>;
+
+    /// Groups the samples into batches.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_size` - The number of samples per batch. The last batch of a bounded source may be
+    ///   smaller.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the batches of a new pass over the samples.
+    fn batches(&self, batch_size: usize) -> Box<dyn Iterator<Item = Batch> + Send + '_> {
+        if batch_size == 0 {
+            panic!("Batch size must be greater than 0.");
+        }
+
+        let mut samples = self.iter();
+        Box::new(std::iter::from_fn(move || {
+            let (inputs, targets): (Vec<Tensor>, Vec<Tensor>) =
+                samples.by_ref().take(batch_size).unzip();
+            if inputs.is_empty() {
+                return None;
+            }
+
+            Some(Batch {
+                inputs: Tensor::stack(&inputs).expect("Failed to stack batch inputs"),
+                targets: Tensor::stack(&targets).expect("Failed to stack batch targets"),
+                sample_weights: None,
+            })
+        }))
+    }
+
+    /// Shuffles the samples with a buffer of a fixed size.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer_size` - The number of samples the shuffle draws from.
+    ///
+    /// # Returns
+    ///
+    /// The shuffled dataset.
+    fn shuffle_buffer(self, buffer_size: usize) -> ShuffleBuffer<Self>
+    where
+        Self: Sized,
+    {
+        ShuffleBuffer::new(self, buffer_size)
+    }
+
+    /// Keeps the samples of one worker out of `num_shards`.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_shards` - The number of workers reading the source.
+    /// * `index` - The index of this worker.
+    ///
+    /// # Returns
+    ///
+    /// The dataset with every `num_shards`-th sample, starting at `index`.
+    fn shard(self, num_shards: usize, index: usize) -> WorkerShard<Self>
+    where
+        Self: Sized,
+    {
+        WorkerShard::new(self, num_shards, index)
+    }
+}
+
+impl<D: IterableDataset + ?Sized> IterableDataset for Box<D> {
+    fn iter(&self) -> SampleIter<'_> {
+        (**self).iter()
+    }
+}
+
+/// A dataset whose samples are produced by a function, such as a generator of synthetic data.
+pub struct GeneratorDataset<F> {
+    generator: F,
+}
+
+impl<F, I> GeneratorDataset<F>
+where
+    F: Fn() -> I + Send + Sync,
+    I: Iterator<Item = (Tensor, Tensor)> + Send + 'static,
+{
+    /// Creates a new `GeneratorDataset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `generator` - The function returning the samples of a new pass.
+    ///
+    /// # Returns
+    ///
+    /// A new `GeneratorDataset` instance.
+    pub fn new(generator: F) -> Self {
+        Self { generator }
+    }
+}
+
+impl<F, I> IterableDataset for GeneratorDataset<F>
+where
+    F: Fn() -> I + Send + Sync,
+    I: Iterator<Item = (Tensor, Tensor)> + Send + 'static,
+{
+    fn iter(&self) -> SampleIter<'_> {
+        Box::new((self.generator)())
+    }
+}
+
+/// A dataset reading the samples sent over a channel, such as the messages of a queue consumer.
+///
+/// A pass blocks until a sample arrives and ends once every sender has been dropped. Samples are
+/// consumed as they are read, so later passes only see the samples sent since.
+pub struct ChannelDataset {
+    receiver: Mutex<Receiver<(Tensor, Tensor)>>,
+}
+
+impl ChannelDataset {
+    /// Creates a new `ChannelDataset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `receiver` - The receiving end of the channel the samples are sent to.
+    ///
+    /// # Returns
+    ///
+    /// A new `ChannelDataset` instance.
+    pub fn new(receiver: Receiver<(Tensor, Tensor)>) -> Self {
+        Self { receiver: Mutex::new(receiver) }
+    }
+}
+
+impl IterableDataset for ChannelDataset {
+    fn iter(&self) -> SampleIter<'_> {
+        Box::new(std::iter::from_fn(move || {
+            self.receiver.lock().expect("Channel receiver lock poisoned").recv().ok()
+        }))
+    }
+}
+
+/// A dataset parsing the lines of a text file, such as a log file.
+///
+/// Lines that are not valid UTF-8 are skipped with a warning, and reading stops with a warning
+/// at any other I/O error.
+pub struct LinesDataset<F> {
+    path: PathBuf,
+    parse: F,
+}
+
+impl<F> LinesDataset<F>
+where
+    F: Fn(&str) -> Option<(Tensor, Tensor)> + Send + Sync,
+{
+    /// Creates a new `LinesDataset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    /// * `parse` - The function parsing a line into a sample, or `None` to skip the line.
+    ///
+    /// # Returns
+    ///
+    /// The dataset, or an error if the file does not exist.
+    pub fn open(path: impl AsRef<Path>, parse: F) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        if !path.is_file() {
+            return Err(format!("File not found: {}", path.display()));
+        }
+        Ok(Self { path, parse })
+    }
+}
+
+impl<F> IterableDataset for LinesDataset<F>
+where
+    F: Fn(&str) -> Option<(Tensor, Tensor)> + Send + Sync,
+{
+    fn iter(&self) -> SampleIter<'_> {
+        let file = File::open(&self.path)
+            .unwrap_or_else(|e| panic!("Failed to open {}: {}", self.path.display(), e));
+        let lines =
+            BufReader::new(file).lines().enumerate().map_while(|(index, line)| match line {
+                Ok(line) => Some(Some(line)),
+                // The reader consumes a line that is not valid UTF-8, so reading can go on.
+                Err(e) if e.kind() == ErrorKind::InvalidData => {
+                    warn!("Skipping line {} of {}: {}", index + 1, self.path.display(), e);
+                    Some(None)
+                }
+                Err(e) => {
+                    warn!("Stopped reading {} at line {}: {}", self.path.display(), index + 1, e);
+                    None
+                }
+            });
+        Box::new(lines.flatten().filter_map(|line| (self.parse)(&line)))
+    }
+}
+
+/// A dataset shuffled with a buffer of a fixed size.
+///
+/// The buffer is filled from the source and every sample is drawn at random from it, so a sample
+/// can move at most `buffer_size` positions ahead. Larger buffers shuffle better but keep more
+/// samples in memory.
+pub struct ShuffleBuffer<D> {
+    source: D,
+    buffer_size: usize,
+    seed: Option<u64>,
+    pass: AtomicU64,
+}
+
+impl<D: IterableDataset> ShuffleBuffer<D> {
+    /// Creates a new `ShuffleBuffer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The dataset to shuffle.
+    /// * `buffer_size` - The number of samples the shuffle draws from.
+    ///
+    /// # Returns
+    ///
+    /// A new `ShuffleBuffer` instance.
+    pub fn new(source: D, buffer_size: usize) -> Self {
+        if buffer_size == 0 {
+            panic!("Buffer size must be greater than 0.");
+        }
+        Self { source, buffer_size, seed: None, pass: AtomicU64::new(0) }
+    }
+
+    /// Sets the seed used to shuffle the samples.
+    ///
+    /// Pass `n` is shuffled with a generator seeded from `seed + n`, so the order differs between
+    /// passes but is reproducible across runs. Without a seed the global generator is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed for the shuffle.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl<D: IterableDataset> IterableDataset for ShuffleBuffer<D> {
+    fn iter(&self) -> SampleIter<'_> {
+        let pass = self.pass.fetch_add(1, Ordering::Relaxed);
+        let mut rng = match self.seed {
+            Some(seed) => rng_from_seed(seed.wrapping_add(pass)),
+            None => fork_global_rng(),
+        };
+        let mut samples = self.source.iter();
+        let mut buffer = Vec::with_capacity(self.buffer_size);
+
+        Box::new(std::iter::from_fn(move || {
+            buffer.extend(samples.by_ref().take(self.buffer_size - buffer.len()));
+            if buffer.is_empty() {
+                return None;
+            }
+            let index = rng.gen_range(0..buffer.len());
+            Some(buffer.swap_remove(index))
+        }))
+    }
+}
+
+/// The samples of one worker when a dataset is read by several workers.
+pub struct WorkerShard<D> {
+    source: D,
+    num_shards: usize,
+    index: usize,
+}
+
+impl<D: IterableDataset> WorkerShard<D> {
+    /// Creates a new `WorkerShard`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The dataset read by every worker.
+    /// * `num_shards` - The number of workers.
+    /// * `index` - The index of this worker.
+    ///
+    /// # Returns
+    ///
+    /// A new `WorkerShard` instance.
+    pub fn new(source: D, num_shards: usize, index: usize) -> Self {
+        if num_shards == 0 {
+            panic!("Number of shards must be greater than 0.");
+        }
+        if index >= num_shards {
+            panic!("Shard index must be less than the number of shards.");
+        }
+        Self { source, num_shards, index }
+    }
+}
+
+impl<D: IterableDataset> IterableDataset for WorkerShard<D> {
+    fn iter(&self) -> SampleIter<'_> {
+        Box::new(self.source.iter().skip(self.index).step_by(self.num_shards))
+    }
+}
+
+/// A dataset alternating between the samples of several sources.
+///
+/// Sources are read in turn, one sample at a time; a source that runs out is skipped and the pass
+/// ends when every source has run out.
+pub struct Interleave {
+    sources: Vec<Box<dyn IterableDataset>>,
+}
+
+impl Interleave {
+    /// Creates a new `Interleave`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sources` - The datasets to interleave.
+    ///
+    /// # Returns
+    ///
+    /// A new `Interleave` instance.
+    pub fn new(sources: Vec<Box<dyn IterableDataset>>) -> Self {
+        Self { sources }
+    }
+}
+
+impl IterableDataset for Interleave {
+    fn iter(&self) -> SampleIter<'_> {
+        let mut iters: Vec<SampleIter<'_>> =
+            self.sources.iter().map(|source| source.iter()).collect();
+        let mut next = 0;
+
+        Box::new(std::iter::from_fn(move || {
+            while !iters.is_empty() {
+                let current = next % iters.len();
+                match iters[current].next() {
+                    Some(sample) => {
+                        next = current + 1;
+                        return Some(sample);
+                    }
+                    None => {
+                        drop(iters.remove(current));
+                        next = current;
+                    }
+                }
+            }
+            None
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::thread;
+
+    use ndarray::{IxDyn, Shape};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn sample(value: f32) -> (Tensor, Tensor) {
+        (
+            Tensor::new(vec![value, -value], Shape::from(IxDyn(&[2]))),
+            Tensor::new(vec![value], Shape::from(IxDyn(&[1]))),
+        )
+    }
+
+    fn range(
+        start: usize,
+        end: usize,
+    ) -> GeneratorDataset<impl Fn() -> std::vec::IntoIter<(Tensor, Tensor)>> {
+        GeneratorDataset::new(move || {
+            (start..end).map(|i| sample(i as f32)).collect::<Vec<_>>().into_iter()
+        })
+    }
+
+    fn values(dataset: &dyn IterableDataset) -> Vec<usize> {
+        dataset.iter().map(|(_, target)| target.data[[0]] as usize).collect()
+    }
+
+    #[test]
+    fn test_batches_stack_samples_and_keep_the_partial_batch() {
+        let batches: Vec<Batch> = range(0, 5).batches(2).collect();
+
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches[0].inputs.data.shape(), &[2, 2]);
+        assert_eq!(batches[1].targets.data.as_slice().unwrap(), [2.0, 3.0]);
+        assert_eq!(batches[2].len(), 1);
+    }
+
+    #[test]
+    fn test_shuffle_buffer_is_a_seeded_permutation() {
+        let shuffled = range(0, 20).shuffle_buffer(8).with_seed(5);
+        let first = values(&shuffled);
+        let second = values(&shuffled);
+
+        let mut sorted = first.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+        assert_ne!(first, (0..20).collect::<Vec<_>>());
+        assert_ne!(first, second);
+        assert_eq!(first, values(&range(0, 20).shuffle_buffer(8).with_seed(5)));
+    }
+
+    #[test]
+    fn test_shuffle_buffer_only_moves_samples_within_the_buffer() {
+        let shuffled = range(0, 50).shuffle_buffer(4).with_seed(1);
+
+        for (position, value) in values(&shuffled).into_iter().enumerate() {
+            assert!(value < position + 4, "{} was drawn at position {}", value, position);
+        }
+    }
+
+    #[test]
+    fn test_shards_partition_the_samples() {
+        let shards: Vec<Vec<usize>> =
+            (0..3).map(|index| values(&range(0, 10).shard(3, index))).collect();
+
+        assert_eq!(shards, [vec![0, 3, 6, 9], vec![1, 4, 7], vec![2, 5, 8]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Shard index must be less than the number of shards.")]
+    fn test_shard_rejects_invalid_index() {
+        range(0, 10).shard(2, 2);
+    }
+
+    #[test]
+    fn test_interleave_alternates_until_every_source_runs_out() {
+        let interleaved = Interleave::new(vec![
+            Box::new(range(0, 2)),
+            Box::new(range(10, 14)),
+            Box::new(range(20, 21)),
+        ]);
+
+        assert_eq!(values(&interleaved), [0, 10, 20, 1, 11, 12, 13]);
+    }
+
+    #[test]
+    fn test_channel_dataset_reads_until_the_senders_are_dropped() {
+        let (sender, receiver) = mpsc::channel();
+        let dataset = ChannelDataset::new(receiver);
+
+        let producer = thread::spawn(move || {
+            for i in 0..4 {
+                sender.send(sample(i as f32)).unwrap();
+            }
+        });
+
+        assert_eq!(values(&dataset), [0, 1, 2, 3]);
+        producer.join().unwrap();
+    }
+
+    #[test]
+    fn test_lines_dataset_parses_and_skips_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("samples.log");
+        std::fs::write(&path, b"# header\n1\n2\nnot a number\n\xff\n3\n").unwrap();
+
+        let dataset =
+            LinesDataset::open(&path, |line| line.parse::<f32>().ok().map(sample)).unwrap();
+
+        assert_eq!(values(&dataset), [1, 2, 3]);
+        assert!(LinesDataset::open(path.with_extension("missing"), |_| None).is_err());
+    }
+}
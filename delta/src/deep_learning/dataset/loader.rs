@@ -0,0 +1,492 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::sync::mpsc;
+use std::thread;
+
+use rand::seq::SliceRandom;
+use rayon::prelude::*;
+
+use super::{DatasetOps, IndexedDataset, Sampler};
+use crate::deep_learning::rng::{fork_global_rng, rng_from_seed};
+use crate::deep_learning::tensor_ops::Tensor;
+use crate::devices::Device;
+
+/// A single batch produced by a `DataLoader`.
+#[derive(Debug, Clone)]
+pub struct Batch {
+    pub inputs: Tensor,
+    pub targets: Tensor,
+    /// Optional weight of each sample in the batch.
+    pub sample_weights: Option<Tensor>,
+}
+
+impl Batch {
+    /// Loads a batch from a dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset` - The dataset to load the batch from.
+    /// * `batch_idx` - The index of the batch.
+    /// * `batch_size` - The size of the batch.
+    ///
+    /// # Returns
+    ///
+    /// The inputs, targets and sample weights of the batch.
+    pub fn from_dataset<D: DatasetOps>(dataset: &D, batch_idx: usize, batch_size: usize) -> Self {
+        let (inputs, targets) = dataset.get_batch(batch_idx, batch_size);
+        let sample_weights = dataset.get_batch_sample_weights(batch_idx, batch_size);
+        Batch { inputs, targets, sample_weights }
+    }
+
+    /// Loads the samples at the given indices as a batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset` - The dataset to load the samples from.
+    /// * `indices` - The indices of the samples, in batch order.
+    ///
+    /// # Returns
+    ///
+    /// The inputs, targets and sample weights of the batch.
+    pub fn from_indices<D: IndexedDataset + ?Sized>(dataset: &D, indices: &[usize]) -> Self {
+        let (inputs, targets) = dataset.get_batch(indices);
+        let sample_weights = dataset.get_sample_weights(indices);
+        Batch { inputs, targets, sample_weights }
+    }
+
+    /// Returns the number of samples in the batch.
+    pub fn len(&self) -> usize {
+        self.targets.shape().raw_dim()[0]
+    }
+
+    /// Returns `true` if the batch contains no samples.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Transfers the batch to the specified device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device to transfer the batch to.
+    pub fn to_device(&mut self, device: &Device) -> Result<(), String> {
+        self.inputs = self.inputs.to_device(device.clone())?;
+        self.targets = self.targets.to_device(device.clone())?;
+        if let Some(weights) = self.sample_weights.as_mut() {
+            *weights = weights.to_device(device.clone())?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterates over the batches of a dataset, reshuffling the sample order every epoch and
+/// optionally loading upcoming batches in the background.
+#[derive(Debug)]
+pub struct DataLoader<D: IndexedDataset> {
+    dataset: D,
+    batch_size: usize,
+    shuffle: bool,
+    seed: Option<u64>,
+    drop_last: bool,
+    prefetch: usize,
+    epoch: u64,
+    order: Vec<usize>,
+    sampler: Option<Box<dyn Sampler>>,
+}
+
+impl<D: IndexedDataset> DataLoader<D> {
+    /// Creates a new `DataLoader` that yields batches in dataset order.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset` - The dataset to load batches from.
+    /// * `batch_size` - The number of samples per batch.
+    ///
+    /// # Returns
+    ///
+    /// A new `DataLoader` instance.
+    pub fn new(dataset: D, batch_size: usize) -> Self {
+        if batch_size == 0 {
+            panic!("Batch size must be greater than 0.");
+        }
+
+        let order = (0..dataset.len()).collect();
+        Self {
+            dataset,
+            batch_size,
+            shuffle: false,
+            seed: None,
+            drop_last: false,
+            prefetch: 0,
+            epoch: 0,
+            order,
+            sampler: None,
+        }
+    }
+
+    /// Enables or disables reshuffling the sample order at the start of every epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `shuffle` - Whether to reshuffle the sample order every epoch.
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Sets the seed used to reshuffle the sample order.
+    ///
+    /// Epoch `n` is shuffled with a generator seeded from `seed + n`, so the order differs between
+    /// epochs but is reproducible across runs. Without a seed the global generator is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed for the shuffle.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the sampler choosing the sample order of every epoch.
+    ///
+    /// A sampler replaces the shuffle of `with_shuffle` and `with_seed`, and may draw more or fewer
+    /// samples than the dataset holds, such as a `ClassBalancedSampler` repeating rare classes.
+    ///
+    /// # Arguments
+    ///
+    /// * `sampler` - The sampler of the sample order.
+    pub fn with_sampler(mut self, sampler: impl Sampler + 'static) -> Self {
+        self.order = sampler.sample(self.dataset.len(), self.epoch);
+        self.sampler = Some(Box::new(sampler));
+        self
+    }
+
+    /// Sets whether the trailing batch is dropped when it has fewer than `batch_size` samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `drop_last` - Whether to drop the trailing partial batch.
+    pub fn with_drop_last(mut self, drop_last: bool) -> Self {
+        self.drop_last = drop_last;
+        self
+    }
+
+    /// Sets the number of batches loaded ahead of the consumer on a background thread.
+    ///
+    /// Batches are loaded in parallel on the rayon thread pool. A value of `0` loads every batch
+    /// on the calling thread when it is needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefetch` - The number of batches to load ahead.
+    pub fn with_prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    /// Returns the number of samples per batch.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Returns the number of batches yielded per epoch.
+    pub fn num_batches(&self) -> usize {
+        let num_samples = match &self.sampler {
+            Some(sampler) => sampler.num_samples(self.dataset.len()),
+            None => self.dataset.len(),
+        };
+        if self.drop_last {
+            num_samples / self.batch_size
+        } else {
+            num_samples.div_ceil(self.batch_size)
+        }
+    }
+
+    /// Returns the number of epochs started so far.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Returns the sample order of the current epoch.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Returns a reference to the wrapped dataset.
+    pub fn dataset(&self) -> &D {
+        &self.dataset
+    }
+
+    /// Returns a mutable reference to the wrapped dataset.
+    pub fn dataset_mut(&mut self) -> &mut D {
+        &mut self.dataset
+    }
+
+    /// Consumes the loader and returns the wrapped dataset.
+    pub fn into_dataset(self) -> D {
+        self.dataset
+    }
+
+    /// Loads a batch in the sample order of the current epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_idx` - The index of the batch.
+    ///
+    /// # Returns
+    ///
+    /// The batch at the given index.
+    pub fn get_batch(&self, batch_idx: usize) -> Batch {
+        Batch::from_indices(&self.dataset, self.batch_indices(batch_idx))
+    }
+
+    /// Iterates over the batches in the sample order of the current epoch, without reshuffling or
+    /// prefetching.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the batches.
+    pub fn iter(&self) -> impl Iterator<Item = Batch> + '_ {
+        (0..self.num_batches()).map(|batch_idx| self.get_batch(batch_idx))
+    }
+
+    /// Returns the sample indices of a batch in the current epoch.
+    fn batch_indices(&self, batch_idx: usize) -> &[usize] {
+        let start = (batch_idx * self.batch_size).min(self.order.len());
+        let end = (start + self.batch_size).min(self.order.len());
+        &self.order[start..end]
+    }
+
+    /// Starts a new epoch, drawing the sample order from the sampler or reshuffling it if shuffling
+    /// is enabled.
+    fn start_epoch(&mut self) {
+        if let Some(sampler) = &self.sampler {
+            self.order = sampler.sample(self.dataset.len(), self.epoch);
+            self.epoch += 1;
+            return;
+        }
+
+        self.order = (0..self.dataset.len()).collect();
+        if self.shuffle {
+            let mut rng = match self.seed {
+                Some(seed) => rng_from_seed(seed.wrapping_add(self.epoch)),
+                None => fork_global_rng(),
+            };
+            self.order.shuffle(&mut rng);
+        }
+        self.epoch += 1;
+    }
+}
+
+impl<D: IndexedDataset + Sync> DataLoader<D> {
+    /// Runs one epoch over the dataset.
+    ///
+    /// The sample order is reshuffled first if shuffling is enabled, then `f` receives an iterator
+    /// over the batches of the epoch. With prefetching enabled, batches are loaded on a background
+    /// thread while `f` consumes them. Loading stops early if `f` returns before the iterator is
+    /// exhausted.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function consuming the batches of the epoch.
+    ///
+    /// # Returns
+    ///
+    /// The value returned by `f`.
+    pub fn run_epoch<R, F>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut dyn Iterator<Item = Batch>) -> R,
+    {
+        self.start_epoch();
+
+        let num_batches = self.num_batches();
+        let prefetch = self.prefetch;
+        let loader = &*self;
+
+        if prefetch == 0 {
+            let mut batches = (0..num_batches).map(|batch_idx| loader.get_batch(batch_idx));
+            return f(&mut batches);
+        }
+
+        thread::scope(|scope| {
+            let (sender, receiver) = mpsc::sync_channel(prefetch);
+
+            scope.spawn(move || {
+                for chunk_start in (0..num_batches).step_by(prefetch) {
+                    let chunk_end = (chunk_start + prefetch).min(num_batches);
+                    let batches: Vec<Batch> = (chunk_start..chunk_end)
+                        .into_par_iter()
+                        .map(|batch_idx| loader.get_batch(batch_idx))
+                        .collect();
+
+                    for batch in batches {
+                        if sender.send(batch).is_err() {
+                            return;
+                        }
+                    }
+                }
+            });
+
+            // Dropping the receiver before the scope joins unblocks the producer if `f` stopped
+            // early.
+            let mut batches = receiver.into_iter();
+            let result = f(&mut batches);
+            drop(batches);
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{IxDyn, Shape};
+
+    use super::*;
+    use crate::deep_learning::dataset::{ClassBalancedSampler, Dataset};
+
+    fn range_dataset(size: usize) -> Dataset {
+        let values: Vec<f32> = (0..size).map(|x| x as f32).collect();
+        let inputs = Tensor::new(values.clone(), Shape::from(IxDyn(&[size, 1])));
+        let labels = Tensor::new(values, Shape::from(IxDyn(&[size])));
+        Dataset::new(inputs, labels)
+    }
+
+    fn epoch_labels<D: IndexedDataset + Sync>(loader: &mut DataLoader<D>) -> Vec<f32> {
+        loader.run_epoch(|batches| batches.flat_map(|b| b.targets.data.into_iter()).collect())
+    }
+
+    #[test]
+    fn test_data_loader_keeps_trailing_partial_batch() {
+        let loader = DataLoader::new(range_dataset(10), 4);
+        let sizes: Vec<usize> = loader.iter().map(|b| b.len()).collect();
+
+        assert_eq!(loader.num_batches(), 3);
+        assert_eq!(sizes, vec![4, 4, 2]);
+    }
+
+    #[test]
+    fn test_data_loader_drop_last() {
+        let mut loader = DataLoader::new(range_dataset(10), 4).with_drop_last(true);
+
+        assert_eq!(loader.num_batches(), 2);
+        assert_eq!(epoch_labels(&mut loader), (0..8).map(|x| x as f32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_data_loader_without_shuffle_keeps_order() {
+        let mut loader = DataLoader::new(range_dataset(10), 3);
+
+        assert_eq!(epoch_labels(&mut loader), (0..10).map(|x| x as f32).collect::<Vec<_>>());
+        assert_eq!(loader.epoch(), 1);
+    }
+
+    #[test]
+    fn test_data_loader_seeded_shuffle_is_reproducible() {
+        let mut first = DataLoader::new(range_dataset(20), 5).with_shuffle(true).with_seed(7);
+        let mut second = DataLoader::new(range_dataset(20), 5).with_shuffle(true).with_seed(7);
+
+        let first_epoch = epoch_labels(&mut first);
+        assert_eq!(first_epoch, epoch_labels(&mut second));
+        assert_eq!(epoch_labels(&mut first), epoch_labels(&mut second));
+
+        let mut sorted = first_epoch.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(sorted, (0..20).map(|x| x as f32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_data_loader_reshuffles_every_epoch() {
+        let mut loader = DataLoader::new(range_dataset(20), 5).with_shuffle(true).with_seed(1);
+
+        let first_epoch = epoch_labels(&mut loader);
+        let second_epoch = epoch_labels(&mut loader);
+
+        assert_ne!(first_epoch, second_epoch);
+        assert_eq!(loader.order().iter().map(|&i| i as f32).collect::<Vec<_>>(), second_epoch);
+    }
+
+    #[test]
+    fn test_data_loader_shuffle_keeps_sample_weights_aligned() {
+        let mut dataset = range_dataset(12);
+        let weights = Tensor::new((0..12).map(|x| x as f32).collect(), Shape::from(IxDyn(&[12])));
+        dataset.set_sample_weights(weights).unwrap();
+        let mut loader = DataLoader::new(dataset, 5).with_shuffle(true).with_seed(3);
+
+        loader.run_epoch(|batches| {
+            for batch in batches {
+                let weights = batch.sample_weights.unwrap();
+                assert_eq!(batch.targets.data, weights.data);
+            }
+        });
+    }
+
+    #[test]
+    fn test_data_loader_prefetch_yields_batches_in_order() {
+        let mut plain = DataLoader::new(range_dataset(23), 4).with_shuffle(true).with_seed(5);
+        let mut prefetched =
+            DataLoader::new(range_dataset(23), 4).with_shuffle(true).with_seed(5).with_prefetch(3);
+
+        assert_eq!(epoch_labels(&mut plain), epoch_labels(&mut prefetched));
+        assert_eq!(epoch_labels(&mut plain), epoch_labels(&mut prefetched));
+    }
+
+    #[test]
+    fn test_data_loader_prefetch_stops_when_consumer_stops() {
+        let mut loader = DataLoader::new(range_dataset(100), 2).with_prefetch(2);
+
+        let first = loader.run_epoch(|batches| batches.next().unwrap());
+
+        assert_eq!(first.targets.to_vec(), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_data_loader_batches_in_sampler_order() {
+        let mut classes = vec![0; 18];
+        classes.extend([1, 1]);
+        let sampler = ClassBalancedSampler::new(&classes).with_num_samples(40).with_seed(2);
+        let mut loader = DataLoader::new(range_dataset(20), 8).with_sampler(sampler.clone());
+
+        assert_eq!(loader.num_batches(), 5);
+        let labels = epoch_labels(&mut loader);
+        let expected: Vec<f32> = sampler.sample(20, 0).into_iter().map(|i| i as f32).collect();
+        assert_eq!(labels, expected);
+        assert!(labels.iter().filter(|&&label| label >= 18.0).count() > 10);
+
+        let next_epoch = epoch_labels(&mut loader);
+        assert_eq!(next_epoch.len(), 40);
+        assert_ne!(next_epoch, labels);
+    }
+
+    #[test]
+    #[should_panic(expected = "Batch size must be greater than 0.")]
+    fn test_data_loader_rejects_zero_batch_size() {
+        DataLoader::new(range_dataset(4), 0);
+    }
+}
@@ -27,22 +27,80 @@
 // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+pub mod builder;
+pub mod cache;
+pub mod download;
+pub mod indexed;
+pub mod iterable;
+pub mod loader;
+pub mod sampler;
+pub mod shard;
+pub mod stats;
+pub mod tabular;
+pub mod text;
 pub mod vision;
 
 use std::future::Future;
 
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
 #[cfg(feature = "vision")]
 pub use vision::{
     Cifar10Dataset,
     Cifar100Dataset,
-    // CustomImageDataset,
+    EmnistDataset,
+    EmnistSplit,
+    FashionMnistDataset,
+    ImageFolderDataset,
+    ImageNetClasses,
     ImageNetV2Dataset,
+    ImageNetV2Variant,
+    KmnistDataset,
     MnistDataset,
+    Stl10Dataset,
+    SvhnDataset,
     TestDataset,
+    TinyImageNetDataset,
+};
+
+#[cfg(feature = "text")]
+pub use text::{
+    AgNewsDataset,
+    BpeTokenizer,
+    CharTokenizer,
+    EncodeOptions,
+    ImdbDataset,
+    TextDataset,
+    Tokenizer,
+    Vocab,
+    WordTokenizer,
+};
+
+pub use builder::{DEFAULT_SPLIT_SEED, DatasetBuilder, LoadDataset, LoadOptions, Split};
+pub use download::{DownloadProgress, Downloader};
+pub use indexed::{
+    ConcatDataset, IndexedDataset, Subset, random_split, stratified_split_indices,
+};
+pub use iterable::{
+    ChannelDataset, GeneratorDataset, Interleave, IterableDataset, LinesDataset, SampleIter,
+    ShuffleBuffer, WorkerShard,
 };
+pub use loader::{Batch, DataLoader};
+pub use sampler::{
+    ClassBalancedSampler, DistributedSampler, RandomSampler, Sampler, SequentialSampler,
+    WeightedRandomSampler,
+};
+pub use shard::{ShardWriter, ShardedDataset};
+pub use stats::{
+    NonFiniteReport, RunningStats, channel_stats, class_distribution, find_duplicates,
+    scan_non_finite, to_image,
+};
+pub use tabular::{Column, ColumnType, CsvBatches, CsvDataset, CsvReadOptions, MissingValues};
 
 use crate::devices::Device;
 
+use super::rng::with_global_rng;
 use super::tensor_ops::Tensor;
 
 /// A struct representing a dataset.
@@ -50,6 +108,8 @@
 pub struct Dataset {
     pub inputs: Tensor,
     pub labels: Tensor,
+    /// Optional weight of each sample, applied by the loss during training.
+    pub sample_weights: Option<Tensor>,
 }
 
 impl Dataset {
@@ -64,7 +124,113 @@ impl Dataset {
     ///
     /// A new `Dataset` instance.
     pub fn new(inputs: Tensor, labels: Tensor) -> Self {
-        Dataset { inputs, labels }
+        Dataset { inputs, labels, sample_weights: None }
+    }
+
+    /// Sets the weight of each sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - A `[samples]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// An error if the number of weights does not match the number of samples.
+    pub fn set_sample_weights(&mut self, weights: Tensor) -> Result<(), String> {
+        let num_samples = self.inputs.shape().raw_dim()[0];
+        if weights.data.ndim() != 1 || weights.data.len() != num_samples {
+            return Err(format!(
+                "Expected {} sample weights, got shape {:?}",
+                num_samples,
+                weights.data.shape()
+            ));
+        }
+
+        self.sample_weights = Some(weights);
+        Ok(())
+    }
+
+    /// Gets the sample weights of a batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_idx` - The index of the batch.
+    /// * `batch_size` - The size of the batch.
+    ///
+    /// # Returns
+    ///
+    /// The weights of the samples in the batch, or `None` if the dataset has no sample weights.
+    pub fn batch_sample_weights(&self, batch_idx: usize, batch_size: usize) -> Option<Tensor> {
+        let weights = self.sample_weights.as_ref()?;
+        let total_samples = weights.data.len();
+        let start_idx = (batch_idx * batch_size).min(total_samples);
+        let end_idx = (start_idx + batch_size).min(total_samples);
+
+        Some(weights.take(&(start_idx..end_idx).collect::<Vec<usize>>()))
+    }
+
+    /// Splits the dataset into two datasets at the given sample index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The number of samples in the first dataset.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the samples before and from `index`.
+    pub fn split_at(&self, index: usize) -> (Dataset, Dataset) {
+        let num_samples = self.inputs.shape().raw_dim()[0];
+        assert!(index <= num_samples, "Index out of bounds for dataset split");
+
+        let part = |indices: Vec<usize>| Dataset {
+            inputs: self.inputs.take(&indices),
+            labels: self.labels.take(&indices),
+            sample_weights: self.sample_weights.as_ref().map(|w| w.take(&indices)),
+        };
+        (part((0..index).collect()), part((index..num_samples).collect()))
+    }
+
+    /// Returns the class of each sample: the index of the largest value of one-hot labels, or
+    /// the value of labels with a single value per sample.
+    pub fn sample_classes(&self) -> Vec<usize> {
+        stats::label_classes(&self.labels.data)
+    }
+
+    /// Splits the dataset into two datasets with the same class proportions.
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - The fraction of the samples of each class in the second dataset.
+    /// * `rng` - The random number generator used to choose the samples.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the remaining samples and the held out samples, each in their original
+    /// order.
+    pub fn stratified_split(&self, fraction: f32, rng: &mut StdRng) -> (Dataset, Dataset) {
+        let (first, second) = stratified_split_indices(&self.sample_classes(), fraction, rng);
+
+        let part = |indices: Vec<usize>| Dataset {
+            inputs: self.inputs.take(&indices),
+            labels: self.labels.take(&indices),
+            sample_weights: self.sample_weights.as_ref().map(|w| w.take(&indices)),
+        };
+        (part(first), part(second))
+    }
+
+    /// Shuffles the samples, keeping inputs, labels and sample weights aligned.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator used to permute the samples.
+    pub fn shuffle_with_rng(&mut self, rng: &mut StdRng) {
+        let num_samples = self.inputs.shape().raw_dim()[0];
+        let mut indices: Vec<usize> = (0..num_samples).collect();
+        indices.shuffle(rng);
+
+        self.inputs = self.inputs.take(&indices);
+        self.labels = self.labels.take(&indices);
+        self.sample_weights = self.sample_weights.as_ref().map(|w| w.take(&indices));
     }
 
     /// Transfers the dataset to the specified device.
@@ -75,6 +241,9 @@ pub fn new(inputs: Tensor, labels: Tensor) -> Self {
     pub fn to_device(&mut self, device: &Device) {
         self.inputs = self.inputs.to_device(device.clone()).unwrap();
         self.labels = self.labels.to_device(device.clone()).unwrap();
+        if let Some(weights) = self.sample_weights.as_mut() {
+            *weights = weights.to_device(device.clone()).unwrap();
+        }
     }
 }
 
@@ -147,6 +316,36 @@ fn is_empty(&self) -> bool {
     /// A tuple containing the input tensor and the target tensor for the batch.
     fn get_batch(&self, batch_idx: usize, batch_size: usize) -> (Tensor, Tensor);
 
+    /// Gets the sample weights of a batch, matching the samples returned by `get_batch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_idx` - The index of the batch to retrieve.
+    /// * `batch_size` - The size of the batch to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// The weights of the samples in the batch, or `None` if the dataset is unweighted.
+    fn get_batch_sample_weights(&self, batch_idx: usize, batch_size: usize) -> Option<Tensor> {
+        let _ = (batch_idx, batch_size);
+        None
+    }
+
+    /// Sets the weight of each sample, e.g. to counter class imbalance during training.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - A `[samples]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// An error if the dataset is not loaded, does not support sample weights, or the number of
+    /// weights does not match the number of samples.
+    fn set_sample_weights(&mut self, weights: Tensor) -> Result<(), String> {
+        let _ = weights;
+        Err("This dataset does not support sample weights".to_string())
+    }
+
     /// Calculates the loss between the predicted outputs and the true targets.
     ///
     /// # Arguments
@@ -171,8 +370,17 @@ fn is_empty(&self) -> bool {
     /// A `Tensor` containing the gradients of the loss with respect to the outputs.
     fn loss_grad(&self, outputs: &Tensor, targets: &Tensor) -> Tensor;
 
-    /// Shuffles the dataset.
-    fn shuffle(&mut self);
+    /// Shuffles the dataset using the global random number generator.
+    fn shuffle(&mut self) {
+        with_global_rng(|rng| self.shuffle_with_rng(rng))
+    }
+
+    /// Shuffles the dataset using the given random number generator.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator used to permute the samples.
+    fn shuffle_with_rng(&mut self, rng: &mut StdRng);
 
     /// Clones the dataset.
     ///
@@ -188,3 +396,66 @@ fn is_empty(&self) -> bool {
     /// * `device` - The device to transfer the dataset to.
     fn to_device(&mut self, device: Device) -> Result<(), String>;
 }
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{IxDyn, Shape};
+
+    use super::*;
+    use crate::deep_learning::rng::rng_from_seed;
+
+    fn dataset() -> Dataset {
+        let inputs = Tensor::new((0..10).map(|x| x as f32).collect(), Shape::from(IxDyn(&[5, 2])));
+        let labels = Tensor::new(vec![0.0, 1.0, 0.0, 1.0, 1.0], Shape::from(IxDyn(&[5])));
+        Dataset::new(inputs, labels)
+    }
+
+    #[test]
+    fn test_dataset_batch_sample_weights() {
+        let mut dataset = dataset();
+        assert!(dataset.batch_sample_weights(0, 2).is_none());
+
+        let weights = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0], Shape::from(IxDyn(&[5])));
+        dataset.set_sample_weights(weights).unwrap();
+
+        assert_eq!(dataset.batch_sample_weights(1, 2).unwrap().to_vec(), vec![3.0, 4.0]);
+        assert_eq!(dataset.batch_sample_weights(2, 2).unwrap().to_vec(), vec![5.0]);
+    }
+
+    #[test]
+    fn test_dataset_sample_weights_with_mismatch() {
+        let mut dataset = dataset();
+        let weights = Tensor::new(vec![1.0; 3], Shape::from(IxDyn(&[3])));
+
+        assert!(dataset.set_sample_weights(weights).is_err());
+        assert!(dataset.sample_weights.is_none());
+    }
+
+    #[test]
+    fn test_dataset_stratified_split() {
+        let mut dataset = dataset();
+        let weights = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0], Shape::from(IxDyn(&[5])));
+        dataset.set_sample_weights(weights).unwrap();
+        assert_eq!(dataset.sample_classes(), vec![0, 1, 0, 1, 1]);
+
+        let (train, val) = dataset.stratified_split(0.5, &mut rng_from_seed(1));
+
+        assert_eq!(val.sample_classes().iter().filter(|&&class| class == 0).count(), 1);
+        assert_eq!(train.len() + val.len(), 5);
+        for part in [&train, &val] {
+            let inputs = part.inputs.to_vec();
+            let weights = part.sample_weights.as_ref().unwrap().to_vec();
+            for (sample, weight) in weights.iter().enumerate() {
+                assert_eq!(inputs[sample * 2], (weight - 1.0) * 2.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample_classes_of_one_hot_labels() {
+        let labels = Tensor::new(vec![0.0, 1.0, 0.0, 0.9, 0.1, 0.0], Shape::from(IxDyn(&[2, 3])));
+        let inputs = Tensor::new(vec![0.0; 2], Shape::from(IxDyn(&[2, 1])));
+
+        assert_eq!(Dataset::new(inputs, labels).sample_classes(), vec![1, 0]);
+    }
+}
@@ -0,0 +1,562 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt::Debug;
+
+use rand::Rng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::deep_learning::rng::{fork_global_rng, rng_from_seed};
+
+/// Chooses the order in which the samples of a dataset are batched in each epoch.
+///
+/// Samplers are used by `DataLoader::with_sampler`, and through the loader by
+/// `Sequential::fit_with_loader`.
+pub trait Sampler: Debug + Send + Sync {
+    /// Returns the number of indices drawn per epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset_len` - The number of samples in the dataset.
+    fn num_samples(&self, dataset_len: usize) -> usize;
+
+    /// Draws the sample indices of an epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset_len` - The number of samples in the dataset.
+    /// * `epoch` - The index of the epoch, used to vary seeded orders between epochs.
+    ///
+    /// # Returns
+    ///
+    /// `num_samples` indices below `dataset_len`, in batch order.
+    fn sample(&self, dataset_len: usize, epoch: u64) -> Vec<usize>;
+}
+
+/// Returns the generator of an epoch: seeded from `seed + epoch`, or forked from the global
+/// generator without a seed.
+fn epoch_rng(seed: Option<u64>, epoch: u64) -> StdRng {
+    match seed {
+        Some(seed) => rng_from_seed(seed.wrapping_add(epoch)),
+        None => fork_global_rng(),
+    }
+}
+
+/// A sampler that yields every sample in dataset order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SequentialSampler;
+
+impl SequentialSampler {
+    /// Creates a new `SequentialSampler`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Sampler for SequentialSampler {
+    fn num_samples(&self, dataset_len: usize) -> usize {
+        dataset_len
+    }
+
+    fn sample(&self, dataset_len: usize, _epoch: u64) -> Vec<usize> {
+        (0..dataset_len).collect()
+    }
+}
+
+/// A sampler that draws the samples uniformly at random.
+///
+/// Without replacement every sample is drawn once per epoch, in a new order every epoch. With
+/// replacement samples are drawn independently, so some may repeat and others be left out.
+#[derive(Debug, Clone, Default)]
+pub struct RandomSampler {
+    replacement: bool,
+    num_samples: Option<usize>,
+    seed: Option<u64>,
+}
+
+impl RandomSampler {
+    /// Creates a sampler that draws every sample once per epoch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether samples are drawn with replacement.
+    ///
+    /// # Arguments
+    ///
+    /// * `replacement` - Whether a sample can be drawn more than once per epoch.
+    pub fn with_replacement(mut self, replacement: bool) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// Sets the number of samples drawn per epoch, the dataset length by default.
+    ///
+    /// Without replacement, a number larger than the dataset draws full permutations one after
+    /// the other.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_samples` - The number of samples per epoch.
+    pub fn with_num_samples(mut self, num_samples: usize) -> Self {
+        self.num_samples = Some(num_samples);
+        self
+    }
+
+    /// Sets the seed of the sampler.
+    ///
+    /// Epoch `n` is drawn with a generator seeded from `seed + n`. Without a seed the global
+    /// generator is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the sampler.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Sampler for RandomSampler {
+    fn num_samples(&self, dataset_len: usize) -> usize {
+        self.num_samples.unwrap_or(dataset_len)
+    }
+
+    fn sample(&self, dataset_len: usize, epoch: u64) -> Vec<usize> {
+        let num_samples = self.num_samples(dataset_len);
+        if dataset_len == 0 {
+            return Vec::new();
+        }
+
+        let mut rng = epoch_rng(self.seed, epoch);
+        if self.replacement {
+            return (0..num_samples).map(|_| rng.gen_range(0..dataset_len)).collect();
+        }
+
+        let mut indices = Vec::with_capacity(num_samples);
+        while indices.len() < num_samples {
+            let mut permutation: Vec<usize> = (0..dataset_len).collect();
+            permutation.shuffle(&mut rng);
+            indices.extend(permutation.into_iter().take(num_samples - indices.len()));
+        }
+        indices
+    }
+}
+
+/// A sampler that draws each sample with a probability proportional to its weight.
+#[derive(Debug, Clone)]
+pub struct WeightedRandomSampler {
+    weights: Vec<f64>,
+    replacement: bool,
+    num_samples: Option<usize>,
+    seed: Option<u64>,
+}
+
+impl WeightedRandomSampler {
+    /// Creates a sampler that draws samples with replacement.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - The weight of each sample of the dataset. Weights do not need to sum to 1.
+    ///
+    /// # Returns
+    ///
+    /// A new `WeightedRandomSampler` instance.
+    pub fn new(weights: Vec<f64>) -> Self {
+        if weights.iter().any(|&weight| !weight.is_finite() || weight < 0.0) {
+            panic!("Sample weights must be finite and non-negative.");
+        }
+        if !weights.iter().any(|&weight| weight > 0.0) {
+            panic!("At least one sample weight must be positive.");
+        }
+        Self { weights, replacement: true, num_samples: None, seed: None }
+    }
+
+    /// Sets whether samples are drawn with replacement.
+    ///
+    /// Without replacement, each epoch is a weighted permutation of the samples with a positive
+    /// weight, so at most that many samples are drawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `replacement` - Whether a sample can be drawn more than once per epoch.
+    pub fn with_replacement(mut self, replacement: bool) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// Sets the number of samples drawn per epoch, the number of weights by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_samples` - The number of samples per epoch.
+    pub fn with_num_samples(mut self, num_samples: usize) -> Self {
+        self.num_samples = Some(num_samples);
+        self
+    }
+
+    /// Sets the seed of the sampler.
+    ///
+    /// Epoch `n` is drawn with a generator seeded from `seed + n`. Without a seed the global
+    /// generator is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the sampler.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Returns the weight of each sample.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+}
+
+impl Sampler for WeightedRandomSampler {
+    fn num_samples(&self, _dataset_len: usize) -> usize {
+        let num_samples = self.num_samples.unwrap_or(self.weights.len());
+        if self.replacement {
+            num_samples
+        } else {
+            num_samples.min(self.weights.iter().filter(|&&weight| weight > 0.0).count())
+        }
+    }
+
+    fn sample(&self, dataset_len: usize, epoch: u64) -> Vec<usize> {
+        if dataset_len != self.weights.len() {
+            panic!(
+                "Expected {} sample weights for the dataset, got {}",
+                dataset_len,
+                self.weights.len()
+            );
+        }
+
+        let num_samples = self.num_samples(dataset_len);
+        let mut rng = epoch_rng(self.seed, epoch);
+
+        if self.replacement {
+            let cumulative: Vec<f64> = self
+                .weights
+                .iter()
+                .scan(0.0, |total, &weight| {
+                    *total += weight;
+                    Some(*total)
+                })
+                .collect();
+            let total = *cumulative.last().unwrap();
+            return (0..num_samples)
+                .map(|_| {
+                    let target = rng.gen::<f64>() * total;
+                    cumulative.partition_point(|&sum| sum <= target).min(dataset_len - 1)
+                })
+                .map(|index| self.skip_zero_weight(index))
+                .collect();
+        }
+
+        // Sorting by the key u^(1/w) draws a weighted permutation (Efraimidis and Spirakis)
+        let mut keys: Vec<(f64, usize)> = self
+            .weights
+            .iter()
+            .enumerate()
+            .filter(|(_, &weight)| weight > 0.0)
+            .map(|(index, &weight)| (rng.gen::<f64>().powf(1.0 / weight), index))
+            .collect();
+        keys.sort_by(|a, b| b.0.total_cmp(&a.0));
+        keys.into_iter().take(num_samples).map(|(_, index)| index).collect()
+    }
+}
+
+impl WeightedRandomSampler {
+    /// Moves an index drawn at a boundary of the cumulative weights off samples of zero weight.
+    fn skip_zero_weight(&self, index: usize) -> usize {
+        if self.weights[index] > 0.0 {
+            return index;
+        }
+        (index..self.weights.len())
+            .chain((0..index).rev())
+            .find(|&index| self.weights[index] > 0.0)
+            .unwrap()
+    }
+}
+
+/// A sampler that draws every class equally often, for imbalanced datasets.
+///
+/// Each sample is weighted by the inverse of the size of its class and drawn with replacement, so
+/// the samples of rare classes repeat within an epoch.
+#[derive(Debug, Clone)]
+pub struct ClassBalancedSampler {
+    sampler: WeightedRandomSampler,
+}
+
+impl ClassBalancedSampler {
+    /// Creates a new `ClassBalancedSampler`.
+    ///
+    /// # Arguments
+    ///
+    /// * `classes` - The class of each sample, such as `Dataset::sample_classes`.
+    ///
+    /// # Returns
+    ///
+    /// A new `ClassBalancedSampler` instance.
+    pub fn new(classes: &[usize]) -> Self {
+        let num_classes = classes.iter().max().map_or(0, |&class| class + 1);
+        let mut counts = vec![0usize; num_classes];
+        for &class in classes {
+            counts[class] += 1;
+        }
+
+        let weights = classes.iter().map(|&class| 1.0 / counts[class] as f64).collect();
+        Self { sampler: WeightedRandomSampler::new(weights) }
+    }
+
+    /// Sets the number of samples drawn per epoch, the dataset length by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_samples` - The number of samples per epoch.
+    pub fn with_num_samples(mut self, num_samples: usize) -> Self {
+        self.sampler = self.sampler.with_num_samples(num_samples);
+        self
+    }
+
+    /// Sets the seed of the sampler.
+    ///
+    /// Epoch `n` is drawn with a generator seeded from `seed + n`. Without a seed the global
+    /// generator is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the sampler.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.sampler = self.sampler.with_seed(seed);
+        self
+    }
+}
+
+impl Sampler for ClassBalancedSampler {
+    fn num_samples(&self, dataset_len: usize) -> usize {
+        self.sampler.num_samples(dataset_len)
+    }
+
+    fn sample(&self, dataset_len: usize, epoch: u64) -> Vec<usize> {
+        self.sampler.sample(dataset_len, epoch)
+    }
+}
+
+/// A sampler that gives each of several replicas a disjoint share of the samples, for
+/// distributed training.
+///
+/// Every replica shuffles with the same seed, so the shares partition the same order. Unless the
+/// last samples are dropped, samples from the start of the order are repeated so that every
+/// replica gets the same number of samples.
+#[derive(Debug, Clone)]
+pub struct DistributedSampler {
+    num_replicas: usize,
+    rank: usize,
+    shuffle: bool,
+    seed: u64,
+    drop_last: bool,
+}
+
+impl DistributedSampler {
+    /// Creates a sampler for one replica that shuffles with the seed 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_replicas` - The number of replicas.
+    /// * `rank` - The index of this replica.
+    ///
+    /// # Returns
+    ///
+    /// A new `DistributedSampler` instance.
+    pub fn new(num_replicas: usize, rank: usize) -> Self {
+        if num_replicas == 0 {
+            panic!("Number of replicas must be greater than 0.");
+        }
+        if rank >= num_replicas {
+            panic!("Rank must be less than the number of replicas.");
+        }
+        Self { num_replicas, rank, shuffle: true, seed: 0, drop_last: false }
+    }
+
+    /// Sets whether the order is shuffled every epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `shuffle` - Whether to shuffle the order.
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Sets the seed shared by every replica.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the shuffle.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets whether the samples left over after an equal share per replica are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `drop_last` - Whether to drop the leftover samples instead of repeating samples.
+    pub fn with_drop_last(mut self, drop_last: bool) -> Self {
+        self.drop_last = drop_last;
+        self
+    }
+}
+
+impl Sampler for DistributedSampler {
+    fn num_samples(&self, dataset_len: usize) -> usize {
+        if self.drop_last {
+            dataset_len / self.num_replicas
+        } else {
+            dataset_len.div_ceil(self.num_replicas)
+        }
+    }
+
+    fn sample(&self, dataset_len: usize, epoch: u64) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..dataset_len).collect();
+        if self.shuffle {
+            order.shuffle(&mut rng_from_seed(self.seed.wrapping_add(epoch)));
+        }
+
+        let total = self.num_samples(dataset_len) * self.num_replicas;
+        if total <= order.len() {
+            order.truncate(total);
+        } else if !order.is_empty() {
+            let padding: Vec<usize> =
+                order.iter().cycle().take(total - order.len()).copied().collect();
+            order.extend(padding);
+        }
+
+        order.into_iter().skip(self.rank).step_by(self.num_replicas).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequential_sampler_keeps_dataset_order() {
+        assert_eq!(SequentialSampler::new().sample(4, 3), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_random_sampler_without_replacement_is_a_seeded_permutation() {
+        let sampler = RandomSampler::new().with_seed(7);
+        let first = sampler.sample(10, 0);
+
+        let mut sorted = first.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+        assert_eq!(first, sampler.sample(10, 0));
+        assert_ne!(first, sampler.sample(10, 1));
+
+        let longer = sampler.with_num_samples(15).sample(10, 0);
+        assert_eq!(longer.len(), 15);
+        assert_eq!(longer[..10], first);
+    }
+
+    #[test]
+    fn test_random_sampler_with_replacement_repeats_samples() {
+        let sampler = RandomSampler::new().with_replacement(true).with_num_samples(50).with_seed(1);
+        let indices = sampler.sample(5, 0);
+
+        assert_eq!(indices.len(), 50);
+        assert!(indices.iter().all(|&index| index < 5));
+        assert!((0..5).all(|index| indices.contains(&index)));
+    }
+
+    #[test]
+    fn test_weighted_random_sampler_follows_the_weights() {
+        let sampler =
+            WeightedRandomSampler::new(vec![1.0, 0.0, 3.0]).with_num_samples(4000).with_seed(2);
+        let indices = sampler.sample(3, 0);
+
+        let count = |target| indices.iter().filter(|&&index| index == target).count();
+        assert_eq!(count(1), 0);
+        assert!((900..1100).contains(&count(0)), "{}", count(0));
+    }
+
+    #[test]
+    fn test_weighted_random_sampler_without_replacement_skips_zero_weights() {
+        let sampler = WeightedRandomSampler::new(vec![1.0, 0.0, 5.0, 2.0])
+            .with_replacement(false)
+            .with_seed(4);
+
+        let mut indices = sampler.sample(4, 0);
+        assert_eq!(sampler.num_samples(4), 3);
+        indices.sort_unstable();
+        assert_eq!(indices, [0, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected 2 sample weights for the dataset, got 3")]
+    fn test_weighted_random_sampler_rejects_mismatched_weights() {
+        WeightedRandomSampler::new(vec![1.0; 3]).sample(2, 0);
+    }
+
+    #[test]
+    fn test_class_balanced_sampler_draws_rare_classes_as_often() {
+        let mut classes = vec![0; 500];
+        classes.push(1);
+        let sampler = ClassBalancedSampler::new(&classes).with_seed(3);
+
+        let indices = sampler.sample(classes.len(), 0);
+        let rare = indices.iter().filter(|&&index| classes[index] == 1).count();
+
+        assert_eq!(indices.len(), 501);
+        assert!((200..300).contains(&rare), "{}", rare);
+    }
+
+    #[test]
+    fn test_distributed_sampler_partitions_the_order() {
+        let shares: Vec<Vec<usize>> = (0..3)
+            .map(|rank| DistributedSampler::new(3, rank).with_seed(5).sample(10, 2))
+            .collect();
+
+        assert!(shares.iter().all(|share| share.len() == 4));
+        let mut all: Vec<usize> = shares.concat();
+        all.sort_unstable();
+        all.dedup();
+        assert_eq!(all, (0..10).collect::<Vec<_>>());
+
+        let dropped = DistributedSampler::new(3, 0).with_shuffle(false).with_drop_last(true);
+        assert_eq!(dropped.sample(10, 0), [0, 3, 6]);
+    }
+}
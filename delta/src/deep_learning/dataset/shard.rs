@@ -0,0 +1,706 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A compact on-disk format for datasets larger than memory.
+//!
+//! A sharded dataset is a directory with an `index.json` and one or more shard files. Each shard
+//! starts with a header of the `SHARD_MAGIC` bytes, the format version as a little-endian `u32`,
+//! the number of values per sample as a `u32` and the number of samples as a `u64`. The header is
+//! followed by the `u8` values of every sample and, if the dataset is labeled, a little-endian
+//! `u32` label per sample. The index lists the shards in order with their sample counts, the shape
+//! of a sample and the classes.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use memmap2::Mmap;
+use ndarray::{IxDyn, Shape};
+use serde_json::{Value, json};
+
+use super::IndexedDataset;
+use crate::deep_learning::tensor_ops::Tensor;
+use crate::devices::Device;
+
+/// The bytes every shard file starts with.
+pub const SHARD_MAGIC: &[u8; 8] = b"DELTASHD";
+
+/// The version of the shard format written by `ShardWriter`.
+pub const SHARD_VERSION: u32 = 1;
+
+const HEADER_LEN: usize = 24;
+const INDEX_FILENAME: &str = "index.json";
+
+/// Writes samples into a sharded dataset directory.
+///
+/// Samples are streamed to the current shard as they are written, so only the labels of one
+/// shard are kept in memory.
+#[derive(Debug)]
+pub struct ShardWriter {
+    dir: PathBuf,
+    shape: Vec<usize>,
+    sample_len: usize,
+    num_classes: usize,
+    classes: Vec<String>,
+    shard_size: usize,
+    labeled: Option<bool>,
+    current: Option<(BufWriter<File>, Vec<u32>)>,
+    shards: Vec<(String, usize)>,
+}
+
+impl ShardWriter {
+    /// The default number of samples per shard.
+    pub const DEFAULT_SHARD_SIZE: usize = 10_000;
+
+    /// Creates a writer that writes shards into a directory, creating it if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory of the sharded dataset.
+    /// * `shape` - The shape of a sample, such as `[height, width, channels]`.
+    /// * `num_classes` - The number of classes of the labels.
+    ///
+    /// # Returns
+    ///
+    /// The writer, or an error if the directory could not be created.
+    pub fn create(
+        dir: impl AsRef<Path>,
+        shape: &[usize],
+        num_classes: usize,
+    ) -> Result<Self, String> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+        Ok(Self {
+            dir,
+            shape: shape.to_vec(),
+            sample_len: shape.iter().product(),
+            num_classes,
+            classes: Vec::new(),
+            shard_size: Self::DEFAULT_SHARD_SIZE,
+            labeled: None,
+            current: None,
+            shards: Vec::new(),
+        })
+    }
+
+    /// Sets the names of the classes stored in the index.
+    ///
+    /// # Arguments
+    ///
+    /// * `classes` - The name of each class, in label order.
+    ///
+    /// # Returns
+    ///
+    /// The writer with the class names.
+    pub fn with_classes(mut self, classes: Vec<String>) -> Self {
+        if classes.len() != self.num_classes {
+            panic!("Number of class names must match the number of classes.");
+        }
+        self.classes = classes;
+        self
+    }
+
+    /// Sets the maximum number of samples per shard.
+    ///
+    /// # Arguments
+    ///
+    /// * `shard_size` - The number of samples per shard.
+    ///
+    /// # Returns
+    ///
+    /// The writer with the shard size.
+    pub fn with_shard_size(mut self, shard_size: usize) -> Self {
+        if shard_size == 0 {
+            panic!("Shard size must be greater than 0.");
+        }
+        self.shard_size = shard_size;
+        self
+    }
+
+    /// Writes a sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values of the sample, in the order of its shape.
+    /// * `label` - The label of the sample, or `None` for an unlabeled dataset.
+    ///
+    /// # Returns
+    ///
+    /// An error if the sample does not match the shape, the label is out of range, labeled and
+    /// unlabeled samples are mixed or the shard could not be written.
+    pub fn write(&mut self, values: &[u8], label: Option<usize>) -> Result<(), String> {
+        if values.len() != self.sample_len {
+            return Err(format!(
+                "Expected a sample of {} values, got {}",
+                self.sample_len,
+                values.len()
+            ));
+        }
+        if let Some(label) = label.filter(|&label| label >= self.num_classes) {
+            return Err(format!(
+                "Label {} is out of range for {} classes",
+                label, self.num_classes
+            ));
+        }
+        if *self.labeled.get_or_insert(label.is_some()) != label.is_some() {
+            return Err("Cannot mix labeled and unlabeled samples".to_string());
+        }
+
+        if self.current.as_ref().is_some_and(|(_, labels)| labels.len() == self.shard_size) {
+            self.finish_shard()?;
+        }
+        if self.current.is_none() {
+            self.start_shard()?;
+        }
+
+        let (writer, labels) = self.current.as_mut().unwrap();
+        writer.write_all(values).map_err(|e| format!("Failed to write shard: {}", e))?;
+        labels.push(label.unwrap_or(0) as u32);
+        Ok(())
+    }
+
+    /// Writes every sample of a dataset, such as a loaded CIFAR-10 split.
+    ///
+    /// Inputs are expected in `[0, 1]` and are stored as `u8`, so images loaded from 8-bit files
+    /// are written without loss. Targets are one-hot rows, single class indices or empty for
+    /// unlabeled samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataset` - The dataset to write.
+    ///
+    /// # Returns
+    ///
+    /// An error if a sample does not match the shape of the shards or could not be written.
+    pub fn write_dataset<D: IndexedDataset + ?Sized>(&mut self, dataset: &D) -> Result<(), String> {
+        let mut values = Vec::with_capacity(self.sample_len);
+        for index in 0..dataset.len() {
+            let (input, target) = dataset.get(index);
+            values.clear();
+            values.extend(
+                input.data.iter().map(|&value| (value * 255.0).round().clamp(0.0, 255.0) as u8),
+            );
+
+            let label = match target.data.len() {
+                0 => None,
+                1 => target.data.iter().next().map(|&class| class.round() as usize),
+                _ => target
+                    .data
+                    .iter()
+                    .enumerate()
+                    .fold(None, |best: Option<(usize, f32)>, (class, &value)| match best {
+                        Some((_, best_value)) if best_value >= value => best,
+                        _ => Some((class, value)),
+                    })
+                    .map(|(class, _)| class),
+            };
+            self.write(&values, label)?;
+        }
+        Ok(())
+    }
+
+    /// Finishes the last shard and writes the index.
+    ///
+    /// # Returns
+    ///
+    /// The number of samples written, or an error if the files could not be written.
+    pub fn finish(mut self) -> Result<usize, String> {
+        self.finish_shard()?;
+
+        let index = json!({
+            "version": SHARD_VERSION,
+            "shape": self.shape,
+            "num_classes": self.num_classes,
+            "classes": self.classes,
+            "labeled": self.labeled.unwrap_or(false),
+            "shards": self
+                .shards
+                .iter()
+                .map(|(file, samples)| json!({ "file": file, "samples": samples }))
+                .collect::<Vec<_>>(),
+        });
+        let path = self.dir.join(INDEX_FILENAME);
+        fs::write(&path, index.to_string())
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+        Ok(self.shards.iter().map(|(_, samples)| samples).sum())
+    }
+
+    /// Creates the next shard file and writes a header for it.
+    fn start_shard(&mut self) -> Result<(), String> {
+        let name = format!("shard-{:05}.bin", self.shards.len());
+        let path = self.dir.join(&name);
+        let file = File::create(&path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+
+        let mut writer = BufWriter::new(file);
+        writer
+            .write_all(&header(self.sample_len, 0))
+            .map_err(|e| format!("Failed to write shard: {}", e))?;
+        self.current = Some((writer, Vec::with_capacity(self.shard_size)));
+        self.shards.push((name, 0));
+        Ok(())
+    }
+
+    /// Appends the labels to the current shard and updates the sample count of its header.
+    fn finish_shard(&mut self) -> Result<(), String> {
+        let Some((mut writer, labels)) = self.current.take() else {
+            return Ok(());
+        };
+
+        let labeled = self.labeled == Some(true);
+        let write = |writer: &mut BufWriter<File>| -> std::io::Result<()> {
+            if labeled {
+                for label in &labels {
+                    writer.write_all(&label.to_le_bytes())?;
+                }
+            }
+            writer.seek(SeekFrom::Start(0))?;
+            writer.write_all(&header(self.sample_len, labels.len()))?;
+            writer.flush()
+        };
+        write(&mut writer).map_err(|e| format!("Failed to write shard: {}", e))?;
+
+        self.shards.last_mut().unwrap().1 = labels.len();
+        Ok(())
+    }
+}
+
+/// Returns the header of a shard.
+fn header(sample_len: usize, count: usize) -> [u8; HEADER_LEN] {
+    let mut header = [0; HEADER_LEN];
+    header[..8].copy_from_slice(SHARD_MAGIC);
+    header[8..12].copy_from_slice(&SHARD_VERSION.to_le_bytes());
+    header[12..16].copy_from_slice(&(sample_len as u32).to_le_bytes());
+    header[16..].copy_from_slice(&(count as u64).to_le_bytes());
+    header
+}
+
+/// A memory-mapped shard file.
+#[derive(Debug)]
+struct Shard {
+    map: Mmap,
+    len: usize,
+}
+
+impl Shard {
+    /// Maps a shard file and validates its header, size and labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the shard file.
+    /// * `sample_len` - The number of values per sample.
+    /// * `num_classes` - The number of classes, or `None` if the samples are unlabeled.
+    ///
+    /// # Returns
+    ///
+    /// The shard, or an error if the file is not a valid shard.
+    fn open(path: &Path, sample_len: usize, num_classes: Option<usize>) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        // SAFETY: Shards are written once by `ShardWriter` and only read afterwards; the map is
+        // invalid if another process truncates the file while it is open.
+        let map = unsafe { Mmap::map(&file) }
+            .map_err(|e| format!("Failed to map {}: {}", path.display(), e))?;
+
+        if map.len() < HEADER_LEN || &map[..8] != SHARD_MAGIC {
+            return Err(format!("{} is not a shard file", path.display()));
+        }
+        let version = u32::from_le_bytes(map[8..12].try_into().unwrap());
+        if version != SHARD_VERSION {
+            return Err(format!("Unsupported shard version {} in {}", version, path.display()));
+        }
+        let stored_len = u32::from_le_bytes(map[12..16].try_into().unwrap()) as usize;
+        if stored_len != sample_len {
+            return Err(format!(
+                "Expected {} values per sample in {}, found {}",
+                sample_len,
+                path.display(),
+                stored_len
+            ));
+        }
+
+        let count = u64::from_le_bytes(map[16..24].try_into().unwrap());
+        let label_len = if num_classes.is_some() { 4 } else { 0 };
+        let invalid = || format!("Invalid sample count {} in {}", count, path.display());
+        let len = usize::try_from(count).map_err(|_| invalid())?;
+        let expected = sample_len
+            .checked_add(label_len)
+            .and_then(|size| size.checked_mul(len))
+            .and_then(|size| size.checked_add(HEADER_LEN))
+            .ok_or_else(invalid)?;
+        if map.len() != expected {
+            return Err(format!(
+                "Shard {} is truncated: expected {} bytes, found {}",
+                path.display(),
+                expected,
+                map.len()
+            ));
+        }
+
+        if let Some(num_classes) = num_classes {
+            let mut labels = map[HEADER_LEN + len * sample_len..]
+                .chunks_exact(4)
+                .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()) as usize);
+            if let Some(label) = labels.find(|&label| label >= num_classes) {
+                return Err(format!(
+                    "Label {} in {} is out of range for {} classes",
+                    label,
+                    path.display(),
+                    num_classes
+                ));
+            }
+        }
+
+        Ok(Self { map, len })
+    }
+}
+
+/// A sharded dataset read through memory maps.
+///
+/// Samples are paged in from disk by the operating system as they are read and stay `u8` until a
+/// batch is converted to `f32`, so datasets larger than memory can be trained on with a
+/// `DataLoader`. Cloning shares the maps.
+#[derive(Debug, Clone)]
+pub struct ShardedDataset {
+    shards: Arc<[Shard]>,
+    offsets: Arc<[usize]>,
+    shape: Vec<usize>,
+    sample_len: usize,
+    num_classes: usize,
+    classes: Arc<[String]>,
+    labeled: bool,
+    device: Device,
+}
+
+impl ShardedDataset {
+    /// Opens a sharded dataset written by `ShardWriter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory of the sharded dataset.
+    ///
+    /// # Returns
+    ///
+    /// The dataset, or an error if the index or a shard is missing or invalid.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, String> {
+        let dir = dir.as_ref();
+        let path = dir.join(INDEX_FILENAME);
+        let json = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let index: Value =
+            serde_json::from_str(&json).map_err(|e| format!("Invalid shard index: {}", e))?;
+
+        if index["version"].as_u64() != Some(SHARD_VERSION as u64) {
+            return Err(format!("Unsupported shard index version: {}", index["version"]));
+        }
+        let count = |value: &Value, field: &str| {
+            value[field]
+                .as_u64()
+                .map(|count| count as usize)
+                .ok_or_else(|| format!("Missing {} in the shard index", field))
+        };
+        let shape: Vec<usize> = index["shape"]
+            .as_array()
+            .and_then(|dims| dims.iter().map(|dim| dim.as_u64().map(|dim| dim as usize)).collect())
+            .ok_or("Missing shape in the shard index")?;
+        let num_classes = count(&index, "num_classes")?;
+        let classes: Vec<String> = index["classes"]
+            .as_array()
+            .map(|names| names.iter().filter_map(|name| name.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        let labeled = index["labeled"].as_bool().unwrap_or(false);
+        let sample_len = shape.iter().product();
+
+        let mut shards = Vec::new();
+        let mut offsets = vec![0];
+        for entry in index["shards"].as_array().ok_or("Missing shards in the shard index")? {
+            let file = entry["file"].as_str().ok_or("Missing file in the shard index")?;
+            let shard = Shard::open(&dir.join(file), sample_len, labeled.then_some(num_classes))?;
+            if shard.len != count(entry, "samples")? {
+                return Err(format!("Shard {} does not match the index", file));
+            }
+            offsets.push(offsets.last().unwrap() + shard.len);
+            shards.push(shard);
+        }
+
+        Ok(Self {
+            shards: shards.into(),
+            offsets: offsets.into(),
+            shape,
+            sample_len,
+            num_classes,
+            classes: classes.into(),
+            labeled,
+            device: Device::default(),
+        })
+    }
+
+    /// Returns the shape of a sample.
+    pub fn sample_shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Returns the number of classes of the labels.
+    pub fn num_classes(&self) -> usize {
+        self.num_classes
+    }
+
+    /// Returns the names of the classes, if they were written.
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// Returns `true` if the samples have labels.
+    pub fn is_labeled(&self) -> bool {
+        self.labeled
+    }
+
+    /// Returns the stored values of a sample, without copying them.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the sample.
+    ///
+    /// # Returns
+    ///
+    /// The `u8` values of the sample.
+    pub fn values(&self, index: usize) -> &[u8] {
+        let (shard, local) = self.locate(index);
+        let start = HEADER_LEN + local * self.sample_len;
+        &shard.map[start..start + self.sample_len]
+    }
+
+    /// Returns the label of a sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the sample.
+    ///
+    /// # Returns
+    ///
+    /// The label, or `None` if the dataset is unlabeled.
+    pub fn label(&self, index: usize) -> Option<usize> {
+        if !self.labeled {
+            return None;
+        }
+        let (shard, local) = self.locate(index);
+        let start = HEADER_LEN + shard.len * self.sample_len + local * 4;
+        Some(u32::from_le_bytes(shard.map[start..start + 4].try_into().unwrap()) as usize)
+    }
+
+    /// Sets the device of the batches read from the dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device of the batches.
+    pub fn to_device(&mut self, device: &Device) {
+        self.device = device.clone();
+    }
+
+    /// Returns the shard of a sample and the index of the sample in it.
+    fn locate(&self, index: usize) -> (&Shard, usize) {
+        if index >= IndexedDataset::len(self) {
+            panic!(
+                "Sample index {} is out of range for {} samples",
+                index,
+                IndexedDataset::len(self)
+            );
+        }
+        let shard = self.offsets.partition_point(|&offset| offset <= index) - 1;
+        (&self.shards[shard], index - self.offsets[shard])
+    }
+
+    /// Returns the number of target values per sample.
+    fn num_targets(&self) -> usize {
+        if self.labeled { self.num_classes } else { 0 }
+    }
+
+    /// Converts the samples at the given indices to `f32` inputs in `[0, 1]` and one-hot targets.
+    fn decode(&self, indices: &[usize]) -> (Vec<f32>, Vec<f32>) {
+        let num_targets = self.num_targets();
+        let mut inputs = Vec::with_capacity(indices.len() * self.sample_len);
+        let mut targets = vec![0.0; indices.len() * num_targets];
+
+        for (i, &index) in indices.iter().enumerate() {
+            inputs.extend(self.values(index).iter().map(|&value| value as f32 / 255.0));
+            if let Some(label) = self.label(index) {
+                targets[i * num_targets + label] = 1.0;
+            }
+        }
+
+        (inputs, targets)
+    }
+
+    /// Creates a tensor on the device of the dataset.
+    fn tensor(&self, data: Vec<f32>, shape: &[usize]) -> Tensor {
+        Tensor { device: self.device.clone(), ..Tensor::new(data, Shape::from(IxDyn(shape))) }
+    }
+}
+
+impl IndexedDataset for ShardedDataset {
+    fn len(&self) -> usize {
+        *self.offsets.last().unwrap()
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        let (input, target) = self.decode(&[index]);
+        (self.tensor(input, &self.shape), self.tensor(target, &[self.num_targets()]))
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        let (inputs, targets) = self.decode(indices);
+        let mut shape = vec![indices.len()];
+        shape.extend(&self.shape);
+        (self.tensor(inputs, &shape), self.tensor(targets, &[indices.len(), self.num_targets()]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::deep_learning::dataset::{DataLoader, Dataset};
+
+    /// Returns a missing shard directory inside a temporary directory, which is removed when the
+    /// returned `TempDir` is dropped.
+    fn shard_dir() -> (TempDir, PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("shards");
+        (temp, dir)
+    }
+
+    /// Five `[2, 1, 2]` samples with the values `10 * i` and `10 * i + 1` and the labels `i % 3`.
+    fn dataset() -> Dataset {
+        let inputs = (0..5)
+            .flat_map(|i| [10 * i, 10 * i, 10 * i + 1, 10 * i + 1])
+            .map(|value| value as f32 / 255.0);
+        let labels = (0..5).flat_map(|i| (0..3).map(move |class| (class == i % 3) as u8 as f32));
+        Dataset::new(
+            Tensor::new(inputs.collect(), Shape::from(IxDyn(&[5, 2, 1, 2]))),
+            Tensor::new(labels.collect(), Shape::from(IxDyn(&[5, 3]))),
+        )
+    }
+
+    fn write_shards(dir: &Path, shard_size: usize) {
+        let mut writer = ShardWriter::create(dir, &[2, 1, 2], 3)
+            .unwrap()
+            .with_classes(vec!["a".into(), "b".into(), "c".into()])
+            .with_shard_size(shard_size);
+        writer.write_dataset(&dataset()).unwrap();
+        assert_eq!(writer.finish().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_round_trip_across_shards() {
+        let (_temp, dir) = shard_dir();
+        write_shards(&dir, 2);
+
+        let shards = ShardedDataset::open(&dir).unwrap();
+        assert_eq!(shards.len(), 5);
+        assert_eq!(shards.shards.len(), 3);
+        assert_eq!(shards.classes(), ["a", "b", "c"]);
+        assert_eq!(shards.values(3), [30, 30, 31, 31]);
+        assert_eq!(shards.label(4), Some(1));
+
+        let (inputs, targets) = shards.get_batch(&[4, 0, 2]);
+        let (expected_inputs, expected_targets) = dataset().get_batch(&[4, 0, 2]);
+        assert_eq!(inputs.data, expected_inputs.data);
+        assert_eq!(targets.data, expected_targets.data);
+
+        let (input, target) = shards.get(1);
+        assert_eq!(input.data.shape(), &[2, 1, 2]);
+        assert_eq!(target.data.as_slice().unwrap(), [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_data_loader_reads_every_sample_once() {
+        let (_temp, dir) = shard_dir();
+        write_shards(&dir, 3);
+
+        let loader =
+            DataLoader::new(ShardedDataset::open(&dir).unwrap(), 2).with_shuffle(true).with_seed(3);
+        let mut firsts: Vec<f32> =
+            loader.iter().flat_map(|batch| batch.inputs.data.into_iter().step_by(4)).collect();
+        firsts.sort_by(f32::total_cmp);
+
+        assert_eq!(firsts, (0..5).map(|i| (10 * i) as f32 / 255.0).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_unlabeled_samples_have_empty_targets() {
+        let (_temp, dir) = shard_dir();
+        let mut writer = ShardWriter::create(&dir, &[2], 0).unwrap();
+        writer.write(&[1, 2], None).unwrap();
+        assert!(writer.write(&[1, 2], Some(0)).is_err());
+        writer.finish().unwrap();
+
+        let shards = ShardedDataset::open(&dir).unwrap();
+        assert!(!shards.is_labeled());
+        assert_eq!(shards.label(0), None);
+        assert_eq!(shards.get_batch(&[0]).1.data.shape(), &[1, 0]);
+    }
+
+    #[test]
+    fn test_writer_rejects_invalid_samples() {
+        let (_temp, dir) = shard_dir();
+        let mut writer = ShardWriter::create(&dir, &[2, 2], 2).unwrap();
+
+        assert!(writer.write(&[0; 3], Some(0)).unwrap_err().contains("Expected a sample of 4"));
+        assert!(writer.write(&[0; 4], Some(2)).unwrap_err().contains("out of range"));
+    }
+
+    #[test]
+    fn test_open_rejects_corrupt_shards() {
+        let (_temp, dir) = shard_dir();
+        write_shards(&dir, 5);
+
+        let path = dir.join("shard-00000.bin");
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        assert!(ShardedDataset::open(&dir).unwrap_err().contains("truncated"));
+
+        fs::write(&path, b"NOT A SHARD FILE").unwrap();
+        assert!(ShardedDataset::open(&dir).unwrap_err().contains("is not a shard file"));
+
+        fs::write(&path, header(4, usize::MAX)).unwrap();
+        assert!(ShardedDataset::open(&dir).unwrap_err().contains("Invalid sample count"));
+
+        // The label of the last sample is stored in the last four bytes
+        let mut corrupt = bytes;
+        let last = corrupt.len() - 4;
+        corrupt[last..].copy_from_slice(&3u32.to_le_bytes());
+        fs::write(&path, corrupt).unwrap();
+        assert!(ShardedDataset::open(&dir).unwrap_err().contains("out of range for 3 classes"));
+    }
+}
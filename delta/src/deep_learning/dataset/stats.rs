@@ -0,0 +1,504 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+
+use image::{Rgb, RgbImage};
+use ndarray::{ArrayD, Axis, Ix2};
+use sha2::{Digest, Sha256};
+
+use crate::deep_learning::dataset::IndexedDataset;
+
+/// The number of samples read at a time when scanning a dataset.
+const SCAN_BATCH_SIZE: usize = 256;
+
+/// Running count, mean, variance and range of a stream of values.
+///
+/// The mean and variance are updated with Welford's algorithm in `f64`, so they stay accurate
+/// over millions of values. NaN and infinite values are counted separately and left out of the
+/// statistics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningStats {
+    count: u64,
+    non_finite: u64,
+    mean: f64,
+    m2: f64,
+    min: f32,
+    max: f32,
+}
+
+impl Default for RunningStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RunningStats {
+    /// Creates statistics of an empty stream.
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            non_finite: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f32::INFINITY,
+            max: f32::NEG_INFINITY,
+        }
+    }
+
+    /// Adds a value to the statistics.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to add.
+    pub fn push(&mut self, value: f32) {
+        if !value.is_finite() {
+            self.non_finite += 1;
+            return;
+        }
+
+        self.count += 1;
+        let delta = value as f64 - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value as f64 - self.mean);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Adds the values of other statistics, as if they had been pushed to these.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The statistics to merge.
+    pub fn merge(&mut self, other: &RunningStats) {
+        self.non_finite += other.non_finite;
+        if other.count == 0 {
+            return;
+        }
+
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 += other.m2 + delta * delta * self.count as f64 * other.count as f64 / count as f64;
+        self.count = count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Returns the number of finite values.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the number of NaN and infinite values, which are left out of the statistics.
+    pub fn non_finite(&self) -> u64 {
+        self.non_finite
+    }
+
+    /// Returns the mean, or 0 without values.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the population variance, or 0 without values.
+    pub fn variance(&self) -> f64 {
+        if self.count == 0 { 0.0 } else { self.m2 / self.count as f64 }
+    }
+
+    /// Returns the population standard deviation, or 0 without values.
+    pub fn std(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Returns the smallest value, or `None` without values.
+    pub fn min(&self) -> Option<f32> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// Returns the largest value, or `None` without values.
+    pub fn max(&self) -> Option<f32> {
+        (self.count > 0).then_some(self.max)
+    }
+}
+
+/// Calls `f` with the inputs and targets of every batch of the dataset, in order.
+fn for_each_batch<D, F>(dataset: &D, mut f: F)
+where
+    D: IndexedDataset + ?Sized,
+    F: FnMut(usize, &ArrayD<f32>, &ArrayD<f32>),
+{
+    let indices: Vec<usize> = (0..dataset.len()).collect();
+    for chunk in indices.chunks(SCAN_BATCH_SIZE) {
+        let (inputs, targets) = dataset.get_batch(chunk);
+        f(chunk[0], &inputs.data, &targets.data);
+    }
+}
+
+/// Computes the statistics of each channel of the inputs, reading the dataset in batches.
+///
+/// Use the means and standard deviations to normalize the inputs, and the ranges to check that
+/// they were scaled as expected.
+///
+/// # Arguments
+///
+/// * `dataset` - The dataset to scan.
+/// * `channel_axis` - The axis of the channels in the shape of a sample, or `None` for its last
+///   axis, such as the `[height, width, channels]` images of the vision datasets.
+///
+/// # Returns
+///
+/// The statistics of each channel, or of all the input values for scalar samples.
+pub fn channel_stats<D: IndexedDataset + ?Sized>(
+    dataset: &D,
+    channel_axis: Option<usize>,
+) -> Vec<RunningStats> {
+    let mut stats: Vec<RunningStats> = Vec::new();
+    for_each_batch(dataset, |_, inputs, _| {
+        if inputs.ndim() == 1 {
+            stats.resize(1, RunningStats::new());
+            inputs.iter().for_each(|&value| stats[0].push(value));
+            return;
+        }
+
+        let axis = channel_axis.map_or(inputs.ndim() - 1, |axis| axis + 1);
+        if axis >= inputs.ndim() {
+            panic!(
+                "Channel axis {} is out of range for samples of {} axes",
+                axis - 1,
+                inputs.ndim() - 1
+            );
+        }
+        stats.resize(inputs.len_of(Axis(axis)), RunningStats::new());
+        for (channel, values) in inputs.axis_iter(Axis(axis)).enumerate() {
+            values.iter().for_each(|&value| stats[channel].push(value));
+        }
+    });
+    stats
+}
+
+/// Returns the class of each row of labels: the index of the largest value of one-hot labels, or
+/// the value of labels with a single value per sample.
+pub(crate) fn label_classes(labels: &ArrayD<f32>) -> Vec<usize> {
+    let num_samples = labels.shape().first().copied().unwrap_or(0);
+    let labels = labels
+        .to_shape((num_samples, labels.len() / num_samples.max(1)))
+        .expect("Labels must have a sample axis");
+    let labels = labels.into_dimensionality::<Ix2>().unwrap();
+
+    labels
+        .rows()
+        .into_iter()
+        .map(|row| match row.len() {
+            1 => row[0].max(0.0).round() as usize,
+            _ => {
+                row.iter()
+                    .enumerate()
+                    .fold((0, f32::NEG_INFINITY), |best, (class, &value)| {
+                        if value > best.1 { (class, value) } else { best }
+                    })
+                    .0
+            }
+        })
+        .collect()
+}
+
+/// Counts the samples of each class.
+///
+/// # Arguments
+///
+/// * `dataset` - The dataset to scan, with one-hot labels or a single class value per sample.
+///
+/// # Returns
+///
+/// The number of samples of each class, up to the largest class found.
+pub fn class_distribution<D: IndexedDataset + ?Sized>(dataset: &D) -> Vec<usize> {
+    let mut counts = Vec::new();
+    for_each_batch(dataset, |_, _, targets| {
+        for class in label_classes(targets) {
+            if class >= counts.len() {
+                counts.resize(class + 1, 0);
+            }
+            counts[class] += 1;
+        }
+    });
+    counts
+}
+
+/// Finds the samples whose inputs are identical, by hashing their values.
+///
+/// Duplicates inflate the apparent size of a dataset and, across a training and a test split,
+/// leak test samples into training; check both with a `ConcatDataset`.
+///
+/// # Arguments
+///
+/// * `dataset` - The dataset to scan.
+///
+/// # Returns
+///
+/// The groups of indices of identical samples, each in increasing order, ordered by their first
+/// index.
+pub fn find_duplicates<D: IndexedDataset + ?Sized>(dataset: &D) -> Vec<Vec<usize>> {
+    let mut groups: HashMap<[u8; 32], Vec<usize>> = HashMap::new();
+    for_each_batch(dataset, |start, inputs, _| {
+        for (offset, sample) in inputs.axis_iter(Axis(0)).enumerate() {
+            let mut hasher = Sha256::new();
+            for &value in sample.iter() {
+                hasher.update(value.to_bits().to_le_bytes());
+            }
+            groups.entry(hasher.finalize().into()).or_default().push(start + offset);
+        }
+    });
+
+    let mut duplicates: Vec<Vec<usize>> =
+        groups.into_values().filter(|indices| indices.len() > 1).collect();
+    duplicates.sort_unstable_by_key(|indices| indices[0]);
+    duplicates
+}
+
+/// The NaN and infinite values found in a dataset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NonFiniteReport {
+    /// The number of NaN values in the inputs and targets.
+    pub nan_values: usize,
+    /// The number of infinite values in the inputs and targets.
+    pub infinite_values: usize,
+    /// The indices of the samples with a NaN or infinite value, in increasing order.
+    pub samples: Vec<usize>,
+}
+
+impl NonFiniteReport {
+    /// Returns `true` if every value is finite.
+    pub fn is_clean(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+/// Scans the inputs and targets of a dataset for NaN and infinite values.
+///
+/// # Arguments
+///
+/// * `dataset` - The dataset to scan.
+///
+/// # Returns
+///
+/// The number of non-finite values and the samples holding them.
+pub fn scan_non_finite<D: IndexedDataset + ?Sized>(dataset: &D) -> NonFiniteReport {
+    let mut report = NonFiniteReport::default();
+    for_each_batch(dataset, |start, inputs, targets| {
+        for (offset, (input, target)) in
+            inputs.axis_iter(Axis(0)).zip(targets.axis_iter(Axis(0))).enumerate()
+        {
+            let mut is_clean = true;
+            for &value in input.iter().chain(target.iter()) {
+                if value.is_nan() {
+                    report.nan_values += 1;
+                    is_clean = false;
+                } else if value.is_infinite() {
+                    report.infinite_values += 1;
+                    is_clean = false;
+                }
+            }
+            if !is_clean {
+                report.samples.push(start + offset);
+            }
+        }
+    });
+    report
+}
+
+/// Renders samples of an image dataset as a grid, to check what was loaded.
+///
+/// The values are rescaled from the range of the rendered samples to `0..=255`, so normalized
+/// images are shown with their contrast; non-finite values are drawn black. Samples are laid out
+/// row by row, separated by one black pixel. Save the image with `RgbImage::save`.
+///
+/// # Arguments
+///
+/// * `dataset` - The dataset with `[height, width]` or `[height, width, channels]` samples of 1 or
+///   3 channels.
+/// * `indices` - The indices of the samples to render.
+/// * `columns` - The number of samples per row of the grid.
+///
+/// # Returns
+///
+/// The grid image, or an error if there are no samples or they are not images.
+pub fn to_image<D: IndexedDataset + ?Sized>(
+    dataset: &D,
+    indices: &[usize],
+    columns: usize,
+) -> Result<RgbImage, String> {
+    if columns == 0 {
+        panic!("Number of columns must be greater than 0.");
+    }
+    if indices.is_empty() {
+        return Err("No samples to render".to_string());
+    }
+
+    let (inputs, _) = dataset.get_batch(indices);
+    let images = inputs.data;
+    let (height, width, channels) = match images.shape()[1..] {
+        [height, width] => (height, width, 1),
+        [height, width, channels @ (1 | 3)] => (height, width, channels),
+        ref shape => return Err(format!("Samples of shape {:?} are not images", shape)),
+    };
+
+    let mut range = RunningStats::new();
+    images.iter().for_each(|&value| range.push(value));
+    let (min, max) = (range.min().unwrap_or(0.0), range.max().unwrap_or(0.0));
+    let scale = if max > min { 255.0 / (max - min) } else { 0.0 };
+    let to_pixel = |value: f32| {
+        if value.is_finite() { ((value - min) * scale).round().clamp(0.0, 255.0) as u8 } else { 0 }
+    };
+
+    let rows = indices.len().div_ceil(columns);
+    let columns = columns.min(indices.len());
+    let mut grid =
+        RgbImage::new((columns * (width + 1) - 1) as u32, (rows * (height + 1) - 1) as u32);
+    for (position, image) in images.axis_iter(Axis(0)).enumerate() {
+        let image = image.to_shape((height, width, channels)).unwrap();
+        let (left, top) = ((position % columns) * (width + 1), (position / columns) * (height + 1));
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = match channels {
+                    1 => [to_pixel(image[[y, x, 0]]); 3],
+                    _ => [0, 1, 2].map(|channel| to_pixel(image[[y, x, channel]])),
+                };
+                grid.put_pixel((left + x) as u32, (top + y) as u32, Rgb(pixel));
+            }
+        }
+    }
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{IxDyn, Shape};
+
+    use super::*;
+    use crate::deep_learning::dataset::Dataset;
+    use crate::deep_learning::tensor_ops::Tensor;
+
+    /// Creates 2x2 RGB images with the given red values and one-hot labels over 3 classes.
+    fn image_dataset(reds: &[f32], classes: &[usize]) -> Dataset {
+        let values = reds.iter().flat_map(|&red| [red, 0.5, 1.0].repeat(4)).collect();
+        let mut labels = vec![0.0; classes.len() * 3];
+        for (index, &class) in classes.iter().enumerate() {
+            labels[index * 3 + class] = 1.0;
+        }
+        Dataset::new(
+            Tensor::new(values, Shape::from(IxDyn(&[reds.len(), 2, 2, 3]))),
+            Tensor::new(labels, Shape::from(IxDyn(&[classes.len(), 3]))),
+        )
+    }
+
+    #[test]
+    fn test_running_stats_merge_matches_pushing_every_value() {
+        let values = [1.0, 4.0, -2.0, 7.5, 3.0, f32::NAN];
+        let mut all = RunningStats::new();
+        values.iter().for_each(|&value| all.push(value));
+
+        let (mut first, mut second) = (RunningStats::new(), RunningStats::new());
+        values[..2].iter().for_each(|&value| first.push(value));
+        values[2..].iter().for_each(|&value| second.push(value));
+        first.merge(&second);
+
+        assert_eq!(all.count(), 5);
+        assert_eq!(all.non_finite(), 1);
+        assert!((all.mean() - 2.7).abs() < 1e-9);
+        assert!((first.variance() - all.variance()).abs() < 1e-9);
+        assert_eq!((first.min(), first.max()), (Some(-2.0), Some(7.5)));
+        assert_eq!(RunningStats::new().min(), None);
+    }
+
+    #[test]
+    fn test_channel_stats_computes_each_channel() {
+        let reds: Vec<f32> = (0..300).map(|index| (index % 2) as f32).collect();
+        let stats = channel_stats(&image_dataset(&reds, &vec![0; 300]), None);
+
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[0].count(), 1200);
+        assert!((stats[0].mean() - 0.5).abs() < 1e-9);
+        assert!((stats[0].std() - 0.5).abs() < 1e-9);
+        assert_eq!(stats[1].std(), 0.0);
+        assert_eq!((stats[2].min(), stats[2].max()), (Some(1.0), Some(1.0)));
+
+        let rows = channel_stats(&image_dataset(&reds, &vec![0; 300]), Some(0));
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn test_class_distribution_counts_one_hot_and_scalar_labels() {
+        assert_eq!(class_distribution(&image_dataset(&[0.0; 4], &[2, 0, 2, 2])), [1, 0, 3]);
+
+        let scalar = Dataset::new(
+            Tensor::new(vec![0.0; 3], Shape::from(IxDyn(&[3, 1]))),
+            Tensor::new(vec![1.0, 1.0, 0.0], Shape::from(IxDyn(&[3]))),
+        );
+        assert_eq!(class_distribution(&scalar), [1, 2]);
+    }
+
+    #[test]
+    fn test_find_duplicates_groups_identical_inputs() {
+        let dataset = image_dataset(&[0.1, 0.2, 0.1, 0.3, 0.2, 0.1], &[0, 1, 2, 0, 1, 2]);
+        assert_eq!(find_duplicates(&dataset), [vec![0, 2, 5], vec![1, 4]]);
+        assert!(find_duplicates(&image_dataset(&[0.1, 0.2], &[0, 0])).is_empty());
+    }
+
+    #[test]
+    fn test_scan_non_finite_reports_samples() {
+        let mut dataset = image_dataset(&[0.0, f32::NAN, f32::INFINITY], &[0, 1, 2]);
+        dataset.labels.data[[0, 1]] = f32::NEG_INFINITY;
+
+        let report = scan_non_finite(&dataset);
+        assert_eq!(report.nan_values, 4);
+        assert_eq!(report.infinite_values, 5);
+        assert_eq!(report.samples, [0, 1, 2]);
+        assert!(!report.is_clean());
+        assert!(scan_non_finite(&image_dataset(&[0.0], &[0])).is_clean());
+    }
+
+    #[test]
+    fn test_to_image_renders_a_rescaled_grid() {
+        let dataset = image_dataset(&[-1.0, 1.0, 0.0], &[0, 1, 2]);
+        let grid = to_image(&dataset, &[0, 1, 2], 2).unwrap();
+
+        assert_eq!(grid.dimensions(), (5, 5));
+        assert_eq!(grid.get_pixel(0, 0), &Rgb([0, 191, 255]));
+        assert_eq!(grid.get_pixel(4, 1), &Rgb([255, 191, 255]));
+        assert_eq!(grid.get_pixel(2, 0), &Rgb([0, 0, 0]));
+        assert_eq!(grid.get_pixel(1, 4), &Rgb([128, 191, 255]));
+
+        let tokens = Dataset::new(
+            Tensor::new(vec![0.0; 8], Shape::from(IxDyn(&[1, 8]))),
+            Tensor::new(vec![0.0], Shape::from(IxDyn(&[1]))),
+        );
+        assert!(to_image(&tokens, &[0], 1).is_err());
+        assert!(to_image(&dataset, &[], 1).is_err());
+    }
+}
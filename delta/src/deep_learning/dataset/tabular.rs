@@ -0,0 +1,766 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use csv::{Reader, ReaderBuilder, StringRecord, StringRecordsIntoIter};
+use ndarray::{Array1, Array2, IxDyn, Shape};
+
+use super::Dataset;
+use crate::deep_learning::tensor_ops::Tensor;
+
+/// The encoded features and targets of a row, kept in `f64` until they are converted to tensors.
+type EncodedRow = (Vec<f64>, Vec<f64>);
+
+/// The type of a CSV column, inferred from its values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    /// Every value is a number. Used as is.
+    Numeric,
+    /// A small set of distinct values. One-hot encoded as features, or encoded as the index of
+    /// the category as a target.
+    Categorical,
+    /// Free text with too many distinct values to encode. Left out of the features.
+    Text,
+}
+
+/// How missing values are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MissingValues {
+    /// Replaces missing numbers with the mean of the column and missing categories with the most
+    /// frequent category.
+    #[default]
+    Impute,
+    /// Replaces missing numbers with a constant and encodes missing categories as all zeros.
+    Fill(f64),
+    /// Skips every row with a missing feature.
+    DropRows,
+}
+
+/// Options for reading and inferring the schema of a CSV file.
+#[derive(Debug, Clone)]
+pub struct CsvReadOptions {
+    /// The field delimiter.
+    pub delimiter: u8,
+    /// Whether the first row holds the column names. Columns are named `column_<i>` otherwise.
+    pub has_headers: bool,
+    /// The number of rows used to infer the column types.
+    pub infer_rows: usize,
+    /// The largest number of distinct values of a categorical column.
+    pub max_categories: usize,
+    /// The values that are treated as missing, compared case-insensitively.
+    pub missing_tokens: Vec<String>,
+}
+
+impl Default for CsvReadOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            has_headers: true,
+            infer_rows: 1000,
+            max_categories: 32,
+            missing_tokens: ["", "na", "n/a", "nan", "null", "none", "?"]
+                .iter()
+                .map(|token| token.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// The inferred type and statistics of a CSV column.
+#[derive(Debug, Clone)]
+pub struct Column {
+    /// The name of the column.
+    pub name: String,
+    /// The inferred type of the column.
+    pub column_type: ColumnType,
+    /// The sorted distinct values of a categorical column.
+    pub categories: Vec<String>,
+    /// The number of missing values.
+    pub missing: usize,
+    /// The mean of a numeric column.
+    pub mean: f64,
+    /// The most frequent value of a categorical column.
+    pub mode: Option<String>,
+}
+
+impl Column {
+    /// Returns the index of a category, if it is one of the categories of the column.
+    fn category_index(&self, value: &str) -> Option<usize> {
+        self.categories.binary_search_by(|category| category.as_str().cmp(value)).ok()
+    }
+}
+
+/// A tabular dataset read from a CSV file.
+///
+/// Opening the file streams through it once to infer the type of every column and to collect
+/// the statistics used to encode and impute values, without keeping the rows in memory. The rows
+/// are then read again when they are encoded, either all at once with `to_dataset` and
+/// `to_arrays`, or in batches with `batches` for files larger than memory.
+#[derive(Debug, Clone)]
+pub struct CsvDataset {
+    path: PathBuf,
+    options: CsvReadOptions,
+    columns: Vec<Column>,
+    num_rows: usize,
+    targets: Vec<usize>,
+    missing_values: MissingValues,
+}
+
+impl CsvDataset {
+    /// Opens a CSV file with the default options.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the CSV file.
+    ///
+    /// # Returns
+    ///
+    /// The dataset, or an error if the file cannot be read.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        Self::open_with(path, CsvReadOptions::default())
+    }
+
+    /// Opens a CSV file and infers its schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the CSV file.
+    /// * `options` - The options for reading the file.
+    ///
+    /// # Returns
+    ///
+    /// The dataset, or an error if the file cannot be read.
+    pub fn open_with<P: AsRef<Path>>(path: P, options: CsvReadOptions) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let mut reader = Self::reader(&path, &options)?;
+        let headers = Self::headers(&mut reader, &path, &options)?;
+
+        let mut stats: Vec<ColumnStats> = headers.iter().map(|_| ColumnStats::default()).collect();
+        let mut num_rows = 0;
+        for record in reader.records() {
+            let record = record.map_err(|e| format!("{}: {}", path.display(), e))?;
+            if record.len() != headers.len() {
+                return Err(format!(
+                    "{}: row {} has {} fields, expected {}",
+                    path.display(),
+                    num_rows + 1,
+                    record.len(),
+                    headers.len()
+                ));
+            }
+
+            for (value, stats) in record.iter().zip(stats.iter_mut()) {
+                stats.observe(value.trim(), num_rows < options.infer_rows, &options);
+            }
+            num_rows += 1;
+        }
+
+        let columns = headers
+            .into_iter()
+            .zip(stats)
+            .map(|(name, stats)| stats.into_column(name, &options))
+            .collect();
+
+        Ok(Self {
+            path,
+            options,
+            columns,
+            num_rows,
+            targets: Vec::new(),
+            missing_values: MissingValues::default(),
+        })
+    }
+
+    /// Creates a reader for the file.
+    fn reader(path: &Path, options: &CsvReadOptions) -> Result<Reader<File>, String> {
+        ReaderBuilder::new()
+            .delimiter(options.delimiter)
+            .has_headers(options.has_headers)
+            .from_path(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Reads the column names, or names the columns by position if the file has no header.
+    fn headers(
+        reader: &mut Reader<File>,
+        path: &Path,
+        options: &CsvReadOptions,
+    ) -> Result<Vec<String>, String> {
+        let headers = reader.headers().map_err(|e| format!("{}: {}", path.display(), e))?;
+        if options.has_headers {
+            Ok(headers.iter().map(|name| name.trim().to_string()).collect())
+        } else {
+            Ok((0..headers.len()).map(|i| format!("column_{}", i)).collect())
+        }
+    }
+
+    /// Sets the target columns. Every other numeric and categorical column is a feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - The names of the target columns.
+    ///
+    /// # Returns
+    ///
+    /// The dataset, or an error if a column does not exist or is a text column.
+    pub fn with_targets(mut self, names: &[&str]) -> Result<Self, String> {
+        self.targets = names
+            .iter()
+            .map(|name| {
+                let index = self
+                    .columns
+                    .iter()
+                    .position(|column| column.name == *name)
+                    .ok_or_else(|| format!("Column {} not found", name))?;
+                if self.columns[index].column_type == ColumnType::Text {
+                    return Err(format!("Text column {} cannot be a target", name));
+                }
+                Ok(index)
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(self)
+    }
+
+    /// Sets how missing feature values are handled. Rows with a missing target are always
+    /// skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `missing_values` - The strategy for missing values.
+    pub fn with_missing_values(mut self, missing_values: MissingValues) -> Self {
+        self.missing_values = missing_values;
+        self
+    }
+
+    /// Returns the inferred columns.
+    pub fn schema(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Returns the number of rows in the file, including rows that may be skipped.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the indices of the feature columns.
+    fn features(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.columns.len()).filter(|index| {
+            !self.targets.contains(index) && self.columns[*index].column_type != ColumnType::Text
+        })
+    }
+
+    /// Returns the names of the encoded features, with one `column=category` feature per
+    /// category of the categorical columns.
+    pub fn feature_names(&self) -> Vec<String> {
+        self.features()
+            .flat_map(|index| {
+                let column = &self.columns[index];
+                match column.column_type {
+                    ColumnType::Categorical => column
+                        .categories
+                        .iter()
+                        .map(|category| format!("{}={}", column.name, category))
+                        .collect(),
+                    _ => vec![column.name.clone()],
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the number of encoded features of a row.
+    fn feature_width(&self) -> usize {
+        self.features()
+            .map(|index| match self.columns[index].column_type {
+                ColumnType::Categorical => self.columns[index].categories.len(),
+                _ => 1,
+            })
+            .sum()
+    }
+
+    /// Returns the number of encoded target values of a row in the tensors.
+    fn target_width(&self) -> usize {
+        self.targets
+            .iter()
+            .map(|&index| match self.columns[index].column_type {
+                ColumnType::Categorical => self.columns[index].categories.len(),
+                _ => 1,
+            })
+            .sum()
+    }
+
+    /// Returns whether a value is one of the missing tokens.
+    fn is_missing(&self, value: &str) -> bool {
+        is_missing(value, &self.options)
+    }
+
+    /// Encodes a row into its features and targets.
+    ///
+    /// Categorical targets are one-hot encoded if `one_hot_targets` is set, and encoded as the
+    /// index of their category otherwise.
+    ///
+    /// # Returns
+    ///
+    /// The features and targets, `None` if the row is skipped, or an error if a value does not
+    /// match the type of its column.
+    fn encode(
+        &self,
+        record: &StringRecord,
+        row: usize,
+        one_hot_targets: bool,
+    ) -> Result<Option<EncodedRow>, String> {
+        let mut targets = Vec::with_capacity(self.targets.len());
+        for &index in &self.targets {
+            let column = &self.columns[index];
+            let value = record.get(index).unwrap_or("").trim();
+            if self.is_missing(value) {
+                return Ok(None);
+            }
+
+            match column.column_type {
+                ColumnType::Categorical => {
+                    let category = column.category_index(value).ok_or_else(|| {
+                        format!(
+                            "Unknown category {:?} in column {} at row {}",
+                            value, column.name, row
+                        )
+                    })?;
+                    if one_hot_targets {
+                        let mut one_hot = vec![0.0; column.categories.len()];
+                        one_hot[category] = 1.0;
+                        targets.extend(one_hot);
+                    } else {
+                        targets.push(category as f64);
+                    }
+                }
+                _ => targets.push(self.parse_number(column, value, row)?),
+            }
+        }
+
+        let mut features = Vec::new();
+        for index in self.features() {
+            let column = &self.columns[index];
+            let value = record.get(index).unwrap_or("").trim();
+            let missing = self.is_missing(value);
+            if missing && self.missing_values == MissingValues::DropRows {
+                return Ok(None);
+            }
+
+            match column.column_type {
+                ColumnType::Categorical => {
+                    let mut one_hot = vec![0.0; column.categories.len()];
+                    let category = match (missing, self.missing_values) {
+                        (false, _) => column.category_index(value),
+                        (true, MissingValues::Impute) => {
+                            column.mode.as_deref().and_then(|mode| column.category_index(mode))
+                        }
+                        (true, _) => None,
+                    };
+                    if let Some(category) = category {
+                        one_hot[category] = 1.0;
+                    }
+                    features.extend(one_hot);
+                }
+                _ => {
+                    let number = match (missing, self.missing_values) {
+                        (false, _) => self.parse_number(column, value, row)?,
+                        (true, MissingValues::Fill(fill)) => fill,
+                        (true, _) => column.mean,
+                    };
+                    features.push(number);
+                }
+            }
+        }
+
+        Ok(Some((features, targets)))
+    }
+
+    /// Parses a value of a numeric column.
+    fn parse_number(&self, column: &Column, value: &str, row: usize) -> Result<f64, String> {
+        value.parse::<f64>().map_err(|_| {
+            format!(
+                "Column {} was inferred as numeric, but row {} has the value {:?}",
+                column.name, row, value
+            )
+        })
+    }
+
+    /// Streams the encoded rows of the file in batches.
+    ///
+    /// Categorical targets are one-hot encoded. Only one batch is held in memory at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_size` - The number of rows in a batch. The last batch may be smaller.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over `[batch, features]` inputs and `[batch, targets]` targets, or an error
+    /// if the file cannot be opened.
+    pub fn batches(&self, batch_size: usize) -> Result<CsvBatches<'_>, String> {
+        if batch_size == 0 {
+            panic!("Batch size must be greater than 0.");
+        }
+
+        let reader = Self::reader(&self.path, &self.options)?;
+        Ok(CsvBatches {
+            dataset: self,
+            records: reader.into_records(),
+            batch_size,
+            row: 0,
+            feature_width: self.feature_width(),
+            target_width: self.target_width(),
+        })
+    }
+
+    /// Reads and encodes every row into memory.
+    fn read_all(&self, one_hot_targets: bool) -> Result<(Vec<f64>, Vec<f64>, usize), String> {
+        let mut features = Vec::new();
+        let mut targets = Vec::new();
+        let mut rows = 0;
+
+        let mut reader = Self::reader(&self.path, &self.options)?;
+        for (row, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| format!("{}: {}", self.path.display(), e))?;
+            if let Some((row_features, row_targets)) =
+                self.encode(&record, row + 1, one_hot_targets)?
+            {
+                features.extend(row_features);
+                targets.extend(row_targets);
+                rows += 1;
+            }
+        }
+
+        Ok((features, targets, rows))
+    }
+
+    /// Reads every row into a `Dataset` for `Sequential`.
+    ///
+    /// # Returns
+    ///
+    /// A dataset with `[rows, features]` inputs and `[rows, targets]` labels, with categorical
+    /// targets one-hot encoded.
+    pub fn to_dataset(&self) -> Result<Dataset, String> {
+        let (features, targets, rows) = self.read_all(true)?;
+        Ok(Dataset::new(
+            to_tensor(features, rows, self.feature_width()),
+            to_tensor(targets, rows, self.target_width()),
+        ))
+    }
+
+    /// Reads every row into arrays for the `classical_ml` algorithms.
+    ///
+    /// # Returns
+    ///
+    /// The `[rows, features]` inputs and the `[rows]` targets, with a categorical target encoded
+    /// as the index of its category, or an error if there is not exactly one target column.
+    pub fn to_arrays(&self) -> Result<(Array2<f64>, Array1<f64>), String> {
+        if self.targets.len() != 1 {
+            return Err(format!(
+                "Arrays require exactly one target column, got {}",
+                self.targets.len()
+            ));
+        }
+
+        let (features, targets, rows) = self.read_all(false)?;
+        let x = Array2::from_shape_vec((rows, self.feature_width()), features)
+            .map_err(|e| e.to_string())?;
+        let y = Array1::from_vec(targets);
+
+        Ok((x, y))
+    }
+}
+
+/// An iterator over the batches of a `CsvDataset`, created by `CsvDataset::batches`.
+pub struct CsvBatches<'a> {
+    dataset: &'a CsvDataset,
+    records: StringRecordsIntoIter<File>,
+    batch_size: usize,
+    row: usize,
+    feature_width: usize,
+    target_width: usize,
+}
+
+impl Iterator for CsvBatches<'_> {
+    type Item = Result<(Tensor, Tensor), String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut features = Vec::new();
+        let mut targets = Vec::new();
+        let mut rows = 0;
+
+        while rows < self.batch_size {
+            let record = match self.records.next() {
+                Some(Ok(record)) => record,
+                Some(Err(e)) => return Some(Err(e.to_string())),
+                None => break,
+            };
+            self.row += 1;
+
+            match self.dataset.encode(&record, self.row, true) {
+                Ok(Some((row_features, row_targets))) => {
+                    features.extend(row_features);
+                    targets.extend(row_targets);
+                    rows += 1;
+                }
+                Ok(None) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        if rows == 0 {
+            return None;
+        }
+
+        Some(Ok((
+            to_tensor(features, rows, self.feature_width),
+            to_tensor(targets, rows, self.target_width),
+        )))
+    }
+}
+
+/// Converts encoded `f64` values into a `[rows, width]` tensor.
+fn to_tensor(values: Vec<f64>, rows: usize, width: usize) -> Tensor {
+    Tensor::new(
+        values.into_iter().map(|value| value as f32).collect(),
+        Shape::from(IxDyn(&[rows, width])),
+    )
+}
+
+/// Returns whether a value is one of the missing tokens.
+fn is_missing(value: &str, options: &CsvReadOptions) -> bool {
+    options.missing_tokens.iter().any(|token| token.eq_ignore_ascii_case(value))
+}
+
+/// The statistics of a column collected while streaming through the file.
+#[derive(Default)]
+struct ColumnStats {
+    non_numeric: bool,
+    inferred_values: HashSet<String>,
+    counts: BTreeMap<String, usize>,
+    too_many_values: bool,
+    sum: f64,
+    numbers: usize,
+    missing: usize,
+}
+
+impl ColumnStats {
+    /// Records a value of the column.
+    fn observe(&mut self, value: &str, inferring: bool, options: &CsvReadOptions) {
+        if is_missing(value, options) {
+            self.missing += 1;
+            return;
+        }
+
+        match value.parse::<f64>() {
+            Ok(number) if number.is_finite() => {
+                self.sum += number;
+                self.numbers += 1;
+            }
+            _ if inferring => self.non_numeric = true,
+            _ => {}
+        }
+
+        if inferring && self.inferred_values.len() <= options.max_categories {
+            self.inferred_values.insert(value.to_string());
+        }
+        // Count every distinct value until the column has too many to be categorical.
+        if !self.too_many_values {
+            *self.counts.entry(value.to_string()).or_default() += 1;
+            if self.counts.len() > options.max_categories {
+                self.too_many_values = true;
+                self.counts.clear();
+            }
+        }
+    }
+
+    /// Infers the type of the column from its statistics.
+    fn into_column(self, name: String, options: &CsvReadOptions) -> Column {
+        let column_type = if !self.non_numeric {
+            ColumnType::Numeric
+        } else if !self.too_many_values && self.inferred_values.len() <= options.max_categories {
+            ColumnType::Categorical
+        } else {
+            ColumnType::Text
+        };
+
+        let (categories, mode) = match column_type {
+            ColumnType::Categorical => {
+                let mode = self
+                    .counts
+                    .iter()
+                    .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                    .map(|(value, _)| value.clone());
+                (self.counts.into_keys().collect(), mode)
+            }
+            _ => (Vec::new(), None),
+        };
+
+        Column {
+            name,
+            column_type,
+            categories,
+            missing: self.missing,
+            mean: if self.numbers > 0 { self.sum / self.numbers as f64 } else { 0.0 },
+            mode,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::deep_learning::dataset::IndexedDataset;
+
+    /// Writes a CSV file into a temporary directory, which is removed when the returned
+    /// `TempDir` is dropped.
+    fn write_csv(contents: &str) -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("data.csv");
+        fs::write(&path, contents).unwrap();
+        (dir, path)
+    }
+
+    const HOUSES: &str = "\
+size,city,description,price
+50,paris,small flat,100
+80,lyon,,150
+NA,paris,large flat with a view,220
+60,?,old house,130
+";
+
+    #[test]
+    fn test_open_infers_schema() {
+        let (_dir, path) = write_csv(HOUSES);
+        let options = CsvReadOptions { max_categories: 2, ..Default::default() };
+        let dataset = CsvDataset::open_with(&path, options).unwrap();
+
+        let types: Vec<ColumnType> =
+            dataset.schema().iter().map(|column| column.column_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                ColumnType::Numeric,
+                ColumnType::Categorical,
+                ColumnType::Text,
+                ColumnType::Numeric
+            ]
+        );
+        assert_eq!(dataset.num_rows(), 4);
+        assert_eq!(dataset.schema()[1].categories, vec!["lyon", "paris"]);
+        assert_eq!(dataset.schema()[1].mode.as_deref(), Some("paris"));
+        assert_eq!(dataset.schema()[0].missing, 1);
+        assert!((dataset.schema()[0].mean - 190.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_to_arrays_imputes_missing_values() {
+        let (_dir, path) = write_csv(HOUSES);
+        let dataset = CsvDataset::open_with(
+            &path,
+            CsvReadOptions { max_categories: 2, ..Default::default() },
+        )
+        .unwrap()
+        .with_targets(&["price"])
+        .unwrap();
+
+        assert_eq!(dataset.feature_names(), vec!["size", "city=lyon", "city=paris"]);
+        let (x, y) = dataset.to_arrays().unwrap();
+        assert_eq!(x.dim(), (4, 3));
+        assert!((x[[2, 0]] - 190.0 / 3.0).abs() < 1e-12);
+        assert_eq!(x.row(3).to_vec()[1..], [0.0, 1.0]);
+        assert_eq!(y.to_vec(), vec![100.0, 150.0, 220.0, 130.0]);
+
+        let dropped = dataset.clone().with_missing_values(MissingValues::DropRows);
+        assert_eq!(dropped.to_arrays().unwrap().0.dim(), (2, 3));
+
+        let filled = dataset.with_missing_values(MissingValues::Fill(-1.0));
+        let (x, _) = filled.to_arrays().unwrap();
+        assert_eq!(x[[2, 0]], -1.0);
+        assert_eq!(x.row(3).to_vec()[1..], [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_to_arrays_keeps_f64_precision() {
+        // 2^24 + 1 and 0.1 are not representable as `f32`.
+        let (_dir, path) = write_csv("x,y\n16777217,0.1\n");
+        let dataset = CsvDataset::open(&path).unwrap().with_targets(&["y"]).unwrap();
+
+        let (x, y) = dataset.to_arrays().unwrap();
+        assert_eq!(x[[0, 0]], 16_777_217.0);
+        assert_eq!(y[0], 0.1);
+    }
+
+    #[test]
+    fn test_categorical_target_is_one_hot_in_tensors() {
+        let (_dir, path) = write_csv("x,y,label\n1,2,cat\n3,4,dog\n5,6,cat\n,8,bird\n9,10,\n");
+        let dataset = CsvDataset::open(&path).unwrap().with_targets(&["label"]).unwrap();
+
+        let data = dataset.to_dataset().unwrap();
+        assert_eq!(IndexedDataset::len(&data), 4);
+        assert_eq!(data.labels.data.shape(), &[4, 3]);
+        assert_eq!(data.labels.take(&[1]).to_vec(), vec![0.0, 0.0, 1.0]);
+        assert_eq!(data.inputs.take(&[3]).to_vec(), vec![4.5, 8.0]);
+
+        let (_, y) = dataset.to_arrays().unwrap();
+        assert_eq!(y.to_vec(), vec![1.0, 2.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_batches_stream_rows() {
+        let rows: String = (0..10).map(|i| format!("{},{}\n", i, i * 2)).collect();
+        let (_dir, path) = write_csv(&format!("a,b\n{}", rows));
+        let dataset = CsvDataset::open(&path).unwrap().with_targets(&["b"]).unwrap();
+
+        let batches: Vec<(Tensor, Tensor)> =
+            dataset.batches(4).unwrap().collect::<Result<_, _>>().unwrap();
+        let sizes: Vec<usize> = batches.iter().map(|(x, _)| x.data.shape()[0]).collect();
+        assert_eq!(sizes, vec![4, 4, 2]);
+        assert_eq!(batches[2].0.to_vec(), vec![8.0, 9.0]);
+        assert_eq!(batches[2].1.to_vec(), vec![16.0, 18.0]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let (_dir, path) = write_csv("a,b\n1,2\n3,x\n");
+        let options = CsvReadOptions { infer_rows: 1, ..Default::default() };
+        let dataset = CsvDataset::open_with(&path, options).unwrap();
+
+        assert!(dataset.clone().with_targets(&["c"]).unwrap_err().contains("not found"));
+        assert!(dataset.to_arrays().unwrap_err().contains("exactly one target"));
+        let err = dataset.with_targets(&["a"]).unwrap().to_arrays().unwrap_err();
+        assert!(err.contains("inferred as numeric"), "{}", err);
+    }
+}
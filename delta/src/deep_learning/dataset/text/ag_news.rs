@@ -0,0 +1,154 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::path::Path;
+
+use crate::deep_learning::dataset::text::corpus::{
+    TextCorpus, TextDataset, TextFuture, TextSource,
+};
+use crate::deep_learning::dataset::{LoadOptions, cache};
+
+/// The AG News dataset: 127,600 news articles in 4 topics.
+pub type AgNewsDataset = TextDataset<AgNews>;
+
+/// The files of the AG News dataset.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AgNews;
+
+impl AgNews {
+    const URL: &'static str =
+        "https://raw.githubusercontent.com/mhjabreel/CharCnn_Keras/master/data/ag_news_csv";
+    const TRAIN_FILENAME: &'static str = "train.csv";
+    const TEST_FILENAME: &'static str = "test.csv";
+
+    /// The names of the classes, in label order.
+    pub const CLASSES: [&'static str; 4] = ["World", "Sports", "Business", "Sci/Tech"];
+
+    /// Parses an AG News CSV file.
+    ///
+    /// Each row has the class, starting at 1, the title and the description of an article. The
+    /// text of an article is its title followed by its description.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Returns
+    ///
+    /// The articles, with the labels of the classes in `CLASSES`.
+    fn read_csv(path: &Path) -> Result<TextCorpus, String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_path(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+        let mut texts = Vec::new();
+        let mut labels = Vec::new();
+        for (row, record) in reader.records().enumerate() {
+            let record = record
+                .map_err(|e| format!("Invalid row {} in {}: {}", row + 1, path.display(), e))?;
+            let (Some(class), Some(title), Some(description)) =
+                (record.get(0), record.get(1), record.get(2))
+            else {
+                return Err(format!(
+                    "Invalid row {} in {}: expected 3 fields",
+                    row + 1,
+                    path.display()
+                ));
+            };
+
+            let label = class
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|class| (1..=Self::CLASSES.len()).contains(class))
+                .ok_or_else(|| format!("Invalid class {} in row {}", class, row + 1))?;
+            // Line breaks in the descriptions are escaped as backslashes
+            texts.push(format!("{} {}", title, description.replace('\\', " ")));
+            labels.push(label - 1);
+        }
+
+        TextCorpus::new(texts, labels, Self::CLASSES.map(String::from).to_vec())
+    }
+}
+
+impl TextSource for AgNews {
+    fn load(&self, is_train: bool, options: LoadOptions) -> TextFuture {
+        Box::pin(async move {
+            let file_name = if is_train { Self::TRAIN_FILENAME } else { Self::TEST_FILENAME };
+            let path = options.resolve_root("ag_news").join(file_name);
+            let url = format!("{}/{}", Self::URL, file_name);
+
+            // No SHA-256 hashes are published for the files; pin them with
+            // `DatasetBuilder::checksum` to verify the downloads.
+            cache::fetch(&url, &path, None, &options).await?;
+
+            Self::read_csv(&path)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::deep_learning::dataset::Split;
+
+    #[tokio::test]
+    async fn test_builder_loads_csv_files_and_holds_out_validation() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join(AgNews::TRAIN_FILENAME),
+            "\"3\",\"Stocks rise\",\"Markets closed higher.\\Analysts cheered.\"\n\
+             \"2\",\"Cup final\",\"A late goal, then a win.\"\n\
+             \"4\",\"New chip\",\"Faster, cheaper.\"\n\
+             \"1\",\"Summit\",\"Leaders met.\"\n",
+        )
+        .unwrap();
+        fs::write(root.join(AgNews::TEST_FILENAME), "\"5\",\"Bad\",\"Out of range.\"\n").unwrap();
+
+        let builder = || AgNewsDataset::builder().root(root).offline(true).validation_split(0.25);
+        let train = builder().build().await.unwrap();
+        let val = builder().split(Split::Val).build().await.unwrap();
+
+        assert_eq!(train.classes(), AgNews::CLASSES);
+        assert_eq!(train.len(), 3);
+        assert_eq!(train.corpus().text(0), "Stocks rise Markets closed higher. Analysts cheered.");
+        assert_eq!(train.corpus().label(0), 2);
+        assert_eq!(val.corpus().texts().collect::<Vec<_>>(), ["Summit Leaders met."]);
+        assert_eq!(val.corpus().label(0), 0);
+
+        let test = builder().split(Split::Test).build().await;
+        assert!(test.expect_err("An invalid class should not load").contains("Invalid class 5"));
+    }
+}
@@ -0,0 +1,353 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use ndarray::{IxDyn, Shape};
+use rand::rngs::StdRng;
+
+use crate::deep_learning::dataset::text::tokenizer::{EncodeOptions, Tokenizer};
+use crate::deep_learning::dataset::{
+    Dataset, DatasetBuilder, LoadDataset, LoadOptions, Split, stratified_split_indices,
+};
+use crate::deep_learning::rng::rng_from_seed;
+use crate::deep_learning::tensor_ops::Tensor;
+
+/// Labeled texts, such as the reviews of a sentiment dataset.
+///
+/// The texts are shared between clones, so splitting a corpus does not copy them.
+#[derive(Debug, Clone)]
+pub struct TextCorpus {
+    texts: Arc<[String]>,
+    labels: Arc<[usize]>,
+    order: Vec<usize>,
+    classes: Arc<[String]>,
+}
+
+impl TextCorpus {
+    /// Creates a new `TextCorpus`.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - The texts.
+    /// * `labels` - The label of each text.
+    /// * `classes` - The name of each class, in label order.
+    ///
+    /// # Returns
+    ///
+    /// The corpus, or an error if the labels do not match the texts or the classes.
+    pub fn new(
+        texts: Vec<String>,
+        labels: Vec<usize>,
+        classes: Vec<String>,
+    ) -> Result<Self, String> {
+        if texts.len() != labels.len() {
+            return Err(format!("Found {} texts but {} labels", texts.len(), labels.len()));
+        }
+        if let Some(label) = labels.iter().find(|&&label| label >= classes.len()) {
+            return Err(format!("Label {} is out of range for {} classes", label, classes.len()));
+        }
+
+        Ok(Self {
+            order: (0..texts.len()).collect(),
+            texts: texts.into(),
+            labels: labels.into(),
+            classes: classes.into(),
+        })
+    }
+
+    /// Returns the number of texts.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns `true` if the corpus has no texts.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Returns the names of the classes, in label order.
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// Returns a text.
+    pub fn text(&self, index: usize) -> &str {
+        &self.texts[self.order[index]]
+    }
+
+    /// Returns the label of a text.
+    pub fn label(&self, index: usize) -> usize {
+        self.labels[self.order[index]]
+    }
+
+    /// Returns an iterator over the texts, in order.
+    pub fn texts(&self) -> impl Iterator<Item = &str> + '_ {
+        self.order.iter().map(|&index| self.texts[index].as_str())
+    }
+
+    /// Splits the corpus into two parts at the given index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the first text of the second part.
+    ///
+    /// # Returns
+    ///
+    /// The texts before the index and the texts from the index on.
+    pub fn split_at(&self, index: usize) -> (TextCorpus, TextCorpus) {
+        let (first, second) = self.order.split_at(index);
+        (self.select(first.to_vec()), self.select(second.to_vec()))
+    }
+
+    /// Splits the corpus into two parts holding out a fraction of the texts of each class.
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - The fraction of each class in the second part.
+    /// * `rng` - The random number generator choosing the held-out texts.
+    ///
+    /// # Returns
+    ///
+    /// The remaining texts and the held-out texts.
+    pub fn stratified_split(&self, fraction: f32, rng: &mut StdRng) -> (TextCorpus, TextCorpus) {
+        let classes: Vec<usize> = (0..self.len()).map(|index| self.label(index)).collect();
+        let (first, second) = stratified_split_indices(&classes, fraction, rng);
+        let select = |indices: Vec<usize>| {
+            self.select(indices.into_iter().map(|index| self.order[index]).collect())
+        };
+        (select(first), select(second))
+    }
+
+    /// Encodes the texts into a dataset of token ids and one-hot labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokenizer` - The tokenizer converting the texts to ids.
+    /// * `options` - The special token, truncation and padding options.
+    ///
+    /// # Returns
+    ///
+    /// A dataset with `[texts, length]` ids as inputs and `[texts, classes]` labels.
+    pub fn encode<T: Tokenizer + ?Sized>(&self, tokenizer: &T, options: &EncodeOptions) -> Dataset {
+        let texts: Vec<&str> = self.texts().collect();
+        let inputs = tokenizer.encode_batch(&texts, options);
+
+        let num_classes = self.classes.len();
+        let mut labels = vec![0.0; self.len() * num_classes];
+        for index in 0..self.len() {
+            labels[index * num_classes + self.label(index)] = 1.0;
+        }
+
+        Dataset::new(inputs, Tensor::new(labels, Shape::from(IxDyn(&[self.len(), num_classes]))))
+    }
+
+    /// Returns the corpus with the given stored texts.
+    fn select(&self, order: Vec<usize>) -> TextCorpus {
+        TextCorpus { order, ..self.clone() }
+    }
+}
+
+/// The future returned by `TextSource::load`.
+pub type TextFuture = Pin<Box<dyn Future<Output = Result<TextCorpus, String>> + Send>>;
+
+/// A dataset whose texts are loaded into a `TextCorpus`, such as IMDB and AG News.
+pub trait TextSource: Clone + Default + Send + Sync + 'static {
+    /// Loads the training or test texts, downloading them if needed and allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_train` - Whether to load the training texts or the test texts.
+    /// * `options` - The options with the directory of the files and whether to download them.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the texts.
+    fn load(&self, is_train: bool, options: LoadOptions) -> TextFuture;
+}
+
+/// A text dataset with a single split loaded.
+///
+/// The texts are encoded into ids with `encode`, which gives a `Dataset` for a `DataLoader`.
+#[derive(Debug, Clone)]
+pub struct TextDataset<S: TextSource> {
+    corpus: TextCorpus,
+    split: Split,
+    source: S,
+}
+
+impl<S: TextSource> TextDataset<S> {
+    /// Creates a builder that loads a single split of the dataset.
+    ///
+    /// # Returns
+    ///
+    /// A `DatasetBuilder` for the dataset.
+    pub fn builder() -> DatasetBuilder<Self> {
+        DatasetBuilder::new()
+    }
+
+    /// Loads a single split of the dataset from a source.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The files to load.
+    /// * `options` - The directory, split and download options.
+    ///
+    /// # Returns
+    ///
+    /// The dataset with the requested split loaded.
+    pub async fn load_source(source: S, options: LoadOptions) -> Result<Self, String> {
+        let split = options.split;
+        let corpus = source.load(split != Split::Test, options.clone()).await?;
+
+        let corpus = match split {
+            Split::Test => corpus,
+            _ => {
+                let validation_start = options.validation_start(corpus.len())?;
+                let (train, val) = match options.split_seed {
+                    Some(seed) => {
+                        corpus.stratified_split(options.validation_split, &mut rng_from_seed(seed))
+                    }
+                    None => corpus.split_at(validation_start),
+                };
+                if split == Split::Val { val } else { train }
+            }
+        };
+        Ok(Self { corpus, split, source })
+    }
+
+    /// Returns the source the dataset was loaded from.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the split that was loaded.
+    pub fn split(&self) -> Split {
+        self.split
+    }
+
+    /// Returns the texts of the loaded split.
+    pub fn corpus(&self) -> &TextCorpus {
+        &self.corpus
+    }
+
+    /// Returns the number of texts of the loaded split.
+    pub fn len(&self) -> usize {
+        self.corpus.len()
+    }
+
+    /// Returns `true` if the loaded split has no texts.
+    pub fn is_empty(&self) -> bool {
+        self.corpus.is_empty()
+    }
+
+    /// Returns the names of the classes, in label order.
+    pub fn classes(&self) -> &[String] {
+        self.corpus.classes()
+    }
+
+    /// Encodes the texts of the loaded split into a dataset of token ids and one-hot labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokenizer` - The tokenizer converting the texts to ids.
+    /// * `options` - The special token, truncation and padding options.
+    ///
+    /// # Returns
+    ///
+    /// A dataset with `[texts, length]` ids as inputs and `[texts, classes]` labels.
+    pub fn encode<T: Tokenizer + ?Sized>(&self, tokenizer: &T, options: &EncodeOptions) -> Dataset {
+        self.corpus.encode(tokenizer, options)
+    }
+}
+
+impl<S: TextSource> LoadDataset for TextDataset<S> {
+    type BuildFuture = Pin<Box<dyn Future<Output = Result<Self, String>> + Send>>;
+
+    /// Loads a single split of the dataset with the default source.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The directory, split and download options.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the dataset with the requested split loaded.
+    fn load_with(options: LoadOptions) -> Self::BuildFuture {
+        Box::pin(Self::load_source(S::default(), options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deep_learning::dataset::text::tokenizer::{Vocab, WordTokenizer};
+
+    fn corpus() -> TextCorpus {
+        let texts = (0..10).map(|i| format!("text {}", i)).collect();
+        let labels = (0..10).map(|i| i % 2).collect();
+        TextCorpus::new(texts, labels, vec!["even".into(), "odd".into()]).unwrap()
+    }
+
+    #[test]
+    fn test_new_validates_labels() {
+        let classes = vec!["a".to_string()];
+        assert!(TextCorpus::new(vec!["x".into()], vec![], classes.clone()).is_err());
+        assert!(TextCorpus::new(vec!["x".into()], vec![1], classes).is_err());
+    }
+
+    #[test]
+    fn test_stratified_split_keeps_class_proportions() {
+        let (train, val) = corpus().stratified_split(0.4, &mut rng_from_seed(1));
+
+        assert_eq!(train.len(), 6);
+        assert_eq!(val.len(), 4);
+        assert_eq!((0..val.len()).filter(|&i| val.label(i) == 1).count(), 2);
+
+        let mut texts: Vec<&str> = train.texts().chain(val.texts()).collect();
+        texts.sort_unstable();
+        let mut expected: Vec<String> = (0..10).map(|i| format!("text {}", i)).collect();
+        expected.sort_unstable();
+        assert_eq!(texts, expected);
+    }
+
+    #[test]
+    fn test_encode_gives_ids_and_one_hot_labels() {
+        let corpus = corpus().split_at(8).1;
+        let tokenizer = WordTokenizer::train(&["text 8"], None);
+
+        let dataset = corpus.encode(&tokenizer, &EncodeOptions::new().with_max_length(4));
+
+        assert_eq!(dataset.inputs.data.shape(), &[2, 4]);
+        assert_eq!(dataset.inputs.data[[1, 2]], Vocab::UNK_ID as f32);
+        assert_eq!(dataset.labels.data.as_slice().unwrap(), [1.0, 0.0, 0.0, 1.0]);
+    }
+}
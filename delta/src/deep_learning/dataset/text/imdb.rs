@@ -0,0 +1,217 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use log::debug;
+use tar::Archive;
+
+use crate::deep_learning::dataset::text::corpus::{
+    TextCorpus, TextDataset, TextFuture, TextSource,
+};
+use crate::deep_learning::dataset::{LoadOptions, cache};
+
+/// The Large Movie Review Dataset: 50,000 movie reviews labeled as negative or positive.
+pub type ImdbDataset = TextDataset<Imdb>;
+
+/// The files of the IMDB dataset.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Imdb;
+
+impl Imdb {
+    const URL: &'static str = "https://ai.stanford.edu/~amaas/data/sentiment/aclImdb_v1.tar.gz";
+    const ARCHIVE_FILENAME: &'static str = "aclImdb_v1.tar.gz";
+    const DIR_NAME: &'static str = "aclImdb";
+
+    /// The names of the classes, in label order.
+    pub const CLASSES: [&'static str; 2] = ["neg", "pos"];
+
+    /// Downloads if needed and extracts the labeled reviews.
+    ///
+    /// The archive is extracted into a temporary directory that is renamed once every review is
+    /// written, so an interrupted extraction is started again. The unlabeled reviews are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options with the cache directory and whether to download.
+    ///
+    /// # Returns
+    ///
+    /// The directory of the extracted reviews.
+    async fn download_and_extract(options: &LoadOptions) -> Result<PathBuf, String> {
+        let cache_path = options.resolve_root("imdb");
+        let dataset_path = cache_path.join(Self::DIR_NAME);
+        if dataset_path.exists() {
+            return Ok(dataset_path);
+        }
+
+        let tarball_path = cache_path.join(Self::ARCHIVE_FILENAME);
+
+        // No SHA-256 hash is published for the archive; pin it with `DatasetBuilder::checksum` to
+        // verify the download.
+        cache::fetch(Self::URL, &tarball_path, None, options).await?;
+
+        let partial_path = cache_path.join(format!("{}.part", Self::DIR_NAME));
+        let _ = fs::remove_dir_all(&partial_path);
+
+        let tar_gz = File::open(&tarball_path).map_err(|e| e.to_string())?;
+        let mut archive = Archive::new(GzDecoder::new(tar_gz));
+        for entry in archive.entries().map_err(|e| e.to_string())? {
+            let mut entry = entry.map_err(|e| e.to_string())?;
+            let path = entry.path().map_err(|e| e.to_string())?.into_owned();
+            let Ok(relative) = path.strip_prefix(Self::DIR_NAME) else {
+                continue;
+            };
+            let is_review = matches!(
+                relative.iter().map(|part| part.to_str().unwrap_or("")).collect::<Vec<_>>()[..],
+                ["train" | "test", "neg" | "pos", _]
+            );
+            if !is_review {
+                continue;
+            }
+
+            let full_path = partial_path.join(relative);
+            fs::create_dir_all(full_path.parent().unwrap()).map_err(|e| e.to_string())?;
+            entry.unpack(&full_path).map_err(|e| e.to_string())?;
+        }
+
+        fs::rename(&partial_path, &dataset_path).map_err(|e| e.to_string())?;
+        debug!("Extracted IMDB reviews to {}", dataset_path.display());
+        Ok(dataset_path)
+    }
+
+    /// Reads the reviews of a split, sorted by file name within each class.
+    ///
+    /// # Arguments
+    ///
+    /// * `split_dir` - The `train` or `test` directory.
+    ///
+    /// # Returns
+    ///
+    /// The reviews, with the labels of the classes in `CLASSES`.
+    fn read_reviews(split_dir: &Path) -> Result<TextCorpus, String> {
+        let mut texts = Vec::new();
+        let mut labels = Vec::new();
+
+        for (label, class) in Self::CLASSES.iter().enumerate() {
+            let class_dir = split_dir.join(class);
+            let mut paths: Vec<PathBuf> = fs::read_dir(&class_dir)
+                .map_err(|e| format!("Failed to read {}: {}", class_dir.display(), e))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                let text = fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                texts.push(text.replace("<br />", " "));
+                labels.push(label);
+            }
+        }
+
+        TextCorpus::new(texts, labels, Self::CLASSES.map(String::from).to_vec())
+    }
+}
+
+impl TextSource for Imdb {
+    fn load(&self, is_train: bool, options: LoadOptions) -> TextFuture {
+        Box::pin(async move {
+            let dataset_path = Self::download_and_extract(&options).await?;
+            Self::read_reviews(&dataset_path.join(if is_train { "train" } else { "test" }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::deep_learning::dataset::Split;
+
+    /// Writes the IMDB archive with three training reviews, two test reviews and an unlabeled
+    /// one.
+    fn write_archive(root: &Path) {
+        let tarball = File::create(root.join(Imdb::ARCHIVE_FILENAME)).unwrap();
+        let mut builder = tar::Builder::new(GzEncoder::new(tarball, Compression::fast()));
+        let files = [
+            ("train/pos/0_9.txt", "Great movie.<br />Loved it."),
+            ("train/neg/1_2.txt", "Dull."),
+            ("train/neg/0_1.txt", "Awful plot."),
+            ("train/unsup/0_0.txt", "No label."),
+            ("test/pos/0_10.txt", "Wonderful!"),
+            ("test/neg/0_3.txt", "Boring."),
+            ("README", "Large Movie Review Dataset"),
+        ];
+        for (name, text) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(text.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, format!("aclImdb/{}", name), text.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_builder_extracts_and_loads_labeled_reviews() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write_archive(root);
+
+        let train = ImdbDataset::builder().root(root).offline(true).build().await.unwrap();
+        let test = ImdbDataset::builder()
+            .root(root)
+            .offline(true)
+            .split(Split::Test)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(train.classes(), ["neg", "pos"]);
+        let corpus = train.corpus();
+        assert_eq!(
+            corpus.texts().collect::<Vec<_>>(),
+            ["Awful plot.", "Dull.", "Great movie. Loved it."]
+        );
+        assert_eq!((0..corpus.len()).map(|i| corpus.label(i)).collect::<Vec<_>>(), [0, 0, 1]);
+        assert_eq!(test.len(), 2);
+        assert_eq!(test.corpus().text(1), "Wonderful!");
+        assert_eq!(test.corpus().label(1), 1);
+
+        let dir = root.join("aclImdb");
+        assert!(!dir.join("train/unsup").exists());
+        assert!(!root.join("aclImdb.part").exists());
+    }
+}
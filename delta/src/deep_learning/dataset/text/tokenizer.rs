@@ -0,0 +1,651 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+
+use ndarray::{IxDyn, Shape};
+
+use crate::deep_learning::tensor_ops::Tensor;
+
+/// A vocabulary mapping tokens to ids.
+///
+/// The special tokens always have the first ids: `PAD_ID`, `UNK_ID`, `BOS_ID` and `EOS_ID`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocab {
+    tokens: Vec<String>,
+    ids: HashMap<String, usize>,
+}
+
+impl Vocab {
+    /// The token used to pad sequences to a fixed length.
+    pub const PAD: &'static str = "<pad>";
+    /// The token replacing tokens missing from the vocabulary.
+    pub const UNK: &'static str = "<unk>";
+    /// The token marking the beginning of a sequence.
+    pub const BOS: &'static str = "<bos>";
+    /// The token marking the end of a sequence.
+    pub const EOS: &'static str = "<eos>";
+
+    /// The id of `PAD`.
+    pub const PAD_ID: usize = 0;
+    /// The id of `UNK`.
+    pub const UNK_ID: usize = 1;
+    /// The id of `BOS`.
+    pub const BOS_ID: usize = 2;
+    /// The id of `EOS`.
+    pub const EOS_ID: usize = 3;
+
+    const SPECIAL_TOKENS: [&'static str; 4] = [Self::PAD, Self::UNK, Self::BOS, Self::EOS];
+
+    /// Creates a vocabulary of the special tokens followed by the given tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokens` - The tokens, in id order. Duplicates and special tokens are skipped.
+    ///
+    /// # Returns
+    ///
+    /// A new `Vocab` instance.
+    pub fn new<I: IntoIterator<Item = String>>(tokens: I) -> Self {
+        let mut vocab = Self { tokens: Vec::new(), ids: HashMap::new() };
+        for token in Self::SPECIAL_TOKENS.iter().map(|token| token.to_string()).chain(tokens) {
+            vocab.push(token);
+        }
+        vocab
+    }
+
+    /// Creates a vocabulary of the most frequent tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `counts` - The number of occurrences of each token.
+    /// * `max_size` - The maximum size of the vocabulary, including the special tokens.
+    ///
+    /// # Returns
+    ///
+    /// The vocabulary, with tokens ordered by decreasing count and then alphabetically.
+    pub fn from_counts(counts: &HashMap<String, usize>, max_size: Option<usize>) -> Self {
+        let mut tokens: Vec<(&String, &usize)> = counts.iter().collect();
+        tokens.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        let limit =
+            max_size.map_or(usize::MAX, |size| size.saturating_sub(Self::SPECIAL_TOKENS.len()));
+        Self::new(tokens.into_iter().take(limit).map(|(token, _)| token.clone()))
+    }
+
+    /// Adds a token at the end of the vocabulary if it is not in it yet.
+    fn push(&mut self, token: String) {
+        if !self.ids.contains_key(&token) {
+            self.ids.insert(token.clone(), self.tokens.len());
+            self.tokens.push(token);
+        }
+    }
+
+    /// Returns the number of tokens, including the special tokens.
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Returns `true` if the vocabulary has no tokens.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Returns the id of a token.
+    pub fn id(&self, token: &str) -> Option<usize> {
+        self.ids.get(token).copied()
+    }
+
+    /// Returns the id of a token, or `UNK_ID` if it is not in the vocabulary.
+    pub fn id_or_unk(&self, token: &str) -> usize {
+        self.id(token).unwrap_or(Self::UNK_ID)
+    }
+
+    /// Returns the token of an id.
+    pub fn token(&self, id: usize) -> Option<&str> {
+        self.tokens.get(id).map(String::as_str)
+    }
+
+    /// Returns `true` if an id is one of the special tokens.
+    pub fn is_special(&self, id: usize) -> bool {
+        id < Self::SPECIAL_TOKENS.len()
+    }
+}
+
+/// The options used to encode texts into ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeOptions {
+    /// Whether to wrap the tokens in `BOS` and `EOS`.
+    pub add_special_tokens: bool,
+    /// The length sequences are truncated to and, with `pad`, padded to.
+    pub max_length: Option<usize>,
+    /// Whether to pad sequences with `PAD` to `max_length`.
+    pub pad: bool,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self { add_special_tokens: true, max_length: None, pad: true }
+    }
+}
+
+impl EncodeOptions {
+    /// Creates the default options: special tokens are added and sequences are not truncated.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether to wrap the tokens in `BOS` and `EOS`.
+    ///
+    /// # Arguments
+    ///
+    /// * `add_special_tokens` - Whether to add the special tokens.
+    pub fn with_special_tokens(mut self, add_special_tokens: bool) -> Self {
+        self.add_special_tokens = add_special_tokens;
+        self
+    }
+
+    /// Sets the length sequences are truncated and padded to.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_length` - The maximum number of ids per sequence.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        if max_length == 0 {
+            panic!("Max length must be greater than 0.");
+        }
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Sets whether to pad sequences shorter than `max_length`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pad` - Whether to pad sequences.
+    pub fn with_padding(mut self, pad: bool) -> Self {
+        self.pad = pad;
+        self
+    }
+}
+
+/// Converts texts to sequences of token ids and back.
+pub trait Tokenizer: Send + Sync {
+    /// Returns the vocabulary of the tokenizer.
+    fn vocab(&self) -> &Vocab;
+
+    /// Splits a text into token ids, without special tokens, truncation or padding.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to split.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the tokens, with `UNK_ID` for unknown tokens.
+    fn tokenize(&self, text: &str) -> Vec<usize>;
+
+    /// Converts ids back to text, skipping the special tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The ids to convert.
+    ///
+    /// # Returns
+    ///
+    /// The text of the ids.
+    fn decode(&self, ids: &[usize]) -> String;
+
+    /// Returns the number of tokens in the vocabulary, the number of rows of an embedding of the
+    /// ids.
+    fn vocab_size(&self) -> usize {
+        self.vocab().len()
+    }
+
+    /// Encodes a text into ids.
+    ///
+    /// Truncation keeps `EOS` as the last id when special tokens are added. Panics if
+    /// `max_length` is `Some(0)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to encode.
+    /// * `options` - The special token, truncation and padding options.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the text.
+    fn encode(&self, text: &str, options: &EncodeOptions) -> Vec<usize> {
+        if options.max_length == Some(0) {
+            panic!("Max length must be greater than 0.");
+        }
+
+        let mut ids = Vec::new();
+        if options.add_special_tokens {
+            ids.push(Vocab::BOS_ID);
+        }
+        ids.extend(self.tokenize(text));
+        if options.add_special_tokens {
+            ids.push(Vocab::EOS_ID);
+        }
+
+        if let Some(max_length) = options.max_length {
+            if ids.len() > max_length {
+                ids.truncate(max_length);
+                if options.add_special_tokens {
+                    ids[max_length - 1] = Vocab::EOS_ID;
+                }
+            } else if options.pad {
+                ids.resize(max_length, Vocab::PAD_ID);
+            }
+        }
+        ids
+    }
+
+    /// Encodes texts into a batch of ids.
+    ///
+    /// Sequences are padded with `PAD_ID` to `max_length`, or to the longest sequence without a
+    /// maximum length. The ids are stored as `f32` in a `[batch, length]` tensor, the input of an
+    /// embedding lookup with `vocab_size` rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - The texts to encode.
+    /// * `options` - The special token, truncation and padding options.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the texts.
+    fn encode_batch(&self, texts: &[&str], options: &EncodeOptions) -> Tensor {
+        let options = EncodeOptions { pad: true, ..*options };
+        let sequences: Vec<Vec<usize>> =
+            texts.iter().map(|text| self.encode(text, &options)).collect();
+        let length = sequences.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut ids = vec![Vocab::PAD_ID as f32; sequences.len() * length];
+        for (row, sequence) in ids.chunks_mut(length.max(1)).zip(&sequences) {
+            for (slot, &id) in row.iter_mut().zip(sequence) {
+                *slot = id as f32;
+            }
+        }
+        Tensor::new(ids, Shape::from(IxDyn(&[sequences.len(), length])))
+    }
+}
+
+/// Splits a text into lowercase words and punctuation marks.
+///
+/// # Arguments
+///
+/// * `text` - The text to split.
+///
+/// # Returns
+///
+/// The words and punctuation marks of the text, in order.
+pub fn split_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() || c == '\'' {
+            word.extend(c.to_lowercase());
+            continue;
+        }
+        if !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        if !c.is_whitespace() {
+            words.push(c.to_string());
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// A tokenizer with one token per character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharTokenizer {
+    vocab: Vocab,
+}
+
+impl CharTokenizer {
+    /// Builds the vocabulary of the characters of a corpus.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - The texts of the corpus.
+    ///
+    /// # Returns
+    ///
+    /// A tokenizer with the characters of the texts, sorted.
+    pub fn train(texts: &[&str]) -> Self {
+        let mut chars: Vec<char> = texts.iter().flat_map(|text| text.chars()).collect();
+        chars.sort_unstable();
+        chars.dedup();
+        Self { vocab: Vocab::new(chars.into_iter().map(String::from)) }
+    }
+}
+
+impl Tokenizer for CharTokenizer {
+    fn vocab(&self) -> &Vocab {
+        &self.vocab
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<usize> {
+        let mut buffer = [0; 4];
+        text.chars().map(|c| self.vocab.id_or_unk(c.encode_utf8(&mut buffer))).collect()
+    }
+
+    fn decode(&self, ids: &[usize]) -> String {
+        ids.iter()
+            .filter(|&&id| !self.vocab.is_special(id))
+            .filter_map(|&id| self.vocab.token(id))
+            .collect()
+    }
+}
+
+/// A tokenizer with one token per word or punctuation mark, split by `split_words`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordTokenizer {
+    vocab: Vocab,
+}
+
+impl WordTokenizer {
+    /// Builds the vocabulary of the most frequent words of a corpus.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - The texts of the corpus.
+    /// * `max_vocab_size` - The maximum size of the vocabulary, including the special tokens.
+    ///   Less frequent words are encoded as `UNK`.
+    ///
+    /// # Returns
+    ///
+    /// A tokenizer with the words of the texts.
+    pub fn train(texts: &[&str], max_vocab_size: Option<usize>) -> Self {
+        let mut counts = HashMap::new();
+        for word in texts.iter().flat_map(|text| split_words(text)) {
+            *counts.entry(word).or_insert(0) += 1;
+        }
+        Self { vocab: Vocab::from_counts(&counts, max_vocab_size) }
+    }
+
+    /// Creates a tokenizer from an existing vocabulary.
+    ///
+    /// # Arguments
+    ///
+    /// * `vocab` - The vocabulary of lowercase words.
+    pub fn from_vocab(vocab: Vocab) -> Self {
+        Self { vocab }
+    }
+}
+
+impl Tokenizer for WordTokenizer {
+    fn vocab(&self) -> &Vocab {
+        &self.vocab
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<usize> {
+        split_words(text).iter().map(|word| self.vocab.id_or_unk(word)).collect()
+    }
+
+    fn decode(&self, ids: &[usize]) -> String {
+        ids.iter()
+            .filter(|&&id| !self.vocab.is_special(id))
+            .filter_map(|&id| self.vocab.token(id))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// A byte-pair encoding tokenizer that splits words into learned subword units.
+///
+/// Words are split by `split_words`, and the last unit of each word ends with `END_OF_WORD`, so
+/// the text can be rebuilt from the units.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BpeTokenizer {
+    vocab: Vocab,
+    merges: Vec<(String, String)>,
+    ranks: HashMap<(String, String), usize>,
+}
+
+impl BpeTokenizer {
+    /// The marker appended to the last unit of a word.
+    pub const END_OF_WORD: &'static str = "</w>";
+
+    /// Learns the merges of a corpus until the vocabulary reaches a size.
+    ///
+    /// The vocabulary starts with the characters of the corpus; the most frequent pair of adjacent
+    /// units is then merged repeatedly, ties being broken alphabetically.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - The texts of the corpus.
+    /// * `vocab_size` - The size of the vocabulary, including the special tokens.
+    ///
+    /// # Returns
+    ///
+    /// A tokenizer with the learned merges.
+    pub fn train(texts: &[&str], vocab_size: usize) -> Self {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for word in texts.iter().flat_map(|text| split_words(text)) {
+            *counts.entry(word).or_insert(0) += 1;
+        }
+        let mut words: Vec<(Vec<String>, usize)> =
+            counts.into_iter().map(|(word, count)| (Self::units(&word), count)).collect();
+        words.sort();
+
+        let mut units: Vec<String> = words.iter().flat_map(|(units, _)| units.clone()).collect();
+        units.sort();
+        units.dedup();
+        let mut vocab = Vocab::new(units);
+        let mut merges = Vec::new();
+
+        while vocab.len() < vocab_size {
+            let mut pairs: HashMap<(&str, &str), usize> = HashMap::new();
+            for (units, count) in &words {
+                for pair in units.windows(2) {
+                    *pairs.entry((pair[0].as_str(), pair[1].as_str())).or_insert(0) += count;
+                }
+            }
+            let Some(((left, right), _)) =
+                pairs.into_iter().max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+            else {
+                break;
+            };
+
+            let pair = (left.to_string(), right.to_string());
+            for (units, _) in &mut words {
+                Self::merge(units, &pair);
+            }
+            vocab.push(format!("{}{}", pair.0, pair.1));
+            merges.push(pair);
+        }
+
+        let ranks = merges.iter().cloned().enumerate().map(|(rank, pair)| (pair, rank)).collect();
+        Self { vocab, merges, ranks }
+    }
+
+    /// Splits a word into its characters, marking the last one with `END_OF_WORD`.
+    fn units(word: &str) -> Vec<String> {
+        let mut units: Vec<String> = word.chars().map(String::from).collect();
+        if let Some(last) = units.last_mut() {
+            last.push_str(Self::END_OF_WORD);
+        }
+        units
+    }
+
+    /// Merges every occurrence of a pair of adjacent units.
+    fn merge(units: &mut Vec<String>, pair: &(String, String)) {
+        let mut i = 0;
+        while i + 1 < units.len() {
+            if units[i] == pair.0 && units[i + 1] == pair.1 {
+                let right = units.remove(i + 1);
+                units[i].push_str(&right);
+            }
+            i += 1;
+        }
+    }
+
+    /// Splits a word into units by applying the merges in the order they were learned.
+    fn encode_word(&self, word: &str) -> Vec<String> {
+        let mut units = Self::units(word);
+        loop {
+            let best = units
+                .windows(2)
+                .filter_map(|pair| self.ranks.get(&(pair[0].clone(), pair[1].clone())))
+                .min();
+            let Some(&rank) = best else {
+                return units;
+            };
+            Self::merge(&mut units, &self.merges[rank]);
+        }
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn vocab(&self) -> &Vocab {
+        &self.vocab
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<usize> {
+        split_words(text)
+            .iter()
+            .flat_map(|word| self.encode_word(word))
+            .map(|unit| self.vocab.id_or_unk(&unit))
+            .collect()
+    }
+
+    fn decode(&self, ids: &[usize]) -> String {
+        let text: String = ids
+            .iter()
+            .filter(|&&id| !self.vocab.is_special(id))
+            .filter_map(|&id| self.vocab.token(id))
+            .collect();
+        text.replace(Self::END_OF_WORD, " ").trim_end().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORPUS: [&str; 3] = ["The lower the better.", "Lowest, newest!", "the newer widest"];
+
+    #[test]
+    fn test_split_words_separates_punctuation() {
+        assert_eq!(split_words("Don't stop, NOW!"), ["don't", "stop", ",", "now", "!"]);
+    }
+
+    #[test]
+    fn test_vocab_starts_with_special_tokens() {
+        let vocab = Vocab::new(["b".to_string(), "a".to_string(), "b".to_string()]);
+
+        assert_eq!(vocab.len(), 6);
+        assert_eq!(vocab.token(Vocab::PAD_ID), Some("<pad>"));
+        assert_eq!(vocab.id("<eos>"), Some(Vocab::EOS_ID));
+        assert_eq!(vocab.id("a"), Some(5));
+        assert_eq!(vocab.id_or_unk("c"), Vocab::UNK_ID);
+    }
+
+    #[test]
+    fn test_char_tokenizer_round_trips_known_characters() {
+        let tokenizer = CharTokenizer::train(&CORPUS);
+        let ids = tokenizer.tokenize("the best");
+
+        assert_eq!(ids.len(), 8);
+        assert_eq!(tokenizer.decode(&ids), "the best");
+        assert_eq!(tokenizer.tokenize("z"), [Vocab::UNK_ID]);
+    }
+
+    #[test]
+    fn test_word_tokenizer_keeps_the_most_frequent_words() {
+        let tokenizer = WordTokenizer::train(&CORPUS, Some(6));
+
+        // "the" occurs three times and the punctuation marks once, so only "the" and the first
+        // punctuation mark alphabetically fit next to the four special tokens
+        assert_eq!(tokenizer.vocab_size(), 6);
+        assert_eq!(tokenizer.tokenize("The wall!"), [4, Vocab::UNK_ID, 5]);
+        assert_eq!(tokenizer.decode(&[Vocab::BOS_ID, 4, 5]), "the !");
+    }
+
+    #[test]
+    fn test_encode_adds_special_tokens_truncates_and_pads() {
+        let tokenizer = WordTokenizer::train(&CORPUS, None);
+        let the = tokenizer.vocab().id("the").unwrap();
+
+        let padded = tokenizer.encode("the", &EncodeOptions::new().with_max_length(5));
+        assert_eq!(padded, [Vocab::BOS_ID, the, Vocab::EOS_ID, Vocab::PAD_ID, Vocab::PAD_ID]);
+
+        let truncated = tokenizer.encode("the the the", &EncodeOptions::new().with_max_length(3));
+        assert_eq!(truncated, [Vocab::BOS_ID, the, Vocab::EOS_ID]);
+
+        let plain = tokenizer.encode("the", &EncodeOptions::new().with_special_tokens(false));
+        assert_eq!(plain, [the]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Max length must be greater than 0.")]
+    fn test_encode_rejects_zero_max_length() {
+        let tokenizer = WordTokenizer::train(&CORPUS, None);
+        let options = EncodeOptions { max_length: Some(0), ..EncodeOptions::new() };
+        tokenizer.encode("the", &options);
+    }
+
+    #[test]
+    fn test_encode_batch_pads_to_the_longest_sequence() {
+        let tokenizer = CharTokenizer::train(&CORPUS);
+        let options = EncodeOptions::new().with_special_tokens(false);
+
+        let ids = tokenizer.encode_batch(&["the", "t"], &options);
+
+        assert_eq!(ids.data.shape(), &[2, 3]);
+        assert_eq!(ids.data[[1, 0]], tokenizer.vocab().id("t").unwrap() as f32);
+        assert_eq!(ids.data[[1, 2]], Vocab::PAD_ID as f32);
+    }
+
+    #[test]
+    fn test_bpe_learns_frequent_subwords() {
+        let tokenizer = BpeTokenizer::train(&CORPUS, 40);
+
+        assert!(tokenizer.vocab_size() <= 40);
+        assert!(tokenizer.vocab().id("the</w>").is_some());
+        assert_eq!(tokenizer.tokenize("the").len(), 1);
+
+        let ids = tokenizer.tokenize("the newest lower");
+        assert!(ids.len() < "thenewestlower".len());
+        assert_eq!(tokenizer.decode(&ids), "the newest lower");
+        assert_eq!(tokenizer.decode(&tokenizer.tokenize("wet")), "wet");
+    }
+
+    #[test]
+    fn test_bpe_training_is_deterministic() {
+        assert_eq!(BpeTokenizer::train(&CORPUS, 30), BpeTokenizer::train(&CORPUS, 30));
+    }
+}
@@ -32,28 +32,38 @@
 use std::fs::File;
 use std::future::Future;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
+use std::sync::Arc;
 
 use flate2::read::GzDecoder;
 use log::debug;
 use ndarray::{IxDyn, Shape};
+use rand::rngs::StdRng;
 use tar::Archive;
 
-use crate::deep_learning::dataset::{Dataset, DatasetOps};
+use crate::deep_learning::dataset::vision::transforms::Compose;
+use crate::deep_learning::dataset::{
+    DEFAULT_SPLIT_SEED, Dataset, DatasetBuilder, DatasetOps, IndexedDataset, LoadDataset,
+    LoadOptions, Split, cache,
+};
+use crate::deep_learning::rng::rng_from_seed;
 use crate::deep_learning::tensor_ops::Tensor;
 use crate::devices::Device;
-use crate::get_workspace_dir;
 
 /// A struct representing the CIFAR-10 dataset.
 pub struct Cifar10Dataset {
     train: Option<Dataset>,
     test: Option<Dataset>,
     val: Option<Dataset>,
+    split: Split,
+    transform: Option<Arc<Compose>>,
 }
 
 impl Cifar10Dataset {
     const CIFAR10_URL: &'static str = "https://www.cs.toronto.edu/~kriz/cifar-10-binary.tar.gz";
+    const CIFAR10_SHA256: &'static str =
+        "c4a38c50a1bc5f3a1c5537f2155ab9d68f9f25eb1ed8d9ddda3db29a59bca1dd";
     const CIFAR10_TRAIN_FILES: [&'static str; 5] = [
         "data_batch_1.bin",
         "data_batch_2.bin",
@@ -64,50 +74,116 @@ impl Cifar10Dataset {
     const CIFAR10_TEST_FILE: &'static str = "test_batch.bin";
     const CIFAR10_IMAGE_SIZE: usize = 32;
     const CIFAR10_NUM_CLASSES: usize = 10;
-    const TRAIN_EXAMPLES: usize = 50_000;
-    const TEST_EXAMPLES: usize = 10_000;
+
+    /// Creates a builder that loads a single split of the CIFAR-10 dataset.
+    ///
+    /// # Returns
+    /// A `DatasetBuilder` for the CIFAR-10 dataset.
+    pub fn builder() -> DatasetBuilder<Self> {
+        DatasetBuilder::new()
+    }
+
+    /// Sets the transforms applied to every sample and batch when they are retrieved.
+    ///
+    /// # Arguments
+    /// * `transform` - The transform pipeline.
+    ///
+    /// # Returns
+    /// The CIFAR-10 dataset with the transforms.
+    pub fn with_transform(mut self, transform: Compose) -> Self {
+        self.transform = Some(Arc::new(transform));
+        self
+    }
+
+    /// Applies the transforms, if any, to a batch.
+    fn transform_batch(&self, (inputs, targets): (Tensor, Tensor)) -> (Tensor, Tensor) {
+        match &self.transform {
+            Some(transform) => transform.apply_to_batch(&inputs, &targets),
+            None => (inputs, targets),
+        }
+    }
+
+    /// Returns the options used by the `DatasetOps` loaders.
+    fn default_options() -> LoadOptions {
+        LoadOptions { download: true, ..Default::default() }
+    }
 
     /// Downloads and extracts the CIFAR-10 dataset.
     ///
     /// This function downloads the CIFAR-10 dataset from the specified URL
-    /// and extracts it to the cache directory.
-    async fn download_and_extract() {
-        let workspace_dir = get_workspace_dir();
-        let cache_path = format!("{}/.cache/dataset/cifar10/", workspace_dir.display());
-        let tarball_path = format!("{}cifar-10-binary.tar.gz", cache_path);
-
-        if !Path::new(&tarball_path).exists() {
-            debug!("Downloading CIFAR-10 dataset from {}", Self::CIFAR10_URL);
-            let response =
-                reqwest::get(Self::CIFAR10_URL).await.expect("Failed to download CIFAR-10");
-            let data = response.bytes().await.expect("Failed to read CIFAR-10 dataset");
-            fs::create_dir_all(cache_path.clone()).unwrap();
-            fs::write(&tarball_path, data).unwrap();
+    /// and extracts it to the cache directory, unless the requested files are already there.
+    /// The archive is verified against its SHA-256 hash and downloaded again if it is corrupted.
+    ///
+    /// # Arguments
+    /// * `files` - The binary files that must be present.
+    /// * `options` - The options with the cache directory and whether to download.
+    ///
+    /// # Returns
+    /// The directory containing the binary files.
+    async fn download_and_extract(
+        files: &[&str],
+        options: &LoadOptions,
+    ) -> Result<PathBuf, String> {
+        let cache_path = options.resolve_root("cifar10");
+        if files.iter().all(|file| cache_path.join(file).exists()) {
+            return Ok(cache_path);
         }
 
-        let tar_gz = File::open(&tarball_path).unwrap();
+        let tarball_path = cache_path.join("cifar-10-binary.tar.gz");
+
+        cache::fetch(Self::CIFAR10_URL, &tarball_path, Some(Self::CIFAR10_SHA256), options).await?;
+
+        let tar_gz = File::open(&tarball_path).map_err(|e| e.to_string())?;
         let tar = GzDecoder::new(tar_gz);
         let mut archive = Archive::new(tar);
         let mut seen_files = HashSet::new();
 
-        for entry in archive.entries().unwrap() {
-            let mut entry = entry.unwrap();
-            let path = entry.path().unwrap().into_owned();
-            let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        for entry in archive.entries().map_err(|e| e.to_string())? {
+            let mut entry = entry.map_err(|e| e.to_string())?;
+            let path = entry.path().map_err(|e| e.to_string())?.into_owned();
+            let Some(file_name) = path.file_name().map(|name| name.to_string_lossy().to_string())
+            else {
+                continue;
+            };
 
-            if path.is_dir() || !path.extension().map_or(false, |ext| ext == "bin") {
+            if path.is_dir() || path.extension().is_none_or(|ext| ext != "bin") {
                 continue;
             }
 
             if seen_files.insert(file_name.clone()) {
-                let full_path = format!("{}{}", cache_path, file_name);
-                if let Some(parent) = Path::new(&full_path).parent() {
-                    fs::create_dir_all(parent).unwrap();
-                }
-                entry.unpack(&full_path).unwrap();
-                debug!("Unarchived file: {}", full_path);
+                let full_path = cache_path.join(&file_name);
+                entry.unpack(&full_path).map_err(|e| e.to_string())?;
+                debug!("Unarchived file: {}", full_path.display());
             }
         }
+
+        Ok(cache_path)
+    }
+
+    /// Downloads if needed and loads the given CIFAR-10 files.
+    ///
+    /// # Arguments
+    /// * `files` - The binary files to load.
+    /// * `options` - The options with the cache directory and whether to download.
+    ///
+    /// # Returns
+    /// A `Dataset` containing the loaded images and labels.
+    async fn load_files(files: &[&str], options: &LoadOptions) -> Result<Dataset, String> {
+        let cache_path = Self::download_and_extract(files, options).await?;
+        Self::load_data(&cache_path, files)
+    }
+
+    /// Returns the split selected when the dataset was loaded.
+    ///
+    /// # Returns
+    /// The data of the split.
+    fn split_data(&self) -> &Dataset {
+        match self.split {
+            Split::Train => self.train.as_ref(),
+            Split::Test => self.test.as_ref(),
+            Split::Val => self.val.as_ref(),
+        }
+        .unwrap_or_else(|| panic!("{:?} split not loaded!", self.split))
     }
 
     /// Parses a CIFAR-10 binary file.
@@ -142,25 +218,26 @@ fn parse_file(file_path: &str, num_examples: usize) -> (Vec<f32>, Vec<f32>) {
     /// Loads the CIFAR-10 dataset.
     ///
     /// # Arguments
+    /// * `cache_path` - The directory containing the files.
     /// * `files` - A slice of file names to load.
-    /// * `total_examples` - The total number of examples to load.
     ///
     /// # Returns
     /// A `Dataset` containing the loaded images and labels.
-    fn load_data(files: &[&str], total_examples: usize) -> Dataset {
+    fn load_data(cache_path: &Path, files: &[&str]) -> Result<Dataset, String> {
         let mut images = Vec::new();
         let mut labels = Vec::new();
+        let mut total_examples = 0;
 
         for &file in files {
-            let (img, lbl) = Self::parse_file(
-                &format!("{}/.cache/dataset/cifar10/{}", get_workspace_dir().display(), file),
-                total_examples / files.len(),
-            );
+            let file_path = cache_path.join(file);
+            let num_examples = Self::count_examples(&file_path)?;
+            let (img, lbl) = Self::parse_file(&file_path.to_string_lossy(), num_examples);
             images.extend(img);
             labels.extend(lbl);
+            total_examples += num_examples;
         }
 
-        Dataset::new(
+        Ok(Dataset::new(
             Tensor::new(
                 images,
                 Shape::from(IxDyn(&[
@@ -171,29 +248,44 @@ fn load_data(files: &[&str], total_examples: usize) -> Dataset {
                 ])),
             ),
             Tensor::new(labels, Shape::from(IxDyn(&[total_examples, Self::CIFAR10_NUM_CLASSES]))),
-        )
+        ))
     }
 
-    /// Splits the training data into training and validation datasets.
+    /// Counts the records of a CIFAR-10 binary file from its size.
     ///
     /// # Arguments
-    ///
-    /// * `validation_split` - The fraction of the training data to use for validation.
+    /// * `file_path` - The path to the CIFAR-10 binary file.
     ///
     /// # Returns
+    /// The number of examples in the file.
+    fn count_examples(file_path: &Path) -> Result<usize, String> {
+        let record_size = 1 + Self::CIFAR10_IMAGE_SIZE * Self::CIFAR10_IMAGE_SIZE * 3;
+        let file_size = fs::metadata(file_path)
+            .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?
+            .len() as usize;
+
+        if file_size % record_size != 0 {
+            return Err(format!("Invalid CIFAR-10 file: {}", file_path.display()));
+        }
+
+        Ok(file_size / record_size)
+    }
+
+    /// Splits the training data into training and validation datasets.
+    ///
+    /// The validation samples are a stratified random fraction of each class, chosen with
+    /// `DEFAULT_SPLIT_SEED` so that repeated loads hold out the same samples.
+    ///
+    /// # Arguments
     ///
-    /// A tuple containing the training and validation datasets.
+    /// * `validation_split` - The fraction of the training data to use for validation.
     fn split_train_validation(&mut self, validation_split: f32) {
         if let Some(train_data) = &self.train {
-            let total_samples = train_data.inputs.shape().raw_dim()[0];
-            let validation_size = (total_samples as f32 * validation_split).round() as usize;
-            let train_size = total_samples - validation_size;
-
-            let (train_inputs, val_inputs) = train_data.inputs.split_at(train_size);
-            let (train_labels, val_labels) = train_data.labels.split_at(train_size);
+            let mut rng = rng_from_seed(DEFAULT_SPLIT_SEED);
+            let (train, val) = train_data.stratified_split(validation_split, &mut rng);
 
-            self.train = Some(Dataset::new(train_inputs, train_labels));
-            self.val = Some(Dataset::new(val_inputs, val_labels));
+            self.train = Some(train);
+            self.val = Some(val);
         } else {
             panic!("Training dataset not loaded!");
         }
@@ -209,9 +301,16 @@ impl DatasetOps for Cifar10Dataset {
     /// A future that resolves to the `Cifar10Dataset` with the training dataset loaded.
     fn load_train() -> Self::LoadFuture {
         Box::pin(async {
-            Self::download_and_extract().await;
-            let train_data = Self::load_data(&Self::CIFAR10_TRAIN_FILES, Self::TRAIN_EXAMPLES);
-            Cifar10Dataset { train: Some(train_data), test: None, val: None }
+            let train_data = Self::load_files(&Self::CIFAR10_TRAIN_FILES, &Self::default_options())
+                .await
+                .unwrap_or_else(|err| panic!("Failed to load train dataset: {}", err));
+            Cifar10Dataset {
+                train: Some(train_data),
+                test: None,
+                val: None,
+                split: Split::Train,
+                transform: None,
+            }
         })
     }
 
@@ -221,17 +320,31 @@ fn load_train() -> Self::LoadFuture {
     /// A future that resolves to the `Cifar10Dataset` with the test dataset loaded.
     fn load_test() -> Self::LoadFuture {
         Box::pin(async {
-            Self::download_and_extract().await;
-            let test_data = Self::load_data(&[Self::CIFAR10_TEST_FILE], Self::TEST_EXAMPLES);
-            Cifar10Dataset { train: None, test: Some(test_data), val: None }
+            let test_data = Self::load_files(&[Self::CIFAR10_TEST_FILE], &Self::default_options())
+                .await
+                .unwrap_or_else(|err| panic!("Failed to load test dataset: {}", err));
+            Cifar10Dataset {
+                train: None,
+                test: Some(test_data),
+                val: None,
+                split: Split::Test,
+                transform: None,
+            }
         })
     }
 
     fn load_val() -> Self::LoadFuture {
         Box::pin(async {
-            Self::download_and_extract().await;
-            let train_data = Self::load_data(&Self::CIFAR10_TRAIN_FILES, Self::TRAIN_EXAMPLES);
-            let mut dataset = Cifar10Dataset { train: Some(train_data), test: None, val: None };
+            let train_data = Self::load_files(&Self::CIFAR10_TRAIN_FILES, &Self::default_options())
+                .await
+                .unwrap_or_else(|err| panic!("Failed to load train dataset: {}", err));
+            let mut dataset = Cifar10Dataset {
+                train: Some(train_data),
+                test: None,
+                val: None,
+                split: Split::Val,
+                transform: None,
+            };
             dataset.split_train_validation(0.2);
             dataset
         })
@@ -301,7 +414,37 @@ fn get_batch(&self, batch_idx: usize, batch_size: usize) -> (Tensor, Tensor) {
 
         let labels_batch = dataset.labels.slice(vec![start_idx..adjusted_end_idx, 0..10]);
 
-        (inputs_batch, labels_batch)
+        self.transform_batch((inputs_batch, labels_batch))
+    }
+
+    /// Gets the sample weights of a batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_idx` - The index of the batch to get.
+    /// * `batch_size` - The size of the batch to get.
+    ///
+    /// # Returns
+    ///
+    /// The weights of the samples in the batch, or `None` if no sample weights are set.
+    fn get_batch_sample_weights(&self, batch_idx: usize, batch_size: usize) -> Option<Tensor> {
+        self.train.as_ref().or(self.test.as_ref())?.batch_sample_weights(batch_idx, batch_size)
+    }
+
+    /// Sets the weight of each sample of the dataset used by `get_batch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - A `[samples]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// An error if the dataset is not loaded or the number of weights does not match.
+    fn set_sample_weights(&mut self, weights: Tensor) -> Result<(), String> {
+        match self.train.as_mut().or(self.test.as_mut()) {
+            Some(dataset) => dataset.set_sample_weights(weights),
+            None => Err("Dataset not loaded!".to_string()),
+        }
     }
 
     /// Calculates the loss between the predicted outputs and the true targets.
@@ -365,15 +508,33 @@ fn loss_grad(&self, outputs: &Tensor, targets: &Tensor) -> Tensor {
         Tensor::new(grad_data, outputs.shape().clone())
     }
 
-    /// Shuffles the dataset.
-    fn shuffle(&mut self) {}
+    /// Shuffles the training and test datasets.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator used to permute the samples.
+    fn shuffle_with_rng(&mut self, rng: &mut StdRng) {
+        if let Some(train) = &mut self.train {
+            train.shuffle_with_rng(rng);
+        }
+
+        if let Some(test) = &mut self.test {
+            test.shuffle_with_rng(rng);
+        }
+    }
 
     /// Clones the dataset.
     ///
     /// # Returns
     /// A new `Cifar10Dataset` instance with the same dataset.
     fn clone(&self) -> Self {
-        Self { train: self.train.clone(), test: self.test.clone(), val: self.val.clone() }
+        Self {
+            train: self.train.clone(),
+            test: self.test.clone(),
+            val: self.val.clone(),
+            split: self.split,
+            transform: self.transform.clone(),
+        }
     }
 
     /// Transfers the dataset to the specified device.
@@ -400,12 +561,74 @@ fn to_device(&mut self, device: Device) -> Result<(), String> {
     }
 }
 
+impl LoadDataset for Cifar10Dataset {
+    type BuildFuture = Pin<Box<dyn Future<Output = Result<Self, String>> + Send>>;
+
+    /// Loads a single split of the CIFAR-10 dataset.
+    ///
+    /// # Arguments
+    /// * `options` - The directory, split and download options.
+    ///
+    /// # Returns
+    /// A future that resolves to the `Cifar10Dataset` with the requested split loaded.
+    fn load_with(options: LoadOptions) -> Self::BuildFuture {
+        Box::pin(async move {
+            let mut dataset = Cifar10Dataset {
+                train: None,
+                test: None,
+                val: None,
+                split: options.split,
+                transform: None,
+            };
+            match options.split {
+                Split::Train => {
+                    let data = Self::load_files(&Self::CIFAR10_TRAIN_FILES, &options).await?;
+                    dataset.train = Some(options.select_training_split(data)?);
+                }
+                Split::Val => {
+                    let data = Self::load_files(&Self::CIFAR10_TRAIN_FILES, &options).await?;
+                    dataset.val = Some(options.select_training_split(data)?);
+                }
+                Split::Test => {
+                    dataset.test =
+                        Some(Self::load_files(&[Self::CIFAR10_TEST_FILE], &options).await?);
+                }
+            }
+            Ok(dataset)
+        })
+    }
+}
+
+impl IndexedDataset for Cifar10Dataset {
+    fn len(&self) -> usize {
+        self.split_data().len()
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        let (input, target) = self.split_data().get(index);
+        match &self.transform {
+            Some(transform) => (transform.apply_sample(&input), target),
+            None => (input, target),
+        }
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        self.transform_batch(self.split_data().get_batch(indices))
+    }
+
+    fn get_sample_weights(&self, indices: &[usize]) -> Option<Tensor> {
+        self.split_data().get_sample_weights(indices)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ndarray::Dimension;
     use serial_test::serial;
+    use tempfile::TempDir;
 
     use super::*;
+    use crate::get_workspace_dir;
 
     fn setup() {
         let workspace_dir = get_workspace_dir();
@@ -419,7 +642,12 @@ fn setup() {
     #[serial]
     async fn test_download_and_extract() {
         setup();
-        Cifar10Dataset::download_and_extract().await;
+        Cifar10Dataset::download_and_extract(
+            &Cifar10Dataset::CIFAR10_TRAIN_FILES,
+            &Cifar10Dataset::default_options(),
+        )
+        .await
+        .unwrap();
         let workspace_dir = get_workspace_dir();
         let cache_path =
             format!("{}/.cache/dataset/cifar10/data_batch_1.bin", workspace_dir.display());
@@ -449,7 +677,11 @@ fn test_load_data() {
         // Ensure the dataset is downloaded before loading data
         test_download_and_extract();
 
-        let dataset = Cifar10Dataset::load_data(&["data_batch_1.bin"], 10000);
+        let dataset = Cifar10Dataset::load_data(
+            &LoadOptions::default().resolve_root("cifar10"),
+            &["data_batch_1.bin"],
+        )
+        .unwrap();
 
         // Compare the shape of inputs
         assert_eq!(
@@ -479,4 +711,34 @@ async fn test_load_test() {
         let dataset = Cifar10Dataset::load_test().await;
         assert!(dataset.test.is_some(), "Test dataset should be loaded");
     }
+
+    fn write_records(path: &Path, labels: &[u8]) {
+        let mut bytes = Vec::new();
+        for &label in labels {
+            bytes.push(label);
+            bytes.extend(std::iter::repeat_n(label * 20, 32 * 32 * 3));
+        }
+        fs::write(path, bytes).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_builder_loads_split_from_root() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        for (i, file) in Cifar10Dataset::CIFAR10_TRAIN_FILES.iter().enumerate() {
+            write_records(&root.join(file), &[i as u8]);
+        }
+        write_records(&root.join(Cifar10Dataset::CIFAR10_TEST_FILE), &[7, 9]);
+
+        let train = Cifar10Dataset::builder().root(root).build().await.unwrap();
+        let test = Cifar10Dataset::builder().root(root).split(Split::Test).build().await.unwrap();
+
+        assert_eq!(IndexedDataset::len(&train), 5);
+        assert_eq!(IndexedDataset::len(&test), 2);
+        let (image, label) = train.get(3);
+        assert_eq!(image.data.shape(), &[32, 32, 3]);
+        assert!((image.data[[0, 0, 0]] - 60.0 / 255.0).abs() < 1e-6);
+        assert_eq!(label.data[[3]], 1.0);
+        assert_eq!(test.get(1).1.data[[9]], 1.0);
+    }
 }
@@ -1,265 +1,455 @@
-// //! BSD 3-Clause License
-// //!
-// //! Copyright (c) 2025, BlackPortal ○
-// //!
-// //! Redistribution and use in source and binary forms, with or without
-// //! modification, are permitted provided that the following conditions are met:
-// //!
-// //! 1. Redistributions of source code must retain the above copyright notice, this
-// //!    list of conditions and the following disclaimer.
-// //!
-// //! 2. Redistributions in binary form must reproduce the above copyright notice,
-// //!    this list of conditions and the following disclaimer in the documentation
-// //!    and/or other materials provided with the distribution.
-// //!
-// //! 3. Neither the name of the copyright holder nor the names of its
-// //!    contributors may be used to endorse or promote products derived from
-// //!    this software without specific prior written permission.
-// //!
-// //! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
-// //! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
-// //! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
-// //! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
-// //! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
-// //! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
-// //! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
-// //! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
-// //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
-// //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+// BSD 3-Clause License
 //
-// use std::fs::File;
-// use std::io::{BufRead, BufReader};
-// use std::path::Path;
-// use crate::common::Tensor;
-// use crate::dataset::{ImageDatasetOps};
-// use std::future::Future;
-// use std::pin::Pin;
-// use image::{DynamicImage, ImageReader};
+// Copyright (c) 2025, BlackPortal ○
 //
-// /// CustomImageDataset is a dataset that loads images and labels from a CSV file.
-// ///
-// /// # Fields
-// ///
-// /// * `images` - A vector of DynamicImages containing the loaded images.
-// /// * `data` - An optional Dataset containing the loaded inputs and labels.
-// #[allow(dead_code)]
-// pub struct CustomImageDataset {
-//     images: Vec<DynamicImage>,
-//     labels: Vec<f32>,
-// }
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
 //
-// impl CustomImageDataset {
-//     /// Creates a new CustomImageDataset from a CSV file.
-//     ///
-//     /// # Arguments
-//     ///
-//     /// * `path` - The path to the CSV file.
-//     ///
-//     /// # Returns
-//     ///
-//     /// A Result containing the CustomImageDataset or an error message.
-//     pub fn from_csv<P: AsRef<Path>>(path: P) -> Result<Self, String> {
-//         let file = File::open(path).map_err(|e| e.to_string())?;
-//         let reader = BufReader::new(file);
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
 //
-//         let mut images = Vec::new();
-//         let mut labels = Vec::new();
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
 //
-//         for line in reader.lines() {
-//             let line = line.map_err(|e| e.to_string())?;
-//             let parts: Vec<&str> = line.split(',').collect();
-//             let image_path = parts[0];
-//             let label: f32 = parts[1].parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
 //
-//             let image = ImageReader::open(image_path)
-//                 .map_err(|e| e.to_string())?
-//                 .decode()
-//                 .map_err(|e| e.to_string())?;
-//
-//             images.push(image);
-//             labels.push(label);
-//         }
-//
-//         Ok(Self { images, labels })
-//     }
-//
-//     /// Asynchronously loads the training dataset from a CSV file.
-//     ///
-//     /// # Arguments
-//     ///
-//     /// * `path` - The path to the CSV file.
-//     ///
-//     /// # Returns
-//     ///
-//     /// A future that resolves to the loaded CustomImageDataset.
-//     pub fn load_train_from_csv<P: AsRef<Path> + Send + 'static>(path: P) -> Pin<Box<dyn Future<Output = Self> + Send>> {
-//         Box::pin(async move {
-//             let file = File::open(path).expect("Failed to open train CSV file");
-//             let reader = BufReader::new(file);
-//             let mut lines = reader.lines();
-//
-//             let mut images = Vec::new();
-//             let mut labels = Vec::new();
-//
-//             while let Some(result) = lines.next() {
-//                 let line = result.expect("Failed to read line");
-//                 let parts: Vec<&str> = line.split(',').collect();
-//                 let image_path = parts[0];
-//                 let label: f32 = parts[1].parse().expect("Failed to parse label");
-//
-//                 let image = ImageReader::open(image_path)
-//                     .expect("Failed to open image file")
-//                     .decode()
-//                     .expect("Failed to decode image");
-//
-//                 images.push(image);
-//                 labels.push(label);
-//             }
-//
-//             CustomImageDataset { images, labels }
-//         })
-//     }
-//
-//     /// Asynchronously loads the test dataset from a CSV file.
-//     ///
-//     /// # Arguments
-//     ///
-//     /// * `path` - The path to the CSV file.
-//     ///
-//     /// # Returns
-//     ///
-//     /// A future that resolves to the loaded CustomImageDataset.
-//     pub fn load_test_from_csv<P: AsRef<Path> + Send + 'static>(path: P) -> Pin<Box<dyn Future<Output = Self> + Send>> {
-//         Box::pin(async move {
-//             let file = File::open(path).expect("Failed to open test CSV file");
-//             let reader = BufReader::new(file);
-//             let mut lines = reader.lines();
-//
-//             let mut images = Vec::new();
-//             let mut labels = Vec::new();
-//
-//             while let Some(line) = lines.next() {
-//                 let line = line.expect("Failed to read line");
-//                 let parts: Vec<&str> = line.split(',').collect();
-//                 let image_path = parts[0];
-//                 let label: f32 = parts[1].parse().expect("Failed to parse label");
-//
-//                 let image = ImageReader::open(image_path)
-//                     .expect("Failed to open image file")
-//                     .decode()
-//                     .expect("Failed to decode image");
-//
-//                 images.push(image);
-//                 labels.push(label);
-//             }
-//
-//             CustomImageDataset { images, labels }
-//         })
-//     }
-// }
-//
-// impl ImageDatasetOps for CustomImageDataset {
-//     type LoadFuture = Pin<Box<dyn Future<Output = Self> + Send>>;
-//
-//     /// Loads the training dataset.
-//     ///
-//     /// # Returns
-//     ///
-//     /// A future that resolves to the loaded training dataset.
-//     fn load_train() -> Self::LoadFuture {
-//         unimplemented!();
-//     }
-//
-//     /// Loads the test dataset.
-//     ///
-//     /// # Returns
-//     ///
-//     /// A future that resolves to the loaded test dataset.
-//     fn load_test() -> Self::LoadFuture {
-//         unimplemented!();
-//     }
-//
-//     /// Normalizes the dataset inputs and labels to a specified range.
-//     ///
-//     /// # Arguments
-//     ///
-//     /// * `min` - The minimum value of the normalization range.
-//     /// * `max` - The maximum value of the normalization range.
-//     fn normalize(&mut self, _min: f32, _max: f32) {
-//         unimplemented!();
-//     }
-//
-//     /// Adds noise to the dataset inputs.
-//     ///
-//     /// # Arguments
-//     ///
-//     /// * `noise_level` - The level of noise to add.
-//     fn add_noise(&mut self, _noise_level: f32) {
-//         unimplemented!();
-//     }
-//
-//     /// Returns the number of samples in the dataset.
-//     ///
-//     /// # Returns
-//     ///
-//     /// The number of samples in the dataset.
-//     fn len(&self) -> usize {
-//         unimplemented!();
-//     }
-//
-//     /// Retrieves a batch of inputs and labels from the dataset.
-//     ///
-//     /// # Arguments
-//     ///
-//     /// * `batch_idx` - The index of the batch to retrieve.
-//     /// * `batch_size` - The size of the batch to retrieve.
-//     ///
-//     /// # Returns
-//     ///
-//     /// A tuple containing the batch of inputs and labels.
-//     fn get_batch(&self, _batch_idx: usize, _batch_size: usize) -> (Tensor, Tensor) {
-//         unimplemented!();
-//     }
-//
-//     /// Computes the loss between the outputs and targets.
-//     ///
-//     /// # Arguments
-//     ///
-//     /// * `outputs` - The predicted outputs.
-//     /// * `targets` - The true targets.
-//     ///
-//     /// # Returns
-//     ///
-//     /// The loss between the outputs and targets.
-//     fn loss(&self, _outputs: &Tensor, _targets: &Tensor) -> f32 {
-//         unimplemented!();
-//     }
-//
-//     /// Computes the gradient of the loss with respect to the outputs.
-//     ///
-//     /// # Arguments
-//     ///
-//     /// * `outputs` - The predicted outputs.
-//     /// * `targets` - The true targets.
-//     ///
-//     /// # Returns
-//     ///
-//     /// A Tensor containing the computed gradients.
-//     fn loss_grad(&self, _outputs: &Tensor, _targets: &Tensor) -> Tensor {
-//         unimplemented!();
-//     }
-//
-//     /// Shuffles the dataset.
-//     fn shuffle(&mut self) {
-//         unimplemented!();
-//     }
-//
-//     /// Clones the dataset.
-//     ///
-//     /// # Returns
-//     ///
-//     /// A clone of the dataset.
-//     fn clone(&self) -> Self {
-//         unimplemented!();
-//     }
-// }
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image::imageops::FilterType;
+use ndarray::{IxDyn, Shape};
+use rayon::prelude::*;
+
+use crate::deep_learning::dataset::{Dataset, IndexedDataset};
+use crate::deep_learning::tensor_ops::Tensor;
+
+/// The channels an image is converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// A single luminance channel.
+    Grayscale,
+    /// Red, green and blue channels.
+    #[default]
+    Rgb,
+    /// Red, green, blue and alpha channels.
+    Rgba,
+}
+
+impl ColorMode {
+    /// Returns the number of channels of the color mode.
+    pub fn channels(&self) -> usize {
+        match self {
+            ColorMode::Grayscale => 1,
+            ColorMode::Rgb => 3,
+            ColorMode::Rgba => 4,
+        }
+    }
+}
+
+/// The memory layout of a decoded image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChannelOrder {
+    /// `[height, width, channels]`, as used by the other vision datasets.
+    #[default]
+    ChannelsLast,
+    /// `[channels, height, width]`.
+    ChannelsFirst,
+}
+
+/// A dataset of image files labeled by class.
+///
+/// Only the paths of the images are kept in memory. Each image is decoded when it is requested,
+/// resized to a fixed size and converted to a fixed color mode, so batches from folders of any
+/// size have the same shape. Batches are decoded in parallel.
+#[derive(Debug, Clone)]
+pub struct ImageFolderDataset {
+    samples: Vec<(PathBuf, usize)>,
+    classes: Vec<String>,
+    height: u32,
+    width: u32,
+    color_mode: ColorMode,
+    channel_order: ChannelOrder,
+}
+
+impl ImageFolderDataset {
+    const IMAGE_EXTENSIONS: [&'static str; 3] = ["png", "jpg", "jpeg"];
+    const DEFAULT_SIZE: u32 = 224;
+
+    /// Creates a dataset from a directory with one subdirectory of images per class.
+    ///
+    /// The classes are the names of the subdirectories of `root`, in sorted order, and each class
+    /// contains the `png` and `jpg` files directly inside its subdirectory.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The directory containing the class subdirectories.
+    ///
+    /// # Returns
+    ///
+    /// The dataset, or an error if the directory cannot be read or contains no images.
+    pub fn from_folder<P: AsRef<Path>>(root: P) -> Result<Self, String> {
+        let root = root.as_ref();
+        let mut classes = Vec::new();
+        for entry in fs::read_dir(root).map_err(|e| format!("{}: {}", root.display(), e))? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            if entry.file_type().map_err(|e| e.to_string())?.is_dir() {
+                classes.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        classes.sort();
+
+        let mut samples = Vec::new();
+        for (class_index, class) in classes.iter().enumerate() {
+            let class_dir = root.join(class);
+            let mut paths = Vec::new();
+            for entry in fs::read_dir(&class_dir).map_err(|e| e.to_string())? {
+                let path = entry.map_err(|e| e.to_string())?.path();
+                if path.is_file() && Self::is_image(&path) {
+                    paths.push(path);
+                }
+            }
+            paths.sort();
+            samples.extend(paths.into_iter().map(|path| (path, class_index)));
+        }
+
+        if samples.is_empty() {
+            return Err(format!("No images found in {}", root.display()));
+        }
+
+        Ok(Self::with_samples(samples, classes))
+    }
+
+    /// Creates a dataset from a CSV manifest with one `path,label` line per image.
+    ///
+    /// A `path,label` header line is skipped. Relative paths are resolved against the directory of
+    /// the manifest. The classes are the distinct labels, sorted numerically if every label is a
+    /// number and alphabetically otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The path of the CSV manifest.
+    ///
+    /// # Returns
+    ///
+    /// The dataset, or an error if the manifest cannot be read, is malformed or is empty.
+    pub fn from_csv<P: AsRef<Path>>(manifest: P) -> Result<Self, String> {
+        let manifest = manifest.as_ref();
+        let contents =
+            fs::read_to_string(manifest).map_err(|e| format!("{}: {}", manifest.display(), e))?;
+        let base_dir = manifest.parent().unwrap_or_else(|| Path::new(""));
+
+        let mut entries = Vec::new();
+        for (line_number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (line_number == 0 && line.eq_ignore_ascii_case("path,label")) {
+                continue;
+            }
+
+            let (path, label) = line.rsplit_once(',').ok_or_else(|| {
+                format!(
+                    "Invalid line {} in {}: expected `path,label`",
+                    line_number + 1,
+                    manifest.display()
+                )
+            })?;
+            entries.push((base_dir.join(path.trim()), label.trim().to_string()));
+        }
+
+        if entries.is_empty() {
+            return Err(format!("No images listed in {}", manifest.display()));
+        }
+
+        let mut classes: Vec<String> = entries.iter().map(|(_, label)| label.clone()).collect();
+        classes.sort();
+        classes.dedup();
+        if classes.iter().all(|class| class.parse::<usize>().is_ok()) {
+            classes.sort_by_key(|class| class.parse::<usize>().unwrap_or_default());
+        }
+
+        let class_to_index: HashMap<&str, usize> =
+            classes.iter().enumerate().map(|(index, class)| (class.as_str(), index)).collect();
+        let samples = entries
+            .iter()
+            .map(|(path, label)| (path.clone(), class_to_index[label.as_str()]))
+            .collect();
+
+        Ok(Self::with_samples(samples, classes))
+    }
+
+    /// Creates a dataset with the default image settings.
+    fn with_samples(samples: Vec<(PathBuf, usize)>, classes: Vec<String>) -> Self {
+        Self {
+            samples,
+            classes,
+            height: Self::DEFAULT_SIZE,
+            width: Self::DEFAULT_SIZE,
+            color_mode: ColorMode::default(),
+            channel_order: ChannelOrder::default(),
+        }
+    }
+
+    /// Returns whether a file has one of the supported image extensions.
+    fn is_image(path: &Path) -> bool {
+        path.extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .is_some_and(|ext| Self::IMAGE_EXTENSIONS.contains(&ext.as_str()))
+    }
+
+    /// Sets the size every image is resized to. Defaults to 224x224.
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - The height of the images.
+    /// * `width` - The width of the images.
+    pub fn with_size(mut self, height: u32, width: u32) -> Self {
+        if height == 0 || width == 0 {
+            panic!("Image size must be greater than 0.");
+        }
+        self.height = height;
+        self.width = width;
+        self
+    }
+
+    /// Sets the color mode every image is converted to. Defaults to `ColorMode::Rgb`.
+    ///
+    /// # Arguments
+    ///
+    /// * `color_mode` - The color mode of the images.
+    pub fn with_color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.color_mode = color_mode;
+        self
+    }
+
+    /// Sets the layout of the decoded images. Defaults to `ChannelOrder::ChannelsLast`.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_order` - The layout of the images.
+    pub fn with_channel_order(mut self, channel_order: ChannelOrder) -> Self {
+        self.channel_order = channel_order;
+        self
+    }
+
+    /// Returns the class names, in label order.
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// Returns the map from class name to label.
+    pub fn class_to_index(&self) -> HashMap<String, usize> {
+        self.classes.iter().enumerate().map(|(index, class)| (class.clone(), index)).collect()
+    }
+
+    /// Returns the path and label of every image.
+    pub fn samples(&self) -> &[(PathBuf, usize)] {
+        &self.samples
+    }
+
+    /// Returns the shape of a decoded image.
+    pub fn image_shape(&self) -> [usize; 3] {
+        let (height, width, channels) =
+            (self.height as usize, self.width as usize, self.color_mode.channels());
+        match self.channel_order {
+            ChannelOrder::ChannelsLast => [height, width, channels],
+            ChannelOrder::ChannelsFirst => [channels, height, width],
+        }
+    }
+
+    /// Decodes, resizes and converts an image.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the image.
+    ///
+    /// # Returns
+    ///
+    /// The image with pixel values in `[0, 1]`, or an error if it cannot be decoded.
+    pub fn load_image(&self, index: usize) -> Result<Tensor, String> {
+        let (path, _) = &self.samples[index];
+        let image = image::open(path)
+            .map_err(|e| format!("Failed to load image {}: {}", path.display(), e))?
+            .resize_exact(self.width, self.height, FilterType::Triangle);
+
+        let pixels = match self.color_mode {
+            ColorMode::Grayscale => image.to_luma8().into_raw(),
+            ColorMode::Rgb => image.to_rgb8().into_raw(),
+            ColorMode::Rgba => image.to_rgba8().into_raw(),
+        };
+        let pixels: Vec<f32> = pixels.iter().map(|&x| x as f32 / 255.0).collect();
+
+        let data = match self.channel_order {
+            ChannelOrder::ChannelsLast => pixels,
+            ChannelOrder::ChannelsFirst => {
+                let channels = self.color_mode.channels();
+                let plane = pixels.len() / channels;
+                (0..channels)
+                    .flat_map(|c| (0..plane).map(move |p| (c, p)))
+                    .map(|(c, p)| pixels[p * channels + c])
+                    .collect()
+            }
+        };
+
+        Ok(Tensor::new(data, Shape::from(IxDyn(&self.image_shape()))))
+    }
+
+    /// Returns the one-hot encoded label of an image.
+    fn one_hot(&self, index: usize) -> Tensor {
+        let mut label = vec![0.0; self.classes.len()];
+        label[self.samples[index].1] = 1.0;
+        Tensor::new(label, Shape::from(IxDyn(&[self.classes.len()])))
+    }
+
+    /// Decodes every image in parallel into an in-memory dataset.
+    ///
+    /// # Returns
+    ///
+    /// The images stacked along the first axis with their one-hot labels, or an error if an image
+    /// cannot be decoded.
+    pub fn to_dataset(&self) -> Result<Dataset, String> {
+        let indices: Vec<usize> = (0..self.samples.len()).collect();
+        let (inputs, labels) = self.try_get_batch(&indices)?;
+        Ok(Dataset::new(inputs, labels))
+    }
+
+    /// Decodes the images at the given indices in parallel.
+    fn try_get_batch(&self, indices: &[usize]) -> Result<(Tensor, Tensor), String> {
+        let images = indices
+            .par_iter()
+            .map(|&index| self.load_image(index))
+            .collect::<Result<Vec<_>, String>>()?;
+        let labels: Vec<Tensor> = indices.iter().map(|&index| self.one_hot(index)).collect();
+
+        Ok((Tensor::stack(&images)?, Tensor::stack(&labels)?))
+    }
+}
+
+impl IndexedDataset for ImageFolderDataset {
+    fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        let image = self.load_image(index).unwrap_or_else(|e| panic!("{}", e));
+        (image, self.one_hot(index))
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        self.try_get_batch(indices).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgb, RgbImage};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn write_image(path: &Path, color: [u8; 3]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        RgbImage::from_pixel(4, 6, Rgb(color)).save(path).unwrap();
+    }
+
+    #[test]
+    fn test_from_folder_builds_sorted_class_map() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        write_image(&root.join("dog/b.png"), [0, 255, 0]);
+        write_image(&root.join("cat/a.jpg"), [255, 0, 0]);
+        write_image(&root.join("dog/a.png"), [0, 0, 255]);
+        fs::write(root.join("dog/notes.txt"), "not an image").unwrap();
+
+        let dataset = ImageFolderDataset::from_folder(root).unwrap();
+
+        assert_eq!(dataset.classes(), &["cat".to_string(), "dog".to_string()]);
+        assert_eq!(dataset.class_to_index()["dog"], 1);
+        assert_eq!(dataset.len(), 3);
+        assert_eq!(dataset.samples()[1], (root.join("dog/a.png"), 1));
+    }
+
+    #[test]
+    fn test_get_resizes_and_orders_channels() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        write_image(&root.join("a/red.png"), [255, 0, 0]);
+        write_image(&root.join("b/blue.png"), [0, 0, 255]);
+
+        let dataset = ImageFolderDataset::from_folder(root).unwrap().with_size(2, 3);
+        let (image, label) = dataset.get(1);
+        assert_eq!(image.data.shape(), &[2, 3, 3]);
+        assert_eq!(image.data[[1, 2, 2]], 1.0);
+        assert_eq!(image.data[[1, 2, 0]], 0.0);
+        assert_eq!(label.to_vec(), vec![0.0, 1.0]);
+
+        let channels_first = dataset.clone().with_channel_order(ChannelOrder::ChannelsFirst);
+        let (image, _) = channels_first.get(1);
+        assert_eq!(image.data.shape(), &[3, 2, 3]);
+        assert_eq!(image.data[[2, 1, 2]], 1.0);
+
+        let grayscale = dataset.with_color_mode(ColorMode::Grayscale);
+        assert_eq!(grayscale.get(0).0.data.shape(), &[2, 3, 1]);
+    }
+
+    #[test]
+    fn test_get_batch_matches_get() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        for (i, color) in [[10, 20, 30], [40, 50, 60], [70, 80, 90]].iter().enumerate() {
+            write_image(&root.join(format!("c{}/{}.png", i % 2, i)), *color);
+        }
+
+        let dataset = ImageFolderDataset::from_folder(root).unwrap().with_size(4, 4);
+        let (images, labels) = dataset.get_batch(&[2, 0]);
+
+        assert_eq!(images.data.shape(), &[2, 4, 4, 3]);
+        assert_eq!(labels.data.shape(), &[2, 2]);
+        assert_eq!(images.take(&[0]).to_vec(), dataset.get(2).0.to_vec());
+        assert_eq!(dataset.to_dataset().unwrap().inputs.data.shape(), &[3, 4, 4, 3]);
+    }
+
+    #[test]
+    fn test_from_csv_resolves_paths_and_sorts_numeric_labels() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        write_image(&root.join("images/x.png"), [0, 0, 0]);
+        write_image(&root.join("images/y.png"), [255, 255, 255]);
+        let manifest = root.join("manifest.csv");
+        fs::write(&manifest, "path,label\nimages/x.png,10\nimages/y.png,2\n").unwrap();
+
+        let dataset = ImageFolderDataset::from_csv(&manifest).unwrap().with_size(1, 1);
+
+        assert_eq!(dataset.classes(), &["2".to_string(), "10".to_string()]);
+        assert_eq!(dataset.samples()[0], (root.join("images/x.png"), 1));
+        assert_eq!(dataset.get(1).0.to_vec(), vec![1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_loading_errors() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        assert!(ImageFolderDataset::from_folder(root).unwrap_err().contains("No images"));
+
+        let manifest = root.join("manifest.csv");
+        fs::write(&manifest, "missing.png\n").unwrap();
+        assert!(ImageFolderDataset::from_csv(&manifest).unwrap_err().contains("Invalid line 1"));
+
+        fs::write(&manifest, "missing.png,cat\n").unwrap();
+        let dataset = ImageFolderDataset::from_csv(&manifest).unwrap();
+        assert!(dataset.load_image(0).unwrap_err().contains("missing.png"));
+    }
+}
@@ -0,0 +1,249 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::deep_learning::dataset::LoadOptions;
+use crate::deep_learning::dataset::vision::idx::{IdxDataset, IdxDownload, IdxSource};
+
+/// The EMNIST dataset: 28x28 grayscale images of handwritten characters, in the format of
+/// MNIST.
+///
+/// The `DatasetOps` and `DatasetBuilder` loaders load the balanced split; use
+/// `EmnistDataset::load_split` to load another one.
+pub type EmnistDataset = IdxDataset<Emnist>;
+
+/// A split of the EMNIST dataset, each with its own classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmnistSplit {
+    /// 26 classes of letters, merging upper and lower case.
+    Letters,
+    /// The 10 digits.
+    Digits,
+    /// 47 classes of digits and letters, with the same number of samples per class.
+    #[default]
+    Balanced,
+}
+
+impl EmnistSplit {
+    /// Returns the name of the split in the EMNIST file names.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EmnistSplit::Letters => "letters",
+            EmnistSplit::Digits => "digits",
+            EmnistSplit::Balanced => "balanced",
+        }
+    }
+
+    /// Returns the number of classes of the split.
+    pub fn num_classes(&self) -> usize {
+        match self {
+            EmnistSplit::Letters => 26,
+            EmnistSplit::Digits => 10,
+            EmnistSplit::Balanced => 47,
+        }
+    }
+}
+
+/// The files of a split of the EMNIST dataset.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Emnist {
+    split: EmnistSplit,
+}
+
+impl Emnist {
+    const URL: &'static str = "https://biometrics.nist.gov/cs_links/EMNIST/gzip.zip";
+    const ARCHIVE_FILENAME: &'static str = "gzip.zip";
+    const ARCHIVE_DIR: &'static str = "gzip";
+
+    /// Creates the files of an EMNIST split.
+    ///
+    /// # Arguments
+    ///
+    /// * `split` - The split to load.
+    pub fn new(split: EmnistSplit) -> Self {
+        Self { split }
+    }
+
+    /// Returns the split of the files.
+    pub fn split(&self) -> EmnistSplit {
+        self.split
+    }
+}
+
+impl IdxSource for Emnist {
+    fn cache_name(&self) -> &'static str {
+        "emnist"
+    }
+
+    fn file_names(&self, is_train: bool) -> (String, String) {
+        let prefix =
+            format!("emnist-{}-{}", self.split.name(), if is_train { "train" } else { "test" });
+        (format!("{}-images-idx3-ubyte.gz", prefix), format!("{}-labels-idx1-ubyte.gz", prefix))
+    }
+
+    fn num_classes(&self) -> usize {
+        self.split.num_classes()
+    }
+
+    fn label_offset(&self) -> usize {
+        // The letters are labelled from 1 to 26.
+        match self.split {
+            EmnistSplit::Letters => 1,
+            _ => 0,
+        }
+    }
+
+    fn transposed(&self) -> bool {
+        true
+    }
+
+    fn download(&self) -> IdxDownload {
+        IdxDownload::Archive {
+            url: Self::URL,
+            file_name: Self::ARCHIVE_FILENAME,
+            member_dir: Self::ARCHIVE_DIR,
+        }
+    }
+}
+
+impl EmnistDataset {
+    /// Loads a single split of a chosen EMNIST split.
+    ///
+    /// The files are extracted from the EMNIST archive, which is downloaded if the files are
+    /// missing and downloading is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `emnist_split` - The EMNIST split to load.
+    /// * `options` - The directory, split and download options.
+    ///
+    /// # Returns
+    ///
+    /// The dataset with the requested split loaded.
+    pub async fn load_split(
+        emnist_split: EmnistSplit,
+        options: LoadOptions,
+    ) -> Result<Self, String> {
+        Self::load_source(Emnist::new(emnist_split), options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Write;
+    use std::path::Path;
+
+    use tempfile::TempDir;
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+    use crate::deep_learning::dataset::vision::idx::fixtures::{gzip, idx_pair, write_gz};
+    use crate::deep_learning::dataset::{IndexedDataset, Split};
+
+    /// Writes the EMNIST archive with the training files of a split.
+    fn write_archive(root: &Path, split: EmnistSplit, labels: &[u8]) {
+        let mut archive = zip::ZipWriter::new(File::create(root.join("gzip.zip")).unwrap());
+        let (images_name, labels_name) = Emnist::new(split).file_names(true);
+        let (images, labels) = idx_pair(labels, 28, 28);
+        for (name, bytes) in [(images_name, images), (labels_name, labels)] {
+            archive.start_file(format!("gzip/{}", name), SimpleFileOptions::default()).unwrap();
+            archive.write_all(&gzip(&bytes)).unwrap();
+        }
+        archive.finish().unwrap();
+    }
+
+    #[test]
+    fn test_splits_have_their_own_files_and_classes() {
+        let letters = Emnist::new(EmnistSplit::Letters);
+        assert_eq!(
+            letters.file_names(false).0,
+            "emnist-letters-test-images-idx3-ubyte.gz".to_string()
+        );
+        assert_eq!(letters.num_classes(), 26);
+        assert_eq!(letters.label_offset(), 1);
+        assert_eq!(Emnist::default().split(), EmnistSplit::Balanced);
+        assert_eq!(Emnist::default().num_classes(), 47);
+        assert_eq!(Emnist::new(EmnistSplit::Digits).label_offset(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_load_split_extracts_files_from_archive() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        write_archive(root, EmnistSplit::Letters, &[1, 20, 3]);
+
+        let options =
+            LoadOptions { root: Some(root.to_path_buf()), offline: true, ..Default::default() };
+        let dataset = EmnistDataset::load_split(EmnistSplit::Letters, options).await.unwrap();
+
+        assert_eq!(IndexedDataset::len(&dataset), 3);
+        assert_eq!(dataset.num_classes(), 26);
+        let (image, label) = dataset.get(1);
+        assert_eq!(label.data.shape(), &[26]);
+        assert_eq!(label.data[[19]], 1.0);
+        assert!((image.data[[0, 0, 0]] - 200.0 / 255.0).abs() < 1e-6);
+        assert!(root.join("emnist-letters-train-images-idx3-ubyte.gz").exists());
+    }
+
+    #[tokio::test]
+    async fn test_builder_loads_balanced_split_and_transposes_images() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let (images_name, labels_name) = Emnist::default().file_names(false);
+        // A single 28x28 image whose first row is stored as the first column.
+        let mut pixels = vec![0u8; 28 * 28];
+        pixels[..28].fill(255);
+        let (mut images, labels) = idx_pair(&[46], 28, 28);
+        let header = images.len() - pixels.len();
+        images[header..].copy_from_slice(&pixels);
+        write_gz(&root.join(images_name), &images);
+        write_gz(&root.join(labels_name), &labels);
+
+        let builder = EmnistDataset::builder().root(root).offline(true).split(Split::Test);
+        let dataset = builder.build().await.unwrap();
+
+        let (image, label) = dataset.get(0);
+        assert_eq!(label.data[[46]], 1.0);
+        assert_eq!(image.data[[27, 0, 0]], 1.0);
+        assert_eq!(image.data[[0, 27, 0]], 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_missing_archive_offline_reports_error() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+
+        let options =
+            LoadOptions { root: Some(root.to_path_buf()), offline: true, ..Default::default() };
+        let result = EmnistDataset::load_split(EmnistSplit::Digits, options).await;
+
+        let err = result.err().expect("Missing files should not load offline");
+        assert!(err.contains("gzip.zip not found and offline mode is enabled"), "{}", err);
+    }
+}
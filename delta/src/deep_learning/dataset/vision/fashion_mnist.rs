@@ -0,0 +1,111 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::deep_learning::dataset::vision::idx::{IdxDataset, IdxDownload, IdxSource};
+
+/// The Fashion-MNIST dataset: 28x28 grayscale images of 10 kinds of clothing, in the same
+/// format as MNIST.
+pub type FashionMnistDataset = IdxDataset<FashionMnist>;
+
+/// The files of the Fashion-MNIST dataset.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FashionMnist;
+
+impl FashionMnist {
+    const URL: &'static str = "http://fashion-mnist.s3-website.eu-central-1.amazonaws.com";
+    /// The names of the classes, in label order.
+    pub const CLASSES: [&'static str; 10] = [
+        "T-shirt/top",
+        "Trouser",
+        "Pullover",
+        "Dress",
+        "Coat",
+        "Sandal",
+        "Shirt",
+        "Sneaker",
+        "Bag",
+        "Ankle boot",
+    ];
+}
+
+impl IdxSource for FashionMnist {
+    fn cache_name(&self) -> &'static str {
+        "fashion_mnist"
+    }
+
+    fn file_names(&self, is_train: bool) -> (String, String) {
+        let prefix = if is_train { "train" } else { "t10k" };
+        (format!("{}-images-idx3-ubyte.gz", prefix), format!("{}-labels-idx1-ubyte.gz", prefix))
+    }
+
+    fn num_classes(&self) -> usize {
+        Self::CLASSES.len()
+    }
+
+    fn download(&self) -> IdxDownload {
+        // No SHA-256 hashes are published for the files; pin them with
+        // `DatasetBuilder::checksum` to verify the downloads.
+        IdxDownload::Files { base_url: Self::URL, checksums: &[] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::deep_learning::dataset::vision::idx::fixtures::{idx_pair, write_gz};
+    use crate::deep_learning::dataset::{IndexedDataset, Split};
+
+    #[tokio::test]
+    async fn test_builder_loads_fixture_splits() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        for (is_train, labels) in [(true, &[0u8, 9, 5][..]), (false, &[7u8][..])] {
+            let (images_name, labels_name) = FashionMnist.file_names(is_train);
+            let (images, labels) = idx_pair(labels, 28, 28);
+            write_gz(&root.join(images_name), &images);
+            write_gz(&root.join(labels_name), &labels);
+        }
+
+        let builder = || FashionMnistDataset::builder().root(root).offline(true);
+        let train = builder().build().await.unwrap();
+        let test = builder().split(Split::Test).build().await.unwrap();
+
+        assert_eq!(IndexedDataset::len(&train), 3);
+        assert_eq!(IndexedDataset::len(&test), 1);
+        assert_eq!(train.num_classes(), 10);
+
+        let (image, label) = train.get(1);
+        assert_eq!(image.data.shape(), &[28, 28, 1]);
+        assert!((image.data[[5, 5, 0]] - 90.0 / 255.0).abs() < 1e-6);
+        assert_eq!(label.data[[9]], 1.0);
+        assert_eq!(test.get(0).1.data[[7]], 1.0);
+    }
+}
@@ -0,0 +1,962 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fs::{self, File};
+use std::future::Future;
+use std::io::{self, Read};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use flate2::read::GzDecoder;
+use log::debug;
+use ndarray::{IxDyn, Shape};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::deep_learning::dataset::vision::transforms::Compose;
+use crate::deep_learning::dataset::{
+    Dataset, DatasetBuilder, DatasetOps, IndexedDataset, LoadDataset, LoadOptions, Split, cache,
+};
+use crate::deep_learning::tensor_ops::Tensor;
+use crate::devices::Device;
+
+/// The type code of unsigned byte data in the magic number of an IDX file.
+const IDX_UNSIGNED_BYTE: u8 = 0x08;
+
+/// A view of an IDX file of unsigned bytes, as used by MNIST and its variants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdxArray<'a> {
+    /// The size of each dimension, starting with the number of items.
+    pub dims: Vec<usize>,
+    /// The items, in row-major order.
+    pub data: &'a [u8],
+}
+
+impl<'a> IdxArray<'a> {
+    /// Parses a decompressed IDX file.
+    ///
+    /// The magic number must start with two zero bytes, followed by the unsigned byte type code
+    /// and the number of dimensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The contents of the file.
+    ///
+    /// # Returns
+    ///
+    /// The dimensions and data of the file, or an error if the magic number is invalid or the
+    /// data does not match the dimensions.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, String> {
+        if bytes.len() < 4 {
+            return Err("Invalid IDX file: too short for the magic number".to_string());
+        }
+        if bytes[0] != 0 || bytes[1] != 0 {
+            return Err(format!(
+                "Invalid IDX magic number 0x{:02x}{:02x}{:02x}{:02x}: expected two zero bytes",
+                bytes[0], bytes[1], bytes[2], bytes[3]
+            ));
+        }
+        if bytes[2] != IDX_UNSIGNED_BYTE {
+            return Err(format!(
+                "Unsupported IDX data type 0x{:02x}: only unsigned bytes (0x08) are supported",
+                bytes[2]
+            ));
+        }
+
+        let num_dims = bytes[3] as usize;
+        if num_dims == 0 {
+            return Err("Invalid IDX file: no dimensions".to_string());
+        }
+
+        let header_len = 4 + 4 * num_dims;
+        if bytes.len() < header_len {
+            return Err(format!("Invalid IDX file: too short for {} dimensions", num_dims));
+        }
+
+        let dims: Vec<usize> = bytes[4..header_len]
+            .chunks_exact(4)
+            .map(|dim| u32::from_be_bytes([dim[0], dim[1], dim[2], dim[3]]) as usize)
+            .collect();
+        let expected = dims.iter().product::<usize>();
+        let data = &bytes[header_len..];
+        if data.len() != expected {
+            return Err(format!(
+                "Invalid IDX file: dimensions {:?} require {} bytes of data, found {}",
+                dims,
+                expected,
+                data.len()
+            ));
+        }
+
+        Ok(Self { dims, data })
+    }
+
+    /// Returns the number of items in the file.
+    pub fn len(&self) -> usize {
+        self.dims[0]
+    }
+
+    /// Returns `true` if the file contains no items.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Converts a file of images to a `[items, height, width, 1]` tensor scaled to [0, 1].
+    ///
+    /// # Arguments
+    ///
+    /// * `transposed` - Whether the images are stored column by column, as in EMNIST.
+    ///
+    /// # Returns
+    ///
+    /// The images, or an error if the file does not have three dimensions.
+    pub fn to_images(&self, transposed: bool) -> Result<Tensor, String> {
+        let (count, height, width) = match self.dims.as_slice() {
+            &[count, height, width] => (count, height, width),
+            dims => return Err(format!("Expected IDX images with 3 dimensions, found {:?}", dims)),
+        };
+
+        let data = if transposed {
+            let mut data = vec![0.0; self.data.len()];
+            for (image, pixels) in self.data.chunks_exact(height * width).enumerate() {
+                let offset = image * height * width;
+                for (i, &pixel) in pixels.iter().enumerate() {
+                    // Stored column by column: pixel `i` is at row `i % height`, column
+                    // `i / height`.
+                    data[offset + (i % height) * width + i / height] = pixel as f32 / 255.0;
+                }
+            }
+            data
+        } else {
+            self.data.iter().map(|&pixel| pixel as f32 / 255.0).collect()
+        };
+
+        Ok(Tensor::new(data, Shape::from(IxDyn(&[count, height, width, 1]))))
+    }
+
+    /// Converts a file of labels to a `[items, num_classes]` one-hot tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_classes` - The number of classes.
+    /// * `offset` - The value of the first label, subtracted from every label.
+    ///
+    /// # Returns
+    ///
+    /// The labels, or an error if the file does not have one dimension or a label is out of
+    /// range.
+    pub fn to_one_hot(&self, num_classes: usize, offset: usize) -> Result<Tensor, String> {
+        if self.dims.len() != 1 {
+            return Err(format!("Expected IDX labels with 1 dimension, found {:?}", self.dims));
+        }
+
+        let mut data = vec![0.0; self.len() * num_classes];
+        for (i, &label) in self.data.iter().enumerate() {
+            let class = (label as usize)
+                .checked_sub(offset)
+                .filter(|&class| class < num_classes)
+                .ok_or_else(|| format!("Invalid label value: {}", label))?;
+            data[i * num_classes + class] = 1.0;
+        }
+
+        Ok(Tensor::new(data, Shape::from(IxDyn(&[self.len(), num_classes]))))
+    }
+}
+
+/// Reads and decompresses a gzip file.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the gzip file.
+///
+/// # Returns
+///
+/// A `Result` containing the decompressed data, or an `io::Error` if the operation fails.
+pub fn read_gz(file_path: &Path) -> io::Result<Vec<u8>> {
+    let mut decoder = GzDecoder::new(File::open(file_path)?);
+    let mut decompressed_data = Vec::new();
+    decoder.read_to_end(&mut decompressed_data)?;
+    debug!("Decompressed file: {}", file_path.display());
+    Ok(decompressed_data)
+}
+
+/// Extracts a single file from a zip archive.
+///
+/// # Arguments
+///
+/// * `archive_path` - The path of the zip archive.
+/// * `member` - The path of the file inside the archive.
+/// * `output_path` - The path the file is written to.
+///
+/// # Returns
+///
+/// An error if the archive cannot be read or does not contain the file.
+pub fn extract_zip_member(
+    archive_path: &Path,
+    member: &str,
+    output_path: &Path,
+) -> Result<(), String> {
+    let file = File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to open {}: {}", archive_path.display(), e))?;
+    let mut entry = archive
+        .by_name(member)
+        .map_err(|e| format!("Failed to find {} in {}: {}", member, archive_path.display(), e))?;
+
+    // Write to a temporary file first so an interrupted extraction is not mistaken for a
+    // complete file.
+    let part_path = output_path.with_extension("part");
+    let mut output = File::create(&part_path).map_err(|e| e.to_string())?;
+    io::copy(&mut entry, &mut output).map_err(|e| e.to_string())?;
+    fs::rename(&part_path, output_path).map_err(|e| e.to_string())?;
+    debug!("Extracted {} to {}", member, output_path.display());
+    Ok(())
+}
+
+/// Parses a pair of IDX image and label files into a dataset.
+///
+/// # Arguments
+///
+/// * `images` - The decompressed image file.
+/// * `labels` - The decompressed label file.
+/// * `num_classes` - The number of classes.
+/// * `label_offset` - The value of the first label.
+/// * `transposed` - Whether the images are stored column by column.
+///
+/// # Returns
+///
+/// The dataset, or an error if a file is invalid or the files have different lengths.
+pub fn parse_idx_pair(
+    images: &[u8],
+    labels: &[u8],
+    num_classes: usize,
+    label_offset: usize,
+    transposed: bool,
+) -> Result<Dataset, String> {
+    let images = IdxArray::parse(images)?;
+    let labels = IdxArray::parse(labels)?;
+    if images.len() != labels.len() {
+        return Err(format!(
+            "The IDX files contain {} images but {} labels",
+            images.len(),
+            labels.len()
+        ));
+    }
+
+    Ok(Dataset::new(images.to_images(transposed)?, labels.to_one_hot(num_classes, label_offset)?))
+}
+
+/// Where the files of an IDX dataset are downloaded from.
+#[derive(Debug, Clone, Copy)]
+pub enum IdxDownload {
+    /// Every file is downloaded from `base_url`, with the known SHA-256 hashes by file name.
+    Files { base_url: &'static str, checksums: &'static [(&'static str, &'static str)] },
+    /// The files are extracted from the `member_dir` directory of a zip archive.
+    Archive { url: &'static str, file_name: &'static str, member_dir: &'static str },
+}
+
+/// A dataset stored as pairs of gzipped IDX files, such as the variants of MNIST.
+pub trait IdxSource: Clone + Default + Send + Sync + 'static {
+    /// Returns the subdirectory of the cache directory the files are stored in.
+    fn cache_name(&self) -> &'static str;
+
+    /// Returns the names of the image and label files.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_train` - Whether to return the training files or the test files.
+    fn file_names(&self, is_train: bool) -> (String, String);
+
+    /// Returns the number of classes.
+    fn num_classes(&self) -> usize;
+
+    /// Returns the value of the first label.
+    fn label_offset(&self) -> usize {
+        0
+    }
+
+    /// Returns whether the images are stored column by column.
+    fn transposed(&self) -> bool {
+        false
+    }
+
+    /// Returns where the files are downloaded from.
+    fn download(&self) -> IdxDownload;
+}
+
+/// A dataset loaded from IDX files, with one split loaded at a time.
+pub struct IdxDataset<S: IdxSource> {
+    train: Option<Dataset>,
+    test: Option<Dataset>,
+    val: Option<Dataset>,
+    split: Split,
+    transform: Option<Arc<Compose>>,
+    source: S,
+}
+
+impl<S: IdxSource> IdxDataset<S> {
+    /// Creates a builder that loads a single split of the dataset.
+    ///
+    /// # Returns
+    ///
+    /// A `DatasetBuilder` for the dataset.
+    pub fn builder() -> DatasetBuilder<Self> {
+        DatasetBuilder::new()
+    }
+
+    /// Sets the transforms applied to every sample and batch when they are retrieved.
+    ///
+    /// # Arguments
+    /// * `transform` - The transform pipeline.
+    ///
+    /// # Returns
+    /// The dataset with the transforms.
+    pub fn with_transform(mut self, transform: Compose) -> Self {
+        self.transform = Some(Arc::new(transform));
+        self
+    }
+
+    /// Returns the source the dataset was loaded from.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the number of classes of the dataset.
+    pub fn num_classes(&self) -> usize {
+        self.source.num_classes()
+    }
+
+    /// Loads a single split of the dataset from a source.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The files to load.
+    /// * `options` - The directory, split and download options.
+    ///
+    /// # Returns
+    ///
+    /// The dataset with the requested split loaded.
+    pub async fn load_source(source: S, options: LoadOptions) -> Result<Self, String> {
+        let mut dataset = Self::empty(source, options.split);
+        match options.split {
+            Split::Train => {
+                let data = dataset.load_data(true, &options).await?;
+                dataset.train = Some(options.select_training_split(data)?);
+            }
+            Split::Val => {
+                let data = dataset.load_data(true, &options).await?;
+                dataset.val = Some(options.select_training_split(data)?);
+            }
+            Split::Test => dataset.test = Some(dataset.load_data(false, &options).await?),
+        }
+        Ok(dataset)
+    }
+
+    /// Creates a dataset with no split loaded.
+    fn empty(source: S, split: Split) -> Self {
+        Self { train: None, test: None, val: None, split, transform: None, source }
+    }
+
+    /// Returns the options used by the `DatasetOps` loaders.
+    fn default_options() -> LoadOptions {
+        LoadOptions { download: true, ..Default::default() }
+    }
+
+    /// Loads the training or test files of the source.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_train` - Whether to load the training files or the test files.
+    /// * `options` - The options with the directory of the files and whether to download them.
+    ///
+    /// # Returns
+    ///
+    /// The parsed dataset, or an error if a file is missing or invalid.
+    async fn load_data(&self, is_train: bool, options: &LoadOptions) -> Result<Dataset, String> {
+        let (images, labels) = self.source.file_names(is_train);
+        let images = self.get_bytes_data(&images, options).await?;
+        let labels = self.get_bytes_data(&labels, options).await?;
+
+        parse_idx_pair(
+            &images,
+            &labels,
+            self.source.num_classes(),
+            self.source.label_offset(),
+            self.source.transposed(),
+        )
+    }
+
+    /// Fetches and decompresses a file of the source.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the gzipped IDX file.
+    /// * `options` - The options with the directory of the file and whether to download it.
+    ///
+    /// # Returns
+    ///
+    /// The decompressed file.
+    async fn get_bytes_data(
+        &self,
+        file_name: &str,
+        options: &LoadOptions,
+    ) -> Result<Vec<u8>, String> {
+        let root = options.resolve_root(self.source.cache_name());
+        let file_path = root.join(file_name);
+
+        match self.source.download() {
+            IdxDownload::Files { base_url, checksums } => {
+                let url = format!("{}/{}", base_url, file_name);
+                let known =
+                    checksums.iter().find(|(name, _)| *name == file_name).map(|(_, hash)| *hash);
+                cache::fetch(&url, &file_path, known, options).await?;
+            }
+            IdxDownload::Archive { url, file_name: archive_name, member_dir } => {
+                if !file_path.exists() {
+                    let archive_path = root.join(archive_name);
+                    // The archive is not published with a SHA-256 hash, so it is only verified
+                    // against a checksum configured in the options.
+                    cache::fetch(url, &archive_path, None, options).await?;
+                    extract_zip_member(
+                        &archive_path,
+                        &format!("{}/{}", member_dir, file_name),
+                        &file_path,
+                    )?;
+                }
+            }
+        }
+
+        read_gz(&file_path).map_err(|e| e.to_string())
+    }
+
+    /// Returns the split selected when the dataset was loaded.
+    fn split_data(&self) -> &Dataset {
+        match self.split {
+            Split::Train => self.train.as_ref(),
+            Split::Test => self.test.as_ref(),
+            Split::Val => self.val.as_ref(),
+        }
+        .unwrap_or_else(|| panic!("{:?} split not loaded!", self.split))
+    }
+
+    /// Returns the split selected when the dataset was loaded, mutably.
+    fn split_data_mut(&mut self) -> &mut Dataset {
+        match self.split {
+            Split::Train => self.train.as_mut(),
+            Split::Test => self.test.as_mut(),
+            Split::Val => self.val.as_mut(),
+        }
+        .unwrap_or_else(|| panic!("{:?} split not loaded!", self.split))
+    }
+
+    /// Applies the transforms, if any, to a batch.
+    fn transform_batch(&self, (inputs, targets): (Tensor, Tensor)) -> (Tensor, Tensor) {
+        match &self.transform {
+            Some(transform) => transform.apply_to_batch(&inputs, &targets),
+            None => (inputs, targets),
+        }
+    }
+
+    /// Loads a split with the `DatasetOps` options, panicking if it fails.
+    fn load_or_panic(is_train: bool, split: Split) -> Pin<Box<dyn Future<Output = Self> + Send>> {
+        Box::pin(async move {
+            let mut dataset = Self::empty(S::default(), split);
+            match dataset.load_data(is_train, &Self::default_options()).await {
+                Ok(data) if is_train => dataset.train = Some(data),
+                Ok(data) => dataset.test = Some(data),
+                Err(err) => panic!("Failed to load {:?} dataset: {}", split, err),
+            }
+            if split == Split::Val {
+                dataset.split_train_validation(0.2);
+            }
+            dataset
+        })
+    }
+
+    /// Splits the training data into training and validation datasets.
+    ///
+    /// # Arguments
+    ///
+    /// * `validation_split` - The fraction of the training data to use for validation.
+    fn split_train_validation(&mut self, validation_split: f32) {
+        if let Some(train_data) = &self.train {
+            let total_samples = train_data.inputs.shape().raw_dim()[0];
+            let validation_size = (total_samples as f32 * validation_split).round() as usize;
+            let (train, val) = train_data.split_at(total_samples - validation_size);
+
+            self.train = Some(train);
+            self.val = Some(val);
+        } else {
+            panic!("Training dataset not loaded!");
+        }
+    }
+}
+
+impl<S: IdxSource> DatasetOps for IdxDataset<S> {
+    type LoadFuture = Pin<Box<dyn Future<Output = Self> + Send>>;
+
+    /// Loads the training dataset of the default source.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the dataset with the training split loaded.
+    fn load_train() -> Self::LoadFuture {
+        Self::load_or_panic(true, Split::Train)
+    }
+
+    /// Loads the test dataset of the default source.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the dataset with the test split loaded.
+    fn load_test() -> Self::LoadFuture {
+        Self::load_or_panic(false, Split::Test)
+    }
+
+    /// Loads the validation dataset of the default source, the last 20% of the training data.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the dataset with the validation split loaded.
+    fn load_val() -> Self::LoadFuture {
+        Self::load_or_panic(true, Split::Val)
+    }
+
+    /// Rescales the inputs of the loaded split from their range to the given range.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The minimum value for normalization.
+    /// * `max` - The maximum value for normalization.
+    fn normalize(&mut self, min: f32, max: f32) {
+        let inputs = &mut self.split_data_mut().inputs;
+        *inputs = inputs.normalize(min, max);
+    }
+
+    /// Adds Gaussian noise drawn from the global generator to the inputs of the loaded split.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise_level` - The standard deviation of the noise.
+    fn add_noise(&mut self, noise_level: f32) {
+        if noise_level < 0.0 {
+            panic!("Noise level must not be negative.");
+        }
+
+        let inputs = &mut self.split_data_mut().inputs;
+        inputs.data += &Tensor::random_normal(inputs.shape(), 0.0, noise_level).data;
+    }
+
+    /// Returns the number of samples in the dataset.
+    ///
+    /// # Returns
+    ///
+    /// The number of samples in the dataset.
+    fn len(&self) -> usize {
+        self.train
+            .as_ref()
+            .or(self.test.as_ref())
+            .map(|ds| ds.inputs.shape().raw_dim()[0])
+            .unwrap_or(0)
+    }
+
+    /// Get a batch of dataset from the dataset
+    ///
+    /// # Arguments
+    /// * `batch_idx` - The index of the batch to get
+    /// * `batch_size` - The size of the batch to get
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the input and label tensors for the batch
+    fn get_batch(&self, batch_idx: usize, batch_size: usize) -> (Tensor, Tensor) {
+        let dataset = match (self.train.as_ref(), self.test.as_ref()) {
+            (Some(train), _) => train,
+            (_, Some(test)) => test,
+            _ => panic!("Dataset not loaded!"),
+        };
+
+        let total_samples = dataset.inputs.shape().raw_dim()[0];
+        let start_idx = batch_idx * batch_size;
+        if start_idx >= total_samples {
+            panic!("Batch index {} out of range. Total samples: {}", batch_idx, total_samples);
+        }
+        let end_idx = (start_idx + batch_size).min(total_samples);
+
+        let indices: Vec<usize> = (start_idx..end_idx).collect();
+        self.transform_batch((dataset.inputs.take(&indices), dataset.labels.take(&indices)))
+    }
+
+    /// Gets the sample weights of a batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_idx` - The index of the batch to get.
+    /// * `batch_size` - The size of the batch to get.
+    ///
+    /// # Returns
+    ///
+    /// The weights of the samples in the batch, or `None` if no sample weights are set.
+    fn get_batch_sample_weights(&self, batch_idx: usize, batch_size: usize) -> Option<Tensor> {
+        self.train.as_ref().or(self.test.as_ref())?.batch_sample_weights(batch_idx, batch_size)
+    }
+
+    /// Sets the weight of each sample of the dataset used by `get_batch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - A `[samples]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// An error if the dataset is not loaded or the number of weights does not match.
+    fn set_sample_weights(&mut self, weights: Tensor) -> Result<(), String> {
+        match self.train.as_mut().or(self.test.as_mut()) {
+            Some(dataset) => dataset.set_sample_weights(weights),
+            None => Err("Dataset not loaded!".to_string()),
+        }
+    }
+
+    /// Calculates the cross-entropy loss between the predicted outputs and the true targets.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputs` - The predicted outputs from the model (probabilities).
+    /// * `targets` - The true target values (one-hot encoded).
+    ///
+    /// # Returns
+    ///
+    /// The calculated loss as a `f32` value.
+    fn loss(&self, outputs: &Tensor, targets: &Tensor) -> f32 {
+        let batch_size = targets.shape().raw_dim()[0];
+        let loss: f32 = outputs
+            .data
+            .iter()
+            .zip(targets.data.iter())
+            .map(|(&predicted, &target)| -target * predicted.max(1e-15).ln())
+            .sum();
+
+        loss / batch_size as f32
+    }
+
+    /// Calculates the gradient of the loss with respect to the predicted outputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputs` - The predicted outputs from the model (probabilities).
+    /// * `targets` - The true target values (one-hot encoded).
+    ///
+    /// # Returns
+    ///
+    /// A `Tensor` containing the gradients of the loss with respect to the outputs.
+    fn loss_grad(&self, outputs: &Tensor, targets: &Tensor) -> Tensor {
+        assert_eq!(
+            outputs.shape().raw_dim(),
+            targets.shape().raw_dim(),
+            "Outputs and targets must have the same shape"
+        );
+        let batch_size = targets.shape().raw_dim()[0] as f32;
+
+        Tensor {
+            data: (&outputs.data - &targets.data) / batch_size,
+            device: outputs.device.clone(),
+        }
+    }
+
+    /// Shuffles the dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator used to permute the samples.
+    fn shuffle_with_rng(&mut self, rng: &mut StdRng) {
+        let mut shuffle_data = |dataset: &mut Dataset| {
+            let num_samples = dataset.inputs.shape().raw_dim()[0];
+            let mut indices: Vec<usize> = (0..num_samples).collect();
+            indices.shuffle(rng);
+
+            dataset.inputs = dataset.inputs.take(&indices);
+            dataset.labels = dataset.labels.take(&indices);
+            dataset.sample_weights = dataset.sample_weights.as_ref().map(|w| w.take(&indices));
+        };
+
+        if let Some(train) = &mut self.train {
+            shuffle_data(train);
+        }
+
+        if let Some(test) = &mut self.test {
+            shuffle_data(test);
+        }
+    }
+
+    /// Clones the dataset.
+    ///
+    /// # Returns
+    ///
+    /// A new dataset that is a clone of the current instance.
+    fn clone(&self) -> Self {
+        Self {
+            train: self.train.clone(),
+            test: self.test.clone(),
+            val: self.val.clone(),
+            split: self.split,
+            transform: self.transform.clone(),
+            source: self.source.clone(),
+        }
+    }
+
+    /// Transfers the dataset to the specified device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device to transfer the dataset to.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the dataset on the specified device.
+    fn to_device(&mut self, device: Device) -> Result<(), String> {
+        for dataset in
+            [self.train.as_mut(), self.test.as_mut(), self.val.as_mut()].into_iter().flatten()
+        {
+            dataset.to_device(&device);
+        }
+        Ok(())
+    }
+}
+
+impl<S: IdxSource> LoadDataset for IdxDataset<S> {
+    type BuildFuture = Pin<Box<dyn Future<Output = Result<Self, String>> + Send>>;
+
+    /// Loads a single split of the dataset from the default source.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The directory, split and download options.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the dataset with the requested split loaded.
+    fn load_with(options: LoadOptions) -> Self::BuildFuture {
+        Box::pin(Self::load_source(S::default(), options))
+    }
+}
+
+impl<S: IdxSource> IndexedDataset for IdxDataset<S> {
+    fn len(&self) -> usize {
+        self.split_data().len()
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        let (input, target) = self.split_data().get(index);
+        match &self.transform {
+            Some(transform) => (transform.apply_sample(&input), target),
+            None => (input, target),
+        }
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        self.transform_batch(self.split_data().get_batch(indices))
+    }
+
+    fn get_sample_weights(&self, indices: &[usize]) -> Option<Tensor> {
+        self.split_data().get_sample_weights(indices)
+    }
+}
+
+/// Helpers that write IDX fixtures for the tests of the IDX datasets.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use std::fs::File;
+    use std::io::Write;
+    use std::path::Path;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    /// Encodes an IDX file of unsigned bytes.
+    pub fn idx_bytes(dims: &[usize], data: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0, 0, 0x08, dims.len() as u8];
+        for &dim in dims {
+            bytes.extend_from_slice(&(dim as u32).to_be_bytes());
+        }
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    /// Encodes a pair of IDX files with one image per label, each image filled with its label
+    /// times 10, saturating at 255.
+    pub fn idx_pair(labels: &[u8], height: usize, width: usize) -> (Vec<u8>, Vec<u8>) {
+        let pixels: Vec<u8> = labels
+            .iter()
+            .flat_map(|&label| std::iter::repeat_n(label.saturating_mul(10), height * width))
+            .collect();
+        (idx_bytes(&[labels.len(), height, width], &pixels), idx_bytes(&[labels.len()], labels))
+    }
+
+    /// Compresses data with gzip.
+    pub fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// Writes a gzipped file.
+    pub fn write_gz(path: &Path, bytes: &[u8]) {
+        File::create(path).unwrap().write_all(&gzip(bytes)).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::idx_bytes;
+    use super::*;
+    use crate::deep_learning::dataset::vision::fashion_mnist::FashionMnist;
+
+    #[test]
+    fn test_parse_reads_dimensions() {
+        let bytes = idx_bytes(&[2, 2, 3], &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+
+        let array = IdxArray::parse(&bytes).unwrap();
+
+        assert_eq!(array.dims, vec![2, 2, 3]);
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.data[11], 11);
+    }
+
+    #[test]
+    fn test_parse_validates_magic_number() {
+        let mut bytes = idx_bytes(&[1], &[3]);
+        bytes[1] = 1;
+        assert!(IdxArray::parse(&bytes).unwrap_err().contains("magic number"));
+
+        let mut bytes = idx_bytes(&[1], &[3]);
+        bytes[2] = 0x0d;
+        assert!(IdxArray::parse(&bytes).unwrap_err().contains("data type 0x0d"));
+
+        let mut bytes = idx_bytes(&[1], &[3]);
+        bytes[3] = 0;
+        assert!(IdxArray::parse(&bytes).unwrap_err().contains("no dimensions"));
+
+        assert!(IdxArray::parse(&[0, 0, 8]).unwrap_err().contains("too short"));
+    }
+
+    #[test]
+    fn test_parse_rejects_truncated_data() {
+        let bytes = idx_bytes(&[2, 2, 2], &[0; 7]);
+        let err = IdxArray::parse(&bytes).unwrap_err();
+        assert!(err.contains("require 8 bytes of data, found 7"), "{}", err);
+
+        let err = IdxArray::parse(&idx_bytes(&[4, 28], &[])[..10]).unwrap_err();
+        assert!(err.contains("too short for 2 dimensions"), "{}", err);
+    }
+
+    #[test]
+    fn test_to_images_scales_and_transposes() {
+        let bytes = idx_bytes(&[1, 2, 3], &[0, 51, 102, 153, 204, 255]);
+        let array = IdxArray::parse(&bytes).unwrap();
+
+        let images = array.to_images(false).unwrap();
+        assert_eq!(images.data.shape(), &[1, 2, 3, 1]);
+        assert!((images.data[[0, 0, 1, 0]] - 0.2).abs() < 1e-6);
+        assert!((images.data[[0, 1, 2, 0]] - 1.0).abs() < 1e-6);
+
+        // Stored column by column, the bytes are (0, 0), (1, 0), (0, 1), (1, 1), ...
+        let transposed = array.to_images(true).unwrap();
+        assert!((transposed.data[[0, 1, 0, 0]] - 0.2).abs() < 1e-6);
+        assert!((transposed.data[[0, 0, 1, 0]] - 0.4).abs() < 1e-6);
+        assert!((transposed.data[[0, 1, 2, 0]] - 1.0).abs() < 1e-6);
+
+        let labels = idx_bytes(&[2], &[0, 1]);
+        assert!(IdxArray::parse(&labels).unwrap().to_images(false).is_err());
+    }
+
+    #[test]
+    fn test_to_one_hot_applies_offset() {
+        let bytes = idx_bytes(&[3], &[1, 3, 2]);
+        let array = IdxArray::parse(&bytes).unwrap();
+
+        let labels = array.to_one_hot(3, 1).unwrap();
+        assert_eq!(labels.data.shape(), &[3, 3]);
+        assert_eq!(labels.data[[0, 0]], 1.0);
+        assert_eq!(labels.data[[1, 2]], 1.0);
+        assert_eq!(labels.data[[2, 1]], 1.0);
+
+        assert!(array.to_one_hot(3, 0).unwrap_err().contains("Invalid label value: 3"));
+        let zero = idx_bytes(&[1], &[0]);
+        assert!(IdxArray::parse(&zero).unwrap().to_one_hot(3, 1).is_err());
+    }
+
+    fn loaded(dataset: Dataset) -> IdxDataset<FashionMnist> {
+        IdxDataset { test: Some(dataset), ..IdxDataset::empty(FashionMnist, Split::Test) }
+    }
+
+    #[test]
+    fn test_normalize_rescales_the_loaded_split() {
+        let images = idx_bytes(&[3, 1, 2], &[0, 51, 102, 153, 204, 255]);
+        let labels = idx_bytes(&[3], &[0, 1, 2]);
+        let mut dataset = loaded(parse_idx_pair(&images, &labels, 10, 0, false).unwrap());
+
+        dataset.normalize(-1.0, 1.0);
+
+        let inputs = &dataset.split_data().inputs.data;
+        assert_eq!(inputs[[0, 0, 0, 0]], -1.0);
+        assert!((inputs[[1, 0, 0, 0]] + 0.2).abs() < 1e-6);
+        assert_eq!(inputs[[2, 0, 1, 0]], 1.0);
+    }
+
+    #[test]
+    fn test_add_noise_perturbs_the_loaded_split() {
+        let images = idx_bytes(&[100, 2, 2], &[128; 400]);
+        let labels = idx_bytes(&[100], &[0; 100]);
+        let mut dataset = loaded(parse_idx_pair(&images, &labels, 10, 0, false).unwrap());
+        let original = dataset.split_data().inputs.clone();
+
+        dataset.add_noise(0.1);
+
+        let noise: Vec<f32> =
+            (&dataset.split_data().inputs.data - &original.data).into_iter().collect();
+        let mean = noise.iter().sum::<f32>() / noise.len() as f32;
+        let std =
+            (noise.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / noise.len() as f32).sqrt();
+        assert!(mean.abs() < 0.02, "{}", mean);
+        assert!((std - 0.1).abs() < 0.02, "{}", std);
+    }
+
+    #[test]
+    fn test_parse_idx_pair_checks_lengths() {
+        let images = idx_bytes(&[2, 1, 1], &[0, 255]);
+
+        let dataset = parse_idx_pair(&images, &idx_bytes(&[2], &[1, 0]), 2, 0, false).unwrap();
+        assert_eq!(dataset.len(), 2);
+        assert_eq!(dataset.labels.data[[0, 1]], 1.0);
+
+        let err = parse_idx_pair(&images, &idx_bytes(&[1], &[1]), 2, 0, false).unwrap_err();
+        assert!(err.contains("2 images but 1 labels"), "{}", err);
+    }
+}
@@ -27,75 +27,376 @@
 // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::fs::File;
+use std::collections::HashMap;
+use std::fs::{self, File};
 use std::future::Future;
 use std::io::{self};
 use std::path::Path;
 use std::pin::Pin;
 use std::process::Command;
+use std::sync::Arc;
 
 use flate2::read::GzDecoder;
 use log::debug;
-use ndarray::{ArrayBase, Axis, Dim, OwnedRepr, s};
+use ndarray::{Axis, s};
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
-use reqwest;
 use tokio::fs as async_fs;
 use walkdir::WalkDir;
 
-use crate::deep_learning::dataset::{Dataset, DatasetOps};
+use crate::deep_learning::dataset::vision::transforms::Compose;
+use crate::deep_learning::dataset::{
+    DEFAULT_SPLIT_SEED, Dataset, DatasetBuilder, DatasetOps, IndexedDataset, LoadDataset,
+    LoadOptions, Split, cache,
+};
 use crate::deep_learning::encoders::one_hot_encode;
+use crate::deep_learning::rng::rng_from_seed;
 use crate::deep_learning::tensor_ops::Tensor;
 use crate::devices::Device;
 
+/// A variant of the ImageNetV2 test set, differing in how the images were selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageNetV2Variant {
+    /// Images sampled to match the selection frequency distribution of the ImageNet validation
+    /// set.
+    #[default]
+    MatchedFrequency,
+    /// Images with a selection frequency of at least 0.7.
+    Threshold0_7,
+    /// The images with the highest selection frequency.
+    TopImages,
+}
+
+impl ImageNetV2Variant {
+    /// All the variants, in the order of their index.
+    pub const ALL: [ImageNetV2Variant; 3] = [
+        ImageNetV2Variant::MatchedFrequency,
+        ImageNetV2Variant::Threshold0_7,
+        ImageNetV2Variant::TopImages,
+    ];
+
+    /// Returns the name of the variant, as used in the archive names.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImageNetV2Variant::MatchedFrequency => "matched-frequency",
+            ImageNetV2Variant::Threshold0_7 => "threshold0.7",
+            ImageNetV2Variant::TopImages => "top-images",
+        }
+    }
+
+    /// Returns the index of the variant in `ALL`.
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|variant| variant == self).unwrap()
+    }
+
+    /// Returns the URL of the archive of the variant.
+    pub fn url(&self) -> String {
+        format!(
+            "https://huggingface.co/datasets/vaishaal/ImageNetV2/resolve/main/imagenetv2-{}.tar.gz",
+            self.name()
+        )
+    }
+}
+
+/// The ImageNet classes, mapping each label to its WordNet synset and name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageNetClasses {
+    synsets: Vec<(String, String)>,
+}
+
+impl ImageNetClasses {
+    const URL: &'static str =
+        "https://storage.googleapis.com/download.tensorflow.org/data/imagenet_class_index.json";
+    const FILENAME: &'static str = "imagenet_class_index.json";
+
+    /// Loads the class index, downloading it to the ImageNetV2 directory if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options with the directory of the file and whether to download it.
+    ///
+    /// # Returns
+    ///
+    /// The classes, or an error if the file is missing or invalid.
+    pub async fn load(options: &LoadOptions) -> Result<Self, String> {
+        let path = options.resolve_root("imagenetv2").join(Self::FILENAME);
+
+        // No SHA-256 hash is published for the file; pin it with `DatasetBuilder::checksum` to
+        // verify the download.
+        cache::fetch(Self::URL, &path, None, options).await?;
+
+        let json = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_json(&json)
+    }
+
+    /// Parses a class index of the form `{"0": ["n01440764", "tench"], ...}`.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The class index.
+    ///
+    /// # Returns
+    ///
+    /// The classes, or an error if the index is invalid or a label is missing.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let index: HashMap<String, (String, String)> = serde_json::from_str(json)
+            .map_err(|e| format!("Invalid ImageNet class index: {}", e))?;
+
+        let mut synsets = vec![None; index.len()];
+        for (label, synset) in index {
+            let label =
+                label.parse::<usize>().ok().filter(|&label| label < synsets.len()).ok_or_else(
+                    || format!("Invalid label in the ImageNet class index: {}", label),
+                )?;
+            synsets[label] = Some(synset);
+        }
+
+        Ok(Self { synsets: synsets.into_iter().map(Option::unwrap).collect() })
+    }
+
+    /// Returns the number of classes.
+    pub fn len(&self) -> usize {
+        self.synsets.len()
+    }
+
+    /// Returns `true` if there are no classes.
+    pub fn is_empty(&self) -> bool {
+        self.synsets.is_empty()
+    }
+
+    /// Returns the WordNet synset of a label, such as `n01440764`.
+    pub fn synset(&self, label: usize) -> Option<&str> {
+        self.synsets.get(label).map(|(synset, _)| synset.as_str())
+    }
+
+    /// Returns the name of a label, such as `tench`.
+    pub fn name(&self, label: usize) -> Option<&str> {
+        self.synsets.get(label).map(|(_, name)| name.as_str())
+    }
+
+    /// Returns the label of a WordNet synset.
+    pub fn label(&self, synset: &str) -> Option<usize> {
+        self.synsets.iter().position(|(id, _)| id == synset)
+    }
+
+    /// Decodes predictions to class names.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputs` - A `[batch, classes]` tensor of scores.
+    ///
+    /// # Returns
+    ///
+    /// The name of the class with the highest score of each sample.
+    pub fn decode(&self, outputs: &Tensor) -> Vec<&str> {
+        outputs
+            .data
+            .axis_iter(Axis(0))
+            .map(|scores| {
+                let label = scores
+                    .iter()
+                    .enumerate()
+                    .fold((0, f32::NEG_INFINITY), |best, (label, &score)| {
+                        if score > best.1 { (label, score) } else { best }
+                    })
+                    .0;
+                self.name(label).unwrap_or("unknown")
+            })
+            .collect()
+    }
+}
+
 /// A struct representing the ImageNetV2 dataset.
+///
+/// ImageNetV2 has no separate training set, so the images of a variant are split into stratified
+/// training, validation and test sets with a seed.
 pub struct ImageNetV2Dataset {
     train: Option<Dataset>,
     val: Option<Dataset>,
+    test: Option<Dataset>,
+    split: Split,
+    transform: Option<Arc<Compose>>,
+    variant: ImageNetV2Variant,
 }
 
 impl ImageNetV2Dataset {
-    const IMAGENETV2_URLS: &'static [&'static str] = &[
-        "https://huggingface.co/datasets/vaishaal/ImageNetV2/resolve/main/imagenetv2-matched-frequency.tar.gz",
-        "https://huggingface.co/datasets/vaishaal/ImageNetV2/resolve/main/imagenetv2-threshold0.7.tar.gz",
-        "https://huggingface.co/datasets/vaishaal/ImageNetV2/resolve/main/imagenetv2-top-images.tar.gz",
-    ];
+    const IMAGENET_NUM_CLASSES: usize = 1000;
+    const DEFAULT_TEST_SPLIT: f32 = 0.2;
+
+    /// Creates a builder that loads a single split of the ImageNetV2 dataset.
+    ///
+    /// # Returns
+    /// A `DatasetBuilder` for the ImageNetV2 dataset.
+    pub fn builder() -> DatasetBuilder<Self> {
+        DatasetBuilder::new()
+    }
+
+    /// Sets the transforms applied to every sample and batch when they are retrieved.
+    ///
+    /// # Arguments
+    /// * `transform` - The transform pipeline.
+    ///
+    /// # Returns
+    /// The ImageNetV2 dataset with the transforms.
+    pub fn with_transform(mut self, transform: Compose) -> Self {
+        self.transform = Some(Arc::new(transform));
+        self
+    }
+
+    /// Returns the variant the dataset was loaded from.
+    pub fn variant(&self) -> ImageNetV2Variant {
+        self.variant
+    }
+
+    /// Loads the ImageNet classes, to decode labels and predictions to synsets and names.
+    ///
+    /// # Arguments
+    /// * `options` - The options with the directory of the class index and whether to download it.
+    ///
+    /// # Returns
+    /// The ImageNet classes.
+    pub async fn load_classes(options: &LoadOptions) -> Result<ImageNetClasses, String> {
+        ImageNetClasses::load(options).await
+    }
+
+    /// Applies the transforms, if any, to a batch.
+    fn transform_batch(&self, (inputs, targets): (Tensor, Tensor)) -> (Tensor, Tensor) {
+        match &self.transform {
+            Some(transform) => transform.apply_to_batch(&inputs, &targets),
+            None => (inputs, targets),
+        }
+    }
 
     /// Load the ImageNetV2 dataset.
     ///
     /// # Arguments
-    /// * `variant_index` - The index of the dataset variant to load.
+    /// * `variant_index` - The index of the dataset variant to load in `ImageNetV2Variant::ALL`.
     ///
     /// # Returns
-    /// A future that resolves to the `ImageNetV2Dataset` with the ImageNetV2 dataset loaded.
+    /// A future that resolves to all the images of the variant.
     pub async fn load(variant_index: usize) -> Result<Dataset, String> {
-        if variant_index >= Self::IMAGENETV2_URLS.len() {
-            return Err("Invalid variant index".to_string());
+        let variant =
+            ImageNetV2Variant::ALL.get(variant_index).ok_or("Invalid variant index".to_string())?;
+        Self::load_with_options(*variant, &LoadOptions { download: true, ..Default::default() })
+            .await
+    }
+
+    /// Loads a single split of a variant of the ImageNetV2 dataset.
+    ///
+    /// The `test_split` fraction of the images of each class, 20% by default, forms `Split::Test`;
+    /// `validation_split` of the remaining images forms `Split::Val`. The splits are chosen with
+    /// the `split_seed`, or `DEFAULT_SPLIT_SEED`, so the splits loaded with the same options do
+    /// not overlap.
+    ///
+    /// # Arguments
+    /// * `variant` - The variant to load.
+    /// * `options` - The directory, split and download options.
+    ///
+    /// # Returns
+    /// The dataset with the requested split loaded.
+    pub async fn load_variant(
+        variant: ImageNetV2Variant,
+        options: LoadOptions,
+    ) -> Result<Self, String> {
+        let data = Self::load_with_options(variant, &options).await?;
+        let seed = options.split_seed.unwrap_or(DEFAULT_SPLIT_SEED);
+        let test_split =
+            if options.test_split > 0.0 { options.test_split } else { Self::DEFAULT_TEST_SPLIT };
+        let (rest, test) = data.stratified_split(test_split, &mut rng_from_seed(seed));
+
+        let options = LoadOptions { split_seed: Some(seed), ..options };
+        let mut dataset = ImageNetV2Dataset {
+            train: None,
+            val: None,
+            test: None,
+            split: options.split,
+            transform: None,
+            variant,
+        };
+        match options.split {
+            Split::Train => dataset.train = Some(options.select_training_split(rest)?),
+            Split::Val => dataset.val = Some(options.select_training_split(rest)?),
+            Split::Test => dataset.test = Some(test),
         }
+        Ok(dataset)
+    }
 
-        let url = Self::IMAGENETV2_URLS[variant_index];
-        let dataset_path = format!(
-            "{}/.cache/dataset/imagenetv2/variant_{}",
-            env!("CARGO_MANIFEST_DIR"),
-            variant_index
-        );
-        let archive_path = format!("{}.tar.gz", dataset_path);
-        debug!("Downloading ImageNetV2 dataset from {}", &url);
-
-        // If archive_path doesn't exist, download it
-        if !Path::new(&archive_path).exists() {
-            let response = reqwest::get(url).await.map_err(|e| e.to_string())?;
-            let data = response.bytes().await.map_err(|e| e.to_string())?;
+    /// Load the ImageNetV2 dataset from the directory configured in `options`.
+    ///
+    /// An already extracted variant is used as is; otherwise the archive is downloaded if
+    /// allowed and extracted.
+    ///
+    /// # Arguments
+    /// * `variant` - The dataset variant to load.
+    /// * `options` - The options with the cache directory and whether to download.
+    ///
+    /// # Returns
+    /// A future that resolves to the parsed Dataset.
+    async fn load_with_options(
+        variant: ImageNetV2Variant,
+        options: &LoadOptions,
+    ) -> Result<Dataset, String> {
+        let url = variant.url();
+        let cache_path = options.resolve_root("imagenetv2");
+        let dataset_path = cache_path.join(format!("variant_{}", variant.index()));
+        let archive_path = cache_path.join(format!("variant_{}.tar.gz", variant.index()));
+
+        if archive_path.exists() || !dataset_path.exists() {
+            // The published archives have no pinned hashes, so they are only verified when a
+            // checksum is configured in the options.
+            cache::fetch(&url, &archive_path, None, options).await?;
             async_fs::create_dir_all(&dataset_path).await.map_err(|e| e.to_string())?;
-            async_fs::write(&archive_path, &data).await.map_err(|e| e.to_string())?;
-            debug!("File successfully written to {}", &archive_path);
-        }
 
-        Self::decompress_and_untar(&archive_path, &dataset_path).map_err(|e| e.to_string())?;
+            Self::decompress_and_untar(
+                &archive_path.to_string_lossy(),
+                &dataset_path.to_string_lossy(),
+            )
+            .map_err(|e| e.to_string())?;
+        }
 
         Self::parse_images_and_labels(&dataset_path).await
     }
 
+    /// Returns the data of the split selected when the dataset was loaded, if it is loaded.
+    fn loaded_data(&self) -> Option<&Dataset> {
+        match self.split {
+            Split::Train => self.train.as_ref(),
+            Split::Test => self.test.as_ref(),
+            Split::Val => self.val.as_ref(),
+        }
+    }
+
+    /// Returns the mutable data of the split selected when the dataset was loaded, if it is
+    /// loaded.
+    fn loaded_data_mut(&mut self) -> Option<&mut Dataset> {
+        match self.split {
+            Split::Train => self.train.as_mut(),
+            Split::Test => self.test.as_mut(),
+            Split::Val => self.val.as_mut(),
+        }
+    }
+
+    /// Returns the split selected when the dataset was loaded.
+    ///
+    /// # Returns
+    /// The data of the split.
+    fn split_data(&self) -> &Dataset {
+        self.loaded_data().unwrap_or_else(|| panic!("{:?} split not loaded!", self.split))
+    }
+
+    /// Loads a split of the default variant with the `DatasetOps` options, panicking if it fails.
+    fn load_default(split: Split) -> Pin<Box<dyn Future<Output = Self> + Send>> {
+        Box::pin(async move {
+            let options =
+                LoadOptions { split, download: true, validation_split: 0.2, ..Default::default() };
+            match Self::load_variant(ImageNetV2Variant::default(), options).await {
+                Ok(dataset) => dataset,
+                Err(err) => panic!("Failed to load dataset: {}", err),
+            }
+        })
+    }
+
     /// Decompresses a gzip file and extracts its contents to the specified output directory.
     ///
     /// This function first attempts to use the Rust implementation to decompress and extract the
@@ -136,10 +437,7 @@ fn decompress_and_untar(file_path: &str, output_path: &str) -> io::Result<()> {
                     debug!("Successfully decompressed using system tar to: {}", output_path);
                     Ok(())
                 } else {
-                    Err(io::Error::new(
-                        io::ErrorKind::Other,
-                        "Both Rust and system tar implementations failed",
-                    ))
+                    Err(io::Error::other("Both Rust and system tar implementations failed"))
                 }
             }
         }
@@ -147,119 +445,79 @@ fn decompress_and_untar(file_path: &str, output_path: &str) -> io::Result<()> {
 
     /// Parses images and labels into a Dataset.
     ///
+    /// The images of each class are in a directory named after the ImageNet label of the class.
+    ///
     /// # Arguments
     /// * `dataset_path` - The path to the directory containing the images and labels.
     ///
     /// # Returns
-    /// A future that resolves to the parsed Dataset.
-    async fn parse_images_and_labels(dataset_path: &str) -> Result<Dataset, String> {
+    /// A future that resolves to the parsed Dataset, with labels one-hot encoded over the 1000
+    /// ImageNet classes.
+    async fn parse_images_and_labels(dataset_path: &Path) -> Result<Dataset, String> {
         let mut images: Vec<Tensor> = vec![];
         let mut labels: Vec<usize> = vec![];
-        let mut label_map: Vec<usize> = vec![];
 
-        for entry in WalkDir::new(dataset_path) {
+        let entries = WalkDir::new(dataset_path).sort_by_file_name();
+        for entry in entries {
             let entry = entry.map_err(|e| e.to_string())?;
-            if entry.file_type().is_file() {
-                let path = entry.path();
-
-                if let Some(parent) = path.parent() {
-                    if let Some(label) = parent.file_name().and_then(|os_str| os_str.to_str()) {
-                        if let Ok(label_idx) = label.parse::<usize>() {
-                            if !label_map.contains(&label_idx) {
-                                label_map.push(label_idx);
-                            }
-                            let img_data = async_fs::read(path).await.map_err(|e| {
-                                format!("Failed to read image {}: {}", path.display(), e)
-                            })?;
-
-                            let img_tensor = Tensor::from_image_bytes(img_data)?;
-                            images.push(img_tensor);
-                            labels.push(label_idx);
-                        }
-                    }
-                }
+            if !entry.file_type().is_file() {
+                continue;
             }
-        }
-
-        let label_data: ArrayBase<OwnedRepr<f32>, Dim<[usize; 2]>> =
-            one_hot_encode(&labels, label_map.len());
-        let label_data_dyn = label_data.into_dyn();
-
-        let image_tensor = Tensor::stack(&images)?;
 
-        Ok(Dataset::new(image_tensor, Tensor { data: label_data_dyn, device: Device::default() }))
-    }
+            let path = entry.path();
+            let Some(label) = path
+                .parent()
+                .and_then(|parent| parent.file_name())
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.parse::<usize>().ok())
+            else {
+                continue;
+            };
+            if label >= Self::IMAGENET_NUM_CLASSES {
+                return Err(format!("Invalid ImageNet label: {}", label));
+            }
 
-    /// Splits the training data into training and validation datasets.
-    ///
-    /// # Arguments
-    ///
-    /// * `validation_split` - The fraction of the training data to use for validation.
-    ///
-    /// # Returns
-    ///
-    /// A tuple containing the training and validation datasets.
-    fn split_train_validation(&mut self, validation_split: f32) {
-        if let Some(train_data) = &self.train {
-            let total_samples = train_data.inputs.shape().raw_dim()[0];
-            let validation_size = (total_samples as f32 * validation_split).round() as usize;
-            let train_size = total_samples - validation_size;
+            let img_data = async_fs::read(path)
+                .await
+                .map_err(|e| format!("Failed to read image {}: {}", path.display(), e))?;
+            images.push(Tensor::from_image_bytes(img_data)?);
+            labels.push(label);
+        }
 
-            let (train_inputs, val_inputs) = train_data.inputs.split_at(train_size);
-            let (train_labels, val_labels) = train_data.labels.split_at(train_size);
+        let label_data = one_hot_encode(&labels, Self::IMAGENET_NUM_CLASSES).into_dyn();
+        let image_tensor = Tensor::stack(&images)?;
 
-            self.train = Some(Dataset::new(train_inputs, train_labels));
-            self.val = Some(Dataset::new(val_inputs, val_labels));
-        } else {
-            panic!("Training dataset not loaded!");
-        }
+        Ok(Dataset::new(image_tensor, Tensor { data: label_data, device: Device::default() }))
     }
 }
 
 impl DatasetOps for ImageNetV2Dataset {
     type LoadFuture = Pin<Box<dyn Future<Output = ImageNetV2Dataset> + Send>>;
 
-    /// Loads the training dataset.
+    /// Loads the training split of the default variant.
     ///
     /// # Returns
-    /// A future that resolves to the `ImageNetV2Dataset` with the ImageNetV2 dataset loaded.
+    /// A future that resolves to the `ImageNetV2Dataset` with the training split loaded.
     fn load_train() -> Self::LoadFuture {
-        Box::pin(async {
-            match ImageNetV2Dataset::load(0).await {
-                Ok(data) => ImageNetV2Dataset { train: Some(data), val: None },
-                Err(err) => panic!("Failed to load dataset: {}", err),
-            }
-        })
+        Self::load_default(Split::Train)
     }
 
-    /// Loads the test dataset.
+    /// Loads the test split of the default variant, held out from the training and validation
+    /// splits.
     ///
     /// # Returns
-    /// A future that resolves to the `ImageNetV2Dataset` with the ImageNetV2 dataset loaded.
+    /// A future that resolves to the `ImageNetV2Dataset` with the test split loaded.
     fn load_test() -> Self::LoadFuture {
-        Box::pin(async {
-            match ImageNetV2Dataset::load(0).await {
-                Ok(data) => ImageNetV2Dataset { train: Some(data), val: None },
-                Err(err) => panic!("Failed to load dataset: {}", err),
-            }
-        })
+        Self::load_default(Split::Test)
     }
 
-    /// Loads the validation dataset.
+    /// Loads the validation split of the default variant, 20% of the images not in the test
+    /// split.
     ///
     /// # Returns
-    /// A future that resolves to the `ImageNetV2Dataset` with the ImageNetV2 dataset loaded.
+    /// A future that resolves to the `ImageNetV2Dataset` with the validation split loaded.
     fn load_val() -> Self::LoadFuture {
-        Box::pin(async {
-            match ImageNetV2Dataset::load(0).await {
-                Ok(data) => {
-                    let mut dataset = ImageNetV2Dataset { train: None, val: Some(data) };
-                    dataset.split_train_validation(0.2); // Use 20% of the training data for validation
-                    dataset
-                }
-                Err(err) => panic!("Failed to load dataset: {}", err),
-            }
-        })
+        Self::load_default(Split::Val)
     }
 
     /// Normalizes the dataset to the given range.
@@ -269,7 +527,7 @@ fn load_val() -> Self::LoadFuture {
     /// * `max` - The maximum value of the normalized range.
     fn normalize(&mut self, min: f32, max: f32) {
         // Ensure the dataset is loaded
-        if let Some(dataset) = &mut self.train {
+        if let Some(dataset) = self.loaded_data_mut() {
             let data_min = dataset.inputs.data.iter().cloned().fold(f32::INFINITY, f32::min);
             let data_max = dataset.inputs.data.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
 
@@ -299,7 +557,7 @@ fn add_noise(&mut self, noise_level: f32) {
     ///
     /// The number of samples in the dataset.
     fn len(&self) -> usize {
-        self.train.as_ref().map(|ds| ds.inputs.shape().raw_dim()[0]).unwrap_or(0)
+        self.loaded_data().map(|ds| ds.inputs.shape().raw_dim()[0]).unwrap_or(0)
     }
 
     /// Gets a batch of dataset from the dataset.
@@ -313,8 +571,7 @@ fn len(&self) -> usize {
     fn get_batch(&self, batch_idx: usize, batch_size: usize) -> (Tensor, Tensor) {
         // Ensure the dataset is loaded
         let dataset = self
-            .train
-            .as_ref()
+            .loaded_data()
             .expect("Dataset not loaded. Call `load_train` or `load_test` first.");
 
         let num_samples = dataset.inputs.shape().raw_dim()[0];
@@ -333,13 +590,43 @@ fn get_batch(&self, batch_idx: usize, batch_size: usize) -> (Tensor, Tensor) {
         let batch_inputs = dataset.inputs.data.slice(s![start_idx..end_idx, ..]).to_owned();
         let batch_labels = dataset.labels.data.slice(s![start_idx..end_idx, ..]).to_owned();
 
-        (
+        self.transform_batch((
             Tensor {
                 data: batch_inputs.into_dyn(), // Convert to dynamic dimensionality
                 device: Device::default(),
             },
             Tensor { data: batch_labels.into_dyn(), device: Device::default() },
-        )
+        ))
+    }
+
+    /// Gets the sample weights of a batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_idx` - The index of the batch to get.
+    /// * `batch_size` - The size of the batch to get.
+    ///
+    /// # Returns
+    ///
+    /// The weights of the samples in the batch, or `None` if no sample weights are set.
+    fn get_batch_sample_weights(&self, batch_idx: usize, batch_size: usize) -> Option<Tensor> {
+        self.loaded_data()?.batch_sample_weights(batch_idx, batch_size)
+    }
+
+    /// Sets the weight of each sample of the dataset used by `get_batch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - A `[samples]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// An error if the dataset is not loaded or the number of weights does not match.
+    fn set_sample_weights(&mut self, weights: Tensor) -> Result<(), String> {
+        match self.loaded_data_mut() {
+            Some(dataset) => dataset.set_sample_weights(weights),
+            None => Err("Dataset not loaded!".to_string()),
+        }
     }
 
     /// Computes the loss between the outputs and targets.
@@ -388,15 +675,19 @@ fn loss_grad(&self, outputs: &Tensor, targets: &Tensor) -> Tensor {
 
     /// Shuffles the dataset.
     ///
-    /// This method shuffles the training dataset by randomly permuting the indices of the samples.
-    fn shuffle(&mut self) {
-        if let Some(dataset) = &mut self.train {
+    /// This method shuffles the loaded split by randomly permuting the indices of the samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator used to permute the samples.
+    fn shuffle_with_rng(&mut self, rng: &mut StdRng) {
+        if let Some(dataset) = self.loaded_data_mut() {
             // Get the number of samples
             let num_samples = dataset.inputs.shape().raw_dim()[0];
 
             // Create an index array and shuffle it
             let mut indices: Vec<usize> = (0..num_samples).collect();
-            indices.shuffle(&mut thread_rng());
+            indices.shuffle(rng);
 
             // Apply the shuffled indices to the inputs and labels
             let shuffled_inputs = dataset.inputs.data.select(Axis(0), &indices);
@@ -405,18 +696,28 @@ fn shuffle(&mut self) {
             // Update the dataset with shuffled dataset
             dataset.inputs.data = shuffled_inputs;
             dataset.labels.data = shuffled_labels;
+            if let Some(weights) = dataset.sample_weights.as_mut() {
+                weights.data = weights.data.select(Axis(0), &indices);
+            }
         }
     }
 
     /// Clones the `ImageNetV2Dataset`.
     ///
-    /// This method creates a deep copy of the `ImageNetV2Dataset`, including the training and validation datasets.
+    /// This method creates a deep copy of the `ImageNetV2Dataset`, including the training, validation and test datasets.
     ///
     /// # Returns
     ///
     /// A new instance of `ImageNetV2Dataset` with the same data as the original.
     fn clone(&self) -> Self {
-        Self { train: self.train.clone(), val: self.val.clone() }
+        Self {
+            train: self.train.clone(),
+            val: self.val.clone(),
+            test: self.test.clone(),
+            split: self.split,
+            transform: self.transform.clone(),
+            variant: self.variant,
+        }
     }
 
     /// Transfers the dataset to the specified device.
@@ -433,76 +734,144 @@ fn to_device(&mut self, device: Device) -> Result<(), String> {
         if let Some(val) = self.val.as_mut() {
             val.to_device(&device);
         }
+        if let Some(test) = self.test.as_mut() {
+            test.to_device(&device);
+        }
         Ok(())
     }
 }
 
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use crate::get_workspace_dir;
-//     use serial_test::serial;
-//     use std::fs;
-//     use tokio::runtime::Runtime;
-
-//     fn setup() {
-//         let workspace_dir = get_workspace_dir();
-//         let cache_path = format!("{}/.cache/dataset/imagenetv2", workspace_dir.display());
-//         if Path::new(&cache_path).exists() {
-//             fs::remove_dir_all(&cache_path).expect("Failed to delete cache directory");
-//         }
-//     }
-
-//     #[test]
-//     #[serial]
-//     fn test_download_and_extract() {
-//         setup();
-//         let rt = Runtime::new().unwrap();
-//         rt.block_on(async {
-//             let _ = ImageNetV2Dataset::load(0).await;
-//             let workspace_dir = get_workspace_dir();
-//             let cache_path = format!(
-//                 "{}/.cache/dataset/imagenetv2/variant_0",
-//                 workspace_dir.display()
-//             );
-//             assert!(
-//                 Path::new(&cache_path).exists(),
-//                 "ImageNetV2 dataset should be downloaded and extracted"
-//             );
-//         });
-//     }
-
-//     #[test]
-//     #[serial]
-//     fn test_parse_images_and_labels() {
-//         // Ensure the dataset is downloaded before parsing
-//         test_download_and_extract();
-
-//         let rt = Runtime::new().unwrap();
-//         rt.block_on(async {
-//             let dataset = ImageNetV2Dataset::load(0).await.unwrap();
-//             assert!(
-//                 dataset.inputs.data.len() > 0,
-//                 "Images should be parsed correctly"
-//             );
-//             assert!(
-//                 dataset.labels.data.len() > 0,
-//                 "Labels should be parsed correctly"
-//             );
-//         });
-//     }
-
-//     #[tokio::test]
-//     #[serial]
-//     async fn test_load_train() {
-//         let dataset = ImageNetV2Dataset::load_train().await;
-//         assert!(dataset.train.is_some(), "Training dataset should be loaded");
-//     }
-
-//     #[tokio::test]
-//     #[serial]
-//     async fn test_load_test() {
-//         let dataset = ImageNetV2Dataset::load_test().await;
-//         assert!(dataset.val.is_some(), "Test dataset should be loaded");
-//     }
-// }
+impl LoadDataset for ImageNetV2Dataset {
+    type BuildFuture = Pin<Box<dyn Future<Output = Result<Self, String>> + Send>>;
+
+    /// Loads a single split of the matched-frequency ImageNetV2 variant.
+    ///
+    /// # Arguments
+    /// * `options` - The directory, split and download options.
+    ///
+    /// # Returns
+    /// A future that resolves to the `ImageNetV2Dataset` with the requested split loaded.
+    fn load_with(options: LoadOptions) -> Self::BuildFuture {
+        Box::pin(Self::load_variant(ImageNetV2Variant::default(), options))
+    }
+}
+
+impl IndexedDataset for ImageNetV2Dataset {
+    fn len(&self) -> usize {
+        self.split_data().len()
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        let (input, target) = self.split_data().get(index);
+        match &self.transform {
+            Some(transform) => (transform.apply_sample(&input), target),
+            None => (input, target),
+        }
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        self.transform_batch(self.split_data().get_batch(indices))
+    }
+
+    fn get_sample_weights(&self, indices: &[usize]) -> Option<Tensor> {
+        self.split_data().get_sample_weights(indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{ImageFormat, Rgb, RgbImage};
+    use ndarray::{IxDyn, Shape};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const CLASSES: [usize; 3] = [0, 7, 999];
+    const IMAGES_PER_CLASS: usize = 10;
+
+    fn png(value: u8) -> Vec<u8> {
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        RgbImage::from_pixel(2, 2, Rgb([value; 3])).write_to(&mut bytes, ImageFormat::Png).unwrap();
+        bytes.into_inner()
+    }
+
+    /// Writes an extracted variant with the images of each class named after their position, so
+    /// every image of the fixture has a distinct pixel value.
+    fn fixture_root() -> TempDir {
+        let root = TempDir::new().unwrap();
+        for (class, label) in CLASSES.iter().enumerate() {
+            let dir = root.path().join("variant_0").join(label.to_string());
+            fs::create_dir_all(&dir).unwrap();
+            for i in 0..IMAGES_PER_CLASS {
+                let value = (class * IMAGES_PER_CLASS + i) as u8;
+                fs::write(dir.join(format!("{}.png", i)), png(value)).unwrap();
+            }
+        }
+        root
+    }
+
+    fn pixel_values(dataset: &ImageNetV2Dataset) -> Vec<u8> {
+        let data = dataset.split_data();
+        (0..data.len()).map(|i| (data.inputs.data[[i, 0, 0, 0]] * 255.0).round() as u8).collect()
+    }
+
+    #[test]
+    fn test_variant_names_and_indices() {
+        assert_eq!(ImageNetV2Variant::default(), ImageNetV2Variant::MatchedFrequency);
+        assert_eq!(ImageNetV2Variant::Threshold0_7.index(), 1);
+        assert!(ImageNetV2Variant::TopImages.url().ends_with("imagenetv2-top-images.tar.gz"));
+    }
+
+    #[tokio::test]
+    async fn test_seeded_splits_are_stratified_and_disjoint() {
+        let fixture = fixture_root();
+        let root = fixture.path();
+
+        let builder = |split| {
+            ImageNetV2Dataset::builder()
+                .root(root)
+                .offline(true)
+                .split(split)
+                .split_seed(7)
+                .test_split(0.2)
+                .validation_split(0.25)
+        };
+        let train = builder(Split::Train).build().await.unwrap();
+        let val = builder(Split::Val).build().await.unwrap();
+        let test = builder(Split::Test).build().await.unwrap();
+
+        assert_eq!(train.variant(), ImageNetV2Variant::MatchedFrequency);
+        assert_eq!(DatasetOps::len(&train), 18);
+        assert_eq!(DatasetOps::len(&val), 6);
+        assert_eq!(DatasetOps::len(&test), 6);
+        assert_eq!(test.split_data().labels.data.shape(), &[6, 1000]);
+
+        let mut classes = test.split_data().sample_classes();
+        classes.sort_unstable();
+        assert_eq!(classes, [0, 0, 7, 7, 999, 999]);
+
+        let mut values: Vec<u8> =
+            [&train, &val, &test].into_iter().flat_map(pixel_values).collect();
+        values.sort_unstable();
+        assert_eq!(values, (0..30).collect::<Vec<u8>>());
+
+        let again = builder(Split::Test).build().await.unwrap();
+        assert_eq!(pixel_values(&again), pixel_values(&test));
+    }
+
+    #[test]
+    fn test_classes_decode_predictions_to_names() {
+        let json = r#"{"1": ["n01443537", "goldfish"], "0": ["n01440764", "tench"]}"#;
+        let classes = ImageNetClasses::from_json(json).unwrap();
+
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes.synset(1), Some("n01443537"));
+        assert_eq!(classes.label("n01440764"), Some(0));
+        assert_eq!(classes.name(2), None);
+
+        let outputs = Tensor::new(vec![0.1, 0.9, 2.0, -1.0], Shape::from(IxDyn(&[2, 2])));
+        assert_eq!(classes.decode(&outputs), ["goldfish", "tench"]);
+
+        assert!(ImageNetClasses::from_json(r#"{"3": ["n01440764", "tench"]}"#).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::deep_learning::dataset::vision::idx::{IdxDataset, IdxDownload, IdxSource};
+
+/// The Kuzushiji-MNIST dataset: 28x28 grayscale images of 10 cursive Japanese characters, in
+/// the same format as MNIST.
+pub type KmnistDataset = IdxDataset<Kmnist>;
+
+/// The files of the Kuzushiji-MNIST dataset.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Kmnist;
+
+impl Kmnist {
+    const URL: &'static str = "http://codh.rois.ac.jp/kmnist/dataset/kmnist";
+    const NUM_CLASSES: usize = 10;
+}
+
+impl IdxSource for Kmnist {
+    fn cache_name(&self) -> &'static str {
+        "kmnist"
+    }
+
+    fn file_names(&self, is_train: bool) -> (String, String) {
+        let prefix = if is_train { "train" } else { "t10k" };
+        (format!("{}-images-idx3-ubyte.gz", prefix), format!("{}-labels-idx1-ubyte.gz", prefix))
+    }
+
+    fn num_classes(&self) -> usize {
+        Self::NUM_CLASSES
+    }
+
+    fn download(&self) -> IdxDownload {
+        // No SHA-256 hashes are published for the files; pin them with
+        // `DatasetBuilder::checksum` to verify the downloads.
+        IdxDownload::Files { base_url: Self::URL, checksums: &[] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::deep_learning::dataset::vision::idx::fixtures::{idx_pair, write_gz};
+    use crate::deep_learning::dataset::{DatasetOps, IndexedDataset, Split, cache};
+
+    #[tokio::test]
+    async fn test_builder_loads_validation_split_with_checksums() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let (images_name, labels_name) = Kmnist.file_names(true);
+        let (images, labels) = idx_pair(&[0, 1, 2, 3, 4], 28, 28);
+        write_gz(&root.join(&images_name), &images);
+        write_gz(&root.join(&labels_name), &labels);
+
+        let builder = || {
+            KmnistDataset::builder()
+                .root(root)
+                .offline(true)
+                .checksum(&images_name, cache::sha256_file(&root.join(&images_name)).unwrap())
+                .checksum(&labels_name, cache::sha256_file(&root.join(&labels_name)).unwrap())
+                .validation_split(0.4)
+        };
+        let train = builder().build().await.unwrap();
+        let val = builder().split(Split::Val).build().await.unwrap();
+
+        assert_eq!(IndexedDataset::len(&train), 3);
+        assert_eq!(IndexedDataset::len(&val), 2);
+        assert_eq!(val.get(0).1.data[[3]], 1.0);
+
+        let (inputs, targets) = DatasetOps::get_batch(&train, 1, 2);
+        assert_eq!(inputs.data.shape(), &[1, 28, 28, 1]);
+        assert_eq!(targets.data[[0, 2]], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_builder_rejects_invalid_magic_number() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let (images_name, labels_name) = Kmnist.file_names(false);
+        let (mut images, labels) = idx_pair(&[1], 28, 28);
+        images[2] = 0x0b;
+        write_gz(&root.join(images_name), &images);
+        write_gz(&root.join(labels_name), &labels);
+
+        let result = KmnistDataset::builder().root(root).split(Split::Test).build().await;
+
+        let err = result.err().expect("A file with an invalid magic number should not load");
+        assert!(err.contains("Unsupported IDX data type 0x0b"), "{}", err);
+    }
+}
@@ -0,0 +1,366 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+
+/// The data type of a compressed element.
+const MI_COMPRESSED: u32 = 15;
+/// The data type of an array element.
+const MI_MATRIX: u32 = 14;
+/// The flag set in the array flags of complex arrays.
+const COMPLEX_FLAG: u32 = 0x0800;
+
+/// The real values of a numeric MAT-file array.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatData {
+    /// Values stored as unsigned bytes in an array of class `uint8`.
+    U8(Vec<u8>),
+    /// Values of any other numeric storage type, converted to `f64`.
+    F64(Vec<f64>),
+}
+
+impl MatData {
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        match self {
+            MatData::U8(values) => values.len(),
+            MatData::F64(values) => values.len(),
+        }
+    }
+
+    /// Returns `true` if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value at an index as `f64`.
+    pub fn get(&self, index: usize) -> f64 {
+        match self {
+            MatData::U8(values) => values[index] as f64,
+            MatData::F64(values) => values[index],
+        }
+    }
+}
+
+/// A numeric array of a MAT-file, with its values in column-major order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatArray {
+    /// The name of the variable.
+    pub name: String,
+    /// The size of each dimension.
+    pub dims: Vec<usize>,
+    /// The values of the array.
+    pub data: MatData,
+}
+
+/// Reads the numeric arrays of a little-endian MATLAB level 5 MAT-file, as used by SVHN.
+///
+/// Compressed variables are decompressed; variables that are not real numeric arrays, such as
+/// cells or structs, are skipped.
+///
+/// # Arguments
+///
+/// * `bytes` - The contents of the file.
+///
+/// # Returns
+///
+/// The numeric arrays, or an error if the file is not a valid MAT-file.
+pub fn read_mat(bytes: &[u8]) -> Result<Vec<MatArray>, String> {
+    if bytes.len() < 128 {
+        return Err("Invalid MAT-file: too short for the header".to_string());
+    }
+    match &bytes[126..128] {
+        b"IM" => {}
+        b"MI" => return Err("Big-endian MAT-files are not supported".to_string()),
+        _ => return Err("Invalid MAT-file: missing the endian indicator".to_string()),
+    }
+
+    let mut arrays = Vec::new();
+    read_elements(&bytes[128..], &mut arrays)?;
+    Ok(arrays)
+}
+
+/// Reads the arrays of a sequence of data elements.
+fn read_elements(bytes: &[u8], arrays: &mut Vec<MatArray>) -> Result<(), String> {
+    let mut pos = 0;
+    while pos + 8 <= bytes.len() {
+        let (data_type, data, next) = read_tag(bytes, pos)?;
+        match data_type {
+            MI_COMPRESSED => {
+                let mut decompressed = Vec::new();
+                ZlibDecoder::new(data)
+                    .read_to_end(&mut decompressed)
+                    .map_err(|e| format!("Failed to decompress MAT-file element: {}", e))?;
+                read_elements(&decompressed, arrays)?;
+                // Compressed elements are not padded to 8 bytes.
+                pos += 8 + data.len();
+                continue;
+            }
+            MI_MATRIX => {
+                if let Some(array) = read_matrix(data)? {
+                    arrays.push(array);
+                }
+            }
+            _ => {}
+        }
+        pos = next;
+    }
+    Ok(())
+}
+
+/// Reads the tag of the data element at `pos`.
+///
+/// # Returns
+///
+/// The data type, the data and the position of the next element, or an error if the tag or the
+/// data runs past the end of `bytes`.
+fn read_tag(bytes: &[u8], pos: usize) -> Result<(u32, &[u8], usize), String> {
+    let first = read_u32(bytes, pos)?;
+    // Small elements pack the size and data type into the first 4 bytes, and the data into the
+    // next 4 bytes.
+    let (data_type, size, start, next) = if first >> 16 != 0 {
+        (first & 0xffff, (first >> 16) as usize, pos + 4, pos + 8)
+    } else {
+        let size = read_u32(bytes, pos + 4)? as usize;
+        (first, size, pos + 8, pos + 8 + size.div_ceil(8) * 8)
+    };
+
+    let data = start
+        .checked_add(size)
+        .and_then(|end| bytes.get(start..end))
+        .ok_or_else(|| format!("Invalid MAT-file: element of {} bytes is truncated", size))?;
+    Ok((data_type, data, next.min(bytes.len())))
+}
+
+/// Reads a little-endian `u32`, or returns an error if it runs past the end of `bytes`.
+fn read_u32(bytes: &[u8], pos: usize) -> Result<u32, String> {
+    bytes
+        .get(pos..pos.saturating_add(4))
+        .map(|value| u32::from_le_bytes(value.try_into().unwrap()))
+        .ok_or_else(|| "Invalid MAT-file: data element tag is truncated".to_string())
+}
+
+/// Reads an array element, returning `None` if it is not a real numeric array.
+fn read_matrix(bytes: &[u8]) -> Result<Option<MatArray>, String> {
+    let (_, flags, pos) = read_tag(bytes, 0)?;
+    if flags.len() < 4 {
+        return Err("Invalid MAT-file: array flags are truncated".to_string());
+    }
+    let flags = read_u32(flags, 0)?;
+    // Classes 6 to 15 are the numeric classes, from `double` to `uint64`.
+    let class = flags & 0xff;
+    if !(6..=15).contains(&class) || flags & COMPLEX_FLAG != 0 {
+        return Ok(None);
+    }
+
+    let (_, dims, pos) = read_tag(bytes, pos)?;
+    let dims: Vec<usize> = dims
+        .chunks_exact(4)
+        .map(|dim| i32::from_le_bytes([dim[0], dim[1], dim[2], dim[3]]).max(0) as usize)
+        .collect();
+    let (_, name, pos) = read_tag(bytes, pos)?;
+    let name = String::from_utf8_lossy(name).into_owned();
+    let (data_type, values, _) = read_tag(bytes, pos)?;
+
+    // Class 9 is `uint8` and data type 2 is `miUINT8`.
+    let data = if class == 9 && data_type == 2 {
+        MatData::U8(values.to_vec())
+    } else {
+        MatData::F64(read_values(data_type, values)?)
+    };
+    if data.len() != dims.iter().product::<usize>() {
+        return Err(format!(
+            "Invalid MAT-file: {} has dimensions {:?} but {} values",
+            name,
+            dims,
+            data.len()
+        ));
+    }
+
+    Ok(Some(MatArray { name, dims, data }))
+}
+
+/// Converts numeric data of a MAT-file data type to `f64`.
+fn read_values(data_type: u32, bytes: &[u8]) -> Result<Vec<f64>, String> {
+    macro_rules! convert {
+        ($ty:ty) => {
+            bytes
+                .chunks_exact(std::mem::size_of::<$ty>())
+                .map(|chunk| <$ty>::from_le_bytes(chunk.try_into().unwrap()) as f64)
+                .collect()
+        };
+    }
+
+    Ok(match data_type {
+        1 => convert!(i8),
+        2 => convert!(u8),
+        3 => convert!(i16),
+        4 => convert!(u16),
+        5 => convert!(i32),
+        6 => convert!(u32),
+        7 => convert!(f32),
+        9 => convert!(f64),
+        12 => convert!(i64),
+        13 => convert!(u64),
+        _ => return Err(format!("Unsupported MAT-file data type {}", data_type)),
+    })
+}
+
+/// Helpers that write MAT-file fixtures for the tests of the MAT-file datasets.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+
+    /// Encodes a data element, padded to 8 bytes.
+    fn element(data_type: u32, data: &[u8]) -> Vec<u8> {
+        let mut bytes = data_type.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(data);
+        bytes.resize(bytes.len().div_ceil(8) * 8, 0);
+        bytes
+    }
+
+    /// Encodes an array element of class `class` with `miUINT8` or `miDOUBLE` data.
+    pub fn matrix(name: &str, dims: &[usize], class: u32, data: &[u8], data_type: u32) -> Vec<u8> {
+        let mut content = element(6, &[class.to_le_bytes(), 0u32.to_le_bytes()].concat());
+        let dims: Vec<u8> = dims.iter().flat_map(|&dim| (dim as i32).to_le_bytes()).collect();
+        content.extend(element(5, &dims));
+        content.extend(element(1, name.as_bytes()));
+        content.extend(element(data_type, data));
+        element(14, &content)
+    }
+
+    /// Encodes a `uint8` array.
+    pub fn u8_matrix(name: &str, dims: &[usize], data: &[u8]) -> Vec<u8> {
+        matrix(name, dims, 9, data, 2)
+    }
+
+    /// Encodes a `double` array.
+    pub fn f64_matrix(name: &str, dims: &[usize], data: &[f64]) -> Vec<u8> {
+        let data: Vec<u8> = data.iter().flat_map(|value| value.to_le_bytes()).collect();
+        matrix(name, dims, 6, &data, 9)
+    }
+
+    /// Compresses an element.
+    pub fn compressed(element: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(element).unwrap();
+        let data = encoder.finish().unwrap();
+        let mut bytes = 15u32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    /// Encodes a MAT-file with the given elements.
+    pub fn mat_file(elements: &[Vec<u8>]) -> Vec<u8> {
+        let mut bytes = vec![b' '; 128];
+        bytes[..10].copy_from_slice(b"MATLAB 5.0");
+        bytes[124..126].copy_from_slice(&0x0100u16.to_le_bytes());
+        bytes[126..128].copy_from_slice(b"IM");
+        for element in elements {
+            bytes.extend_from_slice(element);
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::*;
+    use super::*;
+
+    #[test]
+    fn test_read_mat_reads_plain_and_compressed_arrays() {
+        let bytes = mat_file(&[
+            compressed(&u8_matrix("X", &[2, 3], &[1, 2, 3, 4, 5, 6])),
+            f64_matrix("y", &[3, 1], &[10.0, 1.0, 2.5]),
+        ]);
+
+        let arrays = read_mat(&bytes).unwrap();
+
+        assert_eq!(arrays.len(), 2);
+        assert_eq!(arrays[0].name, "X");
+        assert_eq!(arrays[0].dims, vec![2, 3]);
+        assert_eq!(arrays[0].data, MatData::U8(vec![1, 2, 3, 4, 5, 6]));
+        assert_eq!(arrays[1].name, "y");
+        assert_eq!(arrays[1].data, MatData::F64(vec![10.0, 1.0, 2.5]));
+        assert_eq!(arrays[1].data.get(2), 2.5);
+    }
+
+    #[test]
+    fn test_read_mat_converts_storage_types_and_skips_other_classes() {
+        // A `double` array stored as `miUINT8`, as MATLAB does for small integers.
+        let stored_as_u8 = matrix("y", &[1, 2], 6, &[7, 10], 2);
+        let cell = matrix("c", &[1, 1], 1, &[], 2);
+
+        let arrays = read_mat(&mat_file(&[cell, stored_as_u8])).unwrap();
+
+        assert_eq!(arrays.len(), 1);
+        assert_eq!(arrays[0].data, MatData::F64(vec![7.0, 10.0]));
+    }
+
+    #[test]
+    fn test_read_mat_rejects_invalid_files() {
+        assert!(read_mat(&[0; 64]).unwrap_err().contains("too short"));
+
+        let mut big_endian = mat_file(&[]);
+        big_endian[126..128].copy_from_slice(b"MI");
+        assert!(read_mat(&big_endian).unwrap_err().contains("Big-endian"));
+
+        let mismatched = mat_file(&[u8_matrix("X", &[2, 2], &[1, 2, 3])]);
+        assert!(read_mat(&mismatched).unwrap_err().contains("dimensions [2, 2] but 3 values"));
+
+        let mut truncated = mat_file(&[u8_matrix("X", &[1, 1], &[1])]);
+        truncated.truncate(140);
+        assert!(read_mat(&truncated).unwrap_err().contains("truncated"));
+    }
+
+    #[test]
+    fn test_read_mat_rejects_truncated_array_contents() {
+        // An array element whose declared size ends within its subelements.
+        let array = u8_matrix("X", &[1, 1], &[1]);
+        let truncated_array = |size: usize| {
+            let mut element = 14u32.to_le_bytes().to_vec();
+            element.extend((size as u32).to_le_bytes());
+            element.extend(&array[8..8 + size]);
+            mat_file(&[element])
+        };
+
+        for size in [2, 16, 20, 30] {
+            let err = read_mat(&truncated_array(size)).unwrap_err();
+            assert!(err.contains("truncated"), "{}: {}", size, err);
+        }
+    }
+}
@@ -27,28 +27,27 @@
 // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::fs::File;
 use std::future::Future;
-use std::io::{self, Read};
-use std::path::Path;
 use std::pin::Pin;
+use std::sync::Arc;
 
-use flate2::read::GzDecoder;
-use log::debug;
-use ndarray::{IxDyn, Shape};
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use reqwest;
-use tokio::fs as async_fs;
 
-use crate::deep_learning::dataset::{Dataset, DatasetOps};
+use crate::deep_learning::dataset::vision::idx::{IdxArray, read_gz};
+use crate::deep_learning::dataset::vision::transforms::Compose;
+use crate::deep_learning::dataset::{
+    Dataset, DatasetBuilder, DatasetOps, IndexedDataset, LoadDataset, LoadOptions, Split, cache,
+};
 use crate::deep_learning::tensor_ops::Tensor;
-use crate::get_workspace_dir;
 
 /// A struct representing the MNIST dataset.
 pub struct MnistDataset {
     train: Option<Dataset>,
     test: Option<Dataset>,
     val: Option<Dataset>,
+    split: Split,
+    transform: Option<Arc<Compose>>,
 }
 
 impl MnistDataset {
@@ -57,10 +56,65 @@ impl MnistDataset {
     const MNIST_TRAIN_LABELS_FILENAME: &'static str = "train-labels-idx1-ubyte.gz";
     const MNIST_TEST_DATA_FILENAME: &'static str = "t10k-images-idx3-ubyte.gz";
     const MNIST_TEST_LABELS_FILENAME: &'static str = "t10k-labels-idx1-ubyte.gz";
+    const MNIST_SHA256: [(&'static str, &'static str); 4] = [
+        (
+            Self::MNIST_TRAIN_DATA_FILENAME,
+            "440fcabf73cc546fa21475e81ea370265605f56be210a4024d2ca8f203523609",
+        ),
+        (
+            Self::MNIST_TRAIN_LABELS_FILENAME,
+            "3552534a0a558bbed6aed32b30c495cca23d567ec52cac8be1a0730e8010255c",
+        ),
+        (
+            Self::MNIST_TEST_DATA_FILENAME,
+            "8d422c7b0a1c1c79245a5bcf07fe86e33eeafee792b84584aec276f5a2dbc4e6",
+        ),
+        (
+            Self::MNIST_TEST_LABELS_FILENAME,
+            "f7ae60f92e00ec6debd23a6088c31dbd2371eca3ffa0defaefb259924204aec6",
+        ),
+    ];
     const MNIST_IMAGE_SIZE: usize = 28;
     const MNIST_NUM_CLASSES: usize = 10;
-    const TRAIN_EXAMPLES: usize = 60_000;
-    const TEST_EXAMPLES: usize = 10_000;
+
+    /// Creates a builder that loads a single split of the MNIST dataset.
+    ///
+    /// # Returns
+    ///
+    /// A `DatasetBuilder` for the MNIST dataset.
+    pub fn builder() -> DatasetBuilder<Self> {
+        DatasetBuilder::new()
+    }
+
+    /// Sets the transforms applied to every sample and batch when they are retrieved.
+    ///
+    /// # Arguments
+    /// * `transform` - The transform pipeline.
+    ///
+    /// # Returns
+    /// The MNIST dataset with the transforms.
+    pub fn with_transform(mut self, transform: Compose) -> Self {
+        self.transform = Some(Arc::new(transform));
+        self
+    }
+
+    /// Applies the transforms, if any, to a batch.
+    fn transform_batch(&self, (inputs, targets): (Tensor, Tensor)) -> (Tensor, Tensor) {
+        match &self.transform {
+            Some(transform) => transform.apply_to_batch(&inputs, &targets),
+            None => (inputs, targets),
+        }
+    }
+
+    /// Returns the options used by the `DatasetOps` loaders.
+    fn default_options() -> LoadOptions {
+        LoadOptions { download: true, ..Default::default() }
+    }
+
+    /// Returns the known SHA-256 hash of an MNIST file.
+    fn known_checksum(filename: &str) -> Option<&'static str> {
+        Self::MNIST_SHA256.iter().find(|(name, _)| *name == filename).map(|(_, hash)| *hash)
+    }
 
     /// Asynchronously loads the MNIST dataset.
     ///
@@ -70,30 +124,53 @@ impl MnistDataset {
     /// # Arguments
     ///
     /// * `is_train` - A boolean indicating whether to load the training dataset (`true`) or the test dataset (`false`).
+    /// * `options` - The options with the directory of the files and whether to download them.
     ///
     /// # Returns
     ///
     /// A `Result` containing the loaded `Dataset` or an error message if the operation fails.
-    async fn load_data(is_train: bool) -> Result<Dataset, String> {
-        let (data_filename, labels_filename, num_examples) = if is_train {
-            (
-                Self::MNIST_TRAIN_DATA_FILENAME,
-                Self::MNIST_TRAIN_LABELS_FILENAME,
-                Self::TRAIN_EXAMPLES,
-            )
+    async fn load_data(is_train: bool, options: &LoadOptions) -> Result<Dataset, String> {
+        let (data_filename, labels_filename) = if is_train {
+            (Self::MNIST_TRAIN_DATA_FILENAME, Self::MNIST_TRAIN_LABELS_FILENAME)
         } else {
-            (Self::MNIST_TEST_DATA_FILENAME, Self::MNIST_TEST_LABELS_FILENAME, Self::TEST_EXAMPLES)
+            (Self::MNIST_TEST_DATA_FILENAME, Self::MNIST_TEST_LABELS_FILENAME)
         };
 
-        let data_bytes = Self::get_bytes_data(data_filename).await?;
-        let labels_bytes = Self::get_bytes_data(labels_filename).await?;
+        let data_bytes = Self::get_bytes_data(data_filename, options).await?;
+        let labels_bytes = Self::get_bytes_data(labels_filename, options).await?;
 
+        let num_examples = Self::parse_count(&data_bytes)?;
         let data = Self::parse_images(&data_bytes, num_examples)?;
         let labels = Self::parse_labels(&labels_bytes, num_examples)?;
 
         Ok(Dataset::new(data, labels))
     }
 
+    /// Reads the number of items from the header of an MNIST file.
+    ///
+    /// # Arguments
+    /// * `data` - The decompressed file
+    ///
+    /// # Returns
+    /// The number of items in the file
+    fn parse_count(data: &[u8]) -> Result<usize, String> {
+        Ok(IdxArray::parse(data)?.len())
+    }
+
+    /// Returns the split selected when the dataset was loaded.
+    ///
+    /// # Returns
+    ///
+    /// The data of the split.
+    fn split_data(&self) -> &Dataset {
+        match self.split {
+            Split::Train => self.train.as_ref(),
+            Split::Test => self.test.as_ref(),
+            Split::Val => self.val.as_ref(),
+        }
+        .unwrap_or_else(|| panic!("{:?} split not loaded!", self.split))
+    }
+
     /// Parse the images from the MNIST dataset
     ///
     /// # Arguments
@@ -103,31 +180,16 @@ async fn load_data(is_train: bool) -> Result<Dataset, String> {
     /// # Returns
     /// A tensor containing the parsed images
     fn parse_images(data: &[u8], num_images: usize) -> Result<Tensor, String> {
-        if data.len() < 16 {
-            return Err("Invalid MNIST image dataset file: too short".into());
-        }
-
-        let image_data = &data[16..];
-        let num_pixels = Self::MNIST_IMAGE_SIZE * Self::MNIST_IMAGE_SIZE;
-        let mut tensor_data = vec![0.0; num_images * num_pixels];
-
-        for i in 0..num_images {
-            let start = i * num_pixels;
-            let end = start + num_pixels;
-
-            if end > image_data.len() {
-                return Err("Image dataset file is incomplete".into());
-            }
-
-            for (j, &pixel) in image_data[start..end].iter().enumerate() {
-                tensor_data[i * num_pixels + j] = pixel as f32 / 255.0; // Normalize to [0, 1]
-            }
+        let images = IdxArray::parse(data)?;
+        let expected = [num_images, Self::MNIST_IMAGE_SIZE, Self::MNIST_IMAGE_SIZE];
+        if images.dims != expected {
+            return Err(format!(
+                "Invalid MNIST image dataset file: expected dimensions {:?}, found {:?}",
+                expected, images.dims
+            ));
         }
 
-        Ok(Tensor::new(
-            tensor_data,
-            Shape::from(IxDyn(&[num_images, Self::MNIST_IMAGE_SIZE, Self::MNIST_IMAGE_SIZE, 1])),
-        ))
+        images.to_images(false)
     }
 
     /// Parse the labels from the MNIST dataset
@@ -139,78 +201,35 @@ fn parse_images(data: &[u8], num_images: usize) -> Result<Tensor, String> {
     /// # Returns
     /// A tensor containing the parsed labels
     fn parse_labels(data: &[u8], num_labels: usize) -> Result<Tensor, String> {
-        if data.len() < 8 {
-            return Err("Invalid MNIST label dataset file: too short".into());
-        }
-
-        let label_data = &data[8..];
-        let mut tensor_data = vec![0.0; num_labels * Self::MNIST_NUM_CLASSES];
-
-        for (i, &label) in label_data.iter().enumerate().take(num_labels) {
-            if label as usize >= Self::MNIST_NUM_CLASSES {
-                return Err(format!("Invalid label value: {}", label));
-            }
-            tensor_data[i * Self::MNIST_NUM_CLASSES + label as usize] = 1.0;
+        let labels = IdxArray::parse(data)?;
+        if labels.len() != num_labels {
+            return Err(format!(
+                "Invalid MNIST label dataset file: expected {} labels, found {}",
+                num_labels,
+                labels.len()
+            ));
         }
 
-        Ok(Tensor::new(tensor_data, Shape::from(IxDyn(&[num_labels, Self::MNIST_NUM_CLASSES]))))
+        labels.to_one_hot(Self::MNIST_NUM_CLASSES, 0)
     }
 
     /// Download and decompress a file from the MNIST dataset
     ///
+    /// A cached file is verified against its SHA-256 hash and downloaded again if it is corrupted.
+    ///
     /// # Arguments
     /// * `filename` - The name of the file to download
+    /// * `options` - The options with the directory of the file and whether to download it
     ///
     /// # Returns
     /// A vector containing the decompressed dataset
-    async fn get_bytes_data(filename: &str) -> Result<Vec<u8>, String> {
-        let workspace_dir = get_workspace_dir();
-        let file_path = format!("{}/.cache/dataset/mnist/{}", workspace_dir.display(), filename);
-
-        if Path::new(&file_path).exists() {
-            debug!("Using cached file: {}", &file_path);
-            return Self::decompress_gz(&file_path).map_err(|e| e.to_string());
-        }
-
+    async fn get_bytes_data(filename: &str, options: &LoadOptions) -> Result<Vec<u8>, String> {
+        let file_path = options.resolve_root("mnist").join(filename);
         let url = format!("{}/{}", Self::MNIST_URL, filename);
-        debug!("Downloading MNIST dataset from {}", &url);
-
-        let response = reqwest::get(&url).await.map_err(|e| e.to_string())?;
-        if !response.status().is_success() {
-            return Err(format!(
-                "Request failed with status: {} for URL: {}",
-                response.status(),
-                url
-            ));
-        }
-        let compressed_data = response.bytes().await.map_err(|e| e.to_string())?;
-
-        async_fs::create_dir_all(format!("{}/.cache/dataset/mnist", workspace_dir.display()))
-            .await
-            .map_err(|e| e.to_string())?;
-        async_fs::write(&file_path, &compressed_data).await.map_err(|e| e.to_string())?;
 
-        Self::decompress_gz(&file_path).map_err(|e| e.to_string())
-    }
+        cache::fetch(&url, &file_path, Self::known_checksum(filename), options).await?;
 
-    /// Decompresses a gzip file.
-    ///
-    /// This function opens a gzip file, decompresses its contents, and returns the decompressed data as a `Vec<u8>`.
-    ///
-    /// # Arguments
-    ///
-    /// * `file_path` - A string slice that holds the path to the gzip file.
-    ///
-    /// # Returns
-    ///
-    /// A `Result` containing a vector of bytes with the decompressed data, or an `io::Error` if the operation fails.
-    fn decompress_gz(file_path: &str) -> io::Result<Vec<u8>> {
-        let file = File::open(file_path)?;
-        let mut decoder = GzDecoder::new(file);
-        let mut decompressed_data = Vec::new();
-        decoder.read_to_end(&mut decompressed_data)?;
-        debug!("Decompressed file: {}", file_path);
-        Ok(decompressed_data)
+        read_gz(&file_path).map_err(|e| e.to_string())
     }
 
     /// Splits the training data into training and validation datasets.
@@ -249,8 +268,14 @@ impl DatasetOps for MnistDataset {
     /// A future that resolves to the `MnistDataset` with the MNIST dataset loaded.
     fn load_train() -> Self::LoadFuture {
         Box::pin(async {
-            match MnistDataset::load_data(true).await {
-                Ok(train_data) => MnistDataset { train: Some(train_data), test: None, val: None },
+            match MnistDataset::load_data(true, &Self::default_options()).await {
+                Ok(train_data) => MnistDataset {
+                    train: Some(train_data),
+                    test: None,
+                    val: None,
+                    split: Split::Train,
+                    transform: None,
+                },
                 Err(err) => panic!("Failed to load train dataset: {}", err),
             }
         })
@@ -263,8 +288,14 @@ fn load_train() -> Self::LoadFuture {
     /// A future that resolves to the `MnistDataset` with the MNIST dataset loaded.
     fn load_test() -> Self::LoadFuture {
         Box::pin(async {
-            match MnistDataset::load_data(false).await {
-                Ok(test_data) => MnistDataset { train: None, test: Some(test_data), val: None },
+            match MnistDataset::load_data(false, &Self::default_options()).await {
+                Ok(test_data) => MnistDataset {
+                    train: None,
+                    test: Some(test_data),
+                    val: None,
+                    split: Split::Test,
+                    transform: None,
+                },
                 Err(err) => panic!("Failed to load test dataset: {}", err),
             }
         })
@@ -277,10 +308,15 @@ fn load_test() -> Self::LoadFuture {
     /// A future that resolves to the `MnistDataset` with the MNIST dataset loaded.
     fn load_val() -> Self::LoadFuture {
         Box::pin(async {
-            match MnistDataset::load_data(true).await {
+            match MnistDataset::load_data(true, &Self::default_options()).await {
                 Ok(train_data) => {
-                    let mut dataset =
-                        MnistDataset { train: Some(train_data), test: None, val: None };
+                    let mut dataset = MnistDataset {
+                        train: Some(train_data),
+                        test: None,
+                        val: None,
+                        split: Split::Val,
+                        transform: None,
+                    };
                     dataset.split_train_validation(0.2);
                     dataset
                 }
@@ -370,8 +406,38 @@ fn get_batch(&self, batch_idx: usize, batch_size: usize) -> (Tensor, Tensor) {
             0..10,                       // Full range for the classes (one-hot encoding)
         ]);
 
-        // Return the inputs and labels for the batch
-        (inputs_batch, labels_batch)
+        // Return the transformed inputs and labels for the batch
+        self.transform_batch((inputs_batch, labels_batch))
+    }
+
+    /// Gets the sample weights of a batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_idx` - The index of the batch to get.
+    /// * `batch_size` - The size of the batch to get.
+    ///
+    /// # Returns
+    ///
+    /// The weights of the samples in the batch, or `None` if no sample weights are set.
+    fn get_batch_sample_weights(&self, batch_idx: usize, batch_size: usize) -> Option<Tensor> {
+        self.train.as_ref().or(self.test.as_ref())?.batch_sample_weights(batch_idx, batch_size)
+    }
+
+    /// Sets the weight of each sample of the dataset used by `get_batch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - A `[samples]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// An error if the dataset is not loaded or the number of weights does not match.
+    fn set_sample_weights(&mut self, weights: Tensor) -> Result<(), String> {
+        match self.train.as_mut().or(self.test.as_mut()) {
+            Some(dataset) => dataset.set_sample_weights(weights),
+            None => Err("Dataset not loaded!".to_string()),
+        }
     }
 
     /// Calculates the loss between the predicted outputs and the true targets.
@@ -440,14 +506,19 @@ fn loss_grad(&self, outputs: &Tensor, targets: &Tensor) -> Tensor {
     }
 
     /// Shuffles the dataset.
-    fn shuffle(&mut self) {
-        let shuffle_data = |dataset: &mut Dataset| {
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator used to permute the samples.
+    fn shuffle_with_rng(&mut self, rng: &mut StdRng) {
+        let mut shuffle_data = |dataset: &mut Dataset| {
             let num_samples = dataset.inputs.shape().raw_dim()[0];
             let mut indices: Vec<usize> = (0..num_samples).collect();
-            indices.shuffle(&mut rand::thread_rng());
+            indices.shuffle(rng);
 
             dataset.inputs = dataset.inputs.take(&indices);
             dataset.labels = dataset.labels.take(&indices);
+            dataset.sample_weights = dataset.sample_weights.as_ref().map(|w| w.take(&indices));
         };
 
         if let Some(train) = &mut self.train {
@@ -465,7 +536,13 @@ fn shuffle(&mut self) {
     ///
     /// A new `MnistDataset` instance that is a clone of the current instance.
     fn clone(&self) -> Self {
-        Self { train: self.train.clone(), test: self.test.clone(), val: self.val.clone() }
+        Self {
+            train: self.train.clone(),
+            test: self.test.clone(),
+            val: self.val.clone(),
+            split: self.split,
+            transform: self.transform.clone(),
+        }
     }
 
     /// Transfers the dataset to the specified device.
@@ -491,14 +568,79 @@ fn to_device(&mut self, device: crate::devices::Device) -> Result<(), String> {
     }
 }
 
+impl LoadDataset for MnistDataset {
+    type BuildFuture = Pin<Box<dyn Future<Output = Result<Self, String>> + Send>>;
+
+    /// Loads a single split of the MNIST dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The directory, split and download options.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the `MnistDataset` with the requested split loaded.
+    fn load_with(options: LoadOptions) -> Self::BuildFuture {
+        Box::pin(async move {
+            let mut dataset = MnistDataset {
+                train: None,
+                test: None,
+                val: None,
+                split: options.split,
+                transform: None,
+            };
+            match options.split {
+                Split::Train => {
+                    let data = Self::load_data(true, &options).await?;
+                    dataset.train = Some(options.select_training_split(data)?);
+                }
+                Split::Val => {
+                    let data = Self::load_data(true, &options).await?;
+                    dataset.val = Some(options.select_training_split(data)?);
+                }
+                Split::Test => dataset.test = Some(Self::load_data(false, &options).await?),
+            }
+            Ok(dataset)
+        })
+    }
+}
+
+impl IndexedDataset for MnistDataset {
+    fn len(&self) -> usize {
+        self.split_data().len()
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        let (input, target) = self.split_data().get(index);
+        match &self.transform {
+            Some(transform) => (transform.apply_sample(&input), target),
+            None => (input, target),
+        }
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        self.transform_batch(self.split_data().get_batch(indices))
+    }
+
+    fn get_sample_weights(&self, indices: &[usize]) -> Option<Tensor> {
+        self.split_data().get_sample_weights(indices)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::fs;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::Path;
 
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
     use serial_test::serial;
+    use tempfile::TempDir;
     use tokio::runtime::Runtime;
 
     use super::*;
+    use crate::get_workspace_dir;
 
     fn setup() {
         let workspace_dir = get_workspace_dir();
@@ -514,7 +656,7 @@ fn test_download_and_extract() {
         setup();
         let rt = Runtime::new().unwrap();
         rt.block_on(async {
-            let _ = MnistDataset::load_data(true).await;
+            let _ = MnistDataset::load_data(true, &MnistDataset::default_options()).await;
             let workspace_dir = get_workspace_dir();
             let cache_path = format!("{}/.cache/dataset/mnist", workspace_dir.display());
             assert!(
@@ -527,13 +669,19 @@ fn test_download_and_extract() {
     #[tokio::test]
     async fn test_parse_images() {
         // Ensure the dataset is downloaded before parsing
-        let _ = MnistDataset::get_bytes_data(MnistDataset::MNIST_TRAIN_DATA_FILENAME)
-            .await
-            .expect("Failed to get image data");
-
-        let data_bytes = MnistDataset::get_bytes_data(MnistDataset::MNIST_TRAIN_DATA_FILENAME)
-            .await
-            .expect("Failed to get image data");
+        let _ = MnistDataset::get_bytes_data(
+            MnistDataset::MNIST_TRAIN_DATA_FILENAME,
+            &MnistDataset::default_options(),
+        )
+        .await
+        .expect("Failed to get image data");
+
+        let data_bytes = MnistDataset::get_bytes_data(
+            MnistDataset::MNIST_TRAIN_DATA_FILENAME,
+            &MnistDataset::default_options(),
+        )
+        .await
+        .expect("Failed to get image data");
 
         let images =
             MnistDataset::parse_images(&data_bytes, 60000).expect("Failed to parse images");
@@ -543,9 +691,12 @@ async fn test_parse_images() {
     #[tokio::test]
     #[serial]
     async fn test_parse_labels() {
-        let labels_bytes = MnistDataset::get_bytes_data(MnistDataset::MNIST_TRAIN_LABELS_FILENAME)
-            .await
-            .expect("Failed to get label data");
+        let labels_bytes = MnistDataset::get_bytes_data(
+            MnistDataset::MNIST_TRAIN_LABELS_FILENAME,
+            &MnistDataset::default_options(),
+        )
+        .await
+        .expect("Failed to get label data");
 
         let labels =
             MnistDataset::parse_labels(&labels_bytes, 60000).expect("Failed to parse labels");
@@ -566,4 +717,115 @@ async fn test_load_test() {
         let dataset = MnistDataset::load_test().await;
         assert!(dataset.test.is_some(), "Test dataset should be loaded");
     }
+
+    fn write_gz(path: &Path, bytes: &[u8]) {
+        let mut encoder = GzEncoder::new(File::create(path).unwrap(), Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap();
+    }
+
+    fn write_fixture(root: &Path, images: &str, labels: &str, digits: &[u8]) {
+        let count = (digits.len() as u32).to_be_bytes();
+        let mut image_bytes = vec![0, 0, 8, 3];
+        image_bytes.extend_from_slice(&count);
+        image_bytes.extend_from_slice(&28u32.to_be_bytes());
+        image_bytes.extend_from_slice(&28u32.to_be_bytes());
+        for &digit in digits {
+            image_bytes.extend(std::iter::repeat_n(digit * 25, 28 * 28));
+        }
+        let mut label_bytes = vec![0, 0, 8, 1];
+        label_bytes.extend_from_slice(&count);
+        label_bytes.extend_from_slice(digits);
+
+        write_gz(&root.join(images), &image_bytes);
+        write_gz(&root.join(labels), &label_bytes);
+    }
+
+    fn fixture_root() -> TempDir {
+        let root = TempDir::new().unwrap();
+        write_fixture(
+            root.path(),
+            MnistDataset::MNIST_TRAIN_DATA_FILENAME,
+            MnistDataset::MNIST_TRAIN_LABELS_FILENAME,
+            &[0, 1, 2, 3, 4],
+        );
+        write_fixture(
+            root.path(),
+            MnistDataset::MNIST_TEST_DATA_FILENAME,
+            MnistDataset::MNIST_TEST_LABELS_FILENAME,
+            &[9, 8],
+        );
+        root
+    }
+
+    fn fixture_builder(root: &Path) -> DatasetBuilder<MnistDataset> {
+        MnistDataset::MNIST_SHA256.iter().fold(
+            MnistDataset::builder().root(root).offline(true),
+            |builder, (name, _)| {
+                builder.checksum(*name, cache::sha256_file(&root.join(name)).unwrap())
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_builder_loads_split_from_root() {
+        let fixture = fixture_root();
+        let root = fixture.path();
+
+        let train = fixture_builder(root).split(Split::Train).build().await.unwrap();
+        let test = fixture_builder(root).split(Split::Test).build().await.unwrap();
+
+        assert_eq!(IndexedDataset::len(&train), 5);
+        assert_eq!(IndexedDataset::len(&test), 2);
+
+        let (image, label) = test.get(1);
+        assert_eq!(image.data.shape(), &[28, 28, 1]);
+        assert!((image.data[[0, 0, 0]] - 200.0 / 255.0).abs() < 1e-6);
+        assert_eq!(label.data[[8]], 1.0);
+
+        let (images, labels) = IndexedDataset::get_batch(&train, &[4, 0]);
+        assert_eq!(images.data.shape(), &[2, 28, 28, 1]);
+        assert_eq!(labels.data[[0, 4]], 1.0);
+        assert_eq!(labels.data[[1, 0]], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_builder_validation_split() {
+        let fixture = fixture_root();
+        let root = fixture.path();
+
+        let builder = || fixture_builder(root).validation_split(0.4);
+        let train = builder().split(Split::Train).build().await.unwrap();
+        let val = builder().split(Split::Val).build().await.unwrap();
+
+        assert_eq!(IndexedDataset::len(&train), 3);
+        assert_eq!(IndexedDataset::len(&val), 2);
+        assert_eq!(val.get(0).1.data[[3]], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_builder_offline_rejects_corrupted_cache() {
+        let fixture = fixture_root();
+        let root = fixture.path();
+        let images = root.join(MnistDataset::MNIST_TRAIN_DATA_FILENAME);
+
+        let result = MnistDataset::builder().root(root).download(true).offline(true).build().await;
+
+        let err = result.err().expect("A corrupted cache should not be loaded offline");
+        assert!(err.contains("offline mode is enabled"), "{}", err);
+        assert!(!images.exists());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_builder_without_download_reports_missing_files() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().join("missing");
+
+        let result = MnistDataset::builder().root(&root).download(false).build().await;
+
+        let err = result.err().expect("Loading missing files without download should fail");
+        assert!(err.contains("downloading is disabled"), "{}", err);
+        assert!(!root.exists());
+    }
 }
@@ -29,15 +29,36 @@
 
 pub mod cifar10;
 pub mod cifar100;
+pub mod custom;
+pub mod emnist;
+pub mod fashion_mnist;
+pub mod idx;
 pub mod imagenet_v2;
+pub mod kmnist;
+pub mod mat;
 pub mod mnist;
+pub mod packed;
+pub mod stl10;
+pub mod svhn;
 pub mod test_dataset;
-// pub mod custom;
+pub mod tiny_imagenet;
+pub mod transforms;
 
 pub use cifar10::Cifar10Dataset;
 pub use cifar100::Cifar100Dataset;
-pub use imagenet_v2::ImageNetV2Dataset;
+pub use custom::{ChannelOrder, ColorMode, ImageFolderDataset};
+pub use emnist::{Emnist, EmnistDataset, EmnistSplit};
+pub use fashion_mnist::{FashionMnist, FashionMnistDataset};
+pub use idx::{IdxArray, IdxDataset, IdxDownload, IdxSource};
+pub use imagenet_v2::{ImageNetClasses, ImageNetV2Dataset, ImageNetV2Variant};
+pub use kmnist::{Kmnist, KmnistDataset};
 pub use mnist::MnistDataset;
+pub use packed::{PackedDataset, PackedImages, PackedSource};
+pub use stl10::{Stl10, Stl10Dataset};
+pub use svhn::{Svhn, SvhnDataset};
 pub use test_dataset::TestDataset;
-
-// pub use custom::CustomImageDataset;
+pub use tiny_imagenet::{TinyImageNet, TinyImageNetDataset};
+pub use transforms::{
+    ColorJitter, Compose, CutMix, MixUp, Normalize, RandomCrop, RandomErasing,
+    RandomHorizontalFlip, RandomRotation, RandomVerticalFlip, Resize, Transform,
+};
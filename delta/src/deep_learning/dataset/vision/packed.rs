@@ -0,0 +1,955 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use ndarray::{IxDyn, Shape};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::deep_learning::dataset::vision::transforms::Compose;
+use crate::deep_learning::dataset::{
+    Dataset, DatasetBuilder, DatasetOps, IndexedDataset, LoadDataset, LoadOptions, Split,
+    stratified_split_indices,
+};
+use crate::deep_learning::rng::rng_from_seed;
+use crate::deep_learning::tensor_ops::Tensor;
+use crate::devices::Device;
+
+/// Images kept as `u8` pixels in `[height, width, channels]` layout and decoded to `f32` in
+/// `[0, 1]` when samples are retrieved.
+///
+/// Keeping the pixels as bytes uses a quarter of the memory of a `Dataset`, which matters for
+/// large splits such as the unlabeled images of STL-10. The pixels are shared between clones and
+/// splits; shuffling only permutes the order of the samples, and normalizing only changes the
+/// scale applied when decoding. Added noise belongs to the samples of a split and moves with them.
+#[derive(Clone)]
+pub struct PackedImages {
+    pixels: Arc<[u8]>,
+    scale: f32,
+    offset: f32,
+    noise: Option<Arc<[f32]>>,
+    labels: Option<Arc<[usize]>>,
+    order: Vec<usize>,
+    shape: [usize; 3],
+    classes: Arc<[String]>,
+    sample_weights: Option<Tensor>,
+    device: Device,
+}
+
+impl fmt::Debug for PackedImages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PackedImages")
+            .field("len", &self.order.len())
+            .field("shape", &self.shape)
+            .field("labeled", &self.labels.is_some())
+            .field("classes", &self.classes.len())
+            .finish()
+    }
+}
+
+impl PackedImages {
+    /// Creates a new `PackedImages`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - The pixels of every image, in `[height, width, channels]` layout.
+    /// * `labels` - The label of every image, or `None` for unlabeled images.
+    /// * `shape` - The `[height, width, channels]` shape of an image.
+    /// * `classes` - The class names, in label order.
+    ///
+    /// # Returns
+    ///
+    /// The images, or an error if the number of pixels or labels does not match, or a label is
+    /// out of range.
+    pub fn new(
+        pixels: Vec<u8>,
+        labels: Option<Vec<usize>>,
+        shape: [usize; 3],
+        classes: Vec<String>,
+    ) -> Result<Self, String> {
+        let image_size = shape.iter().product::<usize>();
+        if image_size == 0 || pixels.len() % image_size != 0 {
+            return Err(format!(
+                "{} pixels cannot be split into images of shape {:?}",
+                pixels.len(),
+                shape
+            ));
+        }
+
+        let count = pixels.len() / image_size;
+        if let Some(labels) = &labels {
+            if labels.len() != count {
+                return Err(format!("Found {} images but {} labels", count, labels.len()));
+            }
+            if let Some(label) = labels.iter().find(|&&label| label >= classes.len()) {
+                return Err(format!(
+                    "Label {} is out of range for {} classes",
+                    label,
+                    classes.len()
+                ));
+            }
+        }
+
+        Ok(Self {
+            pixels: pixels.into(),
+            scale: 1.0,
+            offset: 0.0,
+            noise: None,
+            labels: labels.map(Into::into),
+            order: (0..count).collect(),
+            shape,
+            classes: classes.into(),
+            sample_weights: None,
+            device: Device::default(),
+        })
+    }
+
+    /// Returns the `[height, width, channels]` shape of an image.
+    pub fn image_shape(&self) -> [usize; 3] {
+        self.shape
+    }
+
+    /// Returns the class names, in label order.
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// Returns whether the images have labels.
+    ///
+    /// The targets of unlabeled images have no classes, with shape `[0]` per sample.
+    pub fn is_labeled(&self) -> bool {
+        self.labels.is_some()
+    }
+
+    /// Returns the label of a sample, or `None` if the images are unlabeled.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the sample.
+    pub fn label(&self, index: usize) -> Option<usize> {
+        self.labels.as_ref().map(|labels| labels[self.order[index]])
+    }
+
+    /// Splits the samples at an index, sharing the pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the first sample of the second part.
+    ///
+    /// # Returns
+    ///
+    /// The samples before and from the index.
+    pub fn split_at(&self, index: usize) -> (PackedImages, PackedImages) {
+        assert!(index <= self.order.len(), "Index out of bounds for dataset split");
+
+        let indices: Vec<usize> = (0..self.order.len()).collect();
+        (self.select(&indices[..index]), self.select(&indices[index..]))
+    }
+
+    /// Splits the samples into two parts with the same class proportions, sharing the pixels.
+    ///
+    /// Unlabeled images are split at random.
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - The fraction of the samples of each class in the second part.
+    /// * `rng` - The random number generator used to choose the samples.
+    ///
+    /// # Returns
+    ///
+    /// The remaining samples and the held out samples, each in their original order.
+    pub fn stratified_split(
+        &self,
+        fraction: f32,
+        rng: &mut StdRng,
+    ) -> (PackedImages, PackedImages) {
+        let classes: Vec<usize> =
+            (0..self.order.len()).map(|index| self.label(index).unwrap_or(0)).collect();
+        let (first, second) = stratified_split_indices(&classes, fraction, rng);
+        (self.select(&first), self.select(&second))
+    }
+
+    /// Returns the given samples, sharing the pixels.
+    fn select(&self, indices: &[usize]) -> PackedImages {
+        PackedImages {
+            order: indices.iter().map(|&index| self.order[index]).collect(),
+            noise: self.select_noise(indices),
+            sample_weights: self.sample_weights.as_ref().map(|w| w.take(indices)),
+            ..self.clone()
+        }
+    }
+
+    /// Shuffles the order of the samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator used to permute the samples.
+    pub fn shuffle_with_rng(&mut self, rng: &mut StdRng) {
+        let mut indices: Vec<usize> = (0..self.order.len()).collect();
+        indices.shuffle(rng);
+
+        self.order = indices.iter().map(|&index| self.order[index]).collect();
+        self.noise = self.select_noise(&indices);
+        self.sample_weights = self.sample_weights.as_ref().map(|w| w.take(&indices));
+    }
+
+    /// Sets the weight of each sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - A `[samples]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// An error if the number of weights does not match the number of samples.
+    pub fn set_sample_weights(&mut self, weights: Tensor) -> Result<(), String> {
+        if weights.data.ndim() != 1 || weights.data.len() != self.order.len() {
+            return Err(format!(
+                "Expected {} sample weights, got shape {:?}",
+                self.order.len(),
+                weights.data.shape()
+            ));
+        }
+
+        self.sample_weights = Some(weights);
+        Ok(())
+    }
+
+    /// Sets the device of the decoded samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device of the decoded samples.
+    pub fn to_device(&mut self, device: &Device) {
+        self.device = device.clone();
+        if let Some(weights) = self.sample_weights.as_mut() {
+            *weights = weights.to_device(device.clone()).unwrap();
+        }
+    }
+
+    /// Rescales the decoded values from their range over the samples to the given range.
+    ///
+    /// The pixels are kept as bytes; only the scale and offset applied when decoding change. If
+    /// every value is the same, the values are decoded as zeros.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The minimum value for normalization.
+    /// * `max` - The maximum value for normalization.
+    pub fn normalize(&mut self, min: f32, max: f32) {
+        let image_size = self.shape.iter().product::<usize>();
+        let (data_min, data_max) = (0..self.order.len())
+            .flat_map(|index| (0..image_size).map(move |pixel| (index, pixel)))
+            .map(|(index, pixel)| self.value(index, pixel))
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), value| {
+                (lo.min(value), hi.max(value))
+            });
+
+        if data_min >= data_max {
+            self.scale = 0.0;
+            self.offset = 0.0;
+            self.noise = None;
+            return;
+        }
+
+        let factor = (max - min) / (data_max - data_min);
+        self.scale *= factor;
+        self.offset = (self.offset - data_min) * factor + min;
+        if let Some(noise) = &self.noise {
+            self.noise = Some(noise.iter().map(|&value| value * factor).collect());
+        }
+    }
+
+    /// Adds Gaussian noise drawn from the global generator to the decoded values.
+    ///
+    /// The noise is kept as one `f32` per pixel of the samples in this split, so noisy images use
+    /// five times the memory of the bytes they decode.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise_level` - The standard deviation of the noise.
+    pub fn add_noise(&mut self, noise_level: f32) {
+        if noise_level < 0.0 {
+            panic!("Noise level must not be negative.");
+        }
+
+        let image_size = self.shape.iter().product::<usize>();
+        let drawn = Tensor::random_normal(
+            Shape::from(IxDyn(&[self.order.len() * image_size])),
+            0.0,
+            noise_level,
+        );
+        let mut noise = match &self.noise {
+            Some(noise) => noise.to_vec(),
+            None => vec![0.0; self.order.len() * image_size],
+        };
+        noise.iter_mut().zip(drawn.data.iter()).for_each(|(value, &drawn)| *value += drawn);
+        self.noise = Some(noise.into());
+    }
+
+    /// Decodes every image into an in-memory dataset.
+    pub fn to_dataset(&self) -> Dataset {
+        let indices: Vec<usize> = (0..self.order.len()).collect();
+        let (inputs, labels) = IndexedDataset::get_batch(self, &indices);
+        let mut dataset = Dataset::new(inputs, labels);
+        dataset.sample_weights = self.sample_weights.clone();
+        dataset
+    }
+
+    /// Returns the number of classes of the targets.
+    fn num_targets(&self) -> usize {
+        if self.is_labeled() { self.classes.len() } else { 0 }
+    }
+
+    /// Returns the noise of the given samples, in the order of the indices.
+    fn select_noise(&self, indices: &[usize]) -> Option<Arc<[f32]>> {
+        let image_size = self.shape.iter().product::<usize>();
+        self.noise.as_ref().map(|noise| {
+            indices
+                .iter()
+                .flat_map(|&index| &noise[index * image_size..(index + 1) * image_size])
+                .copied()
+                .collect()
+        })
+    }
+
+    /// Decodes a pixel of the sample at the given index.
+    fn value(&self, index: usize, pixel: usize) -> f32 {
+        let image_size = self.shape.iter().product::<usize>();
+        let noise = self.noise.as_ref().map_or(0.0, |noise| noise[index * image_size + pixel]);
+        let stored = self.order[index] * image_size + pixel;
+        self.pixels[stored] as f32 / 255.0 * self.scale + self.offset + noise
+    }
+
+    /// Decodes the images and one-hot labels of the given samples.
+    fn decode(&self, indices: &[usize]) -> (Vec<f32>, Vec<f32>) {
+        let image_size = self.shape.iter().product::<usize>();
+        let num_targets = self.num_targets();
+        let mut images = Vec::with_capacity(indices.len() * image_size);
+        let mut targets = vec![0.0; indices.len() * num_targets];
+
+        for (i, &index) in indices.iter().enumerate() {
+            images.extend((0..image_size).map(|pixel| self.value(index, pixel)));
+            if let Some(label) = self.label(index) {
+                targets[i * num_targets + label] = 1.0;
+            }
+        }
+
+        (images, targets)
+    }
+
+    /// Creates a tensor on the device of the samples.
+    fn tensor(&self, data: Vec<f32>, shape: &[usize]) -> Tensor {
+        Tensor { device: self.device.clone(), ..Tensor::new(data, Shape::from(IxDyn(shape))) }
+    }
+}
+
+impl IndexedDataset for PackedImages {
+    fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        let (image, target) = self.decode(&[index]);
+        (self.tensor(image, &self.shape), self.tensor(target, &[self.num_targets()]))
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        let (images, targets) = self.decode(indices);
+        let [height, width, channels] = self.shape;
+        (
+            self.tensor(images, &[indices.len(), height, width, channels]),
+            self.tensor(targets, &[indices.len(), self.num_targets()]),
+        )
+    }
+
+    fn get_sample_weights(&self, indices: &[usize]) -> Option<Tensor> {
+        self.sample_weights.as_ref().map(|weights| weights.take(indices))
+    }
+}
+
+/// The future returned by `PackedSource::load`.
+pub type PackedFuture = Pin<Box<dyn Future<Output = Result<PackedImages, String>> + Send>>;
+
+/// A dataset whose images are loaded into `PackedImages`, such as SVHN and STL-10.
+pub trait PackedSource: Clone + Default + Send + Sync + 'static {
+    /// Loads the training or test images, downloading them if needed and allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_train` - Whether to load the training images or the test images.
+    /// * `options` - The options with the directory of the files and whether to download them.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the images.
+    fn load(&self, is_train: bool, options: LoadOptions) -> PackedFuture;
+}
+
+/// A dataset loaded into `PackedImages`, with one split loaded at a time.
+pub struct PackedDataset<S: PackedSource> {
+    train: Option<PackedImages>,
+    test: Option<PackedImages>,
+    val: Option<PackedImages>,
+    split: Split,
+    transform: Option<Arc<Compose>>,
+    source: S,
+}
+
+impl<S: PackedSource> PackedDataset<S> {
+    /// Creates a builder that loads a single split of the dataset.
+    ///
+    /// # Returns
+    ///
+    /// A `DatasetBuilder` for the dataset.
+    pub fn builder() -> DatasetBuilder<Self> {
+        DatasetBuilder::new()
+    }
+
+    /// Sets the transforms applied to every sample and batch when they are retrieved.
+    ///
+    /// # Arguments
+    /// * `transform` - The transform pipeline.
+    ///
+    /// # Returns
+    /// The dataset with the transforms.
+    pub fn with_transform(mut self, transform: Compose) -> Self {
+        self.transform = Some(Arc::new(transform));
+        self
+    }
+
+    /// Returns the source the dataset was loaded from.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the class names of the loaded split, in label order.
+    pub fn classes(&self) -> &[String] {
+        self.split_data().classes()
+    }
+
+    /// Returns the images of the loaded split.
+    pub fn images(&self) -> &PackedImages {
+        self.split_data()
+    }
+
+    /// Loads a single split of the dataset from a source.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The files to load.
+    /// * `options` - The directory, split and download options.
+    ///
+    /// # Returns
+    ///
+    /// The dataset with the requested split loaded.
+    pub async fn load_source(source: S, options: LoadOptions) -> Result<Self, String> {
+        let split = options.split;
+        let is_train = split != Split::Test;
+        let images = source.load(is_train, options.clone()).await?;
+
+        let mut dataset = Self::empty(source, split);
+        match split {
+            Split::Test => dataset.test = Some(images),
+            _ => {
+                let validation_start = options.validation_start(images.len())?;
+                let (train, val) = match options.split_seed {
+                    Some(seed) => {
+                        images.stratified_split(options.validation_split, &mut rng_from_seed(seed))
+                    }
+                    None => images.split_at(validation_start),
+                };
+                if split == Split::Val {
+                    dataset.val = Some(val);
+                } else {
+                    dataset.train = Some(train);
+                }
+            }
+        }
+        Ok(dataset)
+    }
+
+    /// Creates a dataset with no split loaded.
+    fn empty(source: S, split: Split) -> Self {
+        Self { train: None, test: None, val: None, split, transform: None, source }
+    }
+
+    /// Returns the split selected when the dataset was loaded.
+    fn split_data(&self) -> &PackedImages {
+        match self.split {
+            Split::Train => self.train.as_ref(),
+            Split::Test => self.test.as_ref(),
+            Split::Val => self.val.as_ref(),
+        }
+        .unwrap_or_else(|| panic!("{:?} split not loaded!", self.split))
+    }
+
+    /// Returns the split selected when the dataset was loaded, mutably.
+    fn split_data_mut(&mut self) -> &mut PackedImages {
+        match self.split {
+            Split::Train => self.train.as_mut(),
+            Split::Test => self.test.as_mut(),
+            Split::Val => self.val.as_mut(),
+        }
+        .unwrap_or_else(|| panic!("{:?} split not loaded!", self.split))
+    }
+
+    /// Applies the transforms, if any, to a batch.
+    fn transform_batch(&self, (inputs, targets): (Tensor, Tensor)) -> (Tensor, Tensor) {
+        match &self.transform {
+            Some(transform) => transform.apply_to_batch(&inputs, &targets),
+            None => (inputs, targets),
+        }
+    }
+
+    /// Loads a split with the `DatasetOps` options, panicking if it fails.
+    fn load_or_panic(split: Split) -> Pin<Box<dyn Future<Output = Self> + Send>> {
+        Box::pin(async move {
+            let validation_split = if split == Split::Val { 0.2 } else { 0.0 };
+            let options =
+                LoadOptions { split, download: true, validation_split, ..Default::default() };
+            match Self::load_source(S::default(), options).await {
+                Ok(dataset) => dataset,
+                Err(err) => panic!("Failed to load {:?} dataset: {}", split, err),
+            }
+        })
+    }
+
+    /// Returns the training images, or the test images if no training images are loaded.
+    fn loaded_data(&self) -> Option<&PackedImages> {
+        self.train.as_ref().or(self.test.as_ref())
+    }
+}
+
+impl<S: PackedSource> DatasetOps for PackedDataset<S> {
+    type LoadFuture = Pin<Box<dyn Future<Output = Self> + Send>>;
+
+    /// Loads the training dataset of the default source.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the dataset with the training split loaded.
+    fn load_train() -> Self::LoadFuture {
+        Self::load_or_panic(Split::Train)
+    }
+
+    /// Loads the test dataset of the default source.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the dataset with the test split loaded.
+    fn load_test() -> Self::LoadFuture {
+        Self::load_or_panic(Split::Test)
+    }
+
+    /// Loads the validation dataset of the default source, the last 20% of the training data.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the dataset with the validation split loaded.
+    fn load_val() -> Self::LoadFuture {
+        Self::load_or_panic(Split::Val)
+    }
+
+    /// Rescales the images of the loaded split from their range to the given range.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The minimum value for normalization.
+    /// * `max` - The maximum value for normalization.
+    fn normalize(&mut self, min: f32, max: f32) {
+        self.split_data_mut().normalize(min, max);
+    }
+
+    /// Adds Gaussian noise drawn from the global generator to the images of the loaded split.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise_level` - The standard deviation of the noise.
+    fn add_noise(&mut self, noise_level: f32) {
+        self.split_data_mut().add_noise(noise_level);
+    }
+
+    /// Returns the number of samples in the dataset.
+    ///
+    /// # Returns
+    ///
+    /// The number of samples in the dataset.
+    fn len(&self) -> usize {
+        self.loaded_data().map(IndexedDataset::len).unwrap_or(0)
+    }
+
+    /// Get a batch of dataset from the dataset
+    ///
+    /// # Arguments
+    /// * `batch_idx` - The index of the batch to get
+    /// * `batch_size` - The size of the batch to get
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the input and label tensors for the batch
+    fn get_batch(&self, batch_idx: usize, batch_size: usize) -> (Tensor, Tensor) {
+        let dataset = self.loaded_data().expect("Dataset not loaded!");
+
+        let total_samples = IndexedDataset::len(dataset);
+        let start_idx = batch_idx * batch_size;
+        if start_idx >= total_samples {
+            panic!("Batch index {} out of range. Total samples: {}", batch_idx, total_samples);
+        }
+        let end_idx = (start_idx + batch_size).min(total_samples);
+
+        let indices: Vec<usize> = (start_idx..end_idx).collect();
+        self.transform_batch(IndexedDataset::get_batch(dataset, &indices))
+    }
+
+    /// Gets the sample weights of a batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_idx` - The index of the batch to get.
+    /// * `batch_size` - The size of the batch to get.
+    ///
+    /// # Returns
+    ///
+    /// The weights of the samples in the batch, or `None` if no sample weights are set.
+    fn get_batch_sample_weights(&self, batch_idx: usize, batch_size: usize) -> Option<Tensor> {
+        let dataset = self.loaded_data()?;
+        let start_idx = batch_idx * batch_size;
+        let end_idx = (start_idx + batch_size).min(IndexedDataset::len(dataset));
+        let indices: Vec<usize> = (start_idx..end_idx).collect();
+        dataset.get_sample_weights(&indices)
+    }
+
+    /// Sets the weight of each sample of the dataset used by `get_batch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - A `[samples]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// An error if the dataset is not loaded or the number of weights does not match.
+    fn set_sample_weights(&mut self, weights: Tensor) -> Result<(), String> {
+        match self.train.as_mut().or(self.test.as_mut()) {
+            Some(dataset) => dataset.set_sample_weights(weights),
+            None => Err("Dataset not loaded!".to_string()),
+        }
+    }
+
+    /// Calculates the cross-entropy loss between the predicted outputs and the true targets.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputs` - The predicted outputs from the model (probabilities).
+    /// * `targets` - The true target values (one-hot encoded).
+    ///
+    /// # Returns
+    ///
+    /// The calculated loss as a `f32` value.
+    fn loss(&self, outputs: &Tensor, targets: &Tensor) -> f32 {
+        let batch_size = targets.shape().raw_dim()[0];
+        let loss: f32 = outputs
+            .data
+            .iter()
+            .zip(targets.data.iter())
+            .map(|(&predicted, &target)| -target * predicted.max(1e-15).ln())
+            .sum();
+
+        loss / batch_size as f32
+    }
+
+    /// Calculates the gradient of the loss with respect to the predicted outputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputs` - The predicted outputs from the model (probabilities).
+    /// * `targets` - The true target values (one-hot encoded).
+    ///
+    /// # Returns
+    ///
+    /// A `Tensor` containing the gradients of the loss with respect to the outputs.
+    fn loss_grad(&self, outputs: &Tensor, targets: &Tensor) -> Tensor {
+        assert_eq!(
+            outputs.shape().raw_dim(),
+            targets.shape().raw_dim(),
+            "Outputs and targets must have the same shape"
+        );
+        let batch_size = targets.shape().raw_dim()[0] as f32;
+
+        Tensor {
+            data: (&outputs.data - &targets.data) / batch_size,
+            device: outputs.device.clone(),
+        }
+    }
+
+    /// Shuffles the dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator used to permute the samples.
+    fn shuffle_with_rng(&mut self, rng: &mut StdRng) {
+        if let Some(train) = &mut self.train {
+            train.shuffle_with_rng(rng);
+        }
+
+        if let Some(test) = &mut self.test {
+            test.shuffle_with_rng(rng);
+        }
+    }
+
+    /// Clones the dataset.
+    ///
+    /// # Returns
+    ///
+    /// A new dataset that is a clone of the current instance.
+    fn clone(&self) -> Self {
+        Self {
+            train: self.train.clone(),
+            test: self.test.clone(),
+            val: self.val.clone(),
+            split: self.split,
+            transform: self.transform.clone(),
+            source: self.source.clone(),
+        }
+    }
+
+    /// Transfers the dataset to the specified device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device to transfer the dataset to.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the dataset on the specified device.
+    fn to_device(&mut self, device: Device) -> Result<(), String> {
+        for dataset in
+            [self.train.as_mut(), self.test.as_mut(), self.val.as_mut()].into_iter().flatten()
+        {
+            dataset.to_device(&device);
+        }
+        Ok(())
+    }
+}
+
+impl<S: PackedSource> LoadDataset for PackedDataset<S> {
+    type BuildFuture = Pin<Box<dyn Future<Output = Result<Self, String>> + Send>>;
+
+    /// Loads a single split of the dataset from the default source.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The directory, split and download options.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to the dataset with the requested split loaded.
+    fn load_with(options: LoadOptions) -> Self::BuildFuture {
+        Box::pin(Self::load_source(S::default(), options))
+    }
+}
+
+impl<S: PackedSource> IndexedDataset for PackedDataset<S> {
+    fn len(&self) -> usize {
+        IndexedDataset::len(self.split_data())
+    }
+
+    fn get(&self, index: usize) -> (Tensor, Tensor) {
+        let (input, target) = self.split_data().get(index);
+        match &self.transform {
+            Some(transform) => (transform.apply_sample(&input), target),
+            None => (input, target),
+        }
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> (Tensor, Tensor) {
+        self.transform_batch(IndexedDataset::get_batch(self.split_data(), indices))
+    }
+
+    fn get_sample_weights(&self, indices: &[usize]) -> Option<Tensor> {
+        self.split_data().get_sample_weights(indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deep_learning::dataset::vision::svhn::Svhn;
+
+    fn images() -> PackedImages {
+        let pixels: Vec<u8> = (0..4u8).flat_map(|image| [image * 50; 4]).collect();
+        let classes = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        PackedImages::new(pixels, Some(vec![0, 1, 2, 1]), [2, 1, 2], classes).unwrap()
+    }
+
+    #[test]
+    fn test_new_validates_pixels_and_labels() {
+        let classes = || vec!["a".to_string()];
+        assert!(PackedImages::new(vec![0; 5], None, [2, 1, 2], classes()).is_err());
+        let err = PackedImages::new(vec![0; 8], Some(vec![0]), [2, 1, 2], classes()).unwrap_err();
+        assert!(err.contains("2 images but 1 labels"), "{}", err);
+        let err = PackedImages::new(vec![0; 4], Some(vec![1]), [2, 1, 2], classes()).unwrap_err();
+        assert!(err.contains("Label 1 is out of range"), "{}", err);
+    }
+
+    #[test]
+    fn test_get_batch_decodes_pixels_and_one_hot_labels() {
+        let images = images();
+
+        let (inputs, targets) = IndexedDataset::get_batch(&images, &[2, 0]);
+
+        assert_eq!(inputs.data.shape(), &[2, 2, 1, 2]);
+        assert!((inputs.data[[0, 1, 0, 1]] - 100.0 / 255.0).abs() < 1e-6);
+        assert_eq!(inputs.data[[1, 0, 0, 0]], 0.0);
+        assert_eq!(targets.data.shape(), &[2, 3]);
+        assert_eq!(targets.data[[0, 2]], 1.0);
+        assert_eq!(targets.data[[1, 0]], 1.0);
+    }
+
+    #[test]
+    fn test_unlabeled_targets_are_empty() {
+        let images =
+            PackedImages::new(vec![255; 8], None, [2, 2, 2], vec!["a".to_string()]).unwrap();
+
+        let (input, target) = images.get(0);
+
+        assert!(!images.is_labeled());
+        assert_eq!(input.data.shape(), &[2, 2, 2]);
+        assert_eq!(target.data.shape(), &[0]);
+        assert_eq!(images.label(0), None);
+    }
+
+    #[test]
+    fn test_shuffle_and_split_keep_samples_and_weights_together() {
+        let mut images = images();
+        images
+            .set_sample_weights(Tensor::new(vec![0.0, 1.0, 2.0, 3.0], Shape::from(IxDyn(&[4]))))
+            .unwrap();
+
+        images.shuffle_with_rng(&mut rng_from_seed(7));
+        let (first, second) = images.split_at(1);
+
+        assert_eq!(IndexedDataset::len(&first), 1);
+        assert_eq!(IndexedDataset::len(&second), 3);
+        for part in [&first, &second] {
+            for index in 0..IndexedDataset::len(part) {
+                let (input, _) = part.get(index);
+                let stored = (input.data[[0, 0, 0]] * 255.0 / 50.0).round();
+                let weight = part.get_sample_weights(&[index]).unwrap().data[[0]];
+                assert_eq!(stored, weight);
+            }
+        }
+
+        let dataset = images.to_dataset();
+        assert_eq!(dataset.inputs.data.shape(), &[4, 2, 1, 2]);
+        assert!(dataset.sample_weights.is_some());
+    }
+
+    #[test]
+    fn test_normalize_rescales_decoded_values() {
+        let mut images = images();
+        let (first, _) = images.split_at(2);
+
+        images.normalize(-1.0, 1.0);
+        let (inputs, _) = IndexedDataset::get_batch(&images, &[0, 1, 3]);
+        assert_eq!(inputs.data[[0, 0, 0, 0]], -1.0);
+        assert!((inputs.data[[1, 0, 0, 0]] + 1.0 / 3.0).abs() < 1e-6);
+        assert!((inputs.data[[2, 1, 0, 1]] - 1.0).abs() < 1e-6);
+
+        // Splits taken before normalizing keep the decoded range
+        let (inputs, _) = first.get(1);
+        assert!((inputs.data[[0, 0, 0]] - 50.0 / 255.0).abs() < 1e-6);
+
+        let mut dataset = PackedDataset {
+            train: Some(first),
+            ..PackedDataset::empty(Svhn::default(), Split::Train)
+        };
+        dataset.normalize(0.0, 10.0);
+        let (inputs, _) = IndexedDataset::get_batch(&dataset, &[0, 1]);
+        assert_eq!(inputs.data[[0, 0, 0, 0]], 0.0);
+        assert!((inputs.data[[1, 0, 0, 0]] - 10.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_add_noise_perturbs_only_the_samples() {
+        let pixels = vec![128; 2 * 20 * 20 * 3];
+        let images = PackedImages::new(pixels, None, [20, 20, 3], vec!["a".into()]).unwrap();
+        let (mut first, second) = images.split_at(1);
+
+        first.add_noise(0.1);
+        assert_eq!(first.noise.as_ref().unwrap().len(), 20 * 20 * 3);
+        first.normalize(0.0, 1.0);
+
+        let (noisy, _) = first.get(0);
+        let mean = noisy.data.mean().unwrap();
+        let std = noisy.data.std(0.0);
+        assert!((mean - 0.5).abs() < 0.1, "{}", mean);
+        assert!(std > 0.05, "{}", std);
+        assert!(noisy.data.iter().all(|&value| (0.0..=1.0).contains(&value)));
+
+        let (clean, _) = second.get(0);
+        assert!(clean.data.iter().all(|&value| value == 128.0 / 255.0));
+    }
+
+    #[test]
+    fn test_add_noise_moves_with_the_samples() {
+        let pixels: Vec<u8> = (0..4u8).flat_map(|image| [image * 60; 4]).collect();
+        let mut images = PackedImages::new(pixels, None, [2, 1, 2], vec!["a".into()]).unwrap();
+        images.add_noise(0.01);
+
+        let noisy = |images: &PackedImages, index| images.get(index).0.to_vec();
+        let before: Vec<_> = (0..4).map(|index| noisy(&images, index)).collect();
+        images.shuffle_with_rng(&mut rng_from_seed(3));
+        let (_, tail) = images.split_at(1);
+
+        for index in 0..4 {
+            let stored = images.order[index];
+            assert_eq!(noisy(&images, index), before[stored]);
+        }
+        assert_eq!(noisy(&tail, 0), before[images.order[1]]);
+    }
+
+    #[test]
+    fn test_stratified_split_keeps_class_proportions() {
+        let pixels: Vec<u8> = (0..8u8).flat_map(|image| [image; 4]).collect();
+        let classes = vec!["a".to_string(), "b".to_string()];
+        let labels = vec![0, 0, 0, 0, 1, 1, 1, 1];
+        let images = PackedImages::new(pixels, Some(labels), [2, 1, 2], classes).unwrap();
+
+        let (train, val) = images.stratified_split(0.25, &mut rng_from_seed(5));
+
+        assert_eq!(IndexedDataset::len(&train), 6);
+        let val_labels: Vec<_> = (0..2).map(|index| val.label(index).unwrap()).collect();
+        assert_eq!(val_labels, vec![0, 1]);
+    }
+}
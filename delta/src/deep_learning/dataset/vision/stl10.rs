@@ -0,0 +1,328 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use log::debug;
+use tar::Archive;
+
+use crate::deep_learning::dataset::vision::packed::{
+    PackedDataset, PackedFuture, PackedImages, PackedSource,
+};
+use crate::deep_learning::dataset::{LoadOptions, cache};
+
+/// The STL-10 dataset: 96x96 color images of 10 classes, with 100,000 unlabeled images for
+/// unsupervised learning.
+pub type Stl10Dataset = PackedDataset<Stl10>;
+
+/// The files of the binary STL-10 dataset.
+///
+/// The training split of `Stl10::unlabeled` is the unlabeled images, whose targets have no
+/// classes; its test split is the labeled test images.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stl10 {
+    unlabeled: bool,
+}
+
+impl Stl10 {
+    const URL: &'static str = "http://ai.stanford.edu/~acoates/stl10/stl10_binary.tar.gz";
+    const ARCHIVE_FILENAME: &'static str = "stl10_binary.tar.gz";
+    const CLASS_NAMES_FILENAME: &'static str = "class_names.txt";
+    const IMAGE_SIZE: usize = 96;
+    const CLASSES: [&'static str; 10] =
+        ["airplane", "bird", "car", "cat", "deer", "dog", "horse", "monkey", "ship", "truck"];
+
+    /// Creates the files of the labeled STL-10 images.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the files of the unlabeled STL-10 images.
+    pub fn unlabeled() -> Self {
+        Self { unlabeled: true }
+    }
+
+    /// Returns whether the training split is the unlabeled images.
+    pub fn is_unlabeled(&self) -> bool {
+        self.unlabeled
+    }
+
+    /// Returns the names of the image file and of the label file, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_train` - Whether to return the training files or the test files.
+    fn file_names(&self, is_train: bool) -> (&'static str, Option<&'static str>) {
+        match (is_train, self.unlabeled) {
+            (true, false) => ("train_X.bin", Some("train_y.bin")),
+            (true, true) => ("unlabeled_X.bin", None),
+            (false, _) => ("test_X.bin", Some("test_y.bin")),
+        }
+    }
+
+    /// Downloads and extracts the STL-10 dataset, unless the requested files are already there.
+    ///
+    /// Only the requested files are extracted, so the unlabeled images are not written to disk
+    /// unless they are used.
+    ///
+    /// # Arguments
+    /// * `files` - The files that must be present.
+    /// * `options` - The options with the cache directory and whether to download.
+    ///
+    /// # Returns
+    /// The directory containing the files.
+    async fn download_and_extract(
+        files: &[&str],
+        options: &LoadOptions,
+    ) -> Result<PathBuf, String> {
+        let cache_path = options.resolve_root("stl10");
+        if files.iter().all(|file| cache_path.join(file).exists()) {
+            return Ok(cache_path);
+        }
+
+        let tarball_path = cache_path.join(Self::ARCHIVE_FILENAME);
+
+        // No SHA-256 hash is published for the archive; pin it with `DatasetBuilder::checksum`
+        // to verify the download.
+        cache::fetch(Self::URL, &tarball_path, None, options).await?;
+
+        let tar_gz = File::open(&tarball_path).map_err(|e| e.to_string())?;
+        let mut archive = Archive::new(GzDecoder::new(tar_gz));
+        let mut missing: HashSet<&str> =
+            files.iter().copied().filter(|file| !cache_path.join(file).exists()).collect();
+
+        for entry in archive.entries().map_err(|e| e.to_string())? {
+            let mut entry = entry.map_err(|e| e.to_string())?;
+            let path = entry.path().map_err(|e| e.to_string())?.into_owned();
+            let Some(file_name) = path.file_name().map(|name| name.to_string_lossy().to_string())
+            else {
+                continue;
+            };
+
+            if missing.remove(file_name.as_str()) {
+                let full_path = cache_path.join(&file_name);
+                entry.unpack(&full_path).map_err(|e| e.to_string())?;
+                debug!("Unarchived file: {}", full_path.display());
+            }
+            if missing.is_empty() {
+                break;
+            }
+        }
+
+        match missing.iter().next() {
+            Some(file) => Err(format!("{} not found in {}", file, tarball_path.display())),
+            None => Ok(cache_path),
+        }
+    }
+
+    /// Parses an STL-10 image file.
+    ///
+    /// Each image is stored as 3 column-major 96x96 planes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The contents of the file.
+    ///
+    /// # Returns
+    ///
+    /// The pixels in `[height, width, channels]` layout.
+    fn parse_images(bytes: &[u8]) -> Result<Vec<u8>, String> {
+        let size = Self::IMAGE_SIZE;
+        let image_size = size * size * 3;
+        if bytes.len() % image_size != 0 {
+            return Err(format!(
+                "Invalid STL-10 image file: {} bytes is not a multiple of {}",
+                bytes.len(),
+                image_size
+            ));
+        }
+
+        let mut pixels = vec![0; bytes.len()];
+        for (image, stored) in bytes.chunks_exact(image_size).enumerate() {
+            let offset = image * image_size;
+            for (index, &pixel) in stored.iter().enumerate() {
+                let (c, w, h) = (index / (size * size), index / size % size, index % size);
+                pixels[offset + (h * size + w) * 3 + c] = pixel;
+            }
+        }
+        Ok(pixels)
+    }
+
+    /// Parses an STL-10 label file, where the labels start at 1.
+    fn parse_labels(bytes: &[u8]) -> Result<Vec<usize>, String> {
+        bytes
+            .iter()
+            .map(|&label| match label {
+                1..=10 => Ok(label as usize - 1),
+                _ => Err(format!("Invalid label value: {}", label)),
+            })
+            .collect()
+    }
+
+    /// Reads the class names, falling back to the known names if the file is missing.
+    fn read_classes(path: &Path) -> Vec<String> {
+        match fs::read_to_string(path) {
+            Ok(names) => names
+                .lines()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect(),
+            Err(_) => Self::CLASSES.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+}
+
+impl PackedSource for Stl10 {
+    fn load(&self, is_train: bool, options: LoadOptions) -> PackedFuture {
+        let (images_file, labels_file) = self.file_names(is_train);
+        Box::pin(async move {
+            let mut files = vec![images_file];
+            files.extend(labels_file);
+            let root = Self::download_and_extract(&files, &options).await?;
+
+            let read = |file: &str| {
+                fs::read(root.join(file)).map_err(|e| format!("Failed to read {}: {}", file, e))
+            };
+            let pixels = Self::parse_images(&read(images_file)?)?;
+            let labels = labels_file.map(|file| Self::parse_labels(&read(file)?)).transpose()?;
+            let classes = Self::read_classes(&root.join(Self::CLASS_NAMES_FILENAME));
+
+            PackedImages::new(pixels, labels, [Self::IMAGE_SIZE, Self::IMAGE_SIZE, 3], classes)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::deep_learning::dataset::{IndexedDataset, Split};
+
+    /// The value of a pixel of the fixtures.
+    fn pixel(h: usize, w: usize, c: usize, n: usize) -> u8 {
+        ((h + 5 * w + 60 * c + 11 * n) % 256) as u8
+    }
+
+    /// Encodes images in the column-major STL-10 layout.
+    fn image_bytes(count: usize) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(count * 96 * 96 * 3);
+        for n in 0..count {
+            for c in 0..3 {
+                for w in 0..96 {
+                    for h in 0..96 {
+                        bytes.push(pixel(h, w, c, n));
+                    }
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Writes the STL-10 archive with train, test and unlabeled images.
+    fn write_archive(root: &Path) {
+        let tarball = File::create(root.join(Stl10::ARCHIVE_FILENAME)).unwrap();
+        let mut builder = tar::Builder::new(GzEncoder::new(tarball, Compression::fast()));
+        let files = [
+            ("train_X.bin", image_bytes(3)),
+            ("train_y.bin", vec![1, 10, 4]),
+            ("test_X.bin", image_bytes(1)),
+            ("test_y.bin", vec![2]),
+            ("unlabeled_X.bin", image_bytes(2)),
+            ("class_names.txt", Stl10::CLASSES.join("\n").into_bytes()),
+        ];
+        for (name, bytes) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(bytes.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, format!("stl10_binary/{}", name), &bytes[..]).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    fn fixture_root() -> TempDir {
+        let root = TempDir::new().unwrap();
+        write_archive(root.path());
+        root
+    }
+
+    #[tokio::test]
+    async fn test_builder_extracts_and_loads_labeled_splits() {
+        let fixture = fixture_root();
+        let root = fixture.path();
+
+        let builder = || Stl10Dataset::builder().root(root).offline(true);
+        let train = builder().build().await.unwrap();
+        let test = builder().split(Split::Test).build().await.unwrap();
+
+        assert_eq!(IndexedDataset::len(&train), 3);
+        assert_eq!(IndexedDataset::len(&test), 1);
+        assert_eq!(train.classes()[7], "monkey");
+        assert!(!root.join("unlabeled_X.bin").exists());
+
+        let (image, label) = train.get(1);
+        assert_eq!(image.data.shape(), &[96, 96, 3]);
+        for (h, w, c) in [(0, 0, 0), (10, 3, 1), (95, 40, 2)] {
+            let expected = pixel(h, w, c, 1) as f32 / 255.0;
+            assert!((image.data[[h, w, c]] - expected).abs() < 1e-6);
+        }
+        assert_eq!(label.data[[9]], 1.0);
+        assert_eq!(test.get(0).1.data[[1]], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_load_source_loads_unlabeled_images() {
+        let fixture = fixture_root();
+        let root = fixture.path();
+
+        let options =
+            LoadOptions { root: Some(root.to_path_buf()), offline: true, ..Default::default() };
+        let dataset = Stl10Dataset::load_source(Stl10::unlabeled(), options).await.unwrap();
+
+        assert!(dataset.source().is_unlabeled());
+        assert!(!dataset.images().is_labeled());
+        let (inputs, targets) = IndexedDataset::get_batch(&dataset, &[0, 1]);
+        assert_eq!(inputs.data.shape(), &[2, 96, 96, 3]);
+        assert_eq!(targets.data.shape(), &[2, 0]);
+    }
+
+    #[test]
+    fn test_parse_validates_files() {
+        assert!(Stl10::parse_images(&[0; 100]).unwrap_err().contains("not a multiple"));
+        assert_eq!(Stl10::parse_labels(&[1, 10]).unwrap(), vec![0, 9]);
+        assert!(Stl10::parse_labels(&[0]).unwrap_err().contains("Invalid label value: 0"));
+    }
+}
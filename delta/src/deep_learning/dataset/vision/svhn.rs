@@ -0,0 +1,289 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fs;
+
+use crate::deep_learning::dataset::vision::mat::{MatArray, MatData, read_mat};
+use crate::deep_learning::dataset::vision::packed::{
+    PackedDataset, PackedFuture, PackedImages, PackedSource,
+};
+use crate::deep_learning::dataset::{LoadOptions, cache};
+
+/// The Street View House Numbers dataset: 32x32 color images of cropped digits.
+pub type SvhnDataset = PackedDataset<Svhn>;
+
+/// The files of the cropped digits of the SVHN dataset.
+///
+/// The training split can include the 531,131 extra images with `with_extra`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Svhn {
+    extra: bool,
+}
+
+impl Svhn {
+    const URL: &'static str = "http://ufldl.stanford.edu/housenumbers";
+    const TRAIN_FILENAME: &'static str = "train_32x32.mat";
+    const TEST_FILENAME: &'static str = "test_32x32.mat";
+    const EXTRA_FILENAME: &'static str = "extra_32x32.mat";
+    const IMAGE_SIZE: usize = 32;
+    const NUM_CLASSES: usize = 10;
+
+    /// Creates the files of the SVHN dataset, without the extra images.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the training split includes the extra images.
+    ///
+    /// # Arguments
+    ///
+    /// * `extra` - Whether to append the extra images to the training images.
+    pub fn with_extra(mut self, extra: bool) -> Self {
+        self.extra = extra;
+        self
+    }
+
+    /// Returns whether the training split includes the extra images.
+    pub fn has_extra(&self) -> bool {
+        self.extra
+    }
+
+    /// Downloads if needed and parses an SVHN file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the `.mat` file.
+    /// * `options` - The options with the directory of the file and whether to download it.
+    ///
+    /// # Returns
+    ///
+    /// The pixels and labels of the images.
+    async fn load_file(
+        file_name: &str,
+        options: &LoadOptions,
+    ) -> Result<(Vec<u8>, Vec<usize>), String> {
+        let path = options.resolve_root("svhn").join(file_name);
+        let url = format!("{}/{}", Self::URL, file_name);
+
+        // No SHA-256 hashes are published for the files; pin them with
+        // `DatasetBuilder::checksum` to verify the downloads.
+        cache::fetch(&url, &path, None, options).await?;
+
+        let bytes =
+            fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse_mat(&bytes)
+    }
+
+    /// Parses the images and labels of an SVHN `.mat` file.
+    ///
+    /// The images are stored as a `[32, 32, 3, N]` column-major array `X`, and the labels as an
+    /// array `y` where the digit 0 has the label 10.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The contents of the file.
+    ///
+    /// # Returns
+    ///
+    /// The pixels in `[height, width, channels]` layout and the labels of the images.
+    fn parse_mat(bytes: &[u8]) -> Result<(Vec<u8>, Vec<usize>), String> {
+        let arrays = read_mat(bytes)?;
+        let find = |name: &str| -> Result<&MatArray, String> {
+            arrays
+                .iter()
+                .find(|array| array.name == name)
+                .ok_or_else(|| format!("Invalid SVHN file: missing the {} variable", name))
+        };
+        let (images, labels) = (find("X")?, find("y")?);
+
+        let size = Self::IMAGE_SIZE;
+        let count = match images.dims.as_slice() {
+            [h, w, 3, count] if *h == size && *w == size => *count,
+            [h, w, 3] if *h == size && *w == size => 1,
+            dims => return Err(format!("Invalid SVHN file: X has dimensions {:?}", dims)),
+        };
+        if labels.data.len() != count {
+            return Err(format!(
+                "Invalid SVHN file: found {} images but {} labels",
+                count,
+                labels.data.len()
+            ));
+        }
+
+        let plane = size * size;
+        let column_major =
+            |h: usize, w: usize, c: usize, n: usize| h + size * w + plane * c + plane * 3 * n;
+        let mut pixels = vec![0; count * plane * 3];
+        for n in 0..count {
+            for h in 0..size {
+                for w in 0..size {
+                    for c in 0..3 {
+                        let index = column_major(h, w, c, n);
+                        pixels[((n * size + h) * size + w) * 3 + c] = match &images.data {
+                            MatData::U8(values) => values[index],
+                            MatData::F64(values) => values[index].clamp(0.0, 255.0) as u8,
+                        };
+                    }
+                }
+            }
+        }
+
+        let labels = (0..count)
+            .map(|index| match labels.data.get(index) {
+                10.0 => Ok(0),
+                label if (0.0..10.0).contains(&label) && label.fract() == 0.0 => Ok(label as usize),
+                label => Err(format!("Invalid label value: {}", label)),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok((pixels, labels))
+    }
+}
+
+impl PackedSource for Svhn {
+    fn load(&self, is_train: bool, options: LoadOptions) -> PackedFuture {
+        let extra = self.extra && is_train;
+        Box::pin(async move {
+            let file_name = if is_train { Self::TRAIN_FILENAME } else { Self::TEST_FILENAME };
+            let (mut pixels, mut labels) = Self::load_file(file_name, &options).await?;
+            if extra {
+                let (extra_pixels, extra_labels) =
+                    Self::load_file(Self::EXTRA_FILENAME, &options).await?;
+                pixels.extend(extra_pixels);
+                labels.extend(extra_labels);
+            }
+
+            let classes = (0..Self::NUM_CLASSES).map(|digit| digit.to_string()).collect();
+            PackedImages::new(
+                pixels,
+                Some(labels),
+                [Self::IMAGE_SIZE, Self::IMAGE_SIZE, 3],
+                classes,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::deep_learning::dataset::vision::mat::fixtures::{
+        compressed, f64_matrix, mat_file, u8_matrix,
+    };
+    use crate::deep_learning::dataset::{IndexedDataset, Split};
+
+    /// The value of a pixel of the fixtures.
+    fn pixel(h: usize, w: usize, c: usize, n: usize) -> u8 {
+        ((h + 3 * w + 100 * c + 7 * n) % 256) as u8
+    }
+
+    fn write_fixture(root: &Path, file_name: &str, labels: &[f64]) {
+        let count = labels.len();
+        let mut values = vec![0; 32 * 32 * 3 * count];
+        for n in 0..count {
+            for c in 0..3 {
+                for w in 0..32 {
+                    for h in 0..32 {
+                        values[h + 32 * w + 1024 * c + 3072 * n] = pixel(h, w, c, n);
+                    }
+                }
+            }
+        }
+        let bytes = mat_file(&[
+            compressed(&u8_matrix("X", &[32, 32, 3, count], &values)),
+            f64_matrix("y", &[count, 1], labels),
+        ]);
+        fs::write(root.join(file_name), bytes).unwrap();
+    }
+
+    fn fixture_root() -> TempDir {
+        let root = TempDir::new().unwrap();
+        write_fixture(root.path(), Svhn::TRAIN_FILENAME, &[10.0, 3.0, 5.0]);
+        write_fixture(root.path(), Svhn::TEST_FILENAME, &[1.0]);
+        write_fixture(root.path(), Svhn::EXTRA_FILENAME, &[9.0, 10.0]);
+        root
+    }
+
+    #[tokio::test]
+    async fn test_builder_loads_mat_files() {
+        let fixture = fixture_root();
+        let root = fixture.path();
+
+        let builder = || SvhnDataset::builder().root(root).offline(true);
+        let train = builder().build().await.unwrap();
+        let test = builder().split(Split::Test).build().await.unwrap();
+
+        assert_eq!(IndexedDataset::len(&train), 3);
+        assert_eq!(IndexedDataset::len(&test), 1);
+        assert_eq!(train.classes().len(), 10);
+
+        let (image, label) = train.get(1);
+        assert_eq!(image.data.shape(), &[32, 32, 3]);
+        for (h, w, c) in [(0, 0, 0), (5, 2, 1), (31, 17, 2)] {
+            let expected = pixel(h, w, c, 1) as f32 / 255.0;
+            assert!((image.data[[h, w, c]] - expected).abs() < 1e-6);
+        }
+        assert_eq!(label.data[[3]], 1.0);
+        // The digit 0 is stored with the label 10.
+        assert_eq!(train.get(0).1.data[[0]], 1.0);
+        assert_eq!(test.get(0).1.data[[1]], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_load_source_appends_extra_images() {
+        let fixture = fixture_root();
+        let root = fixture.path();
+
+        let options =
+            LoadOptions { root: Some(root.to_path_buf()), offline: true, ..Default::default() };
+        let dataset =
+            SvhnDataset::load_source(Svhn::new().with_extra(true), options).await.unwrap();
+
+        assert!(dataset.source().has_extra());
+        assert_eq!(IndexedDataset::len(&dataset), 5);
+        assert_eq!(dataset.get(3).1.data[[9]], 1.0);
+        assert_eq!(dataset.get(4).1.data[[0]], 1.0);
+    }
+
+    #[test]
+    fn test_parse_mat_validates_variables() {
+        let missing = mat_file(&[u8_matrix("X", &[32, 32, 3, 0], &[])]);
+        assert!(Svhn::parse_mat(&missing).unwrap_err().contains("missing the y variable"));
+
+        let bad_label = mat_file(&[
+            u8_matrix("X", &[32, 32, 3, 1], &[0; 3072]),
+            f64_matrix("y", &[1, 1], &[11.0]),
+        ]);
+        assert!(Svhn::parse_mat(&bad_label).unwrap_err().contains("Invalid label value: 11"));
+    }
+}
@@ -31,6 +31,7 @@
 use std::pin::Pin;
 
 use ndarray::{IxDyn, Shape, s};
+use rand::rngs::StdRng;
 
 use crate::deep_learning::dataset::{Dataset, DatasetOps};
 use crate::deep_learning::tensor_ops::Tensor;
@@ -73,7 +74,7 @@ fn generate_dummy_dataset(size: usize, features: usize) -> Dataset {
         );
         let labels =
             Tensor::new((0..size).map(|x| (x % 2) as f32).collect(), Shape::from(IxDyn(&[size])));
-        Dataset { inputs, labels }
+        Dataset::new(inputs, labels)
     }
 
     /// Splits the training data into training and validation datasets.
@@ -183,10 +184,9 @@ fn add_noise(&mut self, noise_level: f32) {
     /// The number of samples in the dataset.
     #[inline]
     fn len(&self) -> usize {
-        self.train
-            .as_ref()
-            .map(|d| d.inputs.data.len())
-            .unwrap_or_else(|| self.test.as_ref().map(|d| d.inputs.data.len()).unwrap_or(0))
+        self.train.as_ref().map(|d| d.inputs.shape().raw_dim()[0]).unwrap_or_else(|| {
+            self.test.as_ref().map(|d| d.inputs.shape().raw_dim()[0]).unwrap_or(0)
+        })
     }
 
     /// Retrieves a batch of dataset from the dataset.
@@ -202,7 +202,7 @@ fn len(&self) -> usize {
     fn get_batch(&self, batch_idx: usize, batch_size: usize) -> (Tensor, Tensor) {
         if let Some(dataset) = &self.train {
             let start = batch_idx * batch_size;
-            let end = (start + batch_size).min(dataset.inputs.data.len());
+            let end = (start + batch_size).min(dataset.inputs.shape().raw_dim()[0]);
 
             let inputs = dataset.inputs.data.slice(s![start..end, ..]).to_owned();
             let labels = dataset.labels.data.slice(s![start..end]).to_owned();
@@ -219,6 +219,36 @@ fn get_batch(&self, batch_idx: usize, batch_size: usize) -> (Tensor, Tensor) {
         (Tensor::default(), Tensor::default())
     }
 
+    /// Gets the sample weights of a batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_idx` - The index of the batch to get.
+    /// * `batch_size` - The size of the batch to get.
+    ///
+    /// # Returns
+    ///
+    /// The weights of the samples in the batch, or `None` if no sample weights are set.
+    fn get_batch_sample_weights(&self, batch_idx: usize, batch_size: usize) -> Option<Tensor> {
+        self.train.as_ref()?.batch_sample_weights(batch_idx, batch_size)
+    }
+
+    /// Sets the weight of each sample of the dataset used by `get_batch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - A `[samples]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// An error if the dataset is not loaded or the number of weights does not match.
+    fn set_sample_weights(&mut self, weights: Tensor) -> Result<(), String> {
+        match self.train.as_mut() {
+            Some(dataset) => dataset.set_sample_weights(weights),
+            None => Err("Dataset not loaded!".to_string()),
+        }
+    }
+
     /// Computes the loss between the outputs and targets.
     ///
     /// # Arguments
@@ -251,8 +281,10 @@ fn loss_grad(&self, outputs: &Tensor, targets: &Tensor) -> Tensor {
     /// Shuffles the dataset.
     ///
     /// This method shuffles the training dataset by randomly permuting the indices of the samples.
-    fn shuffle(&mut self) {
-        todo!();
+    fn shuffle_with_rng(&mut self, rng: &mut StdRng) {
+        if let Some(train) = &mut self.train {
+            train.shuffle_with_rng(rng);
+        }
     }
 
     /// Clones the dataset.
@@ -0,0 +1,311 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use image::imageops::FilterType;
+use log::debug;
+use rayon::prelude::*;
+
+use crate::deep_learning::dataset::vision::packed::{
+    PackedDataset, PackedFuture, PackedImages, PackedSource,
+};
+use crate::deep_learning::dataset::{LoadOptions, cache};
+
+/// The Tiny-ImageNet dataset: 64x64 color images of 200 ImageNet classes.
+///
+/// The official test images are not labeled, so `Split::Test` loads the labeled validation
+/// images.
+pub type TinyImageNetDataset = PackedDataset<TinyImageNet>;
+
+/// The files of the Tiny-ImageNet dataset.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TinyImageNet;
+
+impl TinyImageNet {
+    const URL: &'static str = "http://cs231n.stanford.edu/tiny-imagenet-200.zip";
+    const ARCHIVE_FILENAME: &'static str = "tiny-imagenet-200.zip";
+    const DIR: &'static str = "tiny-imagenet-200";
+    const IMAGE_SIZE: usize = 64;
+
+    /// Reads the WordNet IDs of the classes, in label order.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The `tiny-imagenet-200` directory.
+    ///
+    /// # Returns
+    ///
+    /// The WordNet IDs listed in `wnids.txt`.
+    pub fn read_wnids(dir: &Path) -> Result<Vec<String>, String> {
+        let path = dir.join("wnids.txt");
+        let wnids = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Ok(wnids.lines().map(str::trim).filter(|wnid| !wnid.is_empty()).map(String::from).collect())
+    }
+
+    /// Reads the names of the WordNet IDs.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The `tiny-imagenet-200` directory.
+    ///
+    /// # Returns
+    ///
+    /// The map from WordNet ID to the comma-separated names in `words.txt`.
+    pub fn read_words(dir: &Path) -> Result<HashMap<String, String>, String> {
+        let path = dir.join("words.txt");
+        let words = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Ok(words
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(wnid, names)| (wnid.to_string(), names.trim().to_string()))
+            .collect())
+    }
+
+    /// Downloads and extracts the Tiny-ImageNet dataset, unless it is already there.
+    ///
+    /// The archive is extracted to a temporary directory that is renamed once complete, so an
+    /// interrupted extraction is not mistaken for the dataset.
+    ///
+    /// # Arguments
+    /// * `options` - The options with the cache directory and whether to download.
+    ///
+    /// # Returns
+    /// The `tiny-imagenet-200` directory.
+    async fn download_and_extract(options: &LoadOptions) -> Result<PathBuf, String> {
+        let cache_path = options.resolve_root("tiny_imagenet");
+        let dir = cache_path.join(Self::DIR);
+        if dir.join("wnids.txt").exists() {
+            return Ok(dir);
+        }
+
+        let archive_path = cache_path.join(Self::ARCHIVE_FILENAME);
+
+        // No SHA-256 hash is published for the archive; pin it with `DatasetBuilder::checksum`
+        // to verify the download.
+        cache::fetch(Self::URL, &archive_path, None, options).await?;
+
+        let extract_path = cache_path.join(format!("{}.part", Self::DIR));
+        let _ = fs::remove_dir_all(&extract_path);
+        let file = File::open(&archive_path).map_err(|e| e.to_string())?;
+        zip::ZipArchive::new(file)
+            .and_then(|mut archive| archive.extract(&extract_path))
+            .map_err(|e| format!("Failed to extract {}: {}", archive_path.display(), e))?;
+        fs::rename(extract_path.join(Self::DIR), &dir).map_err(|e| e.to_string())?;
+        let _ = fs::remove_dir_all(&extract_path);
+        debug!("Extracted {} to {}", archive_path.display(), dir.display());
+
+        Ok(dir)
+    }
+
+    /// Lists the training images, stored in `train/<wnid>/images`.
+    fn train_samples(dir: &Path, wnids: &[String]) -> Result<Vec<(PathBuf, usize)>, String> {
+        let mut samples = Vec::new();
+        for (label, wnid) in wnids.iter().enumerate() {
+            let images = dir.join("train").join(wnid).join("images");
+            let mut paths: Vec<PathBuf> = fs::read_dir(&images)
+                .map_err(|e| format!("Failed to read {}: {}", images.display(), e))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_file())
+                .collect();
+            paths.sort();
+            samples.extend(paths.into_iter().map(|path| (path, label)));
+        }
+        Ok(samples)
+    }
+
+    /// Lists the validation images, labeled by `val/val_annotations.txt`.
+    fn val_samples(dir: &Path, wnids: &[String]) -> Result<Vec<(PathBuf, usize)>, String> {
+        let labels: HashMap<&str, usize> =
+            wnids.iter().enumerate().map(|(label, wnid)| (wnid.as_str(), label)).collect();
+        let path = dir.join("val").join("val_annotations.txt");
+        let annotations = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+        annotations
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let mut fields = line.split('\t');
+                let (Some(file_name), Some(wnid)) = (fields.next(), fields.next()) else {
+                    return Err(format!("Invalid annotation in {}: {}", path.display(), line));
+                };
+                let label = labels
+                    .get(wnid)
+                    .ok_or_else(|| format!("Unknown WordNet ID {} in {}", wnid, path.display()))?;
+                Ok((dir.join("val").join("images").join(file_name), *label))
+            })
+            .collect()
+    }
+
+    /// Decodes the images in parallel, resizing any image that is not 64x64.
+    fn decode(samples: &[(PathBuf, usize)]) -> Result<Vec<u8>, String> {
+        let size = Self::IMAGE_SIZE as u32;
+        let images = samples
+            .par_iter()
+            .map(|(path, _)| {
+                let image = image::open(path)
+                    .map_err(|e| format!("Failed to load image {}: {}", path.display(), e))?;
+                let image = if image.width() != size || image.height() != size {
+                    image.resize_exact(size, size, FilterType::Triangle)
+                } else {
+                    image
+                };
+                Ok(image.to_rgb8().into_raw())
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(images.concat())
+    }
+}
+
+impl PackedSource for TinyImageNet {
+    fn load(&self, is_train: bool, options: LoadOptions) -> PackedFuture {
+        Box::pin(async move {
+            let dir = Self::download_and_extract(&options).await?;
+            let wnids = Self::read_wnids(&dir)?;
+            let samples = if is_train {
+                Self::train_samples(&dir, &wnids)?
+            } else {
+                Self::val_samples(&dir, &wnids)?
+            };
+
+            let pixels = Self::decode(&samples)?;
+            let labels = samples.into_iter().map(|(_, label)| label).collect();
+            PackedImages::new(pixels, Some(labels), [Self::IMAGE_SIZE, Self::IMAGE_SIZE, 3], wnids)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use image::{ImageFormat, Rgb, RgbImage};
+    use tempfile::TempDir;
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+    use crate::deep_learning::dataset::{IndexedDataset, Split};
+
+    const WNIDS: [&str; 2] = ["n01443537", "n09193705"];
+
+    fn jpeg(color: [u8; 3], size: u32) -> Vec<u8> {
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        RgbImage::from_pixel(size, size, Rgb(color))
+            .write_to(&mut bytes, ImageFormat::Jpeg)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    /// Writes the Tiny-ImageNet archive with two training images per class and three validation
+    /// images.
+    fn write_archive(root: &Path) {
+        let mut archive =
+            zip::ZipWriter::new(File::create(root.join(TinyImageNet::ARCHIVE_FILENAME)).unwrap());
+        let mut add = |name: &str, bytes: &[u8]| {
+            archive
+                .start_file(format!("tiny-imagenet-200/{}", name), SimpleFileOptions::default())
+                .unwrap();
+            archive.write_all(bytes).unwrap();
+        };
+
+        add("wnids.txt", WNIDS.join("\n").as_bytes());
+        add("words.txt", b"n01443537\tgoldfish, Carassius auratus\nn09193705\talp\n");
+        for (label, wnid) in WNIDS.iter().enumerate() {
+            let color = if label == 0 { [255, 0, 0] } else { [0, 0, 255] };
+            for i in 0..2 {
+                add(&format!("train/{}/images/{}_{}.JPEG", wnid, wnid, i), &jpeg(color, 64));
+            }
+        }
+        add("val/images/val_0.JPEG", &jpeg([0, 0, 255], 64));
+        add("val/images/val_1.JPEG", &jpeg([255, 0, 0], 32));
+        add("val/images/val_2.JPEG", &jpeg([0, 0, 255], 64));
+        add(
+            "val/val_annotations.txt",
+            b"val_0.JPEG\tn09193705\t0\t0\t63\t63\nval_1.JPEG\tn01443537\t1\t2\t30\t30\n\
+              val_2.JPEG\tn09193705\t4\t4\t60\t60\n",
+        );
+        archive.finish().unwrap();
+    }
+
+    fn fixture_root() -> TempDir {
+        let root = TempDir::new().unwrap();
+        write_archive(root.path());
+        root
+    }
+
+    #[tokio::test]
+    async fn test_builder_extracts_and_loads_train_and_val_folders() {
+        let fixture = fixture_root();
+        let root = fixture.path();
+
+        let builder = || TinyImageNetDataset::builder().root(root).offline(true);
+        let train = builder().build().await.unwrap();
+        let test = builder().split(Split::Test).build().await.unwrap();
+
+        assert_eq!(train.classes(), &WNIDS.map(String::from)[..]);
+        assert_eq!(IndexedDataset::len(&train), 4);
+        assert_eq!(IndexedDataset::len(&test), 3);
+        assert!(!root.join("tiny-imagenet-200.part").exists());
+
+        let (image, label) = train.get(2);
+        assert_eq!(image.data.shape(), &[64, 64, 3]);
+        assert!(image.data[[10, 10, 2]] > 0.9 && image.data[[10, 10, 0]] < 0.1);
+        assert_eq!(label.data[[1]], 1.0);
+
+        let (images, labels) = IndexedDataset::get_batch(&test, &[0, 1]);
+        assert_eq!(images.data.shape(), &[2, 64, 64, 3]);
+        assert_eq!(labels.data[[0, 1]], 1.0);
+        assert_eq!(labels.data[[1, 0]], 1.0);
+        assert!(images.data[[1, 20, 20, 0]] > 0.9);
+
+        let dir = root.join("tiny-imagenet-200");
+        assert_eq!(TinyImageNet::read_words(&dir).unwrap()["n09193705"], "alp");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_wnid_in_annotations_is_an_error() {
+        let fixture = fixture_root();
+        let root = fixture.path();
+        let dir = root.join("tiny-imagenet-200");
+        fs::create_dir_all(dir.join("val")).unwrap();
+        fs::write(dir.join("wnids.txt"), WNIDS.join("\n")).unwrap();
+        fs::write(dir.join("val/val_annotations.txt"), "val_0.JPEG\tn00000000\t0\t0\t1\t1\n")
+            .unwrap();
+
+        let result = TinyImageNetDataset::builder().root(root).split(Split::Test).build().await;
+
+        let err = result.err().expect("An unknown WordNet ID should not load");
+        assert!(err.contains("Unknown WordNet ID n00000000"), "{}", err);
+    }
+}
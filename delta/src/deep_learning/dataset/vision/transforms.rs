@@ -0,0 +1,878 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::sync::Mutex;
+
+use ndarray::{Array3, ArrayView3, Axis, Ix3, s};
+use rand::Rng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand_distr::{Beta, Distribution};
+
+use crate::deep_learning::rng::{fork_global_rng, rng_from_seed};
+use crate::deep_learning::tensor_ops::Tensor;
+
+/// A transform applied to images when they are loaded.
+///
+/// Images have the shape `[height, width, channels]` and batches the shape
+/// `[batch, height, width, channels]`, with pixel values in `[0, 1]`.
+pub trait Transform: Send + Sync {
+    /// Applies the transform to a single image.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image to transform.
+    /// * `rng` - The generator used for the random parameters of the transform.
+    ///
+    /// # Returns
+    ///
+    /// The transformed image.
+    fn apply(&self, image: &Tensor, rng: &mut StdRng) -> Tensor;
+
+    /// Applies the transform to a batch of images and their targets.
+    ///
+    /// By default, every image is transformed independently and the targets are unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The batch of images.
+    /// * `targets` - The targets of the batch.
+    /// * `rng` - The generator used for the random parameters of the transform.
+    ///
+    /// # Returns
+    ///
+    /// The transformed images and targets.
+    fn apply_batch(&self, inputs: &Tensor, targets: &Tensor, rng: &mut StdRng) -> (Tensor, Tensor) {
+        let images: Vec<Tensor> = inputs
+            .data
+            .outer_iter()
+            .map(|image| {
+                self.apply(&Tensor { data: image.to_owned(), device: inputs.device.clone() }, rng)
+            })
+            .collect();
+
+        (Tensor::stack(&images).expect("Failed to stack transformed images"), targets.clone())
+    }
+}
+
+/// Returns an image as a `[height, width, channels]` view.
+fn image_view(image: &Tensor) -> ArrayView3<'_, f32> {
+    image
+        .data
+        .view()
+        .into_dimensionality::<Ix3>()
+        .expect("Transforms expect images of shape [height, width, channels]")
+}
+
+/// Wraps a transformed image in a tensor on the device of the original image.
+fn to_tensor(image: Array3<f32>, like: &Tensor) -> Tensor {
+    // Flips produce reversed strides, so copy them into the standard layout.
+    let image = if image.is_standard_layout() {
+        image
+    } else {
+        image.as_standard_layout().into_owned()
+    };
+    Tensor { data: image.into_dyn(), device: like.device.clone() }
+}
+
+/// Panics if a probability is outside `[0, 1]`.
+fn check_probability(p: f32) {
+    if !(0.0..=1.0).contains(&p) {
+        panic!("Probability must be in [0, 1].");
+    }
+}
+
+/// A pipeline of transforms applied in order, with its own seeded generator.
+///
+/// The generator is shared by every call, so a pipeline with a seed produces the same sequence
+/// of augmentations as long as batches are requested in the same order.
+pub struct Compose {
+    transforms: Vec<Box<dyn Transform>>,
+    rng: Mutex<StdRng>,
+}
+
+impl Default for Compose {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Compose {
+    /// Creates an empty pipeline whose generator is forked from the global generator.
+    pub fn new() -> Self {
+        Self { transforms: Vec::new(), rng: Mutex::new(fork_global_rng()) }
+    }
+
+    /// Appends a transform to the pipeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - The transform to apply after the previous ones.
+    pub fn with<T: Transform + 'static>(mut self, transform: T) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Reseeds the generator of the pipeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed for the generator.
+    pub fn with_seed(self, seed: u64) -> Self {
+        *self.rng.lock().expect("Transform RNG lock poisoned") = rng_from_seed(seed);
+        self
+    }
+
+    /// Returns the number of transforms in the pipeline.
+    pub fn len(&self) -> usize {
+        self.transforms.len()
+    }
+
+    /// Returns `true` if the pipeline has no transforms.
+    pub fn is_empty(&self) -> bool {
+        self.transforms.is_empty()
+    }
+
+    /// Applies the pipeline to a single image with its own generator.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image to transform.
+    ///
+    /// # Returns
+    ///
+    /// The transformed image.
+    pub fn apply_sample(&self, image: &Tensor) -> Tensor {
+        let mut rng = self.rng.lock().expect("Transform RNG lock poisoned");
+        self.apply(image, &mut rng)
+    }
+
+    /// Applies the pipeline to a batch with its own generator.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The batch of images.
+    /// * `targets` - The targets of the batch.
+    ///
+    /// # Returns
+    ///
+    /// The transformed images and targets.
+    pub fn apply_to_batch(&self, inputs: &Tensor, targets: &Tensor) -> (Tensor, Tensor) {
+        let mut rng = self.rng.lock().expect("Transform RNG lock poisoned");
+        self.apply_batch(inputs, targets, &mut rng)
+    }
+}
+
+impl Transform for Compose {
+    fn apply(&self, image: &Tensor, rng: &mut StdRng) -> Tensor {
+        self.transforms.iter().fold(image.clone(), |image, transform| transform.apply(&image, rng))
+    }
+
+    fn apply_batch(&self, inputs: &Tensor, targets: &Tensor, rng: &mut StdRng) -> (Tensor, Tensor) {
+        self.transforms.iter().fold((inputs.clone(), targets.clone()), |(inputs, targets), t| {
+            t.apply_batch(&inputs, &targets, rng)
+        })
+    }
+}
+
+/// Crops a random region of an image after padding its borders with zeros.
+#[derive(Debug, Clone, Copy)]
+pub struct RandomCrop {
+    height: usize,
+    width: usize,
+    padding: usize,
+}
+
+impl RandomCrop {
+    /// Creates a new `RandomCrop`.
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - The height of the crop.
+    /// * `width` - The width of the crop.
+    /// * `padding` - The number of zero pixels added on every border before cropping.
+    pub fn new(height: usize, width: usize, padding: usize) -> Self {
+        if height == 0 || width == 0 {
+            panic!("Crop size must be greater than 0.");
+        }
+        Self { height, width, padding }
+    }
+}
+
+impl Transform for RandomCrop {
+    fn apply(&self, image: &Tensor, rng: &mut StdRng) -> Tensor {
+        let view = image_view(image);
+        let (height, width, channels) = view.dim();
+        let (padded_height, padded_width) = (height + 2 * self.padding, width + 2 * self.padding);
+        if self.height > padded_height || self.width > padded_width {
+            panic!(
+                "Crop of {}x{} is larger than the padded image of {}x{}.",
+                self.height, self.width, padded_height, padded_width
+            );
+        }
+
+        let mut padded = Array3::zeros((padded_height, padded_width, channels));
+        padded
+            .slice_mut(s![
+                self.padding..self.padding + height,
+                self.padding..self.padding + width,
+                ..
+            ])
+            .assign(&view);
+
+        let top = rng.gen_range(0..=padded_height - self.height);
+        let left = rng.gen_range(0..=padded_width - self.width);
+        let crop = padded.slice(s![top..top + self.height, left..left + self.width, ..]).to_owned();
+        to_tensor(crop, image)
+    }
+}
+
+/// Mirrors an image left to right with a given probability.
+#[derive(Debug, Clone, Copy)]
+pub struct RandomHorizontalFlip {
+    p: f32,
+}
+
+impl RandomHorizontalFlip {
+    /// Creates a new `RandomHorizontalFlip`.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The probability of flipping an image.
+    pub fn new(p: f32) -> Self {
+        check_probability(p);
+        Self { p }
+    }
+}
+
+impl Transform for RandomHorizontalFlip {
+    fn apply(&self, image: &Tensor, rng: &mut StdRng) -> Tensor {
+        if rng.gen::<f32>() >= self.p {
+            return image.clone();
+        }
+        to_tensor(image_view(image).slice(s![.., ..;-1, ..]).to_owned(), image)
+    }
+}
+
+/// Mirrors an image top to bottom with a given probability.
+#[derive(Debug, Clone, Copy)]
+pub struct RandomVerticalFlip {
+    p: f32,
+}
+
+impl RandomVerticalFlip {
+    /// Creates a new `RandomVerticalFlip`.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The probability of flipping an image.
+    pub fn new(p: f32) -> Self {
+        check_probability(p);
+        Self { p }
+    }
+}
+
+impl Transform for RandomVerticalFlip {
+    fn apply(&self, image: &Tensor, rng: &mut StdRng) -> Tensor {
+        if rng.gen::<f32>() >= self.p {
+            return image.clone();
+        }
+        to_tensor(image_view(image).slice(s![..;-1, .., ..]).to_owned(), image)
+    }
+}
+
+/// Rotates an image around its center by a random angle, filling the corners with zeros.
+#[derive(Debug, Clone, Copy)]
+pub struct RandomRotation {
+    degrees: f32,
+}
+
+impl RandomRotation {
+    /// Creates a new `RandomRotation`.
+    ///
+    /// # Arguments
+    ///
+    /// * `degrees` - The largest angle of rotation; angles are drawn from `[-degrees, degrees]`.
+    pub fn new(degrees: f32) -> Self {
+        if degrees < 0.0 {
+            panic!("Rotation angle must be non-negative.");
+        }
+        Self { degrees }
+    }
+}
+
+impl Transform for RandomRotation {
+    fn apply(&self, image: &Tensor, rng: &mut StdRng) -> Tensor {
+        let angle = rng.gen_range(-self.degrees..=self.degrees).to_radians();
+        let view = image_view(image);
+        let (height, width, channels) = view.dim();
+        let (center_y, center_x) = ((height as f32 - 1.0) / 2.0, (width as f32 - 1.0) / 2.0);
+        let (sin, cos) = angle.sin_cos();
+
+        let mut rotated = Array3::zeros((height, width, channels));
+        for y in 0..height {
+            for x in 0..width {
+                // Map every output pixel back to its nearest source pixel.
+                let (dy, dx) = (y as f32 - center_y, x as f32 - center_x);
+                let source_y = (cos * dy - sin * dx + center_y).round();
+                let source_x = (sin * dy + cos * dx + center_x).round();
+                if source_y >= 0.0
+                    && source_x >= 0.0
+                    && (source_y as usize) < height
+                    && (source_x as usize) < width
+                {
+                    rotated.slice_mut(s![y, x, ..]).assign(&view.slice(s![
+                        source_y as usize,
+                        source_x as usize,
+                        ..
+                    ]));
+                }
+            }
+        }
+
+        to_tensor(rotated, image)
+    }
+}
+
+/// Resizes an image with bilinear interpolation.
+#[derive(Debug, Clone, Copy)]
+pub struct Resize {
+    height: usize,
+    width: usize,
+}
+
+impl Resize {
+    /// Creates a new `Resize`.
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - The height of the resized image.
+    /// * `width` - The width of the resized image.
+    pub fn new(height: usize, width: usize) -> Self {
+        if height == 0 || width == 0 {
+            panic!("Image size must be greater than 0.");
+        }
+        Self { height, width }
+    }
+}
+
+impl Transform for Resize {
+    fn apply(&self, image: &Tensor, _rng: &mut StdRng) -> Tensor {
+        let view = image_view(image);
+        let (height, width, channels) = view.dim();
+        let source = |output: usize, output_size: usize, input_size: usize| {
+            let position = ((output as f32 + 0.5) * input_size as f32 / output_size as f32 - 0.5)
+                .clamp(0.0, input_size as f32 - 1.0);
+            let low = position.floor() as usize;
+            (low, (low + 1).min(input_size - 1), position - low as f32)
+        };
+
+        let mut resized = Array3::zeros((self.height, self.width, channels));
+        for y in 0..self.height {
+            let (y0, y1, wy) = source(y, self.height, height);
+            for x in 0..self.width {
+                let (x0, x1, wx) = source(x, self.width, width);
+                for c in 0..channels {
+                    let top = view[[y0, x0, c]] * (1.0 - wx) + view[[y0, x1, c]] * wx;
+                    let bottom = view[[y1, x0, c]] * (1.0 - wx) + view[[y1, x1, c]] * wx;
+                    resized[[y, x, c]] = top * (1.0 - wy) + bottom * wy;
+                }
+            }
+        }
+
+        to_tensor(resized, image)
+    }
+}
+
+/// Randomly changes the brightness, contrast and saturation of an image.
+#[derive(Debug, Clone, Copy)]
+pub struct ColorJitter {
+    brightness: f32,
+    contrast: f32,
+    saturation: f32,
+}
+
+impl ColorJitter {
+    /// Creates a new `ColorJitter`.
+    ///
+    /// Each factor is drawn from `[1 - amount, 1 + amount]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `brightness` - How much the brightness may change.
+    /// * `contrast` - How much the contrast may change.
+    /// * `saturation` - How much the saturation may change. Ignored for images with fewer than
+    ///   3 channels.
+    pub fn new(brightness: f32, contrast: f32, saturation: f32) -> Self {
+        if brightness < 0.0 || contrast < 0.0 || saturation < 0.0 {
+            panic!("Color jitter amounts must be non-negative.");
+        }
+        Self { brightness, contrast, saturation }
+    }
+
+    /// Draws a factor from `[1 - amount, 1 + amount]`.
+    fn factor(amount: f32, rng: &mut StdRng) -> f32 {
+        if amount == 0.0 { 1.0 } else { rng.gen_range((1.0 - amount).max(0.0)..=1.0 + amount) }
+    }
+}
+
+impl Transform for ColorJitter {
+    fn apply(&self, image: &Tensor, rng: &mut StdRng) -> Tensor {
+        let brightness = Self::factor(self.brightness, rng);
+        let contrast = Self::factor(self.contrast, rng);
+        let saturation = Self::factor(self.saturation, rng);
+
+        let mut jittered = image_view(image).mapv(|x| (x * brightness).clamp(0.0, 1.0));
+
+        let mean = jittered.mean().unwrap_or(0.0);
+        jittered.mapv_inplace(|x| ((x - mean) * contrast + mean).clamp(0.0, 1.0));
+
+        if jittered.dim().2 >= 3 {
+            for mut pixel in jittered.lanes_mut(Axis(2)) {
+                let gray = 0.299 * pixel[0] + 0.587 * pixel[1] + 0.114 * pixel[2];
+                for x in pixel.iter_mut().take(3) {
+                    *x = ((*x - gray) * saturation + gray).clamp(0.0, 1.0);
+                }
+            }
+        }
+
+        to_tensor(jittered, image)
+    }
+}
+
+/// Fills a random rectangle of an image with a constant value, also known as cutout.
+#[derive(Debug, Clone, Copy)]
+pub struct RandomErasing {
+    p: f32,
+    scale: (f32, f32),
+    ratio: (f32, f32),
+    value: f32,
+}
+
+impl RandomErasing {
+    /// Creates a new `RandomErasing` that erases 2% to 33% of the image with zeros, with aspect
+    /// ratios between 0.3 and 3.3.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The probability of erasing a rectangle.
+    pub fn new(p: f32) -> Self {
+        check_probability(p);
+        Self { p, scale: (0.02, 0.33), ratio: (0.3, 3.3), value: 0.0 }
+    }
+
+    /// Sets the range of the fraction of the image that is erased.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The smallest fraction.
+    /// * `max` - The largest fraction.
+    pub fn with_scale(mut self, min: f32, max: f32) -> Self {
+        if !(0.0 < min && min <= max && max <= 1.0) {
+            panic!("Erasing scale must satisfy 0 < min <= max <= 1.");
+        }
+        self.scale = (min, max);
+        self
+    }
+
+    /// Sets the range of the aspect ratio, width over height, of the erased rectangle.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The smallest aspect ratio.
+    /// * `max` - The largest aspect ratio.
+    pub fn with_ratio(mut self, min: f32, max: f32) -> Self {
+        if !(0.0 < min && min <= max) {
+            panic!("Erasing ratio must satisfy 0 < min <= max.");
+        }
+        self.ratio = (min, max);
+        self
+    }
+
+    /// Sets the value the erased rectangle is filled with.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The fill value.
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+}
+
+impl Transform for RandomErasing {
+    fn apply(&self, image: &Tensor, rng: &mut StdRng) -> Tensor {
+        if rng.gen::<f32>() >= self.p {
+            return image.clone();
+        }
+
+        let mut erased = image_view(image).to_owned();
+        let (height, width, _) = erased.dim();
+        let area = (height * width) as f32;
+
+        for _ in 0..10 {
+            let target_area = area * rng.gen_range(self.scale.0..=self.scale.1);
+            let ratio = rng.gen_range(self.ratio.0.ln()..=self.ratio.1.ln()).exp();
+            let erase_height = (target_area / ratio).sqrt().round() as usize;
+            let erase_width = (target_area * ratio).sqrt().round() as usize;
+            if erase_height == 0 || erase_width == 0 || erase_height > height || erase_width > width
+            {
+                continue;
+            }
+
+            let top = rng.gen_range(0..=height - erase_height);
+            let left = rng.gen_range(0..=width - erase_width);
+            erased
+                .slice_mut(s![top..top + erase_height, left..left + erase_width, ..])
+                .fill(self.value);
+            break;
+        }
+
+        to_tensor(erased, image)
+    }
+}
+
+/// Normalizes every channel of an image with its own mean and standard deviation.
+#[derive(Debug, Clone)]
+pub struct Normalize {
+    mean: Vec<f32>,
+    std: Vec<f32>,
+}
+
+impl Normalize {
+    /// Creates a new `Normalize`.
+    ///
+    /// # Arguments
+    ///
+    /// * `mean` - The mean of each channel.
+    /// * `std` - The standard deviation of each channel.
+    pub fn new(mean: Vec<f32>, std: Vec<f32>) -> Self {
+        if mean.len() != std.len() {
+            panic!("Mean and standard deviation must have the same number of channels.");
+        }
+        if std.iter().any(|&s| s <= 0.0) {
+            panic!("Standard deviation must be greater than 0.");
+        }
+        Self { mean, std }
+    }
+}
+
+impl Transform for Normalize {
+    fn apply(&self, image: &Tensor, _rng: &mut StdRng) -> Tensor {
+        let mut normalized = image_view(image).to_owned();
+        let channels = normalized.dim().2;
+        if channels != self.mean.len() {
+            panic!("Expected images with {} channels, got {}.", self.mean.len(), channels);
+        }
+
+        for (c, mut channel) in normalized.axis_iter_mut(Axis(2)).enumerate() {
+            channel.mapv_inplace(|x| (x - self.mean[c]) / self.std[c]);
+        }
+        to_tensor(normalized, image)
+    }
+
+    fn apply_batch(
+        &self,
+        inputs: &Tensor,
+        targets: &Tensor,
+        _rng: &mut StdRng,
+    ) -> (Tensor, Tensor) {
+        let channels = *inputs.data.shape().last().expect("Expected a batch of images");
+        if channels != self.mean.len() {
+            panic!("Expected images with {} channels, got {}.", self.mean.len(), channels);
+        }
+
+        let mut normalized = inputs.clone();
+        let last_axis = Axis(normalized.data.ndim() - 1);
+        for (c, mut channel) in normalized.data.axis_iter_mut(last_axis).enumerate() {
+            channel.mapv_inplace(|x| (x - self.mean[c]) / self.std[c]);
+        }
+        (normalized, targets.clone())
+    }
+}
+
+/// Draws a mixing coefficient from `Beta(alpha, alpha)` and a random pairing of the batch.
+fn mix_parameters(alpha: f32, batch_size: usize, rng: &mut StdRng) -> (f32, Vec<usize>) {
+    let lambda = Beta::new(alpha, alpha).expect("Alpha must be greater than 0.").sample(rng);
+    let mut permutation: Vec<usize> = (0..batch_size).collect();
+    permutation.shuffle(rng);
+    (lambda, permutation)
+}
+
+/// Blends the targets of a batch with the targets of its pairing.
+fn mix_targets(targets: &Tensor, permutation: &[usize], lambda: f32) -> Tensor {
+    let shuffled = targets.take(permutation);
+    Tensor {
+        data: &targets.data * lambda + &shuffled.data * (1.0 - lambda),
+        device: targets.device.clone(),
+    }
+}
+
+/// Blends every image of a batch with another image of the batch, and their targets alike.
+///
+/// The targets must be one-hot encoded. Single images are returned unchanged.
+#[derive(Debug, Clone, Copy)]
+pub struct MixUp {
+    alpha: f32,
+}
+
+impl MixUp {
+    /// Creates a new `MixUp`.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The parameter of the `Beta(alpha, alpha)` distribution of the mixing weight.
+    pub fn new(alpha: f32) -> Self {
+        if alpha <= 0.0 {
+            panic!("Alpha must be greater than 0.");
+        }
+        Self { alpha }
+    }
+}
+
+impl Transform for MixUp {
+    fn apply(&self, image: &Tensor, _rng: &mut StdRng) -> Tensor {
+        image.clone()
+    }
+
+    fn apply_batch(&self, inputs: &Tensor, targets: &Tensor, rng: &mut StdRng) -> (Tensor, Tensor) {
+        let (lambda, permutation) = mix_parameters(self.alpha, inputs.data.shape()[0], rng);
+        let shuffled = inputs.take(&permutation);
+        let mixed = Tensor {
+            data: &inputs.data * lambda + &shuffled.data * (1.0 - lambda),
+            device: inputs.device.clone(),
+        };
+
+        (mixed, mix_targets(targets, &permutation, lambda))
+    }
+}
+
+/// Pastes a random rectangle from another image of the batch into every image, and blends their
+/// targets by the pasted area.
+///
+/// The targets must be one-hot encoded. Single images are returned unchanged.
+#[derive(Debug, Clone, Copy)]
+pub struct CutMix {
+    alpha: f32,
+}
+
+impl CutMix {
+    /// Creates a new `CutMix`.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The parameter of the `Beta(alpha, alpha)` distribution of the kept area.
+    pub fn new(alpha: f32) -> Self {
+        if alpha <= 0.0 {
+            panic!("Alpha must be greater than 0.");
+        }
+        Self { alpha }
+    }
+}
+
+impl Transform for CutMix {
+    fn apply(&self, image: &Tensor, _rng: &mut StdRng) -> Tensor {
+        image.clone()
+    }
+
+    fn apply_batch(&self, inputs: &Tensor, targets: &Tensor, rng: &mut StdRng) -> (Tensor, Tensor) {
+        let shape = inputs.data.shape();
+        if shape.len() != 4 {
+            panic!("CutMix expects batches of shape [batch, height, width, channels].");
+        }
+        let (height, width) = (shape[1], shape[2]);
+        let (lambda, permutation) = mix_parameters(self.alpha, shape[0], rng);
+
+        let cut = (1.0 - lambda).sqrt();
+        let (cut_height, cut_width) =
+            ((height as f32 * cut) as usize, (width as f32 * cut) as usize);
+        let center_y = rng.gen_range(0..height);
+        let center_x = rng.gen_range(0..width);
+        let top = center_y.saturating_sub(cut_height / 2);
+        let bottom = (center_y + cut_height / 2).min(height);
+        let left = center_x.saturating_sub(cut_width / 2);
+        let right = (center_x + cut_width / 2).min(width);
+
+        let shuffled = inputs.take(&permutation);
+        let mut mixed = inputs.clone();
+        mixed
+            .data
+            .slice_each_axis_mut(|axis| match axis.axis.index() {
+                1 => (top..bottom).into(),
+                2 => (left..right).into(),
+                _ => (..).into(),
+            })
+            .assign(&shuffled.data.slice_each_axis(|axis| match axis.axis.index() {
+                1 => (top..bottom).into(),
+                2 => (left..right).into(),
+                _ => (..).into(),
+            }));
+
+        // Use the area that was actually pasted, which shrinks when the box is clipped.
+        let kept = 1.0 - ((bottom - top) * (right - left)) as f32 / (height * width) as f32;
+        (mixed, mix_targets(targets, &permutation, kept))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{IxDyn, Shape};
+
+    use super::*;
+
+    fn gradient_image(height: usize, width: usize, channels: usize) -> Tensor {
+        let values = (0..height * width * channels).map(|x| x as f32 / 100.0).collect();
+        Tensor::new(values, Shape::from(IxDyn(&[height, width, channels])))
+    }
+
+    fn one_hot_batch(labels: &[usize], classes: usize) -> Tensor {
+        let mut values = vec![0.0; labels.len() * classes];
+        for (i, &label) in labels.iter().enumerate() {
+            values[i * classes + label] = 1.0;
+        }
+        Tensor::new(values, Shape::from(IxDyn(&[labels.len(), classes])))
+    }
+
+    #[test]
+    fn test_flips() {
+        let image = gradient_image(2, 3, 1);
+        let mut rng = rng_from_seed(0);
+
+        let horizontal = RandomHorizontalFlip::new(1.0).apply(&image, &mut rng);
+        assert_eq!(horizontal.to_vec(), vec![0.02, 0.01, 0.0, 0.05, 0.04, 0.03]);
+
+        let vertical = RandomVerticalFlip::new(1.0).apply(&image, &mut rng);
+        assert_eq!(vertical.to_vec(), vec![0.03, 0.04, 0.05, 0.0, 0.01, 0.02]);
+
+        assert_eq!(RandomHorizontalFlip::new(0.0).apply(&image, &mut rng).to_vec(), image.to_vec());
+    }
+
+    #[test]
+    fn test_random_crop_with_padding() {
+        let image = gradient_image(4, 4, 2);
+        let crop = RandomCrop::new(4, 4, 2);
+
+        let first = crop.apply(&image, &mut rng_from_seed(7));
+        let second = crop.apply(&image, &mut rng_from_seed(7));
+
+        assert_eq!(first.data.shape(), &[4, 4, 2]);
+        assert_eq!(first.to_vec(), second.to_vec());
+        assert_eq!(
+            RandomCrop::new(2, 3, 0).apply(&image, &mut rng_from_seed(0)).data.shape(),
+            &[2, 3, 2]
+        );
+    }
+
+    #[test]
+    fn test_rotation_and_resize() {
+        let image = gradient_image(3, 3, 1);
+        let mut rng = rng_from_seed(0);
+        assert_eq!(RandomRotation::new(0.0).apply(&image, &mut rng).to_vec(), image.to_vec());
+
+        let constant = Tensor::new(vec![0.5; 2 * 2 * 3], Shape::from(IxDyn(&[2, 2, 3])));
+        let resized = Resize::new(5, 3).apply(&constant, &mut rng);
+        assert_eq!(resized.data.shape(), &[5, 3, 3]);
+        assert!(resized.to_vec().iter().all(|&x| (x - 0.5).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_color_jitter_keeps_values_in_range() {
+        let image = gradient_image(4, 4, 3).map(|x| x.min(1.0));
+        let jittered = ColorJitter::new(0.5, 0.5, 0.5).apply(&image, &mut rng_from_seed(3));
+
+        assert_eq!(jittered.data.shape(), &[4, 4, 3]);
+        assert!(jittered.to_vec().iter().all(|&x| (0.0..=1.0).contains(&x)));
+        let unchanged = ColorJitter::new(0.0, 0.0, 0.0).apply(&image, &mut rng_from_seed(3));
+        for (x, y) in unchanged.to_vec().iter().zip(image.to_vec()) {
+            assert!((x - y).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_random_erasing_fills_rectangle() {
+        let image = Tensor::new(vec![1.0; 10 * 10], Shape::from(IxDyn(&[10, 10, 1])));
+        let erased = RandomErasing::new(1.0)
+            .with_scale(0.25, 0.25)
+            .with_ratio(1.0, 1.0)
+            .with_value(-1.0)
+            .apply(&image, &mut rng_from_seed(1));
+
+        assert_eq!(erased.to_vec().iter().filter(|&&x| x == -1.0).count(), 25);
+    }
+
+    #[test]
+    fn test_normalize_per_channel() {
+        let image = Tensor::new(vec![0.5, 1.0, 0.0, 0.2], Shape::from(IxDyn(&[1, 2, 2])));
+        let normalize = Normalize::new(vec![0.5, 0.0], vec![0.5, 0.2]);
+
+        let normalized = normalize.apply(&image, &mut rng_from_seed(0));
+        assert_eq!(normalized.to_vec(), vec![0.0, 5.0, -1.0, 1.0]);
+
+        let batch = Tensor::stack(&[image.clone(), image]).unwrap();
+        let targets = one_hot_batch(&[0, 1], 2);
+        let (normalized_batch, _) = normalize.apply_batch(&batch, &targets, &mut rng_from_seed(0));
+        assert_eq!(normalized_batch.to_vec(), [0.0, 5.0, -1.0, 1.0].repeat(2));
+    }
+
+    #[test]
+    fn test_mixup_and_cutmix_blend_targets() {
+        let inputs = Tensor::stack(&[gradient_image(4, 4, 3), gradient_image(4, 4, 3)]).unwrap();
+        let targets = one_hot_batch(&[0, 2], 3);
+
+        for transform in [&MixUp::new(0.4) as &dyn Transform, &CutMix::new(1.0)] {
+            let (mixed, mixed_targets) =
+                transform.apply_batch(&inputs, &targets, &mut rng_from_seed(5));
+            assert_eq!(mixed.data.shape(), inputs.data.shape());
+            for row in mixed_targets.data.outer_iter() {
+                assert!((row.sum() - 1.0).abs() < 1e-5);
+                assert_eq!(row[1], 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compose_is_reproducible_with_seed() {
+        let pipeline = || {
+            Compose::new()
+                .with(RandomCrop::new(4, 4, 1))
+                .with(RandomHorizontalFlip::new(0.5))
+                .with(RandomErasing::new(0.5))
+                .with_seed(42)
+        };
+        let inputs = Tensor::stack(&[gradient_image(4, 4, 1), gradient_image(4, 4, 1)]).unwrap();
+        let targets = one_hot_batch(&[0, 1], 2);
+
+        let (first, _) = pipeline().apply_to_batch(&inputs, &targets);
+        let (second, _) = pipeline().apply_to_batch(&inputs, &targets);
+
+        assert_eq!(pipeline().len(), 3);
+        assert_eq!(first.data.shape(), &[2, 4, 4, 1]);
+        assert_eq!(first.to_vec(), second.to_vec());
+    }
+}
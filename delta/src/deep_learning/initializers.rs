@@ -0,0 +1,727 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt::Debug;
+
+use ndarray::{Array2, Dimension, IxDyn, Shape};
+use rand::RngCore;
+use rand_distr::{Distribution, Normal, Uniform};
+
+use super::tensor_ops::Tensor;
+
+/// A trait representing a weight initializer.
+pub trait Initializer: Debug {
+    /// Creates a tensor of the given shape filled with initial values.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - The shape of the tensor to initialize.
+    /// * `rng` - The random number generator to sample from.
+    ///
+    /// # Returns
+    ///
+    /// A new tensor with the initial values.
+    fn initialize(&self, shape: Shape<IxDyn>, rng: &mut dyn RngCore) -> Tensor;
+
+    /// Returns the name of the initializer.
+    ///
+    /// # Returns
+    ///
+    /// A string slice containing the name of the initializer.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>().split("::").last().unwrap_or("Unknown")
+    }
+
+    /// Returns the initializer's configuration as a serializable format.
+    ///
+    /// # Returns
+    ///
+    /// A `serde_json::Value` containing the initializer's configuration.
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+}
+
+/// Computes the fan-in and fan-out of a weight tensor.
+///
+/// For a 2D tensor of shape `[input_units, output_units]` these are the number of input and
+/// output units. For higher-rank kernels the leading dimensions are treated as the receptive
+/// field, following the `[..., input_channels, output_channels]` layout.
+///
+/// # Arguments
+///
+/// * `shape` - The shape of the weight tensor.
+///
+/// # Returns
+///
+/// A tuple `(fan_in, fan_out)`.
+pub fn compute_fans(shape: &Shape<IxDyn>) -> (f32, f32) {
+    let dims = shape.raw_dim().as_array_view().to_vec();
+    match dims.len() {
+        0 => (1.0, 1.0),
+        1 => (dims[0] as f32, dims[0] as f32),
+        n => {
+            let receptive_field: usize = dims[..n - 2].iter().product();
+            ((dims[n - 2] * receptive_field) as f32, (dims[n - 1] * receptive_field) as f32)
+        }
+    }
+}
+
+/// Divides a variance scale by a fan.
+///
+/// A fan of zero only occurs for a shape with a zero dimension, so the tensor has no elements
+/// and a variance of zero is returned instead of dividing by zero.
+///
+/// # Arguments
+///
+/// * `scale` - The variance scale.
+/// * `fan` - The fan-in, fan-out or their sum.
+///
+/// # Returns
+///
+/// The variance `scale / fan`, or zero if `fan` is zero.
+fn scaled_variance(scale: f32, fan: f32) -> f32 {
+    if fan > 0.0 { scale / fan } else { 0.0 }
+}
+
+/// Samples a tensor from a distribution.
+fn sample<D: Distribution<f32>>(
+    shape: Shape<IxDyn>,
+    distribution: D,
+    rng: &mut dyn RngCore,
+) -> Tensor {
+    let data: Vec<f32> = (0..shape.size()).map(|_| distribution.sample(rng)).collect();
+    Tensor::new(data, shape)
+}
+
+/// Samples a tensor from a uniform distribution over `[-limit, limit)`.
+fn sample_uniform(shape: Shape<IxDyn>, limit: f32, rng: &mut dyn RngCore) -> Tensor {
+    if limit <= 0.0 {
+        return Tensor::new(vec![0.0; shape.size()], shape);
+    }
+    sample(shape, Uniform::new(-limit, limit), rng)
+}
+
+/// Samples a tensor from a normal distribution.
+fn sample_normal(shape: Shape<IxDyn>, mean: f32, stddev: f32, rng: &mut dyn RngCore) -> Tensor {
+    let normal = Normal::new(mean, stddev).expect("Failed to create normal distribution");
+    sample(shape, normal, rng)
+}
+
+/// An initializer that fills tensors with a constant value.
+#[derive(Debug)]
+pub struct Constant {
+    value: f32,
+}
+
+impl Constant {
+    /// Creates a new `Constant` initializer.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to fill tensors with.
+    pub fn new(value: f32) -> Self {
+        Self { value }
+    }
+}
+
+impl Initializer for Constant {
+    fn initialize(&self, shape: Shape<IxDyn>, _rng: &mut dyn RngCore) -> Tensor {
+        Tensor::new(vec![self.value; shape.size()], shape)
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({ "value": self.value })
+    }
+}
+
+/// An initializer that fills tensors with zeros.
+#[derive(Debug)]
+pub struct Zeros;
+
+impl Default for Zeros {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Zeros {
+    /// Creates a new `Zeros` initializer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Initializer for Zeros {
+    fn initialize(&self, shape: Shape<IxDyn>, _rng: &mut dyn RngCore) -> Tensor {
+        Tensor::new(vec![0.0; shape.size()], shape)
+    }
+}
+
+/// An initializer that fills tensors with ones.
+#[derive(Debug)]
+pub struct Ones;
+
+impl Default for Ones {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ones {
+    /// Creates a new `Ones` initializer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Initializer for Ones {
+    fn initialize(&self, shape: Shape<IxDyn>, _rng: &mut dyn RngCore) -> Tensor {
+        Tensor::new(vec![1.0; shape.size()], shape)
+    }
+}
+
+/// An initializer that samples from a normal distribution.
+#[derive(Debug)]
+pub struct RandomNormal {
+    mean: f32,
+    stddev: f32,
+}
+
+impl RandomNormal {
+    /// Creates a new `RandomNormal` initializer.
+    ///
+    /// # Arguments
+    ///
+    /// * `mean` - The mean of the normal distribution.
+    /// * `stddev` - The standard deviation of the normal distribution.
+    pub fn new(mean: f32, stddev: f32) -> Self {
+        Self { mean, stddev }
+    }
+}
+
+impl Initializer for RandomNormal {
+    fn initialize(&self, shape: Shape<IxDyn>, rng: &mut dyn RngCore) -> Tensor {
+        sample_normal(shape, self.mean, self.stddev, rng)
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({ "mean": self.mean, "stddev": self.stddev })
+    }
+}
+
+/// An initializer that samples from a uniform distribution.
+#[derive(Debug)]
+pub struct RandomUniform {
+    min: f32,
+    max: f32,
+}
+
+impl RandomUniform {
+    /// Creates a new `RandomUniform` initializer.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The lower bound of the distribution.
+    /// * `max` - The upper bound of the distribution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is not smaller than `max`.
+    pub fn new(min: f32, max: f32) -> Self {
+        if min >= max {
+            panic!("RandomUniform requires min < max, got min = {}, max = {}", min, max);
+        }
+        Self { min, max }
+    }
+}
+
+impl Initializer for RandomUniform {
+    fn initialize(&self, shape: Shape<IxDyn>, rng: &mut dyn RngCore) -> Tensor {
+        sample(shape, Uniform::new(self.min, self.max), rng)
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({ "min": self.min, "max": self.max })
+    }
+}
+
+/// An initializer that samples from a normal distribution truncated to two standard deviations.
+///
+/// Values further than two standard deviations from the mean are redrawn.
+#[derive(Debug)]
+pub struct TruncatedNormal {
+    mean: f32,
+    stddev: f32,
+}
+
+impl TruncatedNormal {
+    /// Creates a new `TruncatedNormal` initializer.
+    ///
+    /// # Arguments
+    ///
+    /// * `mean` - The mean of the normal distribution.
+    /// * `stddev` - The standard deviation of the normal distribution.
+    pub fn new(mean: f32, stddev: f32) -> Self {
+        Self { mean, stddev }
+    }
+}
+
+impl Initializer for TruncatedNormal {
+    fn initialize(&self, shape: Shape<IxDyn>, rng: &mut dyn RngCore) -> Tensor {
+        let normal =
+            Normal::new(self.mean, self.stddev).expect("Failed to create normal distribution");
+        let bound = 2.0 * self.stddev;
+        let data: Vec<f32> = (0..shape.size())
+            .map(|_| {
+                loop {
+                    let value = normal.sample(rng);
+                    if (value - self.mean).abs() <= bound {
+                        break value;
+                    }
+                }
+            })
+            .collect();
+        Tensor::new(data, shape)
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({ "mean": self.mean, "stddev": self.stddev })
+    }
+}
+
+/// The Glorot (Xavier) uniform initializer.
+///
+/// Samples from `U(-limit, limit)` with `limit = sqrt(6 / (fan_in + fan_out))`.
+#[derive(Debug)]
+pub struct GlorotUniform;
+
+/// An alias for the Glorot uniform initializer.
+pub type XavierUniform = GlorotUniform;
+
+impl Default for GlorotUniform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GlorotUniform {
+    /// Creates a new `GlorotUniform` initializer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Initializer for GlorotUniform {
+    fn initialize(&self, shape: Shape<IxDyn>, rng: &mut dyn RngCore) -> Tensor {
+        let (fan_in, fan_out) = compute_fans(&shape);
+        sample_uniform(shape, scaled_variance(6.0, fan_in + fan_out).sqrt(), rng)
+    }
+}
+
+/// The Glorot (Xavier) normal initializer.
+///
+/// Samples from `N(0, stddev)` with `stddev = sqrt(2 / (fan_in + fan_out))`.
+#[derive(Debug)]
+pub struct GlorotNormal;
+
+/// An alias for the Glorot normal initializer.
+pub type XavierNormal = GlorotNormal;
+
+impl Default for GlorotNormal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GlorotNormal {
+    /// Creates a new `GlorotNormal` initializer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Initializer for GlorotNormal {
+    fn initialize(&self, shape: Shape<IxDyn>, rng: &mut dyn RngCore) -> Tensor {
+        let (fan_in, fan_out) = compute_fans(&shape);
+        sample_normal(shape, 0.0, scaled_variance(2.0, fan_in + fan_out).sqrt(), rng)
+    }
+}
+
+/// The He (Kaiming) uniform initializer.
+///
+/// Samples from `U(-limit, limit)` with `limit = sqrt(6 / fan_in)`.
+#[derive(Debug)]
+pub struct HeUniform;
+
+/// An alias for the He uniform initializer.
+pub type KaimingUniform = HeUniform;
+
+impl Default for HeUniform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeUniform {
+    /// Creates a new `HeUniform` initializer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Initializer for HeUniform {
+    fn initialize(&self, shape: Shape<IxDyn>, rng: &mut dyn RngCore) -> Tensor {
+        let (fan_in, _) = compute_fans(&shape);
+        sample_uniform(shape, scaled_variance(6.0, fan_in).sqrt(), rng)
+    }
+}
+
+/// The He (Kaiming) normal initializer.
+///
+/// Samples from `N(0, stddev)` with `stddev = sqrt(2 / fan_in)`.
+#[derive(Debug)]
+pub struct HeNormal;
+
+/// An alias for the He normal initializer.
+pub type KaimingNormal = HeNormal;
+
+impl Default for HeNormal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeNormal {
+    /// Creates a new `HeNormal` initializer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Initializer for HeNormal {
+    fn initialize(&self, shape: Shape<IxDyn>, rng: &mut dyn RngCore) -> Tensor {
+        let (fan_in, _) = compute_fans(&shape);
+        sample_normal(shape, 0.0, scaled_variance(2.0, fan_in).sqrt(), rng)
+    }
+}
+
+/// The LeCun uniform initializer.
+///
+/// Samples from `U(-limit, limit)` with `limit = sqrt(3 / fan_in)`.
+#[derive(Debug)]
+pub struct LecunUniform;
+
+impl Default for LecunUniform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LecunUniform {
+    /// Creates a new `LecunUniform` initializer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Initializer for LecunUniform {
+    fn initialize(&self, shape: Shape<IxDyn>, rng: &mut dyn RngCore) -> Tensor {
+        let (fan_in, _) = compute_fans(&shape);
+        sample_uniform(shape, scaled_variance(3.0, fan_in).sqrt(), rng)
+    }
+}
+
+/// The LeCun normal initializer.
+///
+/// Samples from `N(0, stddev)` with `stddev = sqrt(1 / fan_in)`.
+#[derive(Debug)]
+pub struct LecunNormal;
+
+impl Default for LecunNormal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LecunNormal {
+    /// Creates a new `LecunNormal` initializer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Initializer for LecunNormal {
+    fn initialize(&self, shape: Shape<IxDyn>, rng: &mut dyn RngCore) -> Tensor {
+        let (fan_in, _) = compute_fans(&shape);
+        sample_normal(shape, 0.0, scaled_variance(1.0, fan_in).sqrt(), rng)
+    }
+}
+
+/// An initializer that produces a (semi-)orthogonal matrix.
+///
+/// The tensor is viewed as a matrix of shape `[product of leading dims, last dim]`. A random
+/// normal matrix is orthonormalized with the Gram-Schmidt process and scaled by `gain`.
+#[derive(Debug)]
+pub struct Orthogonal {
+    gain: f32,
+}
+
+impl Default for Orthogonal {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl Orthogonal {
+    /// Creates a new `Orthogonal` initializer.
+    ///
+    /// # Arguments
+    ///
+    /// * `gain` - The multiplicative factor applied to the orthogonal matrix.
+    pub fn new(gain: f32) -> Self {
+        Self { gain }
+    }
+}
+
+impl Initializer for Orthogonal {
+    fn initialize(&self, shape: Shape<IxDyn>, rng: &mut dyn RngCore) -> Tensor {
+        let dims = shape.raw_dim().as_array_view().to_vec();
+        if dims.len() < 2 {
+            panic!("Orthogonal initializer requires at least 2 dimensions, got {:?}", dims);
+        }
+
+        let cols = dims[dims.len() - 1];
+        let rows = shape.size() / cols.max(1);
+        if rows == 0 || cols == 0 {
+            return Tensor::new(vec![], shape);
+        }
+
+        // Orthonormalize the columns of a tall random matrix, then transpose if needed
+        let (tall_rows, tall_cols) = (rows.max(cols), rows.min(cols));
+        let normal = Normal::new(0.0, 1.0).expect("Failed to create normal distribution");
+        let mut matrix =
+            Array2::from_shape_simple_fn((tall_rows, tall_cols), || normal.sample(rng));
+
+        for j in 0..tall_cols {
+            for k in 0..j {
+                let projection: f32 = matrix.column(j).dot(&matrix.column(k));
+                let basis = matrix.column(k).to_owned();
+                matrix.column_mut(j).scaled_add(-projection, &basis);
+            }
+            let squared_norm: f32 = matrix.column(j).dot(&matrix.column(j));
+            let norm = squared_norm.sqrt().max(f32::EPSILON);
+            matrix.column_mut(j).mapv_inplace(|x| x / norm);
+        }
+
+        let matrix = if rows < cols { matrix.reversed_axes() } else { matrix };
+        let data: Vec<f32> = matrix.iter().map(|&x| x * self.gain).collect();
+        Tensor::new(data, shape)
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({ "gain": self.gain })
+    }
+}
+
+/// An initializer that produces an identity matrix scaled by `gain`.
+///
+/// Only 2D tensors are supported. Non-square shapes get ones on the main diagonal.
+#[derive(Debug)]
+pub struct Identity {
+    gain: f32,
+}
+
+impl Default for Identity {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl Identity {
+    /// Creates a new `Identity` initializer.
+    ///
+    /// # Arguments
+    ///
+    /// * `gain` - The value placed on the diagonal.
+    pub fn new(gain: f32) -> Self {
+        Self { gain }
+    }
+}
+
+impl Initializer for Identity {
+    fn initialize(&self, shape: Shape<IxDyn>, _rng: &mut dyn RngCore) -> Tensor {
+        let dims = shape.raw_dim().as_array_view().to_vec();
+        if dims.len() != 2 {
+            panic!("Identity initializer requires a 2D shape, got {:?}", dims);
+        }
+
+        let mut data = vec![0.0; shape.size()];
+        for i in 0..dims[0].min(dims[1]) {
+            data[i * dims[1] + i] = self.gain;
+        }
+        Tensor::new(data, shape)
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({ "gain": self.gain })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deep_learning::rng::rng_from_seed;
+
+    fn shape(dims: &[usize]) -> Shape<IxDyn> {
+        Shape::from(IxDyn(dims))
+    }
+
+    #[test]
+    fn test_compute_fans() {
+        assert_eq!(compute_fans(&shape(&[4, 6])), (4.0, 6.0));
+        assert_eq!(compute_fans(&shape(&[5])), (5.0, 5.0));
+        assert_eq!(compute_fans(&shape(&[3, 3, 2, 8])), (18.0, 72.0));
+    }
+
+    #[test]
+    fn test_constant_zeros_ones() {
+        let mut rng = rng_from_seed(0);
+        assert!(
+            Constant::new(0.5).initialize(shape(&[2, 3]), &mut rng).data.iter().all(|&x| x == 0.5)
+        );
+        assert!(Zeros::new().initialize(shape(&[2, 3]), &mut rng).data.iter().all(|&x| x == 0.0));
+        assert!(Ones::new().initialize(shape(&[2, 3]), &mut rng).data.iter().all(|&x| x == 1.0));
+    }
+
+    #[test]
+    fn test_uniform_initializers_respect_limits() {
+        let mut rng = rng_from_seed(0);
+        let (fan_in, fan_out) = (64.0_f32, 32.0_f32);
+        let cases: Vec<(Box<dyn Initializer>, f32)> = vec![
+            (Box::new(GlorotUniform::new()), (6.0 / (fan_in + fan_out)).sqrt()),
+            (Box::new(HeUniform::new()), (6.0 / fan_in).sqrt()),
+            (Box::new(LecunUniform::new()), (3.0 / fan_in).sqrt()),
+        ];
+
+        for (initializer, limit) in cases {
+            let tensor = initializer.initialize(shape(&[64, 32]), &mut rng);
+            assert_eq!(tensor.data.shape(), &[64, 32]);
+            assert!(
+                tensor.data.iter().all(|&x| x.abs() <= limit),
+                "{} exceeded limit",
+                initializer.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_normal_initializers_have_expected_stddev() {
+        let mut rng = rng_from_seed(0);
+        let (fan_in, fan_out) = (400.0_f32, 200.0_f32);
+        let cases: Vec<(Box<dyn Initializer>, f32)> = vec![
+            (Box::new(GlorotNormal::new()), (2.0 / (fan_in + fan_out)).sqrt()),
+            (Box::new(HeNormal::new()), (2.0 / fan_in).sqrt()),
+            (Box::new(LecunNormal::new()), (1.0 / fan_in).sqrt()),
+        ];
+
+        for (initializer, expected) in cases {
+            let tensor = initializer.initialize(shape(&[400, 200]), &mut rng);
+            let mean = tensor.mean();
+            let stddev = (tensor.data.iter().map(|&x| (x - mean).powi(2)).sum::<f32>()
+                / tensor.data.len() as f32)
+                .sqrt();
+            assert!(
+                (stddev - expected).abs() / expected < 0.05,
+                "{} stddev {}",
+                initializer.name(),
+                stddev
+            );
+        }
+    }
+
+    #[test]
+    fn test_fan_initializers_accept_zero_fans() {
+        let initializers: Vec<Box<dyn Initializer>> = vec![
+            Box::new(GlorotUniform::new()),
+            Box::new(GlorotNormal::new()),
+            Box::new(HeUniform::new()),
+            Box::new(HeNormal::new()),
+            Box::new(LecunUniform::new()),
+            Box::new(LecunNormal::new()),
+        ];
+
+        for initializer in initializers {
+            for dims in [[0, 4], [4, 0], [0, 0]] {
+                let tensor = initializer.initialize(shape(&dims), &mut rng_from_seed(0));
+                assert_eq!(tensor.data.shape(), &dims, "{}", initializer.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_truncated_normal_is_bounded() {
+        let tensor =
+            TruncatedNormal::new(1.0, 0.5).initialize(shape(&[100, 10]), &mut rng_from_seed(0));
+        assert!(tensor.data.iter().all(|&x| (x - 1.0).abs() <= 1.0));
+    }
+
+    #[test]
+    fn test_orthogonal_columns_are_orthonormal() {
+        for dims in [[8, 4], [4, 8]] {
+            let tensor = Orthogonal::new(1.0).initialize(shape(&dims), &mut rng_from_seed(0));
+            let matrix = tensor.data.into_dimensionality::<ndarray::Ix2>().unwrap();
+            let gram =
+                if dims[0] >= dims[1] { matrix.t().dot(&matrix) } else { matrix.dot(&matrix.t()) };
+            for ((i, j), &value) in gram.indexed_iter() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 1e-4, "gram[{}, {}] = {}", i, j, value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_identity() {
+        let tensor = Identity::new(2.0).initialize(shape(&[2, 3]), &mut rng_from_seed(0));
+        assert_eq!(tensor.to_vec(), vec![2.0, 0.0, 0.0, 0.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_initializers_are_reproducible() {
+        let a = HeNormal::new().initialize(shape(&[3, 3]), &mut rng_from_seed(42));
+        let b = HeNormal::new().initialize(shape(&[3, 3]), &mut rng_from_seed(42));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_initializer_config() {
+        assert_eq!(Constant::new(0.5).get_config(), serde_json::json!({ "value": 0.5 }));
+        assert_eq!(GlorotUniform::new().name(), "GlorotUniform");
+    }
+}
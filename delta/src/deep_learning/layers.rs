@@ -28,7 +28,8 @@
 // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use log::debug;
-use ndarray::{Dimension, IxDyn, Shape};
+use ndarray::{Array2, Axis, Dimension, Ix2, Ix3, IxDyn, Shape};
+use rand::rngs::StdRng;
 use serde_json;
 
 use crate::devices::Device;
@@ -36,7 +37,13 @@
 use std::fmt::Debug;
 
 use super::{
-    activations::Activation, errors::LayerError, optimizers::Optimizer, tensor_ops::Tensor,
+    activations::Activation,
+    errors::LayerError,
+    initializers::{Initializer, RandomNormal, Zeros},
+    optimizers::Optimizer,
+    regularizers::Regularizer,
+    rng::{rng_from_seed, with_global_rng},
+    tensor_ops::Tensor,
 };
 
 // A trait representing a neural network layer.
@@ -98,6 +105,16 @@ pub trait Layer: Debug {
     /// * `device` - The device to set for the layer.
     fn set_device(&mut self, _device: &Device);
 
+    /// Seeds the random number generator used by the layer.
+    ///
+    /// Layers that use randomness, such as weight initialization, should draw from a generator
+    /// created from this seed instead of the global generator. The default does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed for the layer's random number generator.
+    fn set_seed(&mut self, _seed: u64) {}
+
     /// Returns the number of units in the layer.
     ///
     /// # Returns
@@ -107,6 +124,28 @@ fn units(&self) -> usize {
         0
     }
 
+    /// Returns the name of the activation whose derivative the backward pass applies on its own.
+    ///
+    /// # Returns
+    ///
+    /// The activation's name for layers that only apply an activation, or `None`. Default is
+    /// `None`.
+    fn activation_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the regularization penalty of the layer.
+    ///
+    /// The penalty covers the layer's weights and the activations from its last forward pass,
+    /// and is added to the loss during training.
+    ///
+    /// # Returns
+    ///
+    /// A `f32` representing the regularization penalty. Default is 0.
+    fn regularization_loss(&self) -> f32 {
+        0.0
+    }
+
     /// Updates the weights of the layer.
     ///
     /// # Arguments
@@ -142,6 +181,17 @@ fn type_name(&self) -> String {
     }
 }
 
+/// Serializes an optional regularizer as its name and configuration.
+fn regularizer_config(regularizer: &Option<Box<dyn Regularizer>>) -> serde_json::Value {
+    match regularizer {
+        Some(regularizer) => serde_json::json!({
+            "name": regularizer.name(),
+            "config": regularizer.get_config()
+        }),
+        None => serde_json::Value::Null,
+    }
+}
+
 /// A struct representing the output of a layer.
 #[derive(Debug)]
 pub struct LayerOutput {
@@ -159,11 +209,18 @@ pub struct Dense {
     bias: Option<Tensor>,
     units: usize,
     activation: Option<Box<dyn Activation>>,
+    kernel_initializer: Option<Box<dyn Initializer>>,
+    bias_initializer: Box<dyn Initializer>,
+    kernel_regularizer: Option<Box<dyn Regularizer>>,
+    bias_regularizer: Option<Box<dyn Regularizer>>,
+    activity_regularizer: Option<Box<dyn Regularizer>>,
     trainable: bool,
     weights_grad: Option<Tensor>,
     bias_grad: Option<Tensor>,
     input: Option<Tensor>,
+    output: Option<Tensor>,
     device: Device,
+    rng: Option<StdRng>,
 }
 
 impl Dense {
@@ -185,11 +242,112 @@ pub fn new<A: Activation + 'static>(
             bias: None,
             units,
             activation: activation.map(|a| Box::new(a) as Box<dyn Activation>),
+            kernel_initializer: None,
+            bias_initializer: Box::new(Zeros::new()),
+            kernel_regularizer: None,
+            bias_regularizer: None,
+            activity_regularizer: None,
             trainable,
             weights_grad: None,
             bias_grad: None,
             input: None,
+            output: None,
             device: Device::default(),
+            rng: None,
+        }
+    }
+
+    /// Sets the initializer used for the layer's weights.
+    ///
+    /// When no kernel initializer is set, weights are sampled from a normal distribution whose
+    /// standard deviation is chosen by the activation function.
+    ///
+    /// # Arguments
+    ///
+    /// * `initializer` - The initializer to use for the weights.
+    ///
+    /// # Returns
+    ///
+    /// The layer with the kernel initializer set.
+    pub fn with_kernel_initializer<I: Initializer + 'static>(mut self, initializer: I) -> Self {
+        self.kernel_initializer = Some(Box::new(initializer));
+        self
+    }
+
+    /// Sets the initializer used for the layer's bias. Defaults to zeros.
+    ///
+    /// # Arguments
+    ///
+    /// * `initializer` - The initializer to use for the bias.
+    ///
+    /// # Returns
+    ///
+    /// The layer with the bias initializer set.
+    pub fn with_bias_initializer<I: Initializer + 'static>(mut self, initializer: I) -> Self {
+        self.bias_initializer = Box::new(initializer);
+        self
+    }
+
+    /// Sets the regularizer applied to the layer's weights.
+    ///
+    /// # Arguments
+    ///
+    /// * `regularizer` - The regularizer to apply to the weights.
+    ///
+    /// # Returns
+    ///
+    /// The layer with the kernel regularizer set.
+    pub fn with_kernel_regularizer<R: Regularizer + 'static>(mut self, regularizer: R) -> Self {
+        self.kernel_regularizer = Some(Box::new(regularizer));
+        self
+    }
+
+    /// Sets the regularizer applied to the layer's bias.
+    ///
+    /// # Arguments
+    ///
+    /// * `regularizer` - The regularizer to apply to the bias.
+    ///
+    /// # Returns
+    ///
+    /// The layer with the bias regularizer set.
+    pub fn with_bias_regularizer<R: Regularizer + 'static>(mut self, regularizer: R) -> Self {
+        self.bias_regularizer = Some(Box::new(regularizer));
+        self
+    }
+
+    /// Sets the regularizer applied to the layer's output activations.
+    ///
+    /// The activity penalty is averaged over the batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `regularizer` - The regularizer to apply to the output.
+    ///
+    /// # Returns
+    ///
+    /// The layer with the activity regularizer set.
+    pub fn with_activity_regularizer<R: Regularizer + 'static>(mut self, regularizer: R) -> Self {
+        self.activity_regularizer = Some(Box::new(regularizer));
+        self
+    }
+
+    /// Returns the kernel initializer used when none is set: a normal distribution whose
+    /// standard deviation is chosen by the activation function from the number of input units.
+    fn default_kernel_initializer(&self, input_units: usize) -> RandomNormal {
+        let stddev = if let Some(ref activation) = self.activation {
+            activation.initialize(input_units)
+        } else {
+            (1.0 / input_units as f32).sqrt() // Xavier initialization for no activation
+        };
+        RandomNormal::new(0.0, stddev)
+    }
+
+    /// Initializes a tensor with the layer's generator, falling back to the global one.
+    fn initialize(&mut self, initializer: &dyn Initializer, shape: Shape<IxDyn>) -> Tensor {
+        match self.rng.as_mut() {
+            Some(rng) => initializer.initialize(shape, rng),
+            None => with_global_rng(|rng| initializer.initialize(shape, rng)),
         }
     }
 }
@@ -210,22 +368,26 @@ fn build(&mut self, input_shape: Shape<IxDyn>) -> Result<(), LayerError> {
         let array_view = raw_dim.as_array_view();
         let input_units = array_view.last().ok_or(LayerError::InvalidInputShape)?;
 
-        // Choose initialization strategy based on the activation function
-        let stddev = if let Some(ref activation) = self.activation {
-            activation.initialize(*input_units)
-        } else {
-            (1.0 / *input_units as f32).sqrt() // Xavier initialization for no activation
-        };
+        let kernel_initializer = self.kernel_initializer.take();
+        let bias_initializer = std::mem::replace(&mut self.bias_initializer, Box::new(Zeros));
 
-        // Initialize weights using random normal distribution
-        self.weights = Some(Tensor::random_normal(
-            Shape::from(IxDyn(&[*input_units, self.units])),
-            0.0,
-            stddev,
-        ));
+        let kernel_shape = Shape::from(IxDyn(&[*input_units, self.units]));
+        let mut weights = match kernel_initializer {
+            Some(ref initializer) => self.initialize(initializer.as_ref(), kernel_shape),
+            None => {
+                let initializer = self.default_kernel_initializer(*input_units);
+                self.initialize(&initializer, kernel_shape)
+            }
+        };
+        let mut bias =
+            self.initialize(bias_initializer.as_ref(), Shape::from(IxDyn(&[self.units])));
+        weights.device = self.device.clone();
+        bias.device = self.device.clone();
 
-        // Initialize bias to zeros
-        self.bias = Some(Tensor::zeros(Shape::from(IxDyn(&[self.units])), self.device.clone()));
+        self.weights = Some(weights);
+        self.bias = Some(bias);
+        self.kernel_initializer = kernel_initializer;
+        self.bias_initializer = bias_initializer;
 
         Ok(())
     }
@@ -252,6 +414,10 @@ fn forward(&mut self, input: &Tensor) -> Result<Tensor, LayerError> {
         let z =
             if let Some(ref activation) = self.activation { activation.activate(&z) } else { z };
 
+        if self.activity_regularizer.is_some() {
+            self.output = Some(z.clone());
+        }
+
         Ok(z)
     }
 
@@ -269,9 +435,28 @@ fn backward(&mut self, grad: &Tensor) -> Result<Tensor, LayerError> {
         let weights = self.weights.as_ref().expect("Weights must be initialized");
         let input = self.input.as_ref().expect("Input must be initialized");
 
+        // Add the gradient of the activity penalty to the incoming gradient
+        let grad = match (&self.activity_regularizer, &self.output) {
+            (Some(regularizer), Some(output)) => {
+                let batch_size = output.data.shape()[0].max(1) as f32;
+                grad.add(&regularizer.gradient(output).div_scalar(batch_size))
+            }
+            _ => grad.clone(),
+        };
+        let grad = &grad;
+
         // Calculate the gradient with respect to weights and bias
-        let weights_grad = input.transpose().dot(grad);
-        let bias_grad = grad.sum_along_axis(0);
+        let mut weights_grad = input.transpose().dot(grad);
+        let mut bias_grad = grad.sum_along_axis(0);
+
+        // Fold the weight penalties into the gradients
+        if let Some(ref regularizer) = self.kernel_regularizer {
+            weights_grad = weights_grad.add(&regularizer.gradient(weights));
+        }
+        if let Some(ref regularizer) = self.bias_regularizer {
+            let bias = self.bias.as_ref().expect("Bias must be initialized");
+            bias_grad = bias_grad.add(&regularizer.gradient(bias));
+        }
 
         // Store the gradients
         if self.trainable {
@@ -315,6 +500,37 @@ fn name(&self) -> &str {
         &self.name
     }
 
+    /// Returns the penalty of the kernel, bias and activity regularizers.
+    ///
+    /// # Returns
+    ///
+    /// A `f32` representing the regularization penalty.
+    fn regularization_loss(&self) -> f32 {
+        let mut penalty = 0.0;
+
+        if let (Some(regularizer), Some(weights)) = (&self.kernel_regularizer, &self.weights) {
+            penalty += regularizer.penalty(weights);
+        }
+        if let (Some(regularizer), Some(bias)) = (&self.bias_regularizer, &self.bias) {
+            penalty += regularizer.penalty(bias);
+        }
+        if let (Some(regularizer), Some(output)) = (&self.activity_regularizer, &self.output) {
+            let batch_size = output.data.shape()[0].max(1) as f32;
+            penalty += regularizer.penalty(output) / batch_size;
+        }
+
+        penalty
+    }
+
+    /// Seeds the random number generator used for weight initialization.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed for the layer's random number generator.
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = Some(rng_from_seed(seed));
+    }
+
     /// Sets the device for the layer.
     ///
     /// # Arguments
@@ -332,6 +548,9 @@ fn set_device(&mut self, device: &Device) {
         if let Some(ref mut input) = self.input {
             input.device = device.clone();
         }
+        if let Some(ref mut output) = self.output {
+            output.device = device.clone();
+        }
     }
 
     /// Updates the weights of the layer using the given gradient and optimizer.
@@ -376,7 +595,22 @@ fn get_config(&self) -> serde_json::Value {
         serde_json::json!({
             "units": self.units,
             "trainable": self.trainable,
-            "activation": self.activation.as_ref().map(|a| a.name())
+            "activation": self.activation.as_ref().map(|a| a.name()),
+            "kernel_initializer": match &self.kernel_initializer {
+                Some(initializer) => serde_json::json!({
+                    "name": initializer.name(),
+                    "config": initializer.get_config()
+                }),
+                // The default's standard deviation depends on the input units of the build
+                None => serde_json::json!({ "name": "Default", "config": {} }),
+            },
+            "bias_initializer": {
+                "name": self.bias_initializer.name(),
+                "config": self.bias_initializer.get_config()
+            },
+            "kernel_regularizer": regularizer_config(&self.kernel_regularizer),
+            "bias_regularizer": regularizer_config(&self.bias_regularizer),
+            "activity_regularizer": regularizer_config(&self.activity_regularizer)
         })
     }
 }
@@ -500,9 +734,357 @@ fn set_device(&mut self, _device: &crate::devices::Device) {
     }
 }
 
+/// A layer that applies an activation function to its input.
+///
+/// Placing the activation in its own layer lets the backward pass apply the activation's
+/// derivative, which `Dense` does not do. Activations whose derivative is a Jacobian, such as
+/// Softmax, are handled with a Jacobian-vector product.
+#[derive(Debug)]
+pub struct ActivationLayer {
+    name: String,
+    activation: Box<dyn Activation>,
+    input_shape: Option<Shape<IxDyn>>,
+    input: Option<Tensor>,
+}
+
+impl ActivationLayer {
+    /// Creates a new activation layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `activation` - The activation function to apply.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the activation layer.
+    pub fn new<A: Activation + 'static>(activation: A) -> Self {
+        Self {
+            name: activation.name().to_string(),
+            activation: Box::new(activation),
+            input_shape: None,
+            input: None,
+        }
+    }
+
+    /// Computes `grad · J` for each sample, where `J` is the `[batch, n, n]` Jacobian.
+    fn jacobian_vector_product(grad: &Tensor, jacobian: &Tensor) -> Result<Tensor, LayerError> {
+        let grad_2d = grad
+            .data
+            .view()
+            .into_dimensionality::<Ix2>()
+            .map_err(|_| LayerError::InvalidInputShape)?;
+        let jacobian_3d = jacobian
+            .data
+            .view()
+            .into_dimensionality::<Ix3>()
+            .map_err(|_| LayerError::InvalidInputShape)?;
+
+        let mut input_grad = Array2::<f32>::zeros(grad_2d.raw_dim());
+        for (b, mut row) in input_grad.rows_mut().into_iter().enumerate() {
+            row.assign(&grad_2d.row(b).dot(&jacobian_3d.index_axis(Axis(0), b)));
+        }
+
+        Ok(Tensor { data: input_grad.into_dyn(), device: grad.device.clone() })
+    }
+}
+
+impl Layer for ActivationLayer {
+    /// Builds the layer with the given input shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_shape` - The shape of the input tensor.
+    fn build(&mut self, input_shape: Shape<IxDyn>) -> Result<(), LayerError> {
+        self.input_shape = Some(input_shape);
+        Ok(())
+    }
+
+    /// Performs a forward pass through the layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor.
+    fn forward(&mut self, input: &Tensor) -> Result<Tensor, LayerError> {
+        self.input = Some(input.clone());
+        Ok(self.activation.activate(input))
+    }
+
+    /// Performs a backward pass through the layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `grad` - The gradient tensor.
+    ///
+    /// # Returns
+    ///
+    /// The gradient tensor with respect to the input.
+    fn backward(&mut self, grad: &Tensor) -> Result<Tensor, LayerError> {
+        let input = self.input.as_ref().ok_or(LayerError::UninitializedInput)?;
+        let derivative = self.activation.derivative(input);
+
+        if derivative.data.ndim() == input.data.ndim() + 1 {
+            Self::jacobian_vector_product(grad, &derivative)
+        } else {
+            Ok(grad.multiply(&derivative))
+        }
+    }
+
+    /// Returns the output shape of the layer.
+    ///
+    /// # Returns
+    ///
+    /// A `Shape` representing the output shape of the layer.
+    fn output_shape(&self) -> Result<Shape<IxDyn>, LayerError> {
+        self.input_shape.clone().ok_or(LayerError::InvalidInputShape)
+    }
+
+    /// Returns the number of parameters in the layer.
+    ///
+    /// # Returns
+    ///
+    /// A `usize` representing the number of parameters in the layer.
+    fn param_count(&self) -> Result<(usize, usize), LayerError> {
+        Ok((0, 0))
+    }
+
+    /// Returns the name of the layer.
+    ///
+    /// # Returns
+    ///
+    /// A `&str` representing the name of the layer.
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the device for the layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device to set for the layer.
+    fn set_device(&mut self, device: &Device) {
+        if let Some(ref mut input) = self.input {
+            input.device = device.clone();
+        }
+    }
+
+    /// Updates the weights of the layer using the given gradient and optimizer.
+    ///
+    /// # Arguments
+    ///
+    /// * `optimizer` - The optimizer to use.
+    fn update_weights(&mut self, _optimizer: &mut Box<dyn Optimizer>) -> Result<(), LayerError> {
+        // Activation layers have no weights
+        Ok(())
+    }
+
+    fn activation_name(&self) -> Option<&str> {
+        Some(self.activation.name())
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({
+            "activation": self.activation.name()
+        })
+    }
+}
+
+/// A Parametric Rectified Linear Unit (PReLU) layer with a learnable slope per channel.
+///
+/// Computes `x` for positive inputs and `alpha * x` otherwise, where `alpha` holds one value
+/// for each entry of the last input axis and is updated by the optimizer.
+#[derive(Debug)]
+pub struct PReLU {
+    name: String,
+    initial_alpha: f32,
+    alpha: Option<Tensor>,
+    alpha_grad: Option<Tensor>,
+    input_shape: Option<Shape<IxDyn>>,
+    input: Option<Tensor>,
+    trainable: bool,
+    device: Device,
+}
+
+impl PReLU {
+    /// Creates a new PReLU layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The initial slope for negative inputs.
+    /// * `trainable` - Whether the slopes are learned.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the PReLU layer.
+    pub fn new(alpha: f32, trainable: bool) -> Self {
+        Self {
+            name: "PReLU".to_string(),
+            initial_alpha: alpha,
+            alpha: None,
+            alpha_grad: None,
+            input_shape: None,
+            input: None,
+            trainable,
+            device: Device::default(),
+        }
+    }
+}
+
+impl Layer for PReLU {
+    /// Builds the layer with the given input shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_shape` - The shape of the input tensor.
+    fn build(&mut self, input_shape: Shape<IxDyn>) -> Result<(), LayerError> {
+        let channels =
+            *input_shape.raw_dim().as_array_view().last().ok_or(LayerError::InvalidInputShape)?;
+
+        let mut alpha = Tensor::ones(Shape::from(IxDyn(&[channels])), self.device.clone())
+            .mul_scalar(self.initial_alpha);
+        alpha.device = self.device.clone();
+
+        self.alpha = Some(alpha);
+        self.input_shape = Some(input_shape);
+        Ok(())
+    }
+
+    /// Performs a forward pass through the layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input tensor.
+    ///
+    /// # Returns
+    ///
+    /// The output tensor.
+    fn forward(&mut self, input: &Tensor) -> Result<Tensor, LayerError> {
+        let alpha = self.alpha.as_ref().ok_or(LayerError::UninitializedWeights)?;
+
+        self.input = Some(input.clone());
+
+        let positive = input.map_max(0.0);
+        let negative = input.map(|x| x.min(0.0)).multiply(alpha);
+        Ok(positive.add(&negative))
+    }
+
+    /// Performs a backward pass through the layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `grad` - The gradient tensor.
+    ///
+    /// # Returns
+    ///
+    /// The gradient tensor with respect to the input.
+    fn backward(&mut self, grad: &Tensor) -> Result<Tensor, LayerError> {
+        let alpha = self.alpha.as_ref().ok_or(LayerError::UninitializedWeights)?;
+        let input = self.input.as_ref().ok_or(LayerError::UninitializedInput)?;
+
+        // d(output)/d(alpha) is the negative part of the input, summed over all but the channel axis
+        if self.trainable {
+            let channels = alpha.data.len();
+            let alpha_grad = grad
+                .multiply(&input.map(|x| x.min(0.0)))
+                .reshape(IxDyn(&[grad.data.len() / channels, channels]))
+                .sum_along_axis(0);
+            self.alpha_grad = Some(alpha_grad);
+        }
+
+        let positive_mask = input.map(|x| if x > 0.0 { 1.0 } else { 0.0 });
+        let negative_slope = input.map(|x| if x > 0.0 { 0.0 } else { 1.0 }).multiply(alpha);
+        Ok(grad.multiply(&positive_mask.add(&negative_slope)))
+    }
+
+    /// Returns the output shape of the layer.
+    ///
+    /// # Returns
+    ///
+    /// A `Shape` representing the output shape of the layer.
+    fn output_shape(&self) -> Result<Shape<IxDyn>, LayerError> {
+        self.input_shape.clone().ok_or(LayerError::InvalidInputShape)
+    }
+
+    /// Returns the number of parameters in the layer.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the number of trainable and non-trainable parameters.
+    fn param_count(&self) -> Result<(usize, usize), LayerError> {
+        let alpha_count = self.alpha.as_ref().map_or(0, |a| a.data.len());
+        if self.trainable { Ok((alpha_count, 0)) } else { Ok((0, alpha_count)) }
+    }
+
+    /// Returns the name of the layer.
+    ///
+    /// # Returns
+    ///
+    /// A `&str` representing the name of the layer.
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the device for the layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device to set for the layer.
+    fn set_device(&mut self, device: &Device) {
+        self.device = device.clone();
+
+        if let Some(ref mut alpha) = self.alpha {
+            alpha.device = device.clone();
+        }
+        if let Some(ref mut input) = self.input {
+            input.device = device.clone();
+        }
+    }
+
+    /// Updates the slopes of the layer using the given optimizer.
+    ///
+    /// # Arguments
+    ///
+    /// * `optimizer` - The optimizer to use.
+    fn update_weights(&mut self, optimizer: &mut Box<dyn Optimizer>) -> Result<(), LayerError> {
+        if !self.trainable {
+            return Ok(());
+        }
+
+        if let Some(ref alpha_grad) = self.alpha_grad {
+            optimizer
+                .step(self.alpha.as_mut().ok_or(LayerError::UninitializedWeights)?, alpha_grad)
+                .map_err(LayerError::OptimizerError)?;
+        }
+
+        self.alpha_grad = None;
+
+        Ok(())
+    }
+
+    fn get_weights(&self) -> serde_json::Value {
+        serde_json::json!({
+            "alpha": self.alpha.as_ref().map(|a| a.to_vec())
+        })
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({
+            "initial_alpha": self.initial_alpha,
+            "trainable": self.trainable
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::deep_learning::activations::ReluActivation;
+    use crate::deep_learning::activations::{ReluActivation, SigmoidActivation, SoftmaxActivation};
+    use crate::deep_learning::initializers::{Constant, Identity};
+    use crate::deep_learning::losses::{CrossEntropyLoss, Loss, MeanSquaredLoss};
+    use crate::deep_learning::optimizers::SGD;
+    use crate::deep_learning::regularizers::{L1, L2};
 
     use super::*;
 
@@ -625,6 +1207,88 @@ fn test_dense_layer_with_large_input() {
         assert_eq!(output.data.len(), 1000 * 256);
     }
 
+    #[test]
+    fn test_dense_layer_set_seed_is_reproducible() {
+        let build = |seed| {
+            let mut dense_layer = Dense::new(8, Some(ReluActivation::new()), true);
+            dense_layer.set_seed(seed);
+            dense_layer.build(Shape::from(IxDyn(&[1, 4]))).expect("Failed to build layer");
+            dense_layer.weights.unwrap()
+        };
+
+        assert_eq!(build(42), build(42));
+        assert_ne!(build(42), build(43));
+    }
+
+    #[test]
+    fn test_dense_layer_with_initializers() {
+        let mut dense_layer = Dense::new(3, None::<ReluActivation>, true)
+            .with_kernel_initializer(Identity::new(1.0))
+            .with_bias_initializer(Constant::new(0.5));
+        dense_layer.build(Shape::from(IxDyn(&[1, 3]))).expect("Failed to build layer");
+
+        let input = Tensor::new(vec![1.0, 2.0, 3.0], Shape::from(IxDyn(&[1, 3])));
+        let output = dense_layer.forward(&input).unwrap();
+        assert_eq!(output.to_vec(), vec![1.5, 2.5, 3.5]);
+
+        let config = dense_layer.get_config();
+        assert_eq!(config["kernel_initializer"]["name"], "Identity");
+        assert_eq!(config["bias_initializer"]["name"], "Constant");
+        assert_eq!(config["bias_initializer"]["config"]["value"], 0.5);
+    }
+
+    #[test]
+    fn test_dense_layer_config_reports_configured_initializers_before_build() {
+        let mut dense_layer = Dense::new(4, Some(ReluActivation::new()), true);
+        let config = dense_layer.get_config();
+        assert_eq!(config["kernel_initializer"]["name"], "Default");
+        assert_eq!(config["bias_initializer"]["name"], "Zeros");
+
+        dense_layer.build(Shape::from(IxDyn(&[1, 8]))).expect("Failed to build layer");
+        assert_eq!(dense_layer.get_config(), config);
+
+        let configured = Dense::new(4, Some(ReluActivation::new()), true)
+            .with_kernel_initializer(RandomNormal::new(0.0, 0.5));
+        let config = configured.get_config();
+        assert_eq!(config["kernel_initializer"]["name"], "RandomNormal");
+        assert_eq!(config["kernel_initializer"]["config"]["stddev"], 0.5);
+    }
+
+    #[test]
+    fn test_dense_layer_kernel_regularizer_gradient() {
+        let mut dense_layer = Dense::new(2, None::<ReluActivation>, true)
+            .with_kernel_initializer(Constant::new(0.5))
+            .with_kernel_regularizer(L2::new(0.1));
+        dense_layer.build(Shape::from(IxDyn(&[3]))).unwrap();
+
+        let input = Tensor::new(vec![1.0, 2.0, 3.0], Shape::from(IxDyn(&[1, 3])));
+        let grad = Tensor::new(vec![1.0, 1.0], Shape::from(IxDyn(&[1, 2])));
+        dense_layer.forward(&input).unwrap();
+        dense_layer.backward(&grad).unwrap();
+
+        // input^T · grad plus the L2 gradient 2 * 0.1 * 0.5
+        let weights_grad = dense_layer.weights_grad.as_ref().unwrap();
+        let expected = [1.1, 1.1, 2.1, 2.1, 3.1, 3.1];
+        for (actual, expected) in weights_grad.to_vec().iter().zip(expected.iter()) {
+            assert!((actual - expected).abs() < 1e-6);
+        }
+        assert!((dense_layer.regularization_loss() - 0.15).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dense_layer_activity_regularizer() {
+        let mut dense_layer = Dense::new(2, None::<ReluActivation>, true)
+            .with_kernel_initializer(Constant::new(0.5))
+            .with_activity_regularizer(L1::new(0.5));
+        dense_layer.build(Shape::from(IxDyn(&[2]))).unwrap();
+
+        let input = Tensor::new(vec![1.0, 1.0, 2.0, 2.0], Shape::from(IxDyn(&[2, 2])));
+        dense_layer.forward(&input).unwrap();
+
+        // Outputs are [1, 1, 2, 2]; the L1 penalty is averaged over the batch of 2
+        assert!((dense_layer.regularization_loss() - 1.5).abs() < 1e-6);
+    }
+
     #[test]
     fn test_flatten_new() {
         let input_shape = Shape::from(IxDyn(&[28, 28]));
@@ -651,4 +1315,161 @@ fn test_flatten_param_count() {
         assert_eq!(trainable, 0);
         assert_eq!(non_trainable, 0);
     }
+
+    #[test]
+    fn test_activation_layer_backward_applies_derivative() {
+        let mut relu = ActivationLayer::new(ReluActivation::new());
+        relu.build(Shape::from(IxDyn(&[4]))).unwrap();
+
+        let input = Tensor::new(vec![1.0, -2.0, 3.0, -4.0], Shape::from(IxDyn(&[1, 4])));
+        let grad = Tensor::new(vec![0.5, 0.5, 0.5, 0.5], Shape::from(IxDyn(&[1, 4])));
+        assert_eq!(relu.forward(&input).unwrap().to_vec(), vec![1.0, 0.0, 3.0, 0.0]);
+        assert_eq!(relu.backward(&grad).unwrap().to_vec(), vec![0.5, 0.0, 0.5, 0.0]);
+        assert_eq!(relu.output_shape().unwrap().raw_dim().as_array_view().to_vec(), vec![4]);
+        assert_eq!(relu.get_config()["activation"], "ReluActivation");
+    }
+
+    #[test]
+    fn test_activation_layer_softmax_backward_matches_finite_differences() {
+        let mut softmax = ActivationLayer::new(SoftmaxActivation::new());
+        let input = Tensor::new(vec![0.5, -1.0, 2.0, 1.0, 0.0, -0.5], Shape::from(IxDyn(&[2, 3])));
+        let grad = Tensor::new(vec![1.0, -2.0, 0.5, 0.3, 0.7, -1.0], Shape::from(IxDyn(&[2, 3])));
+
+        softmax.forward(&input).unwrap();
+        let input_grad = softmax.backward(&grad).unwrap();
+
+        // The scalar objective is sum(grad * softmax(input))
+        let objective =
+            |input: &Tensor| SoftmaxActivation::new().activate(input).multiply(&grad).data.sum();
+        let epsilon = 1e-2;
+        for i in 0..input.data.len() {
+            let mut plus = input.clone();
+            let mut minus = input.clone();
+            plus.data.as_slice_mut().unwrap()[i] += epsilon;
+            minus.data.as_slice_mut().unwrap()[i] -= epsilon;
+            let numeric = (objective(&plus) - objective(&minus)) / (2.0 * epsilon);
+            assert!((numeric - input_grad.to_vec()[i]).abs() < 1e-3);
+        }
+    }
+
+    /// Compares the weight gradient of `dense`, followed by the optional activation layer, with
+    /// central differences of the loss.
+    fn assert_weights_grad_matches_finite_differences(
+        mut dense: Dense,
+        mut activation: Option<ActivationLayer>,
+        loss: &dyn Loss,
+    ) {
+        let input = Tensor::new(vec![0.5, -1.0, 2.0, 1.0, 0.0, -0.5], Shape::from(IxDyn(&[2, 3])));
+        let target = Tensor::new(vec![1.0, 0.0, 0.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 3])));
+        dense.set_seed(5);
+        dense.build(Shape::from(IxDyn(&[2, 3]))).unwrap();
+
+        let forward = |dense: &mut Dense, activation: &mut Option<ActivationLayer>| {
+            let output = dense.forward(&input).unwrap();
+            match activation.as_mut() {
+                Some(layer) => layer.forward(&output).unwrap(),
+                None => output,
+            }
+        };
+
+        let output = forward(&mut dense, &mut activation);
+        let mut grad = loss.calculate_loss_grad(&output, &target);
+        if let Some(layer) = activation.as_mut() {
+            grad = layer.backward(&grad).unwrap();
+        }
+        dense.backward(&grad).unwrap();
+        let weights_grad = dense.weights_grad.clone().unwrap().to_vec();
+
+        let weights = dense.weights.clone().unwrap();
+        let epsilon = 1e-2;
+        for (i, analytic) in weights_grad.into_iter().enumerate() {
+            let mut plus = weights.clone();
+            let mut minus = weights.clone();
+            plus.data.as_slice_mut().unwrap()[i] += epsilon;
+            minus.data.as_slice_mut().unwrap()[i] -= epsilon;
+
+            dense.weights = Some(plus);
+            let loss_plus = loss.calculate_loss(&forward(&mut dense, &mut activation), &target);
+            dense.weights = Some(minus);
+            let loss_minus = loss.calculate_loss(&forward(&mut dense, &mut activation), &target);
+
+            let numeric = (loss_plus - loss_minus) / (2.0 * epsilon);
+            assert!((numeric - analytic).abs() < 2e-3, "{}: {} != {}", i, numeric, analytic);
+        }
+    }
+
+    #[test]
+    fn test_activation_layer_softmax_model_gradient_matches_finite_differences() {
+        assert_weights_grad_matches_finite_differences(
+            Dense::new(3, None::<SoftmaxActivation>, true),
+            Some(ActivationLayer::new(SoftmaxActivation::new())),
+            &MeanSquaredLoss::new(),
+        );
+    }
+
+    #[test]
+    fn test_dense_softmax_cross_entropy_gradient_matches_finite_differences() {
+        assert_weights_grad_matches_finite_differences(
+            Dense::new(3, Some(SoftmaxActivation::new()), true),
+            None,
+            &CrossEntropyLoss::new(),
+        );
+    }
+
+    #[test]
+    fn test_activation_layer_sigmoid_backward() {
+        let mut sigmoid = ActivationLayer::new(SigmoidActivation::new());
+        let input = Tensor::new(vec![0.0, 0.0], Shape::from(IxDyn(&[1, 2])));
+        let grad = Tensor::new(vec![1.0, 2.0], Shape::from(IxDyn(&[1, 2])));
+        sigmoid.forward(&input).unwrap();
+        assert_eq!(sigmoid.backward(&grad).unwrap().to_vec(), vec![0.25, 0.5]);
+    }
+
+    #[test]
+    fn test_prelu_forward_backward() {
+        let mut prelu = PReLU::new(0.25, true);
+        prelu.build(Shape::from(IxDyn(&[2]))).unwrap();
+        assert_eq!(prelu.param_count().unwrap(), (2, 0));
+
+        let input = Tensor::new(vec![1.0, -2.0, -4.0, 3.0], Shape::from(IxDyn(&[2, 2])));
+        let grad = Tensor::new(vec![1.0, 1.0, 1.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        assert_eq!(prelu.forward(&input).unwrap().to_vec(), vec![1.0, -0.5, -1.0, 3.0]);
+        assert_eq!(prelu.backward(&grad).unwrap().to_vec(), vec![1.0, 0.25, 0.25, 1.0]);
+
+        // Each channel accumulates the negative inputs seen in that channel
+        assert_eq!(prelu.alpha_grad.as_ref().unwrap().to_vec(), vec![-4.0, -2.0]);
+    }
+
+    #[test]
+    fn test_prelu_learns_alpha() {
+        let mut prelu = PReLU::new(0.25, true);
+        prelu.build(Shape::from(IxDyn(&[2]))).unwrap();
+
+        let input = Tensor::new(vec![1.0, -2.0, -4.0, 3.0], Shape::from(IxDyn(&[2, 2])));
+        let grad = Tensor::new(vec![1.0, 1.0, 1.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        prelu.forward(&input).unwrap();
+        prelu.backward(&grad).unwrap();
+
+        let mut optimizer: Box<dyn Optimizer> = Box::new(SGD::new(0.1));
+        prelu.update_weights(&mut optimizer).unwrap();
+
+        // alpha - 0.1 * alpha_grad
+        let alpha = prelu.alpha.as_ref().unwrap().to_vec();
+        assert!((alpha[0] - 0.65).abs() < 1e-6);
+        assert!((alpha[1] - 0.45).abs() < 1e-6);
+        assert_eq!(prelu.get_weights()["alpha"].as_array().unwrap().len(), 2);
+        assert!(prelu.alpha_grad.is_none());
+    }
+
+    #[test]
+    fn test_prelu_not_trainable() {
+        let mut prelu = PReLU::new(0.25, false);
+        prelu.build(Shape::from(IxDyn(&[3]))).unwrap();
+        assert_eq!(prelu.param_count().unwrap(), (0, 3));
+
+        let input = Tensor::new(vec![-1.0, -1.0, -1.0], Shape::from(IxDyn(&[1, 3])));
+        prelu.forward(&input).unwrap();
+        prelu.backward(&input).unwrap();
+        assert!(prelu.alpha_grad.is_none());
+    }
 }
@@ -27,16 +27,263 @@
 // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use ndarray::{Dimension, IxDyn, Shape};
+use ndarray::{Array2, ArrayD, ArrayView1, Axis, Dimension, Ix2, IxDyn, Shape};
 
 use crate::devices::Device;
 
 use std::fmt::Debug;
 
-use super::{tensor_ops::Tensor, utils::check_for_nan};
+use super::{activations::SigmoidActivation, tensor_ops::Tensor, utils::check_for_nan};
+
+/// How the per-sample losses of a batch are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Reduction {
+    /// Average the weighted sample losses over the batch.
+    #[default]
+    Mean,
+    /// Sum the weighted sample losses.
+    Sum,
+    /// Keep one weighted loss per sample.
+    None,
+}
+
+/// Options shared by every loss: the reduction mode and optional class weights.
+#[derive(Debug, Clone, Default)]
+pub struct LossOptions {
+    /// How sample losses are combined.
+    pub reduction: Reduction,
+    /// The weight of each class, indexed by the class of a sample's target.
+    pub class_weights: Option<Vec<f32>>,
+}
 
 /// A trait representing a loss function.
+///
+/// Implementations compute the loss and its gradient for each sample; weighting by sample and
+/// class weights and the reduction over the batch are shared by all losses.
 pub trait Loss: Debug {
+    /// Calculates the unweighted loss of each sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output tensor from the model.
+    /// * `target` - The target tensor.
+    ///
+    /// # Returns
+    ///
+    /// A `[batch]` tensor containing the loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor;
+
+    /// Calculates the gradient of the sum of the sample losses with respect to the output tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output tensor from the model.
+    /// * `target` - The target tensor.
+    ///
+    /// # Returns
+    ///
+    /// A `Tensor` of the output's shape where each row is the gradient of that sample's loss.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor;
+
+    /// Returns the reduction and class weight options of the loss.
+    fn options(&self) -> &LossOptions;
+
+    /// Returns the name of the activation whose derivative the gradient already includes.
+    ///
+    /// Some losses over probabilities return the gradient with respect to the logits of the
+    /// activation that produced them, for a last `Dense` layer whose backward pass skips the
+    /// activation's derivative. Placing that activation in an `ActivationLayer` would apply its
+    /// derivative a second time.
+    ///
+    /// # Returns
+    ///
+    /// The name of the fused activation, or `None` if the gradient is taken with respect to the
+    /// output. Default is `None`.
+    fn fused_activation(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the reduction and class weight options of the loss for modification.
+    fn options_mut(&mut self) -> &mut LossOptions;
+
+    /// Sets how the sample losses of a batch are combined.
+    ///
+    /// # Arguments
+    ///
+    /// * `reduction` - The reduction mode.
+    ///
+    /// # Returns
+    ///
+    /// The loss with the reduction set.
+    fn with_reduction(mut self, reduction: Reduction) -> Self
+    where
+        Self: Sized,
+    {
+        self.options_mut().reduction = reduction;
+        self
+    }
+
+    /// Sets a weight for each class.
+    ///
+    /// The class of a sample is its class index, the position of the largest value of a one-hot
+    /// target, or the rounded value of a single-column binary target.
+    ///
+    /// # Arguments
+    ///
+    /// * `class_weights` - The weight of each class.
+    ///
+    /// # Returns
+    ///
+    /// The loss with the class weights set.
+    fn with_class_weights(mut self, class_weights: Vec<f32>) -> Self
+    where
+        Self: Sized,
+    {
+        self.options_mut().class_weights = Some(class_weights);
+        self
+    }
+
+    /// Combines the given sample weights with the class weights of each sample's target.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target tensor.
+    /// * `sample_weights` - Optional `[batch]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// The weight of each sample, or `None` if no weights apply.
+    fn combined_sample_weights(
+        &self,
+        target: &Tensor,
+        sample_weights: Option<&Tensor>,
+    ) -> Option<Vec<f32>> {
+        let class_weights = self.options().class_weights.as_ref().map(|class_weights| {
+            target_classes(target)
+                .into_iter()
+                .map(|class| {
+                    *class_weights.get(class).unwrap_or_else(|| {
+                        panic!(
+                            "No class weight for class {}, got {} class weights",
+                            class,
+                            class_weights.len()
+                        )
+                    })
+                })
+                .collect::<Vec<f32>>()
+        });
+
+        match (sample_weights, class_weights) {
+            (None, class_weights) => class_weights,
+            (Some(sample_weights), class_weights) => {
+                let batch_size = target.data.shape()[0];
+                if sample_weights.data.len() != batch_size {
+                    panic!(
+                        "Expected {} sample weights, got {}",
+                        batch_size,
+                        sample_weights.data.len()
+                    );
+                }
+
+                let weights = sample_weights.data.iter().copied();
+                Some(match class_weights {
+                    Some(class_weights) => {
+                        weights.zip(class_weights).map(|(sample, class)| sample * class).collect()
+                    }
+                    None => weights.collect(),
+                })
+            }
+        }
+    }
+
+    /// Calculates the weighted loss and applies the reduction.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output tensor from the model.
+    /// * `target` - The target tensor.
+    /// * `sample_weights` - Optional `[batch]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// A `[batch]` tensor with `Reduction::None`, otherwise a `[1]` tensor with the reduced loss.
+    fn calculate_losses(
+        &self,
+        output: &Tensor,
+        target: &Tensor,
+        sample_weights: Option<&Tensor>,
+    ) -> Tensor {
+        let mut losses = self.calculate_sample_losses(output, target);
+        if let Some(weights) = self.combined_sample_weights(target, sample_weights) {
+            losses.data.iter_mut().zip(weights).for_each(|(loss, weight)| *loss *= weight);
+        }
+
+        let batch_size = losses.data.len() as f32;
+        let reduced = match self.options().reduction {
+            Reduction::None => return losses,
+            Reduction::Sum => losses.data.sum(),
+            Reduction::Mean => losses.data.sum() / batch_size,
+        };
+
+        let mut reduced = Tensor::new(vec![reduced], Shape::from(IxDyn(&[1])));
+        reduced.device = losses.device;
+        reduced
+    }
+
+    /// Calculates the weighted loss as a single value.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output tensor from the model.
+    /// * `target` - The target tensor.
+    /// * `sample_weights` - Optional `[batch]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// The reduced loss. With `Reduction::None` the sample losses are averaged.
+    fn calculate_weighted_loss(
+        &self,
+        output: &Tensor,
+        target: &Tensor,
+        sample_weights: Option<&Tensor>,
+    ) -> f32 {
+        let losses = self.calculate_losses(output, target, sample_weights);
+        losses.data.mean().unwrap_or(0.0)
+    }
+
+    /// Calculates the gradient of the weighted loss with respect to the output tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output tensor from the model.
+    /// * `target` - The target tensor.
+    /// * `sample_weights` - Optional `[batch]` tensor with the weight of each sample.
+    ///
+    /// # Returns
+    ///
+    /// The gradient of the reduced loss. With `Reduction::None` it is the gradient of the sum.
+    fn calculate_weighted_loss_grad(
+        &self,
+        output: &Tensor,
+        target: &Tensor,
+        sample_weights: Option<&Tensor>,
+    ) -> Tensor {
+        let mut grad = self.calculate_sample_losses_grad(output, target);
+
+        if let Some(weights) = self.combined_sample_weights(target, sample_weights) {
+            for (mut row, weight) in grad.data.outer_iter_mut().zip(weights) {
+                row.mapv_inplace(|x| x * weight);
+            }
+        }
+
+        if self.options().reduction == Reduction::Mean {
+            let batch_size = output.data.shape()[0] as f32;
+            grad.data.mapv_inplace(|x| x / batch_size);
+        }
+
+        grad
+    }
+
     /// Calculates the loss between the output and the target tensors.
     ///
     /// # Arguments
@@ -47,7 +294,9 @@ pub trait Loss: Debug {
     /// # Returns
     ///
     /// The calculated loss as a `f32` value.
-    fn calculate_loss(&self, output: &Tensor, target: &Tensor) -> f32;
+    fn calculate_loss(&self, output: &Tensor, target: &Tensor) -> f32 {
+        self.calculate_weighted_loss(output, target, None)
+    }
 
     /// Calculates the gradient of the loss with respect to the output tensor.
     ///
@@ -59,384 +308,2521 @@ pub trait Loss: Debug {
     /// # Returns
     ///
     /// A `Tensor` containing the gradient of the loss with respect to the output tensor.
-    fn calculate_loss_grad(&self, output: &Tensor, target: &Tensor) -> Tensor;
+    fn calculate_loss_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        self.calculate_weighted_loss_grad(output, target, None)
+    }
 }
 
-/// A struct representing the Cross-Entropy Loss function.
+/// Panics if the output and the target do not have the same shape.
+///
+/// # Arguments
+///
+/// * `output` - The output tensor from the model.
+/// * `target` - The target tensor.
+fn check_shapes(output: &Tensor, target: &Tensor) {
+    if output.data.shape() != target.data.shape() {
+        panic!(
+            "Shape mismatch: output.shape = {:?}, target.shape = {:?}",
+            output.data.shape(),
+            target.data.shape()
+        );
+    }
+}
+
+/// Determines the class of each sample from its target.
+///
+/// # Arguments
+///
+/// * `target` - Class indices, one-hot rows, or a single column of binary labels.
+///
+/// # Returns
+///
+/// The class of each sample.
+fn target_classes(target: &Tensor) -> Vec<usize> {
+    match target.data.ndim() {
+        1 => target.data.iter().map(|&class| class as usize).collect(),
+        2 if target.data.shape()[1] == 1 => {
+            target.data.iter().map(|&label| label.round() as usize).collect()
+        }
+        2 => target
+            .data
+            .outer_iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                    .map(|(idx, _)| idx)
+                    .expect("Row cannot be empty")
+            })
+            .collect(),
+        _ => panic!("Cannot determine classes from target of shape {:?}", target.data.shape()),
+    }
+}
+
+/// Averages an element-wise loss over every axis but the batch axis.
+///
+/// # Arguments
+///
+/// * `elementwise` - The loss of each element, with the batch on the first axis.
+/// * `like` - The tensor whose device the result uses.
+///
+/// # Returns
+///
+/// A `[batch]` tensor containing the loss of each sample.
+fn mean_per_sample(elementwise: ArrayD<f32>, like: &Tensor) -> Tensor {
+    if elementwise.is_empty() {
+        panic!("Cannot calculate loss: no dataset in input tensors");
+    }
+
+    let batch_size = elementwise.shape()[0];
+    let features = elementwise.len() / batch_size;
+    let per_sample = elementwise
+        .into_shape_with_order((batch_size, features))
+        .expect("Failed to reshape loss per sample")
+        .mean_axis(Axis(1))
+        .expect("Mean computation failed unexpectedly");
+
+    Tensor { data: per_sample.into_dyn(), device: like.device.clone() }
+}
+
+/// Returns the number of elements in each sample of a tensor.
+///
+/// # Arguments
+///
+/// * `tensor` - The tensor, with the batch on the first axis.
+///
+/// # Returns
+///
+/// The number of elements per sample.
+fn features_per_sample(tensor: &Tensor) -> f32 {
+    (tensor.data.len() / tensor.data.shape()[0].max(1)) as f32
+}
+
+/// Reshapes a tensor's data into one row per sample.
+///
+/// # Arguments
+///
+/// * `data` - The data, with the batch on the first axis.
+///
+/// # Returns
+///
+/// A `[batch, elements_per_sample]` array.
+fn per_sample_rows(data: &ArrayD<f32>) -> Array2<f32> {
+    let batch_size = data.shape()[0];
+    let features = data.len() / batch_size.max(1);
+    data.as_standard_layout()
+        .into_owned()
+        .into_shape_with_order((batch_size, features))
+        .expect("Failed to reshape tensor per sample")
+}
+
+/// Returns the sigmoid probabilities of an output that holds either probabilities or logits.
+///
+/// # Arguments
+///
+/// * `output` - The predicted probabilities or logits.
+/// * `from_logits` - Whether the output holds logits.
+///
+/// # Returns
+///
+/// The predicted probabilities.
+fn sigmoid_probabilities(output: &Tensor, from_logits: bool) -> Tensor {
+    if from_logits { output.map(SigmoidActivation::sigmoid) } else { output.clone() }
+}
+
+/// Validates a label smoothing factor.
+///
+/// # Arguments
+///
+/// * `label_smoothing` - The label smoothing factor, in `[0, 1)`.
+fn validate_label_smoothing(label_smoothing: f32) {
+    if !(0.0..1.0).contains(&label_smoothing) {
+        panic!("Label smoothing must be in [0, 1).");
+    }
+}
+
+/// Smooths targets towards the uniform distribution over `classes` classes.
+///
+/// # Arguments
+///
+/// * `target` - The target tensor.
+/// * `label_smoothing` - The label smoothing factor.
+/// * `classes` - The number of classes the smoothing mass is spread over.
+///
+/// # Returns
+///
+/// The targets `target * (1 - label_smoothing) + label_smoothing / classes`.
+fn smooth_labels(target: &Tensor, label_smoothing: f32, classes: usize) -> Tensor {
+    if label_smoothing == 0.0 {
+        return target.clone();
+    }
+    target.map(|t| t * (1.0 - label_smoothing) + label_smoothing / classes as f32)
+}
+
+/// Back-propagates a gradient with respect to softmax probabilities to the logits.
+///
+/// # Arguments
+///
+/// * `probs` - The `[batch, classes]` softmax probabilities.
+/// * `grad` - The gradient with respect to the probabilities.
+///
+/// # Returns
+///
+/// The gradient `probs * (grad - sum(probs * grad))` with respect to the logits.
+fn softmax_backward(probs: &ArrayD<f32>, grad: &ArrayD<f32>) -> ArrayD<f32> {
+    let mut logits_grad = grad.clone();
+    for (mut row, probs) in logits_grad.outer_iter_mut().zip(probs.outer_iter()) {
+        let dot = (&row * &probs).sum();
+        row.zip_mut_with(&probs, |g, &p| *g = p * (*g - dot));
+    }
+    logits_grad
+}
+
+/// Splits a `[batch, group, dim]` tensor of stacked embeddings into per-sample groups.
+///
+/// # Arguments
+///
+/// * `output` - The stacked embeddings.
+/// * `group` - The number of embeddings stacked for each sample.
+///
+/// # Returns
+///
+/// The number of samples and the dimension of each embedding.
+fn embedding_groups(output: &Tensor, group: usize) -> (usize, usize) {
+    let shape = output.data.shape();
+    if shape.len() != 3 || shape[1] != group {
+        panic!(
+            "Expected output of shape [batch, {}, dim] with stacked embeddings, got {:?}",
+            group, shape
+        );
+    }
+    (shape[0], shape[2])
+}
+
+/// Computes the Euclidean distance between two embeddings.
+///
+/// The small constant keeps the gradient finite when the embeddings coincide.
+///
+/// # Arguments
+///
+/// * `a` - The first embedding.
+/// * `b` - The second embedding.
+///
+/// # Returns
+///
+/// The distance `sqrt(sum((a - b)^2) + 1e-12)`.
+fn euclidean_distance(a: ArrayView1<f32>, b: ArrayView1<f32>) -> f32 {
+    (a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum::<f32>() + 1e-12).sqrt()
+}
+
+/// Validates that there is one pair label per sample.
+///
+/// # Arguments
+///
+/// * `target` - The pair labels.
+/// * `batch_size` - The number of pairs.
+fn check_pair_targets(target: &Tensor, batch_size: usize) {
+    if target.data.len() != batch_size {
+        panic!(
+            "Expected one label per pair, got target.shape = {:?} for {} pairs",
+            target.data.shape(),
+            batch_size
+        );
+    }
+}
+
+/// Maps a binary label to the `{-1, 1}` labels used by hinge losses.
+///
+/// # Arguments
+///
+/// * `label` - The label, -1, 0 or 1.
+///
+/// # Returns
+///
+/// -1 for labels of 0, otherwise the label itself.
+fn hinge_label(label: f32) -> f32 {
+    if label == 0.0 { -1.0 } else { label }
+}
+
+/// Computes a row-wise log-softmax of a 2D tensor of logits using the log-sum-exp trick.
+///
+/// # Arguments
+///
+/// * `logits` - The `[batch, classes]` tensor of logits.
+///
+/// # Returns
+///
+/// A tensor of the same shape containing the log-probabilities.
+pub(crate) fn log_softmax(logits: &Tensor) -> Tensor {
+    let mut log_probs = logits.data.clone();
+
+    for mut row in log_probs.outer_iter_mut() {
+        let max = row.fold(f32::NEG_INFINITY, |acc, &x| acc.max(x));
+        let log_sum_exp = max + row.iter().map(|&x| (x - max).exp()).sum::<f32>().ln();
+        row.mapv_inplace(|x| x - log_sum_exp);
+    }
+
+    Tensor { data: log_probs, device: logits.device.clone() }
+}
+
+/// Computes a row-wise softmax of a 2D tensor of logits.
+///
+/// # Arguments
+///
+/// * `logits` - The `[batch, classes]` tensor of logits.
+///
+/// # Returns
+///
+/// A tensor of the same shape containing the probabilities.
+pub(crate) fn softmax(logits: &Tensor) -> Tensor {
+    log_softmax(logits).map(|x| x.exp())
+}
+
+/// Computes `ln(sigmoid(x))` as `-softplus(-x)` without overflowing for large `|x|`.
+///
+/// # Arguments
+///
+/// * `x` - The logit.
+///
+/// # Returns
+///
+/// The log-probability of the positive class.
+pub(crate) fn log_sigmoid(x: f32) -> f32 {
+    -((-x).max(0.0) + (-x.abs()).exp().ln_1p())
+}
+
+/// A struct representing the Binary Cross-Entropy Loss function.
+///
+/// The output holds probabilities, e.g. from a `Dense` layer with a Sigmoid activation, and each
+/// element is treated as an independent binary label. Like [`CrossEntropyLoss`], the gradient
+/// `output - target` is taken with respect to the logits, so the probabilities must not come from
+/// an `ActivationLayer` with a Sigmoid activation.
+#[derive(Debug, Default)]
+pub struct BinaryCrossEntropy {
+    label_smoothing: f32,
+    options: LossOptions,
+}
+
+impl BinaryCrossEntropy {
+    /// Creates a new BinaryCrossEntropy instance.
+    pub fn new() -> Self {
+        Self { label_smoothing: 0.0, options: LossOptions::default() }
+    }
+
+    /// Sets the label smoothing factor, which moves targets towards 0.5.
+    ///
+    /// # Arguments
+    ///
+    /// * `label_smoothing` - The label smoothing factor, in `[0, 1)`.
+    ///
+    /// # Returns
+    ///
+    /// The loss with label smoothing set.
+    pub fn with_label_smoothing(mut self, label_smoothing: f32) -> Self {
+        validate_label_smoothing(label_smoothing);
+        self.label_smoothing = label_smoothing;
+        self
+    }
+}
+
+impl Loss for BinaryCrossEntropy {
+    /// Calculates the binary cross-entropy of each sample, averaged over its elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities.
+    /// * `target` - The true labels in `[0, 1]`.
+    ///
+    /// # Returns
+    ///
+    /// The binary cross-entropy loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let target = smooth_labels(target, self.label_smoothing, 2);
+
+        // Clip predictions to avoid log(0)
+        let epsilon = 1e-7;
+        let mut loss = output.data.clone();
+        loss.zip_mut_with(&target.data, |p, &t| {
+            let clipped = p.clamp(epsilon, 1.0 - epsilon);
+            *p = -(t * clipped.ln() + (1.0 - t) * (1.0 - clipped).ln());
+        });
+
+        mean_per_sample(loss, output)
+    }
+
+    /// Calculates the gradient of the binary cross-entropy loss with respect to the logits.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities.
+    /// * `target` - The true labels in `[0, 1]`.
+    ///
+    /// # Returns
+    ///
+    /// The gradient `(output - target) / elements_per_sample`.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        let target = smooth_labels(target, self.label_smoothing, 2);
+        let features = features_per_sample(output);
+        Tensor { data: (&output.data - &target.data) / features, device: output.device.clone() }
+    }
+
+    fn fused_activation(&self) -> Option<&str> {
+        Some("SigmoidActivation")
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the Binary Cross-Entropy Loss function computed from logits.
+///
+/// Fusing the sigmoid into the loss keeps it finite for arbitrarily large logits. An optional
+/// `pos_weight` scales the loss of positive labels to counter class imbalance.
 #[derive(Debug)]
-pub struct CrossEntropyLoss;
+pub struct BinaryCrossEntropyWithLogits {
+    pos_weight: f32,
+    label_smoothing: f32,
+    options: LossOptions,
+}
 
-impl Default for CrossEntropyLoss {
+impl Default for BinaryCrossEntropyWithLogits {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl CrossEntropyLoss {
-    /// Creates a new CrossEntropyLoss instance.
+impl BinaryCrossEntropyWithLogits {
+    /// Creates a new BinaryCrossEntropyWithLogits instance.
     pub fn new() -> Self {
-        Self
+        Self { pos_weight: 1.0, label_smoothing: 0.0, options: LossOptions::default() }
     }
 
-    /// Clips the tensor to avoid issues with log(0) or division by zero.
+    /// Sets the weight applied to the loss of positive labels.
     ///
     /// # Arguments
     ///
-    /// * `tensor` - The tensor to clip.
-    /// * `epsilon` - The epsilon value to use for clipping.
+    /// * `pos_weight` - The weight of positive labels. Must be positive.
     ///
     /// # Returns
     ///
-    /// The clipped tensor.
-    fn clip_tensor(&self, tensor: &Tensor, epsilon: f32) -> Tensor {
-        tensor.map(|x| x.clamp(epsilon, 1.0 - epsilon))
+    /// The loss with the positive weight set.
+    pub fn with_pos_weight(mut self, pos_weight: f32) -> Self {
+        if pos_weight <= 0.0 {
+            panic!("pos_weight must be positive.");
+        }
+        self.pos_weight = pos_weight;
+        self
+    }
+
+    /// Sets the label smoothing factor, which moves targets towards 0.5.
+    ///
+    /// # Arguments
+    ///
+    /// * `label_smoothing` - The label smoothing factor, in `[0, 1)`.
+    ///
+    /// # Returns
+    ///
+    /// The loss with label smoothing set.
+    pub fn with_label_smoothing(mut self, label_smoothing: f32) -> Self {
+        validate_label_smoothing(label_smoothing);
+        self.label_smoothing = label_smoothing;
+        self
     }
 }
 
-impl Loss for CrossEntropyLoss {
-    /// Calculates the cross-entropy loss between the true and predicted values.
+impl Loss for BinaryCrossEntropyWithLogits {
+    /// Calculates the binary cross-entropy of each sample from logits, averaged over its elements.
     ///
     /// # Arguments
     ///
-    /// * `y_true` - The true values.
-    /// * `y_pred` - The predicted values.
+    /// * `output` - The predicted logits.
+    /// * `target` - The true labels in `[0, 1]`.
     ///
     /// # Returns
     ///
-    /// The cross-entropy loss.
-    fn calculate_loss(&self, y_true: &Tensor, y_pred: &Tensor) -> f32 {
-        if y_true.shape().raw_dim() != y_pred.shape().raw_dim() {
-            panic!(
-                "Shape mismatch: y_true.shape = {:?}, y_pred.shape = {:?}",
-                y_true.shape().raw_dim(),
-                y_pred.shape().raw_dim()
-            );
-        }
+    /// The binary cross-entropy loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
 
-        // Check for NaN values in y_true and y_pred
-        check_for_nan(y_true, y_pred);
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
 
-        // Clip predictions to avoid log(0)
-        let epsilon = 1e-12;
-        let clipped_pred = self.clip_tensor(y_pred, epsilon);
+        // -[w * t * ln(sigmoid(x)) + (1 - t) * ln(1 - sigmoid(x))]
+        let target = smooth_labels(target, self.label_smoothing, 2);
+        let pos_weight = self.pos_weight;
+        let mut loss = output.data.clone();
+        loss.zip_mut_with(&target.data, |x, &t| {
+            *x = -(pos_weight * t * log_sigmoid(*x) + (1.0 - t) * log_sigmoid(-*x));
+        });
+
+        mean_per_sample(loss, output)
+    }
+
+    /// Calculates the gradient of the binary cross-entropy loss with respect to the logits.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted logits.
+    /// * `target` - The true labels in `[0, 1]`.
+    ///
+    /// # Returns
+    ///
+    /// The gradient of the loss, which is `sigmoid(x) - target` per element without a positive
+    /// weight, divided by the number of elements per sample.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        let target = smooth_labels(target, self.label_smoothing, 2);
+        let pos_weight = self.pos_weight;
+        let features = features_per_sample(output);
+        let mut grad = output.data.clone();
+        grad.zip_mut_with(&target.data, |x, &t| {
+            let p = SigmoidActivation::sigmoid(*x);
+            *x = ((1.0 - t) * p - pos_weight * t * (1.0 - p)) / features;
+        });
+
+        Tensor { data: grad, device: output.device.clone() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
 
-        // Compute element-wise multiplication
-        let cross_entropy =
-            y_true.data.iter().zip(clipped_pred.data.iter()).map(|(t, p)| t * p.ln()).sum::<f32>();
+/// A struct representing the Contrastive Loss function.
+///
+/// The output stacks two embeddings per sample as a `[batch, 2, dim]` tensor and the target is
+/// 1 for similar pairs and 0 for dissimilar pairs. Similar pairs are pulled together by their
+/// squared distance `d^2`, dissimilar pairs are pushed apart by `max(margin - d, 0)^2`.
+#[derive(Debug)]
+pub struct ContrastiveLoss {
+    margin: f32,
+    options: LossOptions,
+}
+
+impl Default for ContrastiveLoss {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl ContrastiveLoss {
+    /// Creates a new ContrastiveLoss instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `margin` - The distance dissimilar pairs are pushed beyond. Must be positive.
+    pub fn new(margin: f32) -> Self {
+        if margin <= 0.0 {
+            panic!("Margin must be positive.");
+        }
+        Self { margin, options: LossOptions::default() }
+    }
+}
 
-        // Return cross-entropy loss
-        -cross_entropy
+impl Loss for ContrastiveLoss {
+    /// Calculates the contrastive loss of each pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The `[batch, 2, dim]` stacked embedding pairs.
+    /// * `target` - The `[batch]` labels, 1 for similar and 0 for dissimilar pairs.
+    ///
+    /// # Returns
+    ///
+    /// The contrastive loss of each pair.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        let (batch_size, _) = embedding_groups(output, 2);
+        check_pair_targets(target, batch_size);
+        check_for_nan(output, target);
+
+        let losses: Vec<f32> = output
+            .data
+            .outer_iter()
+            .zip(target.data.iter())
+            .map(|(pair, &similar)| {
+                let pair = pair.into_dimensionality::<Ix2>().unwrap();
+                let distance = euclidean_distance(pair.row(0), pair.row(1));
+                similar * distance.powi(2)
+                    + (1.0 - similar) * (self.margin - distance).max(0.0).powi(2)
+            })
+            .collect();
+
+        let mut losses = Tensor::new(losses, Shape::from(IxDyn(&[batch_size])));
+        losses.device = output.device.clone();
+        losses
+    }
+
+    /// Calculates the gradient of the contrastive loss with respect to both embeddings.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The `[batch, 2, dim]` stacked embedding pairs.
+    /// * `target` - The `[batch]` labels, 1 for similar and 0 for dissimilar pairs.
+    ///
+    /// # Returns
+    ///
+    /// A `[batch, 2, dim]` tensor with the gradient of each embedding.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        let (batch_size, _) = embedding_groups(output, 2);
+        check_pair_targets(target, batch_size);
+
+        let mut grad = ArrayD::zeros(output.data.raw_dim());
+        for ((pair, &similar), mut pair_grad) in
+            output.data.outer_iter().zip(target.data.iter()).zip(grad.outer_iter_mut())
+        {
+            let pair = pair.into_dimensionality::<Ix2>().unwrap();
+            let (first, second) = (pair.row(0), pair.row(1));
+            let distance = euclidean_distance(first, second);
+
+            // d(d^2)/dx1 = 2 (x1 - x2) and d(d)/dx1 = (x1 - x2) / d
+            let diff = &first - &second;
+            let scale = 2.0 * similar
+                - 2.0 * (1.0 - similar) * (self.margin - distance).max(0.0) / distance;
+            pair_grad.index_axis_mut(Axis(0), 0).assign(&(&diff * scale));
+            pair_grad.index_axis_mut(Axis(0), 1).assign(&(&diff * -scale));
+        }
+
+        Tensor { data: grad, device: output.device.clone() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the Cosine Embedding Loss function.
+///
+/// The output stacks two embeddings per sample as a `[batch, 2, dim]` tensor and the target is
+/// 1 for similar pairs and -1 for dissimilar pairs. Similar pairs are penalized by
+/// `1 - cos(x1, x2)`, dissimilar pairs by `max(cos(x1, x2) - margin, 0)`.
+#[derive(Debug, Default)]
+pub struct CosineEmbeddingLoss {
+    margin: f32,
+    options: LossOptions,
+}
+
+impl CosineEmbeddingLoss {
+    /// Creates a new CosineEmbeddingLoss instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `margin` - The cosine similarity dissimilar pairs are pushed below, in `[-1, 1]`.
+    pub fn new(margin: f32) -> Self {
+        if !(-1.0..=1.0).contains(&margin) {
+            panic!("Margin must be in [-1, 1].");
+        }
+        Self { margin, options: LossOptions::default() }
+    }
+
+    /// Computes the cosine similarity of two embeddings and their norms.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The first embedding.
+    /// * `second` - The second embedding.
+    ///
+    /// # Returns
+    ///
+    /// A tuple `(cos, |first|, |second|)`.
+    fn cosine(&self, first: ArrayView1<f32>, second: ArrayView1<f32>) -> (f32, f32, f32) {
+        let epsilon = 1e-8;
+        let first_norm = first.dot(&first).sqrt().max(epsilon);
+        let second_norm = second.dot(&second).sqrt().max(epsilon);
+        (first.dot(&second) / (first_norm * second_norm), first_norm, second_norm)
+    }
+}
+
+impl Loss for CosineEmbeddingLoss {
+    /// Calculates the cosine embedding loss of each pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The `[batch, 2, dim]` stacked embedding pairs.
+    /// * `target` - The `[batch]` labels, 1 for similar and -1 for dissimilar pairs.
+    ///
+    /// # Returns
+    ///
+    /// The cosine embedding loss of each pair.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        let (batch_size, _) = embedding_groups(output, 2);
+        check_pair_targets(target, batch_size);
+        check_for_nan(output, target);
+
+        let losses: Vec<f32> = output
+            .data
+            .outer_iter()
+            .zip(target.data.iter())
+            .map(|(pair, &label)| {
+                let pair = pair.into_dimensionality::<Ix2>().unwrap();
+                let (cos, _, _) = self.cosine(pair.row(0), pair.row(1));
+                if label > 0.0 { 1.0 - cos } else { (cos - self.margin).max(0.0) }
+            })
+            .collect();
+
+        let mut losses = Tensor::new(losses, Shape::from(IxDyn(&[batch_size])));
+        losses.device = output.device.clone();
+        losses
+    }
+
+    /// Calculates the gradient of the cosine embedding loss with respect to both embeddings.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The `[batch, 2, dim]` stacked embedding pairs.
+    /// * `target` - The `[batch]` labels, 1 for similar and -1 for dissimilar pairs.
+    ///
+    /// # Returns
+    ///
+    /// A `[batch, 2, dim]` tensor with the gradient of each embedding.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        let (batch_size, _) = embedding_groups(output, 2);
+        check_pair_targets(target, batch_size);
+
+        let mut grad = ArrayD::zeros(output.data.raw_dim());
+        for ((pair, &label), mut pair_grad) in
+            output.data.outer_iter().zip(target.data.iter()).zip(grad.outer_iter_mut())
+        {
+            let pair = pair.into_dimensionality::<Ix2>().unwrap();
+            let (first, second) = (pair.row(0), pair.row(1));
+            let (cos, first_norm, second_norm) = self.cosine(first, second);
+
+            // The loss is linear in the cosine, with slope -1, 1 or 0
+            let slope = if label > 0.0 {
+                -1.0
+            } else if cos > self.margin {
+                1.0
+            } else {
+                0.0
+            };
+
+            // d(cos)/dx1 = x2 / (|x1| |x2|) - cos * x1 / |x1|^2
+            let first_grad =
+                &second / (first_norm * second_norm) - &first * (cos / first_norm.powi(2));
+            let second_grad =
+                &first / (first_norm * second_norm) - &second * (cos / second_norm.powi(2));
+            pair_grad.index_axis_mut(Axis(0), 0).assign(&(first_grad * slope));
+            pair_grad.index_axis_mut(Axis(0), 1).assign(&(second_grad * slope));
+        }
+
+        Tensor { data: grad, device: output.device.clone() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the Cross-Entropy Loss function.
+///
+/// By default the output is expected to hold probabilities, e.g. from a `Dense` layer with a
+/// Softmax activation. With `from_logits` set, the output holds raw logits and the softmax is
+/// fused into the loss. In both cases the gradient of a sample is `softmax - target`, the
+/// gradient with respect to the logits, so it flows straight into the last `Dense` layer. For
+/// the same reason, probabilities from an `ActivationLayer` with a Softmax activation are
+/// rejected by `Sequential`; drop that layer and set `from_logits` instead.
+#[derive(Debug, Default)]
+pub struct CrossEntropyLoss {
+    from_logits: bool,
+    label_smoothing: f32,
+    options: LossOptions,
+}
+
+impl CrossEntropyLoss {
+    /// Creates a new CrossEntropyLoss instance.
+    pub fn new() -> Self {
+        Self { from_logits: false, label_smoothing: 0.0, options: LossOptions::default() }
+    }
+
+    /// Sets the label smoothing factor, which mixes the targets with the uniform distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `label_smoothing` - The label smoothing factor, in `[0, 1)`.
+    ///
+    /// # Returns
+    ///
+    /// The loss with label smoothing set.
+    pub fn with_label_smoothing(mut self, label_smoothing: f32) -> Self {
+        validate_label_smoothing(label_smoothing);
+        self.label_smoothing = label_smoothing;
+        self
+    }
+
+    /// Sets whether the output holds raw logits instead of probabilities.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_logits` - Whether to apply a fused softmax to the output.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `from_logits` set.
+    pub fn with_from_logits(mut self, from_logits: bool) -> Self {
+        self.from_logits = from_logits;
+        self
+    }
+
+    /// Clips the tensor to avoid issues with log(0) or division by zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `tensor` - The tensor to clip.
+    /// * `epsilon` - The epsilon value to use for clipping.
+    ///
+    /// # Returns
+    ///
+    /// The clipped tensor.
+    fn clip_tensor(&self, tensor: &Tensor, epsilon: f32) -> Tensor {
+        tensor.map(|x| x.clamp(epsilon, 1.0 - epsilon))
+    }
+}
+
+impl Loss for CrossEntropyLoss {
+    /// Calculates the cross-entropy loss of each sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities, or logits if `from_logits` is set.
+    /// * `target` - The true values.
+    ///
+    /// # Returns
+    ///
+    /// The cross-entropy loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let target = smooth_labels(target, self.label_smoothing, output.data.shape()[1]);
+
+        // Work in log space, either from the logits or from clipped probabilities
+        let log_probs = if self.from_logits {
+            log_softmax(output)
+        } else {
+            let epsilon = 1e-12;
+            self.clip_tensor(output, epsilon).map(|x| x.ln())
+        };
+
+        let losses: Vec<f32> = log_probs
+            .data
+            .outer_iter()
+            .zip(target.data.outer_iter())
+            .map(|(log_probs, target)| -(&log_probs * &target).sum())
+            .collect();
+
+        let mut losses = Tensor::new(losses, Shape::from(IxDyn(&[output.data.shape()[0]])));
+        losses.device = output.device.clone();
+        losses
+    }
+
+    /// Calculates the gradient of the cross-entropy loss with respect to the logits.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities, or logits if `from_logits` is set.
+    /// * `target` - The true values.
+    ///
+    /// # Returns
+    ///
+    /// The gradient `softmax - target` of each sample.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        let target = smooth_labels(target, self.label_smoothing, output.data.shape()[1]);
+        let probs = if self.from_logits { softmax(output) } else { output.clone() };
+
+        Tensor { data: &probs.data - &target.data, device: output.device.clone() }
+    }
+
+    fn fused_activation(&self) -> Option<&str> {
+        if self.from_logits { None } else { Some("SoftmaxActivation") }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the soft Dice Loss function for segmentation masks.
+///
+/// Each sample contributes `1 - (2 * sum(p * t) + smooth) / (sum(p) + sum(t) + smooth)` over all
+/// elements of its mask. The output holds probabilities unless `from_logits` is set; as with
+/// [`BinaryCrossEntropy`], the gradient is taken with respect to the logits of a Sigmoid, so
+/// probabilities must not come from an `ActivationLayer` with a Sigmoid activation.
+#[derive(Debug)]
+pub struct DiceLoss {
+    smooth: f32,
+    from_logits: bool,
+    options: LossOptions,
+}
+
+impl Default for DiceLoss {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiceLoss {
+    /// Creates a new DiceLoss instance with a smoothing term of 1.
+    pub fn new() -> Self {
+        Self { smooth: 1.0, from_logits: false, options: LossOptions::default() }
+    }
+
+    /// Sets the smoothing term added to the numerator and the denominator.
+    ///
+    /// # Arguments
+    ///
+    /// * `smooth` - The smoothing term. Must be positive.
+    ///
+    /// # Returns
+    ///
+    /// The loss with the smoothing term set.
+    pub fn with_smooth(mut self, smooth: f32) -> Self {
+        if smooth <= 0.0 {
+            panic!("Smooth must be positive.");
+        }
+        self.smooth = smooth;
+        self
+    }
+
+    /// Sets whether the output holds raw logits instead of probabilities.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_logits` - Whether to apply a fused sigmoid to the output.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `from_logits` set.
+    pub fn with_from_logits(mut self, from_logits: bool) -> Self {
+        self.from_logits = from_logits;
+        self
+    }
+}
+
+impl Loss for DiceLoss {
+    /// Calculates the Dice loss of each sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted mask probabilities, or logits if `from_logits` is set.
+    /// * `target` - The true mask, with values in `[0, 1]`.
+    ///
+    /// # Returns
+    ///
+    /// The Dice loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let probs = per_sample_rows(&sigmoid_probabilities(output, self.from_logits).data);
+        let target = per_sample_rows(&target.data);
+        let losses: Vec<f32> = probs
+            .outer_iter()
+            .zip(target.outer_iter())
+            .map(|(p, t)| {
+                let intersection = p.dot(&t);
+                1.0 - (2.0 * intersection + self.smooth) / (p.sum() + t.sum() + self.smooth)
+            })
+            .collect();
+
+        let mut losses = Tensor::new(losses, Shape::from(IxDyn(&[probs.nrows()])));
+        losses.device = output.device.clone();
+        losses
+    }
+
+    /// Calculates the gradient of the Dice loss with respect to the logits.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted mask probabilities, or logits if `from_logits` is set.
+    /// * `target` - The true mask, with values in `[0, 1]`.
+    ///
+    /// # Returns
+    ///
+    /// The gradient `-(2 * t * D - N) / D^2` with respect to the probabilities, where `N` and `D`
+    /// are the numerator and denominator of the Dice coefficient, times `p * (1 - p)`.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        let mut probs = per_sample_rows(&sigmoid_probabilities(output, self.from_logits).data);
+        let target = per_sample_rows(&target.data);
+        for (mut p, t) in probs.outer_iter_mut().zip(target.outer_iter()) {
+            let numerator = 2.0 * p.dot(&t) + self.smooth;
+            let denominator = p.sum() + t.sum() + self.smooth;
+            p.zip_mut_with(&t, |p, &t| {
+                let probs_grad = -(2.0 * t * denominator - numerator) / denominator.powi(2);
+                *p = probs_grad * *p * (1.0 - *p);
+            });
+        }
+
+        let grad = probs.into_shape_with_order(output.data.raw_dim()).unwrap();
+        Tensor { data: grad, device: output.device.clone() }
+    }
+
+    fn fused_activation(&self) -> Option<&str> {
+        if self.from_logits { None } else { Some("SigmoidActivation") }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the binary Focal Loss function.
+///
+/// Focal loss down-weights well-classified examples by `(1 - p_t)^gamma`, where `p_t` is the
+/// probability of the true label, and balances classes with `alpha`. Targets must be 0 or 1.
+/// The output holds probabilities unless `from_logits` is set; the gradient is taken with respect
+/// to the logits in both cases, so probabilities must not come from an `ActivationLayer` with a
+/// Sigmoid activation.
+#[derive(Debug)]
+pub struct FocalLoss {
+    alpha: f32,
+    gamma: f32,
+    from_logits: bool,
+    options: LossOptions,
+}
+
+impl Default for FocalLoss {
+    fn default() -> Self {
+        Self::new(0.25, 2.0)
+    }
+}
+
+impl FocalLoss {
+    /// Creates a new FocalLoss instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The weight of the positive class, in `[0, 1]`.
+    /// * `gamma` - The focusing parameter. Must be non-negative.
+    pub fn new(alpha: f32, gamma: f32) -> Self {
+        if !(0.0..=1.0).contains(&alpha) {
+            panic!("Alpha must be in [0, 1].");
+        }
+        if gamma < 0.0 {
+            panic!("Gamma must be non-negative.");
+        }
+        Self { alpha, gamma, from_logits: false, options: LossOptions::default() }
+    }
+
+    /// Sets whether the output holds raw logits instead of probabilities.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_logits` - Whether to apply a fused sigmoid to the output.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `from_logits` set.
+    pub fn with_from_logits(mut self, from_logits: bool) -> Self {
+        self.from_logits = from_logits;
+        self
+    }
+
+    /// Computes `p_t`, `ln(p_t)` and `alpha_t` for a single element.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probability or logit.
+    /// * `target` - The true label.
+    ///
+    /// # Returns
+    ///
+    /// A tuple `(p_t, ln(p_t), alpha_t)`.
+    fn focal_terms(&self, output: f32, target: f32) -> (f32, f32, f32) {
+        let alpha_t = self.alpha * target + (1.0 - self.alpha) * (1.0 - target);
+
+        let (p_t, log_p_t) = if self.from_logits {
+            // ln(p_t) is ln(sigmoid(x)) for positives and ln(sigmoid(-x)) for negatives
+            let log_p_t = target * log_sigmoid(output) + (1.0 - target) * log_sigmoid(-output);
+            (log_p_t.exp(), log_p_t)
+        } else {
+            let epsilon = 1e-7;
+            let p = output.clamp(epsilon, 1.0 - epsilon);
+            let p_t = target * p + (1.0 - target) * (1.0 - p);
+            (p_t, p_t.ln())
+        };
+
+        (p_t, log_p_t, alpha_t)
+    }
+}
+
+impl Loss for FocalLoss {
+    /// Calculates the focal loss of each sample, averaged over its elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities, or logits if `from_logits` is set.
+    /// * `target` - The true labels, 0 or 1.
+    ///
+    /// # Returns
+    ///
+    /// The focal loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let mut loss = output.data.clone();
+        loss.zip_mut_with(&target.data, |x, &t| {
+            let (p_t, log_p_t, alpha_t) = self.focal_terms(*x, t);
+            *x = -alpha_t * (1.0 - p_t).powf(self.gamma) * log_p_t;
+        });
+
+        mean_per_sample(loss, output)
+    }
+
+    /// Calculates the gradient of the focal loss with respect to the logits.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities, or logits if `from_logits` is set.
+    /// * `target` - The true labels, 0 or 1.
+    ///
+    /// # Returns
+    ///
+    /// The gradient `alpha_t * (2t - 1) * (1 - p_t)^gamma * (gamma * p_t * ln(p_t) - (1 - p_t))`,
+    /// divided by the number of elements per sample.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        let features = features_per_sample(output);
+        let mut grad = output.data.clone();
+        grad.zip_mut_with(&target.data, |x, &t| {
+            let (p_t, log_p_t, alpha_t) = self.focal_terms(*x, t);
+            let focal_grad = alpha_t
+                * (2.0 * t - 1.0)
+                * (1.0 - p_t).powf(self.gamma)
+                * (self.gamma * p_t * log_p_t - (1.0 - p_t));
+            *x = focal_grad / features;
+        });
+
+        Tensor { data: grad, device: output.device.clone() }
+    }
+
+    fn fused_activation(&self) -> Option<&str> {
+        if self.from_logits { None } else { Some("SigmoidActivation") }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the Gaussian Negative Log-Likelihood loss function.
+///
+/// For heteroscedastic regression the model predicts a mean and a variance per target. The
+/// output is a `[batch, 2 * d]` tensor whose first `d` columns are the means and last `d`
+/// columns the variances, for a `[batch, d]` target. Each element contributes
+/// `(ln(var) + (mean - target)^2 / var) / 2`, with the variance clamped to `epsilon`. With
+/// `log_variance` set, the last columns hold `ln(var)` instead, which needs no clamping.
+#[derive(Debug)]
+pub struct GaussianNLLLoss {
+    full: bool,
+    log_variance: bool,
+    epsilon: f32,
+    options: LossOptions,
+}
+
+impl Default for GaussianNLLLoss {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GaussianNLLLoss {
+    /// Creates a new GaussianNLLLoss instance.
+    pub fn new() -> Self {
+        Self { full: false, log_variance: false, epsilon: 1e-6, options: LossOptions::default() }
+    }
+
+    /// Sets whether to include the constant `ln(2 * pi) / 2` term.
+    ///
+    /// # Arguments
+    ///
+    /// * `full` - Whether to compute the full negative log-likelihood.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `full` set.
+    pub fn with_full(mut self, full: bool) -> Self {
+        self.full = full;
+        self
+    }
+
+    /// Sets whether the variance columns hold the log-variance.
+    ///
+    /// # Arguments
+    ///
+    /// * `log_variance` - Whether the output holds `ln(var)` instead of `var`.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `log_variance` set.
+    pub fn with_log_variance(mut self, log_variance: bool) -> Self {
+        self.log_variance = log_variance;
+        self
+    }
+
+    /// Sets the lower bound the variance is clamped to.
+    ///
+    /// # Arguments
+    ///
+    /// * `epsilon` - The minimum variance. Must be positive.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `epsilon` set.
+    pub fn with_epsilon(mut self, epsilon: f32) -> Self {
+        if epsilon <= 0.0 {
+            panic!("Epsilon must be positive.");
+        }
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Validates that the output holds a mean and a variance for each target.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The `[batch, 2 * d]` predicted means and variances.
+    /// * `target` - The `[batch, d]` targets.
+    ///
+    /// # Returns
+    ///
+    /// The number of targets `d` per sample.
+    fn targets_per_sample(&self, output: &Tensor, target: &Tensor) -> usize {
+        let (output_shape, target_shape) = (output.data.shape(), target.data.shape());
+        if output_shape.len() != 2
+            || target_shape.len() != 2
+            || output_shape[0] != target_shape[0]
+            || output_shape[1] != 2 * target_shape[1]
+        {
+            panic!(
+                "Expected output of shape [batch, 2 * d] for target of shape [batch, d], got output.shape = {:?}, target.shape = {:?}",
+                output_shape, target_shape
+            );
+        }
+        target_shape[1]
+    }
+
+    /// Converts a predicted variance column value into the variance.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The predicted variance or log-variance.
+    ///
+    /// # Returns
+    ///
+    /// The variance.
+    fn variance(&self, value: f32) -> f32 {
+        if self.log_variance { value.exp() } else { value.max(self.epsilon) }
+    }
+}
+
+impl Loss for GaussianNLLLoss {
+    /// Calculates the Gaussian negative log-likelihood of each sample, averaged over its targets.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The `[batch, 2 * d]` predicted means and variances.
+    /// * `target` - The `[batch, d]` targets.
+    ///
+    /// # Returns
+    ///
+    /// The Gaussian negative log-likelihood of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        let d = self.targets_per_sample(output, target);
+
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let constant = if self.full { 0.5 * (2.0 * std::f32::consts::PI).ln() } else { 0.0 };
+        let losses: Vec<f32> = output
+            .data
+            .outer_iter()
+            .zip(target.data.outer_iter())
+            .map(|(row, target)| {
+                let total: f32 = (0..d)
+                    .map(|j| {
+                        let variance = self.variance(row[d + j]);
+                        0.5 * (variance.ln() + (row[j] - target[j]).powi(2) / variance) + constant
+                    })
+                    .sum();
+                total / d as f32
+            })
+            .collect();
+
+        let mut losses = Tensor::new(losses, Shape::from(IxDyn(&[output.data.shape()[0]])));
+        losses.device = output.device.clone();
+        losses
+    }
+
+    /// Calculates the gradient of the Gaussian negative log-likelihood with respect to the output.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The `[batch, 2 * d]` predicted means and variances.
+    /// * `target` - The `[batch, d]` targets.
+    ///
+    /// # Returns
+    ///
+    /// A `[batch, 2 * d]` tensor with the gradients of the means and the variances.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        let d = self.targets_per_sample(output, target);
+
+        let mut grad = ArrayD::zeros(output.data.raw_dim());
+        for ((row, target), mut row_grad) in
+            output.data.outer_iter().zip(target.data.outer_iter()).zip(grad.outer_iter_mut())
+        {
+            for j in 0..d {
+                let variance = self.variance(row[d + j]);
+                let diff = row[j] - target[j];
+                row_grad[j] = diff / variance / d as f32;
+                row_grad[d + j] = if self.log_variance {
+                    0.5 * (1.0 - diff.powi(2) / variance) / d as f32
+                } else if row[d + j] > self.epsilon {
+                    0.5 * (1.0 / variance - diff.powi(2) / variance.powi(2)) / d as f32
+                } else {
+                    // The clamped variance does not depend on the output
+                    0.0
+                };
+            }
+        }
+
+        Tensor { data: grad, device: output.device.clone() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the Hinge Loss function.
+///
+/// The output holds raw scores and the targets are -1 or 1; targets of 0 are treated as -1.
+/// Each element contributes `max(1 - target * output, 0)`.
+#[derive(Debug, Default)]
+pub struct HingeLoss {
+    options: LossOptions,
+}
+
+impl HingeLoss {
+    /// Creates a new HingeLoss instance.
+    pub fn new() -> Self {
+        Self { options: LossOptions::default() }
+    }
+}
+
+impl Loss for HingeLoss {
+    /// Calculates the hinge loss of each sample, averaged over its elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted scores.
+    /// * `target` - The true labels, -1 (or 0) and 1.
+    ///
+    /// # Returns
+    ///
+    /// The hinge loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let mut loss = output.data.clone();
+        loss.zip_mut_with(&target.data, |x, &t| *x = (1.0 - hinge_label(t) * *x).max(0.0));
+
+        mean_per_sample(loss, output)
+    }
+
+    /// Calculates the gradient of the hinge loss with respect to the output tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted scores.
+    /// * `target` - The true labels, -1 (or 0) and 1.
+    ///
+    /// # Returns
+    ///
+    /// The gradient `-target` where the margin is violated, divided by the number of elements
+    /// per sample.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        let features = features_per_sample(output);
+        let mut grad = output.data.clone();
+        grad.zip_mut_with(&target.data, |x, &t| {
+            let t = hinge_label(t);
+            *x = if 1.0 - t * *x > 0.0 { -t / features } else { 0.0 };
+        });
+
+        Tensor { data: grad, device: output.device.clone() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+#[derive(Debug)]
+pub struct HuberLoss {
+    delta: f32,
+    options: LossOptions,
+}
+
+impl HuberLoss {
+    /// Creates a new HuberLoss instance with the specified delta value.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The delta value for the Huber loss.
+    pub fn new(delta: f32) -> Self {
+        if delta <= 0.0 {
+            panic!("Delta must be positive.");
+        }
+        Self { delta, options: LossOptions::default() }
+    }
+}
+
+impl Loss for HuberLoss {
+    /// Calculates the Huber loss of each sample, averaged over its elements.
+    ///
+    /// # Arguments
+    /// * `output` - The predicted tensor.
+    /// * `target` - The ground truth tensor.
+    ///
+    /// # Returns
+    ///
+    /// The Huber loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        // Step 1: Ensure the shapes of output and target match
+        check_shapes(output, target);
+
+        // Step 2: Check for NaN values in output and target
+        check_for_nan(target, output);
+
+        // Step 3: Compute the absolute differences
+        let diff = (&target.data - &output.data).mapv(|x| x.abs());
+
+        // Step 4: Compute the Huber loss per element
+        let huber_loss = diff.mapv(|x| {
+            if x <= self.delta { 0.5 * x.powi(2) } else { self.delta * (x - 0.5 * self.delta) }
+        });
+
+        // Step 5: Calculate the mean of the Huber loss values of each sample
+        mean_per_sample(huber_loss, output)
+    }
+
+    /// Calculates the gradient of the Huber loss with respect to the output tensor.
+    ///
+    /// # Arguments
+    /// * `output` - The output tensor from the model.
+    /// * `target` - The target tensor.
+    ///
+    /// # Returns
+    ///
+    /// A `Tensor` containing the gradient of the Huber loss with respect to the output tensor.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Calculate the difference
+        let diff = &output.data - &target.data;
+
+        // Compute the gradient
+        let gradient =
+            diff.mapv(|x| if x.abs() <= self.delta { x } else { self.delta * x.signum() });
+
+        // Normalize the gradient by the number of elements in each sample
+        let normalized_gradient = &gradient / features_per_sample(output);
+
+        Tensor { data: normalized_gradient, device: Device::default() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the soft Jaccard (IoU) Loss function for segmentation masks.
+///
+/// Each sample contributes `1 - (I + smooth) / (sum(p) + sum(t) - I + smooth)` with the soft
+/// intersection `I = sum(p * t)` over all elements of its mask. The output holds probabilities
+/// unless `from_logits` is set; as with [`DiceLoss`], the gradient is taken with respect to the
+/// logits of a Sigmoid, so probabilities must not come from an `ActivationLayer` with a Sigmoid
+/// activation.
+#[derive(Debug)]
+pub struct JaccardLoss {
+    smooth: f32,
+    from_logits: bool,
+    options: LossOptions,
+}
+
+impl Default for JaccardLoss {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JaccardLoss {
+    /// Creates a new JaccardLoss instance with a smoothing term of 1.
+    pub fn new() -> Self {
+        Self { smooth: 1.0, from_logits: false, options: LossOptions::default() }
+    }
+
+    /// Sets the smoothing term added to the intersection and the union.
+    ///
+    /// # Arguments
+    ///
+    /// * `smooth` - The smoothing term. Must be positive.
+    ///
+    /// # Returns
+    ///
+    /// The loss with the smoothing term set.
+    pub fn with_smooth(mut self, smooth: f32) -> Self {
+        if smooth <= 0.0 {
+            panic!("Smooth must be positive.");
+        }
+        self.smooth = smooth;
+        self
+    }
+
+    /// Sets whether the output holds raw logits instead of probabilities.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_logits` - Whether to apply a fused sigmoid to the output.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `from_logits` set.
+    pub fn with_from_logits(mut self, from_logits: bool) -> Self {
+        self.from_logits = from_logits;
+        self
+    }
+}
+
+impl Loss for JaccardLoss {
+    /// Calculates the Jaccard loss of each sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted mask probabilities, or logits if `from_logits` is set.
+    /// * `target` - The true mask, with values in `[0, 1]`.
+    ///
+    /// # Returns
+    ///
+    /// The Jaccard loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let probs = per_sample_rows(&sigmoid_probabilities(output, self.from_logits).data);
+        let target = per_sample_rows(&target.data);
+        let losses: Vec<f32> = probs
+            .outer_iter()
+            .zip(target.outer_iter())
+            .map(|(p, t)| {
+                let intersection = p.dot(&t);
+                let union = p.sum() + t.sum() - intersection;
+                1.0 - (intersection + self.smooth) / (union + self.smooth)
+            })
+            .collect();
+
+        let mut losses = Tensor::new(losses, Shape::from(IxDyn(&[probs.nrows()])));
+        losses.device = output.device.clone();
+        losses
+    }
+
+    /// Calculates the gradient of the Jaccard loss with respect to the logits.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted mask probabilities, or logits if `from_logits` is set.
+    /// * `target` - The true mask, with values in `[0, 1]`.
+    ///
+    /// # Returns
+    ///
+    /// The gradient `-(t * U - I * (1 - t)) / U^2` with respect to the probabilities, where `I`
+    /// and `U` are the smoothed intersection and union, times `p * (1 - p)`.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        let mut probs = per_sample_rows(&sigmoid_probabilities(output, self.from_logits).data);
+        let target = per_sample_rows(&target.data);
+        for (mut p, t) in probs.outer_iter_mut().zip(target.outer_iter()) {
+            let intersection = p.dot(&t);
+            let numerator = intersection + self.smooth;
+            let union = p.sum() + t.sum() - intersection + self.smooth;
+            p.zip_mut_with(&t, |p, &t| {
+                let probs_grad = -(t * union - numerator * (1.0 - t)) / union.powi(2);
+                *p = probs_grad * *p * (1.0 - *p);
+            });
+        }
+
+        let grad = probs.into_shape_with_order(output.data.raw_dim()).unwrap();
+        Tensor { data: grad, device: output.device.clone() }
+    }
+
+    fn fused_activation(&self) -> Option<&str> {
+        if self.from_logits { None } else { Some("SigmoidActivation") }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the Jensen-Shannon Divergence loss function.
+///
+/// Measures the symmetric divergence `(KL(p || m) + KL(t || m)) / 2` between the predicted
+/// distribution `p` and the target distribution `t`, where `m = (p + t) / 2`. As with
+/// [`CrossEntropyLoss`], the output holds probabilities unless `from_logits` is set, and the
+/// gradient is taken with respect to the logits, so probabilities must not come from an
+/// `ActivationLayer` with a Softmax activation.
+#[derive(Debug, Default)]
+pub struct JensenShannonDivergence {
+    from_logits: bool,
+    options: LossOptions,
+}
+
+impl JensenShannonDivergence {
+    /// Creates a new JensenShannonDivergence instance.
+    pub fn new() -> Self {
+        Self { from_logits: false, options: LossOptions::default() }
+    }
+
+    /// Sets whether the output holds raw logits instead of probabilities.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_logits` - Whether to apply a fused softmax to the output.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `from_logits` set.
+    pub fn with_from_logits(mut self, from_logits: bool) -> Self {
+        self.from_logits = from_logits;
+        self
+    }
+
+    /// Returns the predicted probabilities, clipped away from zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities, or logits if `from_logits` is set.
+    ///
+    /// # Returns
+    ///
+    /// The predicted probabilities.
+    fn probabilities(&self, output: &Tensor) -> Tensor {
+        let epsilon = 1e-7;
+        let probs = if self.from_logits { softmax(output) } else { output.clone() };
+        probs.map(|p| p.max(epsilon))
+    }
+}
+
+impl Loss for JensenShannonDivergence {
+    /// Calculates the Jensen-Shannon divergence of each sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities, or logits if `from_logits` is set.
+    /// * `target` - The target distributions.
+    ///
+    /// # Returns
+    ///
+    /// The Jensen-Shannon divergence of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let probs = self.probabilities(output);
+        let mut divergence = probs.data.clone();
+        divergence.zip_mut_with(&target.data, |p, &t| {
+            let m = (*p + t) / 2.0;
+            let target_term = if t > 0.0 { t * (t / m).ln() } else { 0.0 };
+            *p = 0.5 * (*p * (*p / m).ln() + target_term);
+        });
+
+        let losses: Vec<f32> = divergence.outer_iter().map(|row| row.sum()).collect();
+        let mut losses = Tensor::new(losses, Shape::from(IxDyn(&[output.data.shape()[0]])));
+        losses.device = output.device.clone();
+        losses
+    }
+
+    /// Calculates the gradient of the Jensen-Shannon divergence with respect to the logits.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities, or logits if `from_logits` is set.
+    /// * `target` - The target distributions.
+    ///
+    /// # Returns
+    ///
+    /// The gradient `ln(p / m) / 2` back-propagated through the softmax.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        let probs = self.probabilities(output);
+        let mut probs_grad = probs.data.clone();
+        probs_grad.zip_mut_with(&target.data, |p, &t| *p = 0.5 * (*p / ((*p + t) / 2.0)).ln());
+
+        Tensor { data: softmax_backward(&probs.data, &probs_grad), device: output.device.clone() }
+    }
+
+    fn fused_activation(&self) -> Option<&str> {
+        if self.from_logits { None } else { Some("SoftmaxActivation") }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the Kullback-Leibler Divergence loss function.
+///
+/// Measures `KL(t || p) = sum(t * ln(t / p))` from the target distribution `t`, e.g. the soft
+/// labels of a teacher model, to the predicted distribution `p`. As with [`CrossEntropyLoss`],
+/// the output holds probabilities unless `from_logits` is set, and the gradient `p - t` is taken
+/// with respect to the logits, so probabilities must not come from an `ActivationLayer` with a
+/// Softmax activation.
+#[derive(Debug, Default)]
+pub struct KLDivergence {
+    from_logits: bool,
+    options: LossOptions,
+}
+
+impl KLDivergence {
+    /// Creates a new KLDivergence instance.
+    pub fn new() -> Self {
+        Self { from_logits: false, options: LossOptions::default() }
+    }
+
+    /// Sets whether the output holds raw logits instead of probabilities.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_logits` - Whether to apply a fused softmax to the output.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `from_logits` set.
+    pub fn with_from_logits(mut self, from_logits: bool) -> Self {
+        self.from_logits = from_logits;
+        self
+    }
+}
+
+impl Loss for KLDivergence {
+    /// Calculates the Kullback-Leibler divergence of each sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities, or logits if `from_logits` is set.
+    /// * `target` - The target distributions.
+    ///
+    /// # Returns
+    ///
+    /// The Kullback-Leibler divergence of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        // Work in log space, either from the logits or from clipped probabilities
+        let log_probs = if self.from_logits {
+            log_softmax(output)
+        } else {
+            let epsilon = 1e-7;
+            output.map(|p| p.max(epsilon).ln())
+        };
+
+        // Elements with a zero target contribute nothing
+        let mut divergence = log_probs.data;
+        divergence.zip_mut_with(&target.data, |log_p, &t| {
+            *log_p = if t > 0.0 { t * (t.ln() - *log_p) } else { 0.0 };
+        });
+
+        let losses: Vec<f32> = divergence.outer_iter().map(|row| row.sum()).collect();
+        let mut losses = Tensor::new(losses, Shape::from(IxDyn(&[output.data.shape()[0]])));
+        losses.device = output.device.clone();
+        losses
+    }
+
+    /// Calculates the gradient of the Kullback-Leibler divergence with respect to the logits.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities, or logits if `from_logits` is set.
+    /// * `target` - The target distributions.
+    ///
+    /// # Returns
+    ///
+    /// The gradient `softmax - target` of each sample.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        let probs = if self.from_logits { softmax(output) } else { output.clone() };
+
+        Tensor { data: &probs.data - &target.data, device: output.device.clone() }
+    }
+
+    fn fused_activation(&self) -> Option<&str> {
+        if self.from_logits { None } else { Some("SoftmaxActivation") }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the Log-Cosh Loss function.
+///
+/// Each element contributes `ln(cosh(output - target))`, which behaves like half the squared
+/// error for small errors and like the absolute error for large ones.
+#[derive(Debug, Default)]
+pub struct LogCoshLoss {
+    options: LossOptions,
+}
+
+impl LogCoshLoss {
+    /// Creates a new LogCoshLoss instance.
+    pub fn new() -> Self {
+        Self { options: LossOptions::default() }
+    }
+}
+
+impl Loss for LogCoshLoss {
+    /// Calculates the log-cosh loss of each sample, averaged over its elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted tensor.
+    /// * `target` - The ground truth tensor.
+    ///
+    /// # Returns
+    ///
+    /// The log-cosh loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        // ln(cosh(x)) = |x| + ln(1 + exp(-2|x|)) - ln(2) does not overflow for large |x|
+        let loss = (&output.data - &target.data)
+            .mapv(|x| x.abs() + (-2.0 * x.abs()).exp().ln_1p() - std::f32::consts::LN_2);
+
+        mean_per_sample(loss, output)
+    }
+
+    /// Calculates the gradient of the log-cosh loss with respect to the output tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted tensor.
+    /// * `target` - The ground truth tensor.
+    ///
+    /// # Returns
+    ///
+    /// The gradient `tanh(output - target)`, divided by the number of elements per sample.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        let features = features_per_sample(output);
+        let gradient = (&output.data - &target.data).mapv(|x| x.tanh() / features);
+
+        Tensor { data: gradient, device: output.device.clone() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MeanAbsoluteError {
+    options: LossOptions,
+}
+
+impl MeanAbsoluteError {
+    pub fn new() -> Self {
+        Self { options: LossOptions::default() }
+    }
+}
+
+impl Loss for MeanAbsoluteError {
+    /// Calculates the mean absolute error (MAE) of each sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted tensor.
+    /// * `target` - The ground truth tensor.
+    ///
+    /// # Returns
+    ///
+    /// The mean absolute error of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        // Step 1: Ensure the shapes of output and target match
+        check_shapes(output, target);
+
+        // Step 2: Check for NaN values in output and target
+        check_for_nan(target, output);
+
+        // Step 3: Compute the absolute differences
+        let abs_diff = (&target.data - &output.data).mapv(|x| x.abs());
+
+        // Step 4: Calculate the mean of the absolute differences of each sample
+        mean_per_sample(abs_diff, output)
+    }
+
+    /// Calculates the gradient of the loss with respect to the output tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output tensor from the model.
+    /// * `target` - The target tensor.
+    ///
+    /// # Returns
+    ///
+    /// A `Tensor` containing the gradient of the loss with respect to the output tensor.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Compute the gradient
+        let features = features_per_sample(output);
+        let diff = &output.data - &target.data;
+        let gradient = diff.mapv(|x| if x > 0.0 { 1.0 } else { -1.0 } / features);
+
+        Tensor { data: gradient, device: Device::default() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the Mean Squared Loss function.
+#[derive(Debug, Default)]
+pub struct MeanSquaredLoss {
+    options: LossOptions,
+}
+
+impl MeanSquaredLoss {
+    /// Creates a new instance of `MeanSquaredLoss`.
+    pub fn new() -> Self {
+        Self { options: LossOptions::default() }
+    }
+}
+
+impl Loss for MeanSquaredLoss {
+    /// Calculates the mean squared error (MSE) of each sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted tensor.
+    /// * `target` - The ground truth tensor.
+    ///
+    /// # Returns
+    ///
+    /// The mean squared error of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        // Step 1: Ensure the shapes of output and target match
+        check_shapes(output, target);
+
+        // Step 2: Check for NaN values in output and target
+        check_for_nan(target, output);
+
+        // Step 3: Compute the squared differences
+        let squared_diff = (&target.data - &output.data).mapv(|x| x.powi(2));
+
+        // Step 4: Calculate the mean of the squared differences of each sample
+        mean_per_sample(squared_diff, output)
+    }
+
+    /// Calculates the gradient of the loss with respect to the output tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output tensor from the model.
+    /// * `target` - The target tensor.
+    ///
+    /// # Returns
+    ///
+    /// A `Tensor` containing the gradient of the loss with respect to the output tensor.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Calculate the number of elements in each sample
+        let features = features_per_sample(output);
+
+        // Compute the gradient
+        let diff = &output.data - &target.data;
+        let gradient = &diff * 2.0 / features;
+
+        Tensor { data: gradient, device: Device::default() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the multi-class Margin Loss function.
+///
+/// The output holds raw class scores and the targets are class indices or one-hot rows. Each
+/// sample contributes `sum(max(margin - x[y] + x[j], 0)^p) / classes` over the classes `j` other
+/// than its class `y`, with `p` being 1 or 2.
+#[derive(Debug)]
+pub struct MultiClassMarginLoss {
+    margin: f32,
+    squared: bool,
+    options: LossOptions,
+}
+
+impl Default for MultiClassMarginLoss {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl MultiClassMarginLoss {
+    /// Creates a new MultiClassMarginLoss instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `margin` - The margin the score of the true class must exceed the others by.
+    pub fn new(margin: f32) -> Self {
+        if margin <= 0.0 {
+            panic!("Margin must be positive.");
+        }
+        Self { margin, squared: false, options: LossOptions::default() }
+    }
+
+    /// Sets whether margin violations are squared.
+    ///
+    /// # Arguments
+    ///
+    /// * `squared` - Whether to use `p = 2` instead of `p = 1`.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `squared` set.
+    pub fn with_squared(mut self, squared: bool) -> Self {
+        self.squared = squared;
+        self
     }
 
-    /// Calculates the gradient of the cross-entropy loss with respect to the predicted values.
+    /// Converts the target into class indices, validating them against the output.
     ///
     /// # Arguments
     ///
-    /// * `output` - The predicted values.
-    /// * `target` - The true values.
+    /// * `output` - The `[batch, classes]` predicted scores.
+    /// * `target` - The class indices or one-hot encoded targets.
     ///
     /// # Returns
     ///
-    /// The gradient of the cross-entropy loss with respect to the predicted values.
-    fn calculate_loss_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
-        // Ensure shapes match
-        if output.shape().raw_dim() != target.shape().raw_dim() {
+    /// A vector with the class index of each sample.
+    fn class_indices(&self, output: &Tensor, target: &Tensor) -> Vec<usize> {
+        if output.data.ndim() != 2 {
+            panic!("Expected output of shape [batch, classes], got {:?}", output.data.shape());
+        }
+
+        let num_classes = output.data.shape()[1];
+        let classes = target_classes(target);
+        if classes.len() != output.data.shape()[0] {
             panic!(
                 "Shape mismatch: output.shape = {:?}, target.shape = {:?}",
-                output.shape(),
-                target.shape()
+                output.data.shape(),
+                target.data.shape()
             );
         }
-
-        // Clip predictions to avoid division by zero
-        let epsilon = 1e-12;
-        let clipped_pred = self.clip_tensor(output, epsilon);
-
-        // Compute gradient: (predictions - targets) / batch_size
-        let grad_data: Vec<f32> = clipped_pred
-            .data
-            .iter()
-            .zip(target.data.iter())
-            .map(|(p, t)| (p - t) / target.data.len() as f32)
-            .collect();
-
-        // Create Tensor from grad_data
-        let grad_shape = clipped_pred.shape().raw_dim().as_array_view().to_vec(); // Ensure correct shape
-        Tensor {
-            data: ndarray::Array::from_shape_vec(ndarray::IxDyn(&grad_shape), grad_data)
-                .expect("Failed to create gradient tensor"),
-            device: Device::default(),
+        if let Some(&class) = classes.iter().find(|&&class| class >= num_classes) {
+            panic!(
+                "Invalid class index in target: {}, exceeds number of classes: {}",
+                class, num_classes
+            );
         }
+        classes
     }
 }
 
-#[derive(Debug)]
-pub struct HuberLoss {
-    delta: f32,
-}
-
-impl HuberLoss {
-    /// Creates a new HuberLoss instance with the specified delta value.
+impl Loss for MultiClassMarginLoss {
+    /// Calculates the multi-class margin loss of each sample.
     ///
     /// # Arguments
     ///
-    /// * `delta` - The delta value for the Huber loss.
-    pub fn new(delta: f32) -> Self {
-        if delta <= 0.0 {
-            panic!("Delta must be positive.");
-        }
-        Self { delta }
-    }
-}
-
-impl Loss for HuberLoss {
-    /// Calculates the Huber loss between two tensors.
-    ///
-    /// # Arguments
-    /// * `y_true` - The ground truth tensor.
-    /// * `y_pred` - The predicted tensor.
+    /// * `output` - The predicted scores.
+    /// * `target` - The class indices or one-hot encoded targets.
     ///
     /// # Returns
     ///
-    /// The Huber loss between the two tensors.
-    fn calculate_loss(&self, y_true: &Tensor, y_pred: &Tensor) -> f32 {
-        // Step 1: Ensure the shapes of y_true and y_pred match
-        if y_true.data.shape() != y_pred.data.shape() {
-            panic!(
-                "Shape mismatch: y_true.shape = {:?}, y_pred.shape = {:?}",
-                y_true.data.shape(),
-                y_pred.data.shape()
-            );
-        }
-
-        // Step 2: Check for NaN values in y_true and y_pred
-        check_for_nan(y_true, y_pred);
-
-        // Step 3: Compute the absolute differences
-        let diff = (&y_true.data - &y_pred.data).mapv(|x| x.abs());
+    /// The multi-class margin loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
 
-        // Step 4: Compute the Huber loss per element
-        let huber_loss = diff.mapv(|x| {
-            if x <= self.delta { 0.5 * x.powi(2) } else { self.delta * (x - 0.5 * self.delta) }
-        });
+        let classes = self.class_indices(output, target);
+        let num_classes = output.data.shape()[1] as f32;
 
-        // Step 5: Calculate the mean of the Huber loss values
-        if huber_loss.is_empty() {
-            panic!("Cannot calculate loss: no dataset in input tensors");
-        }
+        let losses: Vec<f32> = output
+            .data
+            .outer_iter()
+            .zip(classes.iter())
+            .map(|(scores, &class)| {
+                let violations =
+                    scores.iter().enumerate().filter(|&(j, _)| j != class).map(|(_, &score)| {
+                        let violation = (self.margin - scores[class] + score).max(0.0);
+                        if self.squared { violation.powi(2) } else { violation }
+                    });
+                violations.sum::<f32>() / num_classes
+            })
+            .collect();
 
-        huber_loss.mean().expect("Mean computation failed unexpectedly")
+        let mut losses = Tensor::new(losses, Shape::from(IxDyn(&[classes.len()])));
+        losses.device = output.device.clone();
+        losses
     }
 
-    /// Calculates the gradient of the Huber loss with respect to the output tensor.
+    /// Calculates the gradient of the multi-class margin loss with respect to the output tensor.
     ///
     /// # Arguments
-    /// * `output` - The output tensor from the model.
-    /// * `target` - The target tensor.
+    ///
+    /// * `output` - The predicted scores.
+    /// * `target` - The class indices or one-hot encoded targets.
     ///
     /// # Returns
     ///
-    /// A `Tensor` containing the gradient of the Huber loss with respect to the output tensor.
-    fn calculate_loss_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
-        // Ensure shapes match
-        if output.data.shape() != target.data.shape() {
-            panic!(
-                "Shape mismatch: output.shape = {:?}, target.shape = {:?}",
-                output.data.shape(),
-                target.data.shape()
-            );
+    /// A `Tensor` containing the gradient of each sample's loss.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        let classes = self.class_indices(output, target);
+        let num_classes = output.data.shape()[1] as f32;
+
+        let mut grad = ArrayD::zeros(output.data.raw_dim());
+        for ((scores, &class), mut row) in
+            output.data.outer_iter().zip(classes.iter()).zip(grad.outer_iter_mut())
+        {
+            for (j, &score) in scores.iter().enumerate().filter(|&(j, _)| j != class) {
+                let violation = (self.margin - scores[class] + score).max(0.0);
+                if violation > 0.0 {
+                    let slope = if self.squared { 2.0 * violation } else { 1.0 } / num_classes;
+                    row[j] += slope;
+                    row[class] -= slope;
+                }
+            }
         }
 
-        // Calculate the difference
-        let diff = &output.data - &target.data;
-
-        // Compute the gradient
-        let gradient =
-            diff.mapv(|x| if x.abs() <= self.delta { x } else { self.delta * x.signum() });
+        Tensor { data: grad, device: output.device.clone() }
+    }
 
-        // Normalize the gradient by the number of elements
-        let total_elements = output.data.len() as f32;
-        let normalized_gradient = &gradient / total_elements;
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
 
-        Tensor { data: normalized_gradient, device: Device::default() }
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
     }
 }
 
+/// A struct representing the Poisson Negative Log-Likelihood loss function.
+///
+/// For count regression, each element contributes `exp(x) - t * x` when the output holds the
+/// log-rate `x` (the default), or `x - t * ln(x + epsilon)` when it holds the rate itself. With
+/// `full` set, the Stirling approximation `t * ln(t) - t + ln(2 * pi * t) / 2` of `ln(t!)` is
+/// added for targets above 1.
 #[derive(Debug)]
-pub struct MeanAbsoluteError;
+pub struct PoissonNLLLoss {
+    log_input: bool,
+    full: bool,
+    epsilon: f32,
+    options: LossOptions,
+}
 
-impl Default for MeanAbsoluteError {
+impl Default for PoissonNLLLoss {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl MeanAbsoluteError {
+impl PoissonNLLLoss {
+    /// Creates a new PoissonNLLLoss instance that expects log-rates.
     pub fn new() -> Self {
-        Self
+        Self { log_input: true, full: false, epsilon: 1e-8, options: LossOptions::default() }
     }
-}
 
-impl Loss for MeanAbsoluteError {
-    /// Calculates the mean absolute error (MAE) between two tensors.
+    /// Sets whether the output holds the log-rate instead of the rate.
     ///
     /// # Arguments
     ///
-    /// * `y_true` - The ground truth tensor.
-    /// * `y_pred` - The predicted tensor.
+    /// * `log_input` - Whether the output holds `ln(rate)`.
     ///
     /// # Returns
     ///
-    /// The mean absolute error between the two tensors.
-    fn calculate_loss(&self, y_true: &Tensor, y_pred: &Tensor) -> f32 {
-        // Step 1: Ensure the shapes of y_true and y_pred match
-        if y_true.data.shape() != y_pred.data.shape() {
-            panic!(
-                "Shape mismatch: y_true.shape = {:?}, y_pred.shape = {:?}",
-                y_true.data.shape(),
-                y_pred.data.shape()
-            );
-        }
-
-        // Step 2: Check for NaN values in y_true and y_pred
-        check_for_nan(y_true, y_pred);
+    /// The loss with `log_input` set.
+    pub fn with_log_input(mut self, log_input: bool) -> Self {
+        self.log_input = log_input;
+        self
+    }
 
-        // Step 3: Compute the absolute differences
-        let abs_diff = (&y_true.data - &y_pred.data).mapv(|x| x.abs());
+    /// Sets whether to add the Stirling approximation of `ln(target!)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `full` - Whether to compute the full negative log-likelihood.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `full` set.
+    pub fn with_full(mut self, full: bool) -> Self {
+        self.full = full;
+        self
+    }
+}
 
-        // Step 4: Calculate the mean of the absolute differences
-        if abs_diff.is_empty() {
-            panic!("Cannot calculate loss: no dataset in input tensors");
-        }
+impl Loss for PoissonNLLLoss {
+    /// Calculates the Poisson negative log-likelihood of each sample, averaged over its elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted log-rates, or rates if `log_input` is unset.
+    /// * `target` - The observed counts.
+    ///
+    /// # Returns
+    ///
+    /// The Poisson negative log-likelihood of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
+
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let mut loss = output.data.clone();
+        loss.zip_mut_with(&target.data, |x, &t| {
+            let nll =
+                if self.log_input { x.exp() - t * *x } else { *x - t * (*x + self.epsilon).ln() };
+            let stirling = if self.full && t > 1.0 {
+                t * t.ln() - t + 0.5 * (2.0 * std::f32::consts::PI * t).ln()
+            } else {
+                0.0
+            };
+            *x = nll + stirling;
+        });
 
-        abs_diff.mean().expect("Mean computation failed unexpectedly")
+        mean_per_sample(loss, output)
     }
 
-    /// Calculates the gradient of the loss with respect to the output tensor.
+    /// Calculates the gradient of the Poisson negative log-likelihood with respect to the output.
     ///
     /// # Arguments
     ///
-    /// * `output` - The output tensor from the model.
-    /// * `target` - The target tensor.
+    /// * `output` - The predicted log-rates, or rates if `log_input` is unset.
+    /// * `target` - The observed counts.
     ///
     /// # Returns
     ///
-    /// A `Tensor` containing the gradient of the loss with respect to the output tensor.
-    fn calculate_loss_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
-        // Ensure shapes match
-        if output.data.shape() != target.data.shape() {
-            panic!(
-                "Shape mismatch: output.shape = {:?}, target.shape = {:?}",
-                output.data.shape(),
-                target.data.shape()
-            );
-        }
+    /// The gradient `exp(x) - t`, or `1 - t / (x + epsilon)` for rates, divided by the number of
+    /// elements per sample.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
 
-        // Compute the gradient
-        let diff = &output.data - &target.data;
-        let gradient = diff.mapv(|x| if x > 0.0 { 1.0 } else { -1.0 });
+        let features = features_per_sample(output);
+        let mut grad = output.data.clone();
+        grad.zip_mut_with(&target.data, |x, &t| {
+            let nll_grad = if self.log_input { x.exp() - t } else { 1.0 - t / (*x + self.epsilon) };
+            *x = nll_grad / features;
+        });
 
-        Tensor { data: gradient, device: Device::default() }
+        Tensor { data: grad, device: output.device.clone() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
     }
 }
 
-/// A struct representing the Mean Squared Loss function.
+/// A struct representing the Quantile (pinball) Loss function.
+///
+/// For forecasting intervals the model predicts one value per quantile along the last axis of
+/// the output. The target either matches the output shape or has a last axis of 1, in which case
+/// it is compared against every quantile. With the residual `r = target - output`, each element
+/// contributes `max(q * r, (q - 1) * r)` for its quantile `q`.
 #[derive(Debug)]
-pub struct MeanSquaredLoss;
+pub struct QuantileLoss {
+    quantiles: Vec<f32>,
+    options: LossOptions,
+}
 
-impl Default for MeanSquaredLoss {
+impl Default for QuantileLoss {
     fn default() -> Self {
-        Self::new()
+        Self::new(vec![0.5])
     }
 }
 
-impl MeanSquaredLoss {
-    /// Creates a new instance of `MeanSquaredLoss`.
-    pub fn new() -> Self {
-        Self
+impl QuantileLoss {
+    /// Creates a new QuantileLoss instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `quantiles` - The quantile predicted by each entry of the output's last axis, each in
+    ///   `(0, 1)`.
+    pub fn new(quantiles: Vec<f32>) -> Self {
+        if quantiles.is_empty() {
+            panic!("At least one quantile is required.");
+        }
+        if quantiles.iter().any(|&q| q <= 0.0 || q >= 1.0) {
+            panic!("Quantiles must be in (0, 1).");
+        }
+        Self { quantiles, options: LossOptions::default() }
     }
-}
 
-impl Loss for MeanSquaredLoss {
-    /// Calculates the mean squared error (MSE) between two tensors.
+    /// Flattens the output and the target into rows of one entry per quantile.
     ///
     /// # Arguments
     ///
-    /// * `y_true` - The ground truth tensor.
-    /// * `y_pred` - The predicted tensor.
+    /// * `output` - The predicted quantiles.
+    /// * `target` - The true values, with the output's shape or a last axis of 1.
     ///
     /// # Returns
     ///
-    /// The mean squared error between the two tensors.
-    fn calculate_loss(&self, y_true: &Tensor, y_pred: &Tensor) -> f32 {
-        // Step 1: Ensure the shapes of y_true and y_pred match
-        if y_true.data.shape() != y_pred.data.shape() {
+    /// The `[n, quantiles]` output and the `[n, quantiles]` or `[n, 1]` target.
+    fn quantile_rows(&self, output: &Tensor, target: &Tensor) -> (Array2<f32>, Array2<f32>) {
+        let num_quantiles = self.quantiles.len();
+        let output_shape = output.data.shape();
+        let target_shape = target.data.shape();
+        let broadcast = output_shape[..output_shape.len() - 1]
+            == target_shape[..target_shape.len().saturating_sub(1)]
+            && target_shape.last() == Some(&1);
+        if output_shape.last() != Some(&num_quantiles)
+            || (output_shape != target_shape && !broadcast)
+        {
             panic!(
-                "Shape mismatch: y_true.shape = {:?}, y_pred.shape = {:?}",
-                y_true.data.shape(),
-                y_pred.data.shape()
+                "Shape mismatch: output.shape = {:?}, target.shape = {:?} for {} quantiles",
+                output_shape, target_shape, num_quantiles
             );
         }
 
-        // Step 2: Check for NaN values in y_true and y_pred
-        check_for_nan(y_true, y_pred);
-
-        // Step 3: Compute the squared differences
-        let squared_diff = (&y_true.data - &y_pred.data).mapv(|x| x.powi(2));
+        let rows = output.data.len() / num_quantiles;
+        let output = output
+            .data
+            .as_standard_layout()
+            .into_owned()
+            .into_shape_with_order((rows, num_quantiles))
+            .unwrap();
+        let target = target
+            .data
+            .as_standard_layout()
+            .into_owned()
+            .into_shape_with_order((rows, target_shape[target_shape.len() - 1]))
+            .unwrap();
+        (output, target)
+    }
+}
 
-        // Step 4: Calculate the mean of the squared differences
-        if squared_diff.is_empty() {
-            panic!("Cannot calculate loss: no dataset in input tensors");
+impl Loss for QuantileLoss {
+    /// Calculates the quantile loss of each sample, averaged over its elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted quantiles.
+    /// * `target` - The true values, with the output's shape or a last axis of 1.
+    ///
+    /// # Returns
+    ///
+    /// The quantile loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let (predictions, targets) = self.quantile_rows(output, target);
+        let mut loss = predictions.clone();
+        for (mut row, target) in loss.outer_iter_mut().zip(targets.outer_iter()) {
+            for (j, (x, &q)) in row.iter_mut().zip(self.quantiles.iter()).enumerate() {
+                let residual = target[j.min(target.len() - 1)] - *x;
+                *x = (q * residual).max((q - 1.0) * residual);
+            }
         }
 
-        squared_diff.mean().expect("Mean computation failed unexpectedly")
+        let loss = loss.into_shape_with_order(output.data.raw_dim()).unwrap();
+        mean_per_sample(loss, output)
     }
 
-    /// Calculates the gradient of the loss with respect to the output tensor.
+    /// Calculates the gradient of the quantile loss with respect to the output tensor.
     ///
     /// # Arguments
     ///
-    /// * `output` - The output tensor from the model.
-    /// * `target` - The target tensor.
+    /// * `output` - The predicted quantiles.
+    /// * `target` - The true values, with the output's shape or a last axis of 1.
     ///
     /// # Returns
     ///
-    /// A `Tensor` containing the gradient of the loss with respect to the output tensor.
-    fn calculate_loss_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
-        // Ensure shapes match
-        if output.data.shape() != target.data.shape() {
-            panic!(
-                "Shape mismatch: output.shape = {:?}, target.shape = {:?}",
-                output.data.shape(),
-                target.data.shape()
-            );
+    /// The gradient `-q` for under-predictions and `1 - q` for over-predictions, divided by the
+    /// number of elements per sample.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        let (predictions, targets) = self.quantile_rows(output, target);
+        let features = features_per_sample(output);
+
+        let mut grad = predictions;
+        for (mut row, target) in grad.outer_iter_mut().zip(targets.outer_iter()) {
+            for (j, (x, &q)) in row.iter_mut().zip(self.quantiles.iter()).enumerate() {
+                let residual = target[j.min(target.len() - 1)] - *x;
+                *x = if residual > 0.0 {
+                    -q / features
+                } else if residual < 0.0 {
+                    (1.0 - q) / features
+                } else {
+                    0.0
+                };
+            }
         }
 
-        // Calculate the total number of elements in the tensor
-        let total_elements = output.data.len() as f32;
+        let grad = grad.into_shape_with_order(output.data.raw_dim()).unwrap();
+        Tensor { data: grad, device: output.device.clone() }
+    }
 
-        // Compute the gradient
-        let diff = &output.data - &target.data;
-        let gradient = &diff * 2.0 / total_elements;
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
 
-        Tensor { data: gradient, device: Device::default() }
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
     }
 }
 
 /// A struct representing the Sparse Categorical Cross-Entropy Loss function.
-#[derive(Debug)]
-pub struct SparseCategoricalCrossEntropyLoss;
-
-impl Default for SparseCategoricalCrossEntropyLoss {
-    fn default() -> Self {
-        Self::new()
-    }
+///
+/// Targets are class indices or one-hot rows. As with [`CrossEntropyLoss`], the output holds
+/// probabilities unless `from_logits` is set, and the gradient is taken with respect to the logits,
+/// so the probabilities must not come from an `ActivationLayer` with a Softmax activation.
+#[derive(Debug, Default)]
+pub struct SparseCategoricalCrossEntropyLoss {
+    from_logits: bool,
+    label_smoothing: f32,
+    options: LossOptions,
 }
 
 impl SparseCategoricalCrossEntropyLoss {
     /// Creates a new SparseCategoricalCrossEntropyLoss instance.
     pub fn new() -> Self {
-        Self
+        Self { from_logits: false, label_smoothing: 0.0, options: LossOptions::default() }
+    }
+
+    /// Sets whether the output holds raw logits instead of probabilities.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_logits` - Whether to apply a fused softmax to the output.
+    ///
+    /// # Returns
+    ///
+    /// The loss with `from_logits` set.
+    pub fn with_from_logits(mut self, from_logits: bool) -> Self {
+        self.from_logits = from_logits;
+        self
+    }
+
+    /// Sets the label smoothing factor, which mixes the targets with the uniform distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `label_smoothing` - The label smoothing factor, in `[0, 1)`.
+    ///
+    /// # Returns
+    ///
+    /// The loss with label smoothing set.
+    pub fn with_label_smoothing(mut self, label_smoothing: f32) -> Self {
+        validate_label_smoothing(label_smoothing);
+        self.label_smoothing = label_smoothing;
+        self
     }
 
     /// Clips the tensor to avoid issues with log(0) or division by zero.
@@ -524,132 +2910,314 @@ fn preprocess_one_hot(&self, y_true: &Tensor) -> Tensor {
         new_tensor.device = y_true.device.clone();
         new_tensor
     }
+
+    /// Converts the target into class indices, validating them against the output.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted values.
+    /// * `target` - The class indices or one-hot encoded targets.
+    ///
+    /// # Returns
+    ///
+    /// A vector with the class index of each sample.
+    fn class_indices(&self, output: &Tensor, target: &Tensor) -> Vec<usize> {
+        let target = if target.shape().raw_dim().ndim() == 2 {
+            // Handle one-hot encoding
+            if target.shape().raw_dim()[1] != output.shape().raw_dim()[1] {
+                panic!(
+                    "If target is one-hot encoded, it must have the same number of classes as output. \nGot target: {:?}\nGot output: {:?}",
+                    target.shape(),
+                    output.shape()
+                );
+            }
+            let target = self.preprocess_one_hot(target);
+
+            self.one_hot_to_indices(&target)
+        } else {
+            // Validate 1D tensor
+            if target.shape().raw_dim().ndim() != 1 {
+                panic!(
+                    "Expected target to be a 1D tensor of class indices, but got shape: {:?}",
+                    target.shape()
+                );
+            }
+            target.clone()
+        };
+
+        let num_classes = output.shape().raw_dim()[1];
+        target
+            .data
+            .iter()
+            .map(|&class| {
+                let class = class as usize;
+                if class >= num_classes {
+                    panic!(
+                        "Invalid class index in target: {}, exceeds number of classes: {}",
+                        class, num_classes
+                    );
+                }
+                class
+            })
+            .collect()
+    }
+}
+
+impl Loss for SparseCategoricalCrossEntropyLoss {
+    /// Calculates the sparse categorical cross-entropy loss of each sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities, or logits if `from_logits` is set.
+    /// * `target` - The true values (indices of the correct classes or one-hot encoded).
+    ///
+    /// # Returns
+    ///
+    /// The sparse categorical cross-entropy loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let classes = self.class_indices(output, target);
+
+        // Work in log space, either from the logits or from clipped probabilities
+        let log_probs = if self.from_logits {
+            log_softmax(output)
+        } else {
+            let epsilon = 1e-12;
+            self.clip_tensor(output, epsilon).map(|x| x.ln())
+        };
+
+        // With label smoothing, part of the target mass is spread uniformly over all classes
+        let num_classes = output.data.shape()[1] as f32;
+        let smoothing = self.label_smoothing;
+        let losses: Vec<f32> = classes
+            .iter()
+            .zip(log_probs.data.outer_iter())
+            .map(|(&class, log_probs)| {
+                -(1.0 - smoothing) * log_probs[class] - smoothing / num_classes * log_probs.sum()
+            })
+            .collect();
+
+        let mut losses = Tensor::new(losses, Shape::from(IxDyn(&[classes.len()])));
+        losses.device = output.device.clone();
+        losses
+    }
+
+    /// Calculates the gradient of the sparse categorical cross-entropy loss with respect to the logits.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted probabilities, or logits if `from_logits` is set.
+    /// * `target` - The true values (indices of the correct classes or one-hot encoded).
+    ///
+    /// # Returns
+    ///
+    /// The gradient `softmax - one_hot(target)` of each sample, with the one-hot target smoothed.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        let classes = self.class_indices(output, target);
+
+        let num_classes = output.data.shape()[1] as f32;
+        let smoothing = self.label_smoothing;
+        let mut grad = if self.from_logits { softmax(output) } else { output.clone() };
+        grad.data.mapv_inplace(|p| p - smoothing / num_classes);
+        for (i, &class) in classes.iter().enumerate() {
+            grad.data[[i, class]] -= 1.0 - smoothing;
+        }
+
+        Tensor { data: grad.data, device: output.device.clone() }
+    }
+
+    fn fused_activation(&self) -> Option<&str> {
+        if self.from_logits { None } else { Some("SoftmaxActivation") }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/// A struct representing the Squared Hinge Loss function.
+///
+/// The output holds raw scores and the targets are -1 or 1; targets of 0 are treated as -1.
+/// Each element contributes `max(1 - target * output, 0)^2`.
+#[derive(Debug, Default)]
+pub struct SquaredHingeLoss {
+    options: LossOptions,
+}
+
+impl SquaredHingeLoss {
+    /// Creates a new SquaredHingeLoss instance.
+    pub fn new() -> Self {
+        Self { options: LossOptions::default() }
+    }
 }
 
-impl Loss for SparseCategoricalCrossEntropyLoss {
-    /// Calculates the sparse categorical cross-entropy loss between the true and predicted values.
+impl Loss for SquaredHingeLoss {
+    /// Calculates the squared hinge loss of each sample, averaged over its elements.
     ///
     /// # Arguments
     ///
-    /// * `y_true` - The true values (indices of the correct classes or one-hot encoded).
-    /// * `y_pred` - The predicted values (probability distributions).
+    /// * `output` - The predicted scores.
+    /// * `target` - The true labels, -1 (or 0) and 1.
     ///
     /// # Returns
     ///
-    /// The sparse categorical cross-entropy loss.
-    fn calculate_loss(&self, y_true: &Tensor, y_pred: &Tensor) -> f32 {
-        let y_true = if y_true.shape().raw_dim().ndim() == 2 {
-            // Handle one-hot encoding
-            if y_true.shape().raw_dim()[1] != y_pred.shape().raw_dim()[1] {
-                panic!(
-                    "If y_true is one-hot encoded, it must have the same number of classes as y_pred. \nGot y_true: {:?}\nGot y_pred: {:?}",
-                    y_true.shape(),
-                    y_pred.shape()
-                );
-            }
-            let y_true = self.preprocess_one_hot(y_true);
+    /// The squared hinge loss of each sample.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
 
-            self.one_hot_to_indices(&y_true)
-        } else {
-            // Validate 1D tensor
-            if y_true.shape().raw_dim().ndim() != 1 {
-                panic!(
-                    "Expected y_true to be a 1D tensor of class indices, but got shape: {:?}",
-                    y_true.shape()
-                );
-            }
-            y_true.clone()
-        };
+        // Check for NaN values in output and target
+        check_for_nan(output, target);
+
+        let mut loss = output.data.clone();
+        loss.zip_mut_with(&target.data, |x, &t| {
+            *x = (1.0 - hinge_label(t) * *x).max(0.0).powi(2);
+        });
 
-        // Check for NaN values in y_true and y_pred
-        check_for_nan(&y_true, y_pred);
+        mean_per_sample(loss, output)
+    }
 
-        // Clip predictions to avoid log(0)
-        let epsilon = 1e-12;
-        let clipped_pred = self.clip_tensor(y_pred, epsilon);
+    /// Calculates the gradient of the squared hinge loss with respect to the output tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The predicted scores.
+    /// * `target` - The true labels, -1 (or 0) and 1.
+    ///
+    /// # Returns
+    ///
+    /// The gradient `-2 * target * max(1 - target * output, 0)`, divided by the number of
+    /// elements per sample.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        check_shapes(output, target);
 
-        // Compute cross-entropy loss
-        let batch_size = y_true.shape().raw_dim()[0];
-        let num_classes = y_pred.shape().raw_dim()[1];
-        let mut loss = 0.0;
+        let features = features_per_sample(output);
+        let mut grad = output.data.clone();
+        grad.zip_mut_with(&target.data, |x, &t| {
+            let t = hinge_label(t);
+            *x = -2.0 * t * (1.0 - t * *x).max(0.0) / features;
+        });
 
-        for i in 0..batch_size {
-            let true_class = y_true.data[i] as usize;
-            if true_class >= num_classes {
-                panic!(
-                    "Invalid class index in y_true: {}, exceeds number of classes: {}",
-                    true_class, num_classes
-                );
-            }
-            loss -= clipped_pred.data[[i, true_class]].ln();
-        }
+        Tensor { data: grad, device: output.device.clone() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
 
-        loss / batch_size as f32
+/// A struct representing the Triplet Margin Loss function.
+///
+/// The output stacks an anchor, a positive and a negative embedding per sample as a
+/// `[batch, 3, dim]` tensor. Each triplet contributes `max(d(a, p) - d(a, n) + margin, 0)` with
+/// the Euclidean distance `d`. The target is not used beyond determining class weights.
+#[derive(Debug)]
+pub struct TripletMarginLoss {
+    margin: f32,
+    options: LossOptions,
+}
+
+impl Default for TripletMarginLoss {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl TripletMarginLoss {
+    /// Creates a new TripletMarginLoss instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `margin` - The margin the negative must be farther from the anchor than the positive.
+    pub fn new(margin: f32) -> Self {
+        if margin <= 0.0 {
+            panic!("Margin must be positive.");
+        }
+        Self { margin, options: LossOptions::default() }
     }
+}
 
-    /// Calculates the gradient of the sparse categorical cross-entropy loss with respect to the predicted values.
+impl Loss for TripletMarginLoss {
+    /// Calculates the triplet margin loss of each triplet.
     ///
     /// # Arguments
     ///
-    /// * `output` - The predicted values (probability distributions).
-    /// * `target` - The true values (indices of the correct classes).
+    /// * `output` - The `[batch, 3, dim]` stacked anchor, positive and negative embeddings.
+    /// * `target` - Unused labels with one entry per triplet.
     ///
     /// # Returns
     ///
-    /// The gradient of the sparse categorical cross-entropy loss with respect to the predicted values.
-    fn calculate_loss_grad(&self, output: &Tensor, target: &Tensor) -> Tensor {
-        let target = if target.shape().raw_dim().ndim() == 2 {
-            // Handle one-hot encoding
-            if target.shape().raw_dim()[1] != output.shape().raw_dim()[1] {
-                panic!(
-                    "If target is one-hot encoded, it must have the same number of classes as output. Got: {:?}",
-                    target.shape()
-                );
-            }
-            let target = self.preprocess_one_hot(target);
+    /// The triplet margin loss of each triplet.
+    fn calculate_sample_losses(&self, output: &Tensor, target: &Tensor) -> Tensor {
+        let (batch_size, _) = embedding_groups(output, 3);
+        check_for_nan(output, target);
 
-            self.one_hot_to_indices(&target)
-        } else {
-            // Validate 1D tensor
-            if target.shape().raw_dim().ndim() != 1 {
-                panic!(
-                    "Expected target to be a 1D tensor of class indices, but got shape: {:?}",
-                    target.shape()
-                );
-            }
-            target.clone()
-        };
+        let losses: Vec<f32> = output
+            .data
+            .outer_iter()
+            .map(|triplet| {
+                let triplet = triplet.into_dimensionality::<Ix2>().unwrap();
+                let (anchor, positive, negative) = (triplet.row(0), triplet.row(1), triplet.row(2));
+                (euclidean_distance(anchor, positive) - euclidean_distance(anchor, negative)
+                    + self.margin)
+                    .max(0.0)
+            })
+            .collect();
 
-        let batch_size = target.shape().raw_dim()[0];
-        let num_classes = output.shape().raw_dim()[1];
-        let epsilon = 1e-12;
-        let clipped_pred = self.clip_tensor(output, epsilon);
+        let mut losses = Tensor::new(losses, Shape::from(IxDyn(&[batch_size])));
+        losses.device = output.device.clone();
+        losses
+    }
 
-        let mut grad_data = vec![0.0; clipped_pred.data.len()];
-        for i in 0..batch_size {
-            let true_class = target.data[i] as usize;
-            if true_class >= num_classes {
-                panic!(
-                    "Invalid class index in target: {}, exceeds number of classes: {}",
-                    true_class, num_classes
-                );
-            }
-            for j in 0..num_classes {
-                let pred = clipped_pred.data[[i, j]];
-                grad_data[i * num_classes + j] = if j == true_class {
-                    (pred - 1.0) / batch_size as f32
-                } else {
-                    pred / batch_size as f32
-                };
+    /// Calculates the gradient of the triplet margin loss with respect to all three embeddings.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The `[batch, 3, dim]` stacked anchor, positive and negative embeddings.
+    /// * `target` - Unused labels with one entry per triplet.
+    ///
+    /// # Returns
+    ///
+    /// A `[batch, 3, dim]` tensor with the gradient of each embedding.
+    fn calculate_sample_losses_grad(&self, output: &Tensor, _target: &Tensor) -> Tensor {
+        embedding_groups(output, 3);
+
+        let mut grad = ArrayD::zeros(output.data.raw_dim());
+        for (triplet, mut triplet_grad) in output.data.outer_iter().zip(grad.outer_iter_mut()) {
+            let triplet = triplet.into_dimensionality::<Ix2>().unwrap();
+            let (anchor, positive, negative) = (triplet.row(0), triplet.row(1), triplet.row(2));
+            let positive_distance = euclidean_distance(anchor, positive);
+            let negative_distance = euclidean_distance(anchor, negative);
+            if positive_distance - negative_distance + self.margin <= 0.0 {
+                continue;
             }
-        }
 
-        let grad_shape = clipped_pred.shape().raw_dim().as_array_view().to_vec();
-        Tensor {
-            data: ndarray::Array::from_shape_vec(ndarray::IxDyn(&grad_shape), grad_data)
-                .expect("Failed to create gradient tensor"),
-            device: Device::default(),
+            // d(d(a, b))/da = (a - b) / d(a, b)
+            let positive_grad = (&anchor - &positive) / positive_distance;
+            let negative_grad = (&anchor - &negative) / negative_distance;
+            triplet_grad.index_axis_mut(Axis(0), 0).assign(&(&positive_grad - &negative_grad));
+            triplet_grad.index_axis_mut(Axis(0), 1).assign(&-positive_grad);
+            triplet_grad.index_axis_mut(Axis(0), 2).assign(&negative_grad);
         }
+
+        Tensor { data: grad, device: output.device.clone() }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
     }
 }
 
@@ -660,6 +3228,7 @@ mod tests {
     use ndarray::{IxDyn, Shape};
 
     use super::*;
+    use crate::deep_learning::activations::Activation;
 
     #[test]
     fn test_cross_entropy_loss() {
@@ -667,7 +3236,7 @@ fn test_cross_entropy_loss() {
         let y_pred = Tensor::new(vec![0.7, 0.2, 0.1], Shape::from(IxDyn(&[1, 3])));
 
         let loss = CrossEntropyLoss::new();
-        let calculated_loss = loss.calculate_loss(&y_true, &y_pred);
+        let calculated_loss = loss.calculate_loss(&y_pred, &y_true);
 
         let expected_loss = -(1.0 * (0.7f32.ln())) / 1.0;
 
@@ -687,7 +3256,7 @@ fn test_cross_entropy_loss_grad() {
         let loss = CrossEntropyLoss::new();
         let grad = loss.calculate_loss_grad(&y_pred, &y_true);
 
-        let expected_grad = vec![-0.1, 0.06666667, 0.03333334]; // Adjusted to correct gradient
+        let expected_grad = vec![-0.3, 0.2, 0.1]; // (softmax - target) / batch_size
         assert!(
             grad.data.iter().zip(expected_grad.iter()).all(|(a, b)| (a - b).abs() < 1e-6),
             "Expected gradient: {:?}, got: {:?}",
@@ -852,7 +3421,7 @@ fn test_sparse_categorical_cross_entropy_loss() {
             Shape::from(IxDyn(&[3, 3])),
         );
         let loss = SparseCategoricalCrossEntropyLoss::new();
-        let loss_value = loss.calculate_loss(&y_true, &y_pred);
+        let loss_value = loss.calculate_loss(&y_pred, &y_true);
         assert_eq!(loss_value, 1.0336976);
     }
 
@@ -875,4 +3444,612 @@ fn test_preprocess_one_hot() {
         let result = loss.preprocess_one_hot(&y_true);
         assert_eq!(result.data.iter().cloned().collect::<Vec<f32>>(), vec![1.0, 0.0, 0.0]);
     }
+
+    /// Checks a loss gradient against central finite differences of the loss.
+    fn assert_loss_grad_matches(loss: &dyn Loss, output: &Tensor, target: &Tensor) {
+        let grad = loss.calculate_loss_grad(output, target);
+        let epsilon = 1e-2;
+
+        for i in 0..output.data.len() {
+            let mut plus = output.clone();
+            let mut minus = output.clone();
+            plus.data.as_slice_mut().unwrap()[i] += epsilon;
+            minus.data.as_slice_mut().unwrap()[i] -= epsilon;
+            let numeric = (loss.calculate_loss(&plus, target)
+                - loss.calculate_loss(&minus, target))
+                / (2.0 * epsilon);
+            let analytic = grad.data.as_slice().unwrap()[i];
+            assert!(
+                (numeric - analytic).abs() < 1e-3,
+                "Gradient mismatch at {}: numeric = {}, analytic = {}",
+                i,
+                numeric,
+                analytic
+            );
+        }
+    }
+
+    #[test]
+    fn test_log_softmax_is_stable() {
+        let logits = Tensor::new(vec![1000.0, 0.0, -1000.0], Shape::from(IxDyn(&[1, 3])));
+        let log_probs = log_softmax(&logits);
+        assert!(log_probs.data.iter().all(|x| x.is_finite()));
+        assert_eq!(log_probs.to_vec(), vec![0.0, -1000.0, -2000.0]);
+        assert_eq!(softmax(&logits).to_vec(), vec![1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_cross_entropy_loss_from_logits() {
+        let logits = Tensor::new(vec![2.0, 1.0, 0.1, 0.5, 2.5, -1.0], Shape::from(IxDyn(&[2, 3])));
+        let target = Tensor::new(vec![1.0, 0.0, 0.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 3])));
+
+        let fused = CrossEntropyLoss::new().with_from_logits(true);
+        let probabilities = CrossEntropyLoss::new();
+        let expected = probabilities.calculate_loss(&softmax(&logits), &target);
+
+        assert!((fused.calculate_loss(&logits, &target) - expected).abs() < 1e-5);
+        assert_loss_grad_matches(&fused, &logits, &target);
+    }
+
+    #[test]
+    fn test_cross_entropy_loss_from_extreme_logits() {
+        let logits = Tensor::new(vec![1000.0, -1000.0], Shape::from(IxDyn(&[1, 2])));
+        let target = Tensor::new(vec![0.0, 1.0], Shape::from(IxDyn(&[1, 2])));
+
+        let loss = CrossEntropyLoss::new().with_from_logits(true);
+        assert_eq!(loss.calculate_loss(&logits, &target), 2000.0);
+        assert_eq!(loss.calculate_loss_grad(&logits, &target).to_vec(), vec![1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_sparse_categorical_cross_entropy_loss_from_logits() {
+        let logits = Tensor::new(vec![2.0, 1.0, 0.1, 0.5, 2.5, -1.0], Shape::from(IxDyn(&[2, 3])));
+        let indices = Tensor::new(vec![0.0, 2.0], Shape::from(IxDyn(&[2])));
+        let one_hot = Tensor::new(vec![1.0, 0.0, 0.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 3])));
+
+        let sparse = SparseCategoricalCrossEntropyLoss::new().with_from_logits(true);
+        let dense = CrossEntropyLoss::new().with_from_logits(true);
+
+        let expected = dense.calculate_loss(&logits, &one_hot);
+        assert!((sparse.calculate_loss(&logits, &indices) - expected).abs() < 1e-6);
+        assert!((sparse.calculate_loss(&logits, &one_hot) - expected).abs() < 1e-6);
+        assert_eq!(
+            sparse.calculate_loss_grad(&logits, &indices),
+            dense.calculate_loss_grad(&logits, &one_hot)
+        );
+        assert_loss_grad_matches(&sparse, &logits, &indices);
+    }
+
+    /// Logits away from zero and saturation, with binary labels.
+    fn binary_logits_and_targets() -> (Tensor, Tensor) {
+        let logits =
+            Tensor::new(vec![2.0, -1.5, 0.3, -0.2, 3.0, -4.0], Shape::from(IxDyn(&[3, 2])));
+        let target = Tensor::new(vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0], Shape::from(IxDyn(&[3, 2])));
+        (logits, target)
+    }
+
+    #[test]
+    fn test_binary_cross_entropy() {
+        let output = Tensor::new(vec![0.9, 0.2], Shape::from(IxDyn(&[2, 1])));
+        let target = Tensor::new(vec![1.0, 0.0], Shape::from(IxDyn(&[2, 1])));
+        let loss = BinaryCrossEntropy::new();
+
+        let expected = -(0.9f32.ln() + 0.8f32.ln()) / 2.0;
+        assert!((loss.calculate_loss(&output, &target) - expected).abs() < 1e-6);
+
+        let grad = loss.calculate_loss_grad(&output, &target).to_vec();
+        assert!((grad[0] + 0.05).abs() < 1e-6 && (grad[1] - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_binary_cross_entropy_with_saturated_probabilities() {
+        let output = Tensor::new(vec![0.0, 1.0], Shape::from(IxDyn(&[1, 2])));
+        let target = Tensor::new(vec![1.0, 0.0], Shape::from(IxDyn(&[1, 2])));
+        let loss = BinaryCrossEntropy::new().calculate_loss(&output, &target);
+        assert!(loss.is_finite());
+    }
+
+    #[test]
+    fn test_binary_cross_entropy_with_logits() {
+        let (logits, target) = binary_logits_and_targets();
+        let fused = BinaryCrossEntropyWithLogits::new();
+
+        // Matches the probability-based loss, and its gradient
+        let probabilities = SigmoidActivation::new().activate(&logits);
+        let expected = BinaryCrossEntropy::new().calculate_loss(&probabilities, &target);
+        assert!((fused.calculate_loss(&logits, &target) - expected).abs() < 1e-5);
+        assert_loss_grad_matches(&fused, &logits, &target);
+    }
+
+    #[test]
+    fn test_binary_cross_entropy_with_logits_pos_weight() {
+        let (logits, target) = binary_logits_and_targets();
+        let loss = BinaryCrossEntropyWithLogits::new().with_pos_weight(3.0);
+        assert_loss_grad_matches(&loss, &logits, &target);
+
+        // Only positive labels are scaled
+        let logits = Tensor::new(vec![0.0, 0.0], Shape::from(IxDyn(&[1, 2])));
+        let target = Tensor::new(vec![1.0, 0.0], Shape::from(IxDyn(&[1, 2])));
+        let expected = (3.0 * 2f32.ln() + 2f32.ln()) / 2.0;
+        assert!((loss.calculate_loss(&logits, &target) - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_binary_cross_entropy_with_extreme_logits() {
+        let logits =
+            Tensor::new(vec![1000.0, -1000.0, 1000.0, -1000.0], Shape::from(IxDyn(&[2, 2])));
+        let target = Tensor::new(vec![1.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let loss = BinaryCrossEntropyWithLogits::new();
+
+        // Two correct predictions contribute 0, two wrong ones contribute 1000 each
+        assert_eq!(loss.calculate_loss(&logits, &target), 500.0);
+        assert_eq!(
+            loss.calculate_loss_grad(&logits, &target).to_vec(),
+            vec![0.0, 0.0, 0.25, -0.25]
+        );
+    }
+
+    #[test]
+    fn test_focal_loss_reduces_to_binary_cross_entropy() {
+        let (logits, target) = binary_logits_and_targets();
+        let focal = FocalLoss::new(0.5, 0.0).with_from_logits(true);
+        let bce = BinaryCrossEntropyWithLogits::new();
+
+        let expected = 0.5 * bce.calculate_loss(&logits, &target);
+        assert!((focal.calculate_loss(&logits, &target) - expected).abs() < 1e-6);
+
+        let expected_grad = bce.calculate_loss_grad(&logits, &target).mul_scalar(0.5);
+        for (a, b) in
+            focal.calculate_loss_grad(&logits, &target).to_vec().iter().zip(expected_grad.to_vec())
+        {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_focal_loss_grad() {
+        let (logits, target) = binary_logits_and_targets();
+        let focal = FocalLoss::new(0.25, 2.0).with_from_logits(true);
+        assert_loss_grad_matches(&focal, &logits, &target);
+
+        // The probability path agrees with the logits path
+        let probabilities = SigmoidActivation::new().activate(&logits);
+        let focal_probabilities = FocalLoss::new(0.25, 2.0);
+        assert!(
+            (focal_probabilities.calculate_loss(&probabilities, &target)
+                - focal.calculate_loss(&logits, &target))
+            .abs()
+                < 1e-5
+        );
+    }
+
+    #[test]
+    fn test_focal_loss_with_extreme_logits() {
+        let logits =
+            Tensor::new(vec![1000.0, -1000.0, 1000.0, -1000.0], Shape::from(IxDyn(&[2, 2])));
+        let target = Tensor::new(vec![1.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let focal = FocalLoss::new(0.25, 2.0).with_from_logits(true);
+
+        let loss = focal.calculate_loss(&logits, &target);
+        let grad = focal.calculate_loss_grad(&logits, &target);
+        assert!(loss.is_finite());
+        assert!(grad.data.iter().all(|x| x.is_finite()));
+        assert_eq!(grad.to_vec()[0], 0.0);
+    }
+
+    fn mse_outputs_and_targets() -> (Tensor, Tensor) {
+        let output = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], Shape::from(IxDyn(&[2, 2])));
+        let target = Tensor::new(vec![0.0; 4], Shape::from(IxDyn(&[2, 2])));
+        (output, target)
+    }
+
+    #[test]
+    fn test_loss_reduction_modes() {
+        let (output, target) = mse_outputs_and_targets();
+
+        let mean = MeanSquaredLoss::new();
+        let sum = MeanSquaredLoss::new().with_reduction(Reduction::Sum);
+        let none = MeanSquaredLoss::new().with_reduction(Reduction::None);
+
+        assert_eq!(mean.calculate_losses(&output, &target, None).to_vec(), vec![7.5]);
+        assert_eq!(sum.calculate_losses(&output, &target, None).to_vec(), vec![15.0]);
+        assert_eq!(none.calculate_losses(&output, &target, None).to_vec(), vec![2.5, 12.5]);
+        assert_eq!(sum.calculate_loss(&output, &target), 15.0);
+
+        // The sum gradient is the mean gradient scaled by the batch size
+        let mean_grad = mean.calculate_loss_grad(&output, &target).to_vec();
+        let sum_grad = sum.calculate_loss_grad(&output, &target).to_vec();
+        assert_eq!(mean_grad, vec![0.5, 1.0, 1.5, 2.0]);
+        assert_eq!(sum_grad, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_sample_weights_scale_loss_and_grad() {
+        let (output, target) = mse_outputs_and_targets();
+        let weights = Tensor::new(vec![1.0, 0.0], Shape::from(IxDyn(&[2])));
+        let loss = MeanSquaredLoss::new();
+
+        assert_eq!(loss.calculate_weighted_loss(&output, &target, Some(&weights)), 1.25);
+        assert_eq!(
+            loss.calculate_weighted_loss_grad(&output, &target, Some(&weights)).to_vec(),
+            vec![0.5, 1.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected 2 sample weights, got 3")]
+    fn test_sample_weights_with_mismatch() {
+        let (output, target) = mse_outputs_and_targets();
+        let weights = Tensor::new(vec![1.0; 3], Shape::from(IxDyn(&[3])));
+
+        MeanSquaredLoss::new().calculate_weighted_loss(&output, &target, Some(&weights));
+    }
+
+    #[test]
+    fn test_class_weights_for_cross_entropy() {
+        let logits = Tensor::new(vec![2.0, 1.0, 0.5, 1.5], Shape::from(IxDyn(&[2, 2])));
+        let target = Tensor::new(vec![1.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let unweighted = CrossEntropyLoss::new().with_from_logits(true);
+        let weighted =
+            CrossEntropyLoss::new().with_from_logits(true).with_class_weights(vec![1.0, 3.0]);
+
+        let losses = unweighted
+            .with_reduction(Reduction::None)
+            .calculate_losses(&logits, &target, None)
+            .to_vec();
+        let expected = (losses[0] + 3.0 * losses[1]) / 2.0;
+        assert!((weighted.calculate_loss(&logits, &target) - expected).abs() < 1e-6);
+
+        assert_loss_grad_matches(&weighted, &logits, &target);
+    }
+
+    #[test]
+    fn test_class_weights_combine_with_sample_weights() {
+        let output = Tensor::new(vec![0.7, 0.2, 0.1, 0.1, 0.3, 0.6], Shape::from(IxDyn(&[2, 3])));
+        let target = Tensor::new(vec![0.0, 2.0], Shape::from(IxDyn(&[2])));
+        let weights = Tensor::new(vec![2.0, 0.5], Shape::from(IxDyn(&[2])));
+        let loss = SparseCategoricalCrossEntropyLoss::new()
+            .with_class_weights(vec![1.0, 1.0, 4.0])
+            .with_reduction(Reduction::None);
+
+        let losses = loss.calculate_losses(&output, &target, Some(&weights)).to_vec();
+        assert!((losses[0] - 2.0 * -(0.7f32).ln()).abs() < 1e-6);
+        assert!((losses[1] - 2.0 * -(0.6f32).ln()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_class_weights_for_binary_targets() {
+        let logits = Tensor::new(vec![2.0, -1.5, 0.3, -0.2], Shape::from(IxDyn(&[4, 1])));
+        let target = Tensor::new(vec![1.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[4, 1])));
+        let loss = BinaryCrossEntropyWithLogits::new().with_class_weights(vec![0.5, 2.0]);
+
+        let losses = BinaryCrossEntropyWithLogits::new()
+            .with_reduction(Reduction::None)
+            .calculate_losses(&logits, &target, None)
+            .to_vec();
+        let expected =
+            (2.0 * losses[0] + 0.5 * losses[1] + 0.5 * losses[2] + 2.0 * losses[3]) / 4.0;
+        assert!((loss.calculate_loss(&logits, &target) - expected).abs() < 1e-6);
+
+        assert_loss_grad_matches(&loss, &logits, &target);
+    }
+
+    fn distributions() -> (Tensor, Tensor) {
+        let logits = Tensor::new(vec![0.5, -1.0, 2.0, 0.0, 1.0, -0.5], Shape::from(IxDyn(&[2, 3])));
+        let target = Tensor::new(vec![0.2, 0.3, 0.5, 0.6, 0.4, 0.0], Shape::from(IxDyn(&[2, 3])));
+        (logits, target)
+    }
+
+    #[test]
+    fn test_kl_divergence() {
+        let output = Tensor::new(vec![0.25, 0.75], Shape::from(IxDyn(&[1, 2])));
+        let target = Tensor::new(vec![0.5, 0.5], Shape::from(IxDyn(&[1, 2])));
+
+        let expected = 0.5 * (0.5f32 / 0.25).ln() + 0.5 * (0.5f32 / 0.75).ln();
+        assert!((KLDivergence::new().calculate_loss(&output, &target) - expected).abs() < 1e-6);
+        assert!(KLDivergence::new().calculate_loss(&target, &target).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_kl_divergence_grad() {
+        let (logits, target) = distributions();
+        let loss = KLDivergence::new().with_from_logits(true);
+
+        assert_loss_grad_matches(&loss, &logits, &target);
+        assert_eq!(loss.fused_activation(), None);
+        assert_eq!(KLDivergence::new().fused_activation(), Some("SoftmaxActivation"));
+    }
+
+    #[test]
+    fn test_jensen_shannon_divergence() {
+        let first = Tensor::new(vec![0.25, 0.75], Shape::from(IxDyn(&[1, 2])));
+        let second = Tensor::new(vec![0.5, 0.5], Shape::from(IxDyn(&[1, 2])));
+        let disjoint = Tensor::new(vec![1.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let swapped = Tensor::new(vec![0.0, 1.0, 1.0, 0.0], Shape::from(IxDyn(&[2, 2])));
+        let loss = JensenShannonDivergence::new();
+
+        // Symmetric, and bounded by ln(2) for disjoint distributions
+        let forward = loss.calculate_loss(&first, &second);
+        let backward = loss.calculate_loss(&second, &first);
+        assert!((forward - backward).abs() < 1e-6);
+        assert!((loss.calculate_loss(&disjoint, &swapped) - 2f32.ln()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_jensen_shannon_divergence_grad() {
+        let (logits, target) = distributions();
+        let loss = JensenShannonDivergence::new().with_from_logits(true);
+
+        assert_loss_grad_matches(&loss, &logits, &target);
+        assert_eq!(loss.fused_activation(), None);
+        assert_eq!(JensenShannonDivergence::new().fused_activation(), Some("SoftmaxActivation"));
+    }
+
+    #[test]
+    fn test_hinge_losses() {
+        let output = Tensor::new(vec![0.5, -2.0, 0.3, 1.5], Shape::from(IxDyn(&[2, 2])));
+        let target = Tensor::new(vec![1.0, 0.0, -1.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+
+        // Margins violated by 0.5 and 1.3, labels of 0 are treated as -1
+        let hinge = HingeLoss::new().calculate_loss(&output, &target);
+        let squared = SquaredHingeLoss::new().calculate_loss(&output, &target);
+        assert!((hinge - (0.5 + 1.3) / 4.0).abs() < 1e-6);
+        assert!((squared - (0.25 + 1.69) / 4.0).abs() < 1e-6);
+
+        assert_loss_grad_matches(&HingeLoss::new(), &output, &target);
+        assert_loss_grad_matches(&SquaredHingeLoss::new(), &output, &target);
+    }
+
+    #[test]
+    fn test_multi_class_margin_loss() {
+        let output = Tensor::new(vec![0.1, 0.2, 0.4, 0.8], Shape::from(IxDyn(&[1, 4])));
+        let target = Tensor::new(vec![3.0], Shape::from(IxDyn(&[1])));
+
+        // (1 - 0.8 + 0.1) + (1 - 0.8 + 0.2) + (1 - 0.8 + 0.4) over 4 classes
+        let loss = MultiClassMarginLoss::new(1.0);
+        assert!((loss.calculate_loss(&output, &target) - 1.3 / 4.0).abs() < 1e-6);
+
+        let output = Tensor::new(vec![0.1, 0.2, 0.4, 0.8, 1.5, 0.3], Shape::from(IxDyn(&[2, 3])));
+        let target = Tensor::new(vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0], Shape::from(IxDyn(&[2, 3])));
+        assert_loss_grad_matches(&MultiClassMarginLoss::new(1.0), &output, &target);
+        assert_loss_grad_matches(
+            &MultiClassMarginLoss::new(1.0).with_squared(true),
+            &output,
+            &target,
+        );
+    }
+
+    #[test]
+    fn test_triplet_margin_loss() {
+        let output = Tensor::new(
+            vec![0.0, 0.0, 3.0, 4.0, 0.0, 1.0, 1.0, 0.5, 1.2, 0.4, -0.3, 0.9],
+            Shape::from(IxDyn(&[2, 3, 2])),
+        );
+        let target = Tensor::new(vec![0.0, 0.0], Shape::from(IxDyn(&[2])));
+        let loss = TripletMarginLoss::new(1.0).with_reduction(Reduction::None);
+
+        // d(a, p) = 5 and d(a, n) = 1 for the first triplet
+        let losses = loss.calculate_losses(&output, &target, None).to_vec();
+        assert!((losses[0] - 5.0).abs() < 1e-5);
+
+        assert_loss_grad_matches(&TripletMarginLoss::new(1.0), &output, &target);
+    }
+
+    #[test]
+    fn test_contrastive_loss() {
+        let output = Tensor::new(
+            vec![0.0, 0.0, 0.3, 0.4, 0.0, 0.0, 0.3, 0.4, 1.0, 0.5, -0.2, 0.8],
+            Shape::from(IxDyn(&[3, 2, 2])),
+        );
+        let target = Tensor::new(vec![1.0, 0.0, 0.0], Shape::from(IxDyn(&[3])));
+        let loss = ContrastiveLoss::new(1.0).with_reduction(Reduction::None);
+
+        // The pairs are 0.5 apart: similar pairs pay d^2, dissimilar pairs (1 - d)^2
+        let losses = loss.calculate_losses(&output, &target, None).to_vec();
+        assert!((losses[0] - 0.25).abs() < 1e-5);
+        assert!((losses[1] - 0.25).abs() < 1e-5);
+
+        assert_loss_grad_matches(&ContrastiveLoss::new(1.0), &output, &target);
+    }
+
+    #[test]
+    fn test_cosine_embedding_loss() {
+        let output = Tensor::new(
+            vec![1.0, 0.0, 0.0, 2.0, 1.0, 1.0, 2.0, 2.5, 0.5, -1.0, 0.3, 0.8],
+            Shape::from(IxDyn(&[3, 2, 2])),
+        );
+        let target = Tensor::new(vec![1.0, -1.0, -1.0], Shape::from(IxDyn(&[3])));
+        let loss = CosineEmbeddingLoss::new(0.0).with_reduction(Reduction::None);
+
+        // Orthogonal similar pair, and a dissimilar pair with cosine above the margin
+        let losses = loss.calculate_losses(&output, &target, None).to_vec();
+        let cos = (2.0 + 2.5) / (2f32.sqrt() * (4.0f32 + 6.25).sqrt());
+        assert!((losses[0] - 1.0).abs() < 1e-6);
+        assert!((losses[1] - cos).abs() < 1e-6);
+
+        assert_loss_grad_matches(&CosineEmbeddingLoss::new(0.0), &output, &target);
+    }
+
+    #[test]
+    fn test_cross_entropy_label_smoothing() {
+        let logits = Tensor::new(vec![2.0, 1.0, 0.5, 0.1, 1.5, -0.5], Shape::from(IxDyn(&[2, 3])));
+        let one_hot = Tensor::new(vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0], Shape::from(IxDyn(&[2, 3])));
+        let indices = Tensor::new(vec![0.0, 1.0], Shape::from(IxDyn(&[2])));
+        let smoothed = Tensor::new(
+            vec![0.9 + 0.1 / 3.0, 0.1 / 3.0, 0.1 / 3.0, 0.1 / 3.0, 0.9 + 0.1 / 3.0, 0.1 / 3.0],
+            Shape::from(IxDyn(&[2, 3])),
+        );
+
+        let loss = CrossEntropyLoss::new().with_from_logits(true).with_label_smoothing(0.1);
+        let sparse = SparseCategoricalCrossEntropyLoss::new()
+            .with_from_logits(true)
+            .with_label_smoothing(0.1);
+        let expected =
+            CrossEntropyLoss::new().with_from_logits(true).calculate_loss(&logits, &smoothed);
+
+        assert!((loss.calculate_loss(&logits, &one_hot) - expected).abs() < 1e-6);
+        assert!((sparse.calculate_loss(&logits, &indices) - expected).abs() < 1e-6);
+        assert_loss_grad_matches(&loss, &logits, &one_hot);
+
+        let sparse_grad = sparse.calculate_loss_grad(&logits, &indices).to_vec();
+        let grad = loss.calculate_loss_grad(&logits, &one_hot).to_vec();
+        sparse_grad.iter().zip(grad.iter()).for_each(|(a, b)| assert!((a - b).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_binary_cross_entropy_label_smoothing() {
+        let (logits, target) = binary_logits_and_targets();
+        let smoothed = target.map(|t| t * 0.8 + 0.1);
+        let loss = BinaryCrossEntropyWithLogits::new().with_label_smoothing(0.2);
+
+        let expected = BinaryCrossEntropyWithLogits::new().calculate_loss(&logits, &smoothed);
+        assert!((loss.calculate_loss(&logits, &target) - expected).abs() < 1e-6);
+        assert_loss_grad_matches(&loss, &logits, &target);
+
+        let probs = logits.map(SigmoidActivation::sigmoid);
+        let expected = BinaryCrossEntropy::new().calculate_loss(&probs, &smoothed);
+        let loss = BinaryCrossEntropy::new().with_label_smoothing(0.2);
+        assert!((loss.calculate_loss(&probs, &target) - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Label smoothing must be in [0, 1).")]
+    fn test_label_smoothing_out_of_range() {
+        CrossEntropyLoss::new().with_label_smoothing(1.0);
+    }
+
+    fn mask_logits_and_targets() -> (Tensor, Tensor) {
+        let logits = Tensor::new(
+            vec![2.0, -1.0, 0.5, -2.5, 1.5, 0.2, -0.7, 3.0],
+            Shape::from(IxDyn(&[2, 2, 2])),
+        );
+        let target = Tensor::new(
+            vec![1.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0],
+            Shape::from(IxDyn(&[2, 2, 2])),
+        );
+        (logits, target)
+    }
+
+    #[test]
+    fn test_dice_loss() {
+        let output = Tensor::new(vec![0.9, 0.1, 0.8, 0.2], Shape::from(IxDyn(&[1, 4])));
+        let target = Tensor::new(vec![1.0, 0.0, 1.0, 0.0], Shape::from(IxDyn(&[1, 4])));
+        let loss = DiceLoss::new().with_smooth(1.0);
+
+        // 1 - (2 * 1.7 + 1) / (2.0 + 2.0 + 1)
+        assert!((loss.calculate_loss(&output, &target) - (1.0 - 4.4 / 5.0)).abs() < 1e-6);
+        assert!(DiceLoss::new().calculate_loss(&target, &target).abs() < 1e-6);
+
+        let (logits, target) = mask_logits_and_targets();
+        assert_loss_grad_matches(&DiceLoss::new().with_from_logits(true), &logits, &target);
+        assert_eq!(DiceLoss::new().fused_activation(), Some("SigmoidActivation"));
+        assert_eq!(DiceLoss::new().with_from_logits(true).fused_activation(), None);
+    }
+
+    #[test]
+    fn test_jaccard_loss() {
+        let output = Tensor::new(vec![0.9, 0.1, 0.8, 0.2], Shape::from(IxDyn(&[1, 4])));
+        let target = Tensor::new(vec![1.0, 0.0, 1.0, 0.0], Shape::from(IxDyn(&[1, 4])));
+        let loss = JaccardLoss::new();
+
+        // 1 - (1.7 + 1) / (2.0 + 2.0 - 1.7 + 1)
+        assert!((loss.calculate_loss(&output, &target) - (1.0 - 2.7 / 3.3)).abs() < 1e-6);
+        assert!(JaccardLoss::new().calculate_loss(&target, &target).abs() < 1e-6);
+
+        let (logits, target) = mask_logits_and_targets();
+        assert_loss_grad_matches(&JaccardLoss::new().with_from_logits(true), &logits, &target);
+        assert_eq!(JaccardLoss::new().fused_activation(), Some("SigmoidActivation"));
+        assert_eq!(JaccardLoss::new().with_from_logits(true).fused_activation(), None);
+    }
+
+    #[test]
+    fn test_log_cosh_loss() {
+        let output = Tensor::new(vec![0.5, -1.0, 100.0, 2.0], Shape::from(IxDyn(&[2, 2])));
+        let target = Tensor::new(vec![0.0, 0.0, 0.0, 2.5], Shape::from(IxDyn(&[2, 2])));
+        let loss = LogCoshLoss::new().with_reduction(Reduction::None);
+
+        let losses = loss.calculate_losses(&output, &target, None).to_vec();
+        let expected = ((0.5f32).cosh().ln() + (1.0f32).cosh().ln()) / 2.0;
+        assert!((losses[0] - expected).abs() < 1e-6);
+        assert!((losses[1] - (100.0 - 2f32.ln() + (0.5f32).cosh().ln()) / 2.0).abs() < 1e-4);
+
+        assert_loss_grad_matches(&LogCoshLoss::new(), &output, &target);
+    }
+
+    #[test]
+    fn test_quantile_loss() {
+        let output = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], Shape::from(IxDyn(&[2, 3])));
+        let target = Tensor::new(vec![2.5, 4.5], Shape::from(IxDyn(&[2, 1])));
+        let loss = QuantileLoss::new(vec![0.1, 0.5, 0.9]).with_reduction(Reduction::None);
+
+        // Residuals 1.5, 0.5, -0.5 and 0.5, -0.5, -1.5
+        let losses = loss.calculate_losses(&output, &target, None).to_vec();
+        assert!((losses[0] - (0.15 + 0.25 + 0.05) / 3.0).abs() < 1e-6);
+        assert!((losses[1] - (0.05 + 0.25 + 0.15) / 3.0).abs() < 1e-6);
+
+        let broadcast =
+            Tensor::new(vec![2.5, 2.5, 2.5, 4.5, 4.5, 4.5], Shape::from(IxDyn(&[2, 3])));
+        let loss = QuantileLoss::new(vec![0.1, 0.5, 0.9]);
+        assert_eq!(loss.calculate_loss(&output, &target), loss.calculate_loss(&output, &broadcast));
+        assert_loss_grad_matches(&loss, &output, &target);
+    }
+
+    #[test]
+    #[should_panic(expected = "Quantiles must be in (0, 1).")]
+    fn test_quantile_loss_with_invalid_quantile() {
+        QuantileLoss::new(vec![0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_poisson_nll_loss() {
+        let output = Tensor::new(vec![0.5, -1.0, 1.2, 0.1], Shape::from(IxDyn(&[2, 2])));
+        let target = Tensor::new(vec![2.0, 0.0, 3.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+
+        let loss = PoissonNLLLoss::new().with_reduction(Reduction::None);
+        let losses = loss.calculate_losses(&output, &target, None).to_vec();
+        let expected = ((0.5f32.exp() - 2.0 * 0.5) + (-1.0f32).exp()) / 2.0;
+        assert!((losses[0] - expected).abs() < 1e-6);
+
+        assert_loss_grad_matches(&PoissonNLLLoss::new(), &output, &target);
+        assert_loss_grad_matches(&PoissonNLLLoss::new().with_full(true), &output, &target);
+
+        let rates = output.map(|x| x.exp());
+        let rate_loss = PoissonNLLLoss::new().with_log_input(false);
+        let log_loss = PoissonNLLLoss::new();
+        assert!(
+            (rate_loss.calculate_loss(&rates, &target) - log_loss.calculate_loss(&output, &target))
+                .abs()
+                < 1e-5
+        );
+        assert_loss_grad_matches(&rate_loss, &rates, &target);
+    }
+
+    #[test]
+    fn test_gaussian_nll_loss() {
+        let output = Tensor::new(vec![1.0, 0.5, 2.0, 0.25], Shape::from(IxDyn(&[2, 2])));
+        let target = Tensor::new(vec![2.0, 0.0], Shape::from(IxDyn(&[2, 1])));
+        let loss = GaussianNLLLoss::new().with_reduction(Reduction::None);
+
+        let losses = loss.calculate_losses(&output, &target, None).to_vec();
+        assert!((losses[0] - 0.5 * (0.5f32.ln() + 1.0 / 0.5)).abs() < 1e-6);
+        assert!((losses[1] - 0.5 * (0.25f32.ln() + 4.0 / 0.25)).abs() < 1e-6);
+
+        let full = GaussianNLLLoss::new().with_full(true);
+        let constant = 0.5 * (2.0 * std::f32::consts::PI).ln();
+        assert!(
+            (full.calculate_loss(&output, &target)
+                - GaussianNLLLoss::new().calculate_loss(&output, &target)
+                - constant)
+                .abs()
+                < 1e-5
+        );
+
+        let output = Tensor::new(
+            vec![1.0, -0.5, 0.8, 1.2, 0.3, -0.2, 0.6, 0.9],
+            Shape::from(IxDyn(&[2, 4])),
+        );
+        let target = Tensor::new(vec![0.4, 0.1, -0.3, 0.5], Shape::from(IxDyn(&[2, 2])));
+        assert_loss_grad_matches(&GaussianNLLLoss::new(), &output, &target);
+        assert_loss_grad_matches(&GaussianNLLLoss::new().with_log_variance(true), &output, &target);
+    }
 }
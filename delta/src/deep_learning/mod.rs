@@ -31,9 +31,12 @@
 pub mod dataset;
 pub mod encoders;
 pub mod errors;
+pub mod initializers;
 pub mod layers;
 pub mod losses;
 pub mod models;
 pub mod optimizers;
+pub mod regularizers;
+pub mod rng;
 pub mod tensor_ops;
 pub mod utils;
@@ -33,6 +33,8 @@
 use std::time::Instant;
 
 use ndarray::Dimension;
+use rand::Rng;
+use rand::rngs::StdRng;
 use serde_json;
 
 use crate::deep_learning::utils::format_with_commas;
@@ -40,11 +42,12 @@
 #[cfg(all(target_os = "macos", feature = "metal"))]
 use crate::devices::osx_metal;
 
-use super::dataset::DatasetOps;
+use super::dataset::{Batch, DataLoader, DatasetOps, IndexedDataset, IterableDataset};
 use super::errors::ModelError;
 use super::layers::Layer;
 use super::losses::Loss;
 use super::optimizers::Optimizer;
+use super::rng::{rng_from_seed, with_global_rng};
 use super::tensor_ops::Tensor;
 
 /// A sequential model that contains a list of layers, an optimizer, and a loss function.
@@ -57,6 +60,8 @@ pub struct Sequential {
     layer_names: Vec<String>,
 
     device: Option<Device>,
+
+    rng: Option<StdRng>,
 }
 
 impl Default for Sequential {
@@ -78,9 +83,29 @@ pub fn new() -> Self {
             optimizer: None,
             loss: None,
             device: None,
+            rng: None,
         }
     }
 
+    /// Seeds the model's random number generator.
+    ///
+    /// Every layer added after this call receives a seed drawn from the model's generator, so
+    /// two models built with the same seed and the same layers have bit-identical weights.
+    /// Without a model seed, layer seeds are drawn from the global generator (see
+    /// `rng::set_seed`).
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed for the model's random number generator.
+    ///
+    /// # Returns
+    ///
+    /// The model with the seeded generator.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Some(rng_from_seed(seed));
+        self
+    }
+
     /// Add a layer to the model
     ///
     /// # Arguments
@@ -98,6 +123,13 @@ pub fn add<L: Layer + 'static>(mut self, mut layer: L) -> Self {
             _ => format!("{}_{}", layer_type, self.layers.len()),
         };
 
+        // Seed the layer from the model generator, or from the global one if unset
+        let layer_seed = match self.rng.as_mut() {
+            Some(rng) => rng.gen(),
+            None => with_global_rng(|rng| rng.gen()),
+        };
+        layer.set_seed(layer_seed);
+
         // Call the build method to initialize weights and biases
         if !self.layers.is_empty() {
             // Not sure how to do an elegant way to return a Result without unwrapping
@@ -132,6 +164,9 @@ pub fn compile<O: Optimizer + 'static, L: Loss + 'static>(&mut self, optimizer:
 
     /// Trains the model with the given training dataset, number of epochs, and batch size.
     ///
+    /// Sample weights set on the dataset with `DatasetOps::set_sample_weights` are applied to the
+    /// loss of each batch, together with any class weights configured on the loss.
+    ///
     /// # Arguments
     ///
     /// * `train_data` - The training dataset.
@@ -149,15 +184,117 @@ pub fn fit<D: DatasetOps>(
     ) -> Result<(), ModelError> {
         self.set_device_to_dataset(train_data).map_err(ModelError::DeviceError)?;
         self.ensure_optimizer_and_loss()?;
+        self.ensure_activation_not_fused()?;
+
+        let mut optimizer = self.optimizer.take().unwrap();
+        let num_batches = train_data.len().div_ceil(batch_size);
+
+        // The optimizer is put back before any error is returned, so the model stays compiled
+        let result = (0..epochs).try_for_each(|epoch| {
+            println!("\nEpoch {}/{}", epoch + 1, epochs);
+            let mut batches = (0..num_batches)
+                .map(|batch_idx| Batch::from_dataset(&*train_data, batch_idx, batch_size));
+            self.train_one_epoch(&mut batches, num_batches, &mut optimizer)?;
+            Ok(())
+        });
+
+        self.optimizer = Some(optimizer);
+        result?;
+
+        println!();
+        Ok(())
+    }
+
+    /// Trains the model with the batches of a `DataLoader` for the given number of epochs.
+    ///
+    /// The loader reshuffles the sample order at the start of every epoch if shuffling is enabled,
+    /// and its `drop_last` setting decides whether the trailing partial batch is used. Batches are
+    /// moved to the model's device as they arrive.
+    ///
+    /// # Arguments
+    ///
+    /// * `train_loader` - The loader yielding the training batches.
+    /// * `epochs` - The number of epochs to train.
+    ///
+    /// # Returns
+    ///
+    /// None
+    pub fn fit_with_loader<D: IndexedDataset + Sync>(
+        &mut self,
+        train_loader: &mut DataLoader<D>,
+        epochs: i32,
+    ) -> Result<(), ModelError> {
+        self.ensure_optimizer_and_loss()?;
+        self.ensure_activation_not_fused()?;
 
         let mut optimizer = self.optimizer.take().unwrap();
+        let num_batches = train_loader.num_batches();
 
-        for epoch in 0..epochs {
+        // The optimizer is put back before any error is returned, so the model stays compiled
+        let result = (0..epochs).try_for_each(|epoch| {
             println!("\nEpoch {}/{}", epoch + 1, epochs);
-            self.train_one_epoch(train_data, batch_size, &mut optimizer)?;
+            train_loader
+                .run_epoch(|batches| self.train_one_epoch(batches, num_batches, &mut optimizer))?;
+            Ok(())
+        });
+
+        self.optimizer = Some(optimizer);
+        result?;
+
+        println!();
+        Ok(())
+    }
+
+    /// Trains the model with the batches of an `IterableDataset` for the given number of epochs.
+    ///
+    /// An epoch is `steps_per_epoch` batches, since streamed datasets may have no end. Every epoch
+    /// continues where the previous one stopped, and a bounded dataset is read again from the
+    /// start when it runs out. Batches are moved to the model's device as they arrive.
+    ///
+    /// # Arguments
+    ///
+    /// * `train_data` - The dataset yielding the training samples.
+    /// * `epochs` - The number of epochs to train.
+    /// * `batch_size` - The batch size to use.
+    /// * `steps_per_epoch` - The number of batches per epoch.
+    ///
+    /// # Returns
+    ///
+    /// None
+    pub fn fit_with_iterable<D: IterableDataset + ?Sized>(
+        &mut self,
+        train_data: &D,
+        epochs: i32,
+        batch_size: usize,
+        steps_per_epoch: usize,
+    ) -> Result<(), ModelError> {
+        if steps_per_epoch == 0 {
+            return Err(ModelError::TrainingError(
+                "Steps per epoch must be greater than 0.".to_string(),
+            ));
         }
+        self.ensure_optimizer_and_loss()?;
+        self.ensure_activation_not_fused()?;
+
+        let mut optimizer = self.optimizer.take().unwrap();
+        let mut batches = train_data.batches(batch_size);
+
+        // The optimizer is put back before any error is returned, so the model stays compiled
+        let result = (0..epochs).try_for_each(|epoch| {
+            println!("\nEpoch {}/{}", epoch + 1, epochs);
+            let mut steps = std::iter::from_fn(|| {
+                batches.next().or_else(|| {
+                    batches = train_data.batches(batch_size);
+                    batches.next()
+                })
+            })
+            .take(steps_per_epoch);
+            self.train_one_epoch(&mut steps, steps_per_epoch, &mut optimizer)?;
+            Ok(())
+        });
 
         self.optimizer = Some(optimizer);
+        result?;
 
         println!();
         Ok(())
@@ -174,34 +311,52 @@ fn ensure_optimizer_and_loss(&mut self) -> Result<(), ModelError> {
         Ok(())
     }
 
-    /// Trains the model for one epoch using the given training dataset and batch size.
+    /// Ensures that the last layer does not apply an activation whose derivative the loss
+    /// gradient already includes, which would apply the derivative twice during training.
+    fn ensure_activation_not_fused(&self) -> Result<(), ModelError> {
+        let fused = self.loss.as_ref().and_then(|loss| loss.fused_activation());
+        let last = self.layers.last().and_then(|layer| layer.activation_name());
+
+        match (fused, last) {
+            (Some(fused), Some(last)) if fused == last => Err(ModelError::TrainingError(format!(
+                "the loss gradient already includes the {} derivative; apply the activation in \
+                 the last Dense layer or drop the activation layer and compute the loss from logits",
+                fused
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Trains the model for one epoch on the given batches.
     ///
     /// # Arguments
     ///
-    /// * `train_data` - The training dataset.
-    /// * `batch_size` - The batch size to use.
+    /// * `batches` - The batches of the epoch.
+    /// * `num_batches` - The number of batches in the epoch, used for the progress bar.
     /// * `optimizer` - The optimizer to use.
     ///
     /// # Returns
     ///
     /// The average loss for the epoch.
-    fn train_one_epoch<D: DatasetOps>(
+    fn train_one_epoch(
         &mut self,
-        train_data: &mut D,
-        batch_size: usize,
+        batches: &mut dyn Iterator<Item = Batch>,
+        num_batches: usize,
         optimizer: &mut Box<dyn Optimizer>,
     ) -> Result<f32, ModelError> {
-        let num_batches = train_data.len() / batch_size;
         let mut epoch_loss = 0.0;
         let mut correct_predictions = 0;
         let mut total_samples = 0;
+        let mut batches_seen = 0;
 
         let start_time = Instant::now();
 
-        for batch_idx in 0..num_batches {
-            let (inputs, targets) = train_data.get_batch(batch_idx, batch_size);
-            let batch_loss = self.train_one_batch(&inputs, &targets, optimizer)?;
+        for (batch_idx, batch) in batches.enumerate() {
+            let Batch { inputs, targets, sample_weights } = self.batch_to_device(batch)?;
+            let batch_loss =
+                self.train_one_batch(&inputs, &targets, sample_weights.as_ref(), optimizer)?;
             epoch_loss += batch_loss;
+            batches_seen += 1;
 
             let outputs = self.forward(&inputs)?;
             let predictions = outputs.argmax(1);
@@ -218,7 +373,11 @@ fn train_one_epoch<D: DatasetOps>(
             self.display_progress(batch_idx, num_batches, epoch_loss, accuracy, start_time);
         }
 
-        Ok(epoch_loss / num_batches as f32)
+        if batches_seen == 0 {
+            return Err(ModelError::DatasetError("No samples found in the dataset".to_string()));
+        }
+
+        Ok(epoch_loss / batches_seen as f32)
     }
 
     /// Trains the model for one batch using the given inputs and targets.
@@ -227,6 +386,7 @@ fn train_one_epoch<D: DatasetOps>(
     ///
     /// * `inputs` - The inputs for the batch.
     /// * `targets` - The targets for the batch.
+    /// * `sample_weights` - The optional weight of each sample in the batch.
     /// * `optimizer` - The optimizer to use.
     ///
     /// # Returns
@@ -236,6 +396,7 @@ fn train_one_batch(
         &mut self,
         inputs: &Tensor,
         targets: &Tensor,
+        sample_weights: Option<&Tensor>,
         optimizer: &mut Box<dyn Optimizer>,
     ) -> Result<f32, ModelError> {
         let mut outputs = inputs.clone();
@@ -244,9 +405,12 @@ fn train_one_batch(
         }
 
         let loss_fn = self.loss.as_ref().ok_or(ModelError::MissingLossFunction)?;
-        let batch_loss = loss_fn.calculate_loss(&outputs, targets);
+        let regularization_loss: f32 =
+            self.layers.iter().map(|layer| layer.regularization_loss()).sum();
+        let batch_loss = loss_fn.calculate_weighted_loss(&outputs, targets, sample_weights)
+            + regularization_loss;
 
-        let mut grad = loss_fn.calculate_loss_grad(&outputs, targets);
+        let mut grad = loss_fn.calculate_weighted_loss_grad(&outputs, targets, sample_weights);
         for layer in self.layers.iter_mut().rev() {
             grad = layer.backward(&grad).map_err(ModelError::LayerError)?;
             layer.update_weights(optimizer).map_err(ModelError::LayerError)?;
@@ -277,13 +441,12 @@ fn display_progress(
         let bar_width = 30;
         let filled = (progress * bar_width as f32) as usize;
         let arrow = if filled < bar_width { ">" } else { "=" };
-        let bar: String = std::iter::repeat('=')
-            .take(filled)
+        let bar: String = std::iter::repeat_n('=', filled)
             .chain(std::iter::once(arrow.chars().next().unwrap()))
-            .chain(
-                std::iter::repeat(' ')
-                    .take((bar_width as isize - filled as isize - 1).max(0) as usize),
-            )
+            .chain(std::iter::repeat_n(
+                ' ',
+                (bar_width as isize - filled as isize - 1).max(0) as usize,
+            ))
             .collect();
 
         let elapsed = start_time.elapsed();
@@ -300,6 +463,8 @@ fn display_progress(
 
     /// Validates the model with the given validation dataset.
     ///
+    /// As in training, the loss includes the regularization penalty of the layers.
+    ///
     /// # Arguments
     ///
     /// * `validation_data` - The validation dataset.
@@ -316,23 +481,71 @@ pub fn validate<D: DatasetOps>(
         self.set_device_to_dataset(validation_data).map_err(ModelError::DeviceError)?;
         self.ensure_optimizer_and_loss()?;
 
-        let loss_fn = self.loss.as_ref().ok_or(ModelError::MissingLossFunction)?;
         let num_batches = validation_data.len().div_ceil(batch_size);
-        let mut total_loss = 0.0;
+        let mut batches = (0..num_batches)
+            .map(|batch_idx| Batch::from_dataset(&*validation_data, batch_idx, batch_size));
+        self.validate_batches(&mut batches)
+    }
 
-        for batch_idx in 0..num_batches {
-            let (inputs, targets) = validation_data.get_batch(batch_idx, batch_size);
+    /// Validates the model with the batches of a `DataLoader`.
+    ///
+    /// As in training, the loss includes the regularization penalty of the layers.
+    ///
+    /// # Arguments
+    ///
+    /// * `validation_loader` - The loader yielding the validation batches.
+    ///
+    /// # Returns
+    ///
+    /// The average validation loss.
+    pub fn validate_with_loader<D: IndexedDataset + Sync>(
+        &mut self,
+        validation_loader: &mut DataLoader<D>,
+    ) -> Result<f32, ModelError> {
+        self.ensure_optimizer_and_loss()?;
+
+        validation_loader.run_epoch(|batches| self.validate_batches(batches))
+    }
+
+    /// Calculates the average loss of the model over the given batches, plus the regularization
+    /// penalty of the layers.
+    ///
+    /// # Arguments
+    ///
+    /// * `batches` - The validation batches.
+    ///
+    /// # Returns
+    ///
+    /// The average validation loss.
+    fn validate_batches(
+        &mut self,
+        batches: &mut dyn Iterator<Item = Batch>,
+    ) -> Result<f32, ModelError> {
+        let loss_fn = self.loss.as_ref().ok_or(ModelError::MissingLossFunction)?;
+        let mut total_loss = 0.0;
+        let mut num_batches = 0;
 
-            let mut outputs = inputs.clone();
+        for batch in batches {
+            let Batch { inputs, targets, sample_weights } = self.batch_to_device(batch)?;
+            let mut outputs = inputs;
             for layer in &mut self.layers {
                 outputs = layer.forward(&outputs).map_err(ModelError::LayerError)?;
             }
 
-            let batch_loss = loss_fn.calculate_loss(&outputs, &targets);
+            let batch_loss =
+                loss_fn.calculate_weighted_loss(&outputs, &targets, sample_weights.as_ref());
             total_loss += batch_loss;
+            num_batches += 1;
         }
 
-        Ok(total_loss / num_batches as f32)
+        if num_batches == 0 {
+            return Err(ModelError::DatasetError("No samples found in the dataset".to_string()));
+        }
+
+        // The weights do not change while validating, so the penalty is the same for every batch
+        let regularization_loss: f32 =
+            self.layers.iter().map(|layer| layer.regularization_loss()).sum();
+        Ok(total_loss / num_batches as f32 + regularization_loss)
     }
 
     /// Evaluates the model with the given test dataset.
@@ -351,15 +564,48 @@ pub fn evaluate<D: DatasetOps>(
         batch_size: usize,
     ) -> Result<f32, ModelError> {
         self.set_device_to_dataset(test_data).map_err(ModelError::DeviceError)?;
-        let mut correct_predictions = 0;
-        let mut total_samples = 0;
 
         let num_batches = test_data.len().div_ceil(batch_size);
+        let mut batches = (0..num_batches)
+            .map(|batch_idx| Batch::from_dataset(&*test_data, batch_idx, batch_size));
+        self.evaluate_batches(&mut batches)
+    }
 
-        for batch_idx in 0..num_batches {
-            let (inputs, targets) = test_data.get_batch(batch_idx, batch_size);
+    /// Evaluates the model with the batches of a `DataLoader`.
+    ///
+    /// # Arguments
+    ///
+    /// * `test_loader` - The loader yielding the test batches.
+    ///
+    /// # Returns
+    ///
+    /// The evaluation metric.
+    pub fn evaluate_with_loader<D: IndexedDataset + Sync>(
+        &mut self,
+        test_loader: &mut DataLoader<D>,
+    ) -> Result<f32, ModelError> {
+        test_loader.run_epoch(|batches| self.evaluate_batches(batches))
+    }
 
-            let mut outputs = inputs.clone();
+    /// Calculates the accuracy of the model over the given batches.
+    ///
+    /// # Arguments
+    ///
+    /// * `batches` - The test batches.
+    ///
+    /// # Returns
+    ///
+    /// The evaluation metric.
+    fn evaluate_batches(
+        &mut self,
+        batches: &mut dyn Iterator<Item = Batch>,
+    ) -> Result<f32, ModelError> {
+        let mut correct_predictions = 0;
+        let mut total_samples = 0;
+
+        for batch in batches {
+            let Batch { inputs, targets, .. } = self.batch_to_device(batch)?;
+            let mut outputs = inputs;
             for layer in &mut self.layers {
                 outputs = layer.forward(&outputs).map_err(ModelError::LayerError)?;
             }
@@ -507,17 +753,42 @@ pub fn use_optimized_device(&mut self) {
     fn set_device_to_dataset<D: DatasetOps>(&mut self, dataset: &mut D) -> Result<(), String> {
         dataset.to_device(self.device.clone().unwrap())
     }
+
+    /// Moves a batch to the device of the model, if one is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - The batch to move.
+    ///
+    /// # Returns
+    ///
+    /// The batch on the device of the model.
+    fn batch_to_device(&self, mut batch: Batch) -> Result<Batch, ModelError> {
+        if let Some(device) = &self.device {
+            batch.to_device(device).map_err(ModelError::DeviceError)?;
+        }
+        Ok(batch)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use ndarray::{IxDyn, Shape};
+    use tempfile::TempDir;
 
     use crate::deep_learning::{
-        activations::{ReluActivation, SoftmaxActivation},
-        layers::{Dense, Flatten},
-        losses::MeanSquaredLoss,
-        optimizers::Adam,
+        activations::{ReluActivation, SigmoidActivation, SoftmaxActivation},
+        dataset::{Batch, DataLoader, Dataset, GeneratorDataset},
+        errors::ModelError,
+        initializers::Constant,
+        layers::{ActivationLayer, Dense, Flatten, PReLU},
+        losses::{BinaryCrossEntropy, CrossEntropyLoss, FocalLoss, MeanSquaredLoss},
+        optimizers::{Adam, Optimizer, SGD},
+        regularizers::L2,
+        tensor_ops::Tensor,
     };
 
     use super::Sequential;
@@ -541,6 +812,21 @@ fn test_sequential_add() {
         assert_eq!(model.layers.len(), 3);
     }
 
+    #[test]
+    fn test_sequential_with_seed_is_reproducible() {
+        let weights = |seed| {
+            let model = Sequential::new()
+                .with_seed(seed)
+                .add(Flatten::new(Shape::from(IxDyn(&[4, 4]))))
+                .add(Dense::new(8, Some(ReluActivation::new()), true))
+                .add(Dense::new(2, None::<SoftmaxActivation>, true));
+            model.layers.iter().map(|layer| layer.get_weights()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(weights(42), weights(42));
+        assert_ne!(weights(42), weights(43));
+    }
+
     #[test]
     fn test_sequential_compile() {
         let mut model = create_sequential_model();
@@ -550,4 +836,237 @@ fn test_sequential_compile() {
         assert!(model.optimizer.is_some());
         assert!(model.loss.is_some());
     }
+
+    #[test]
+    fn test_sequential_train_one_batch_includes_regularization_loss() {
+        let mut model = Sequential::new().add(Flatten::new(Shape::from(IxDyn(&[2])))).add(
+            Dense::new(1, None::<ReluActivation>, true)
+                .with_kernel_initializer(Constant::new(0.5))
+                .with_kernel_regularizer(L2::new(0.1)),
+        );
+        model.compile(Adam::new(0.001), MeanSquaredLoss::new());
+
+        let inputs = Tensor::new(vec![1.0, 1.0], Shape::from(IxDyn(&[1, 2])));
+        let targets = Tensor::new(vec![0.0], Shape::from(IxDyn(&[1, 1])));
+        let mut optimizer: Box<dyn Optimizer> = Box::new(Adam::new(0.001));
+        let loss = model.train_one_batch(&inputs, &targets, None, &mut optimizer).unwrap();
+
+        // MSE of 1.0 plus 0.1 * (0.5^2 + 0.5^2)
+        assert!((loss - 1.05).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sequential_validate_includes_regularization_loss() {
+        let mut model = Sequential::new().add(Flatten::new(Shape::from(IxDyn(&[2])))).add(
+            Dense::new(1, None::<ReluActivation>, true)
+                .with_kernel_initializer(Constant::new(0.5))
+                .with_kernel_regularizer(L2::new(0.1)),
+        );
+        model.compile(Adam::new(0.001), MeanSquaredLoss::new());
+
+        let inputs = Tensor::new(vec![1.0, 1.0, 1.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let targets = Tensor::new(vec![0.0, 0.0], Shape::from(IxDyn(&[2, 1])));
+        let mut loader = DataLoader::new(Dataset::new(inputs, targets), 1);
+        let loss = model.validate_with_loader(&mut loader).unwrap();
+
+        // MSE of 1.0 in both batches plus 0.1 * (0.5^2 + 0.5^2) once
+        assert!((loss - 1.05).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sequential_save_includes_prelu_alpha() {
+        let model = Sequential::new()
+            .add(Flatten::new(Shape::from(IxDyn(&[2, 2]))))
+            .add(Dense::new(3, None::<ReluActivation>, true))
+            .add(PReLU::new(0.25, true));
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("model.json");
+        model.save(path.to_str().unwrap()).unwrap();
+
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        assert_eq!(saved["layers"][2]["type"], "PReLU");
+        assert_eq!(saved["layers"][2]["weights"]["alpha"], serde_json::json!([0.25, 0.25, 0.25]));
+    }
+
+    #[test]
+    fn test_sequential_fused_cross_entropy_reduces_loss() {
+        let mut model = Sequential::new()
+            .with_seed(7)
+            .add(Flatten::new(Shape::from(IxDyn(&[2]))))
+            .add(Dense::new(2, None::<ReluActivation>, true));
+        model.compile(SGD::new(0.5), CrossEntropyLoss::new().with_from_logits(true));
+
+        let inputs = Tensor::new(vec![1.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let targets = Tensor::new(vec![1.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let mut optimizer: Box<dyn Optimizer> = Box::new(SGD::new(0.5));
+
+        let first_loss = model.train_one_batch(&inputs, &targets, None, &mut optimizer).unwrap();
+        let mut last_loss = first_loss;
+        for _ in 0..20 {
+            last_loss = model.train_one_batch(&inputs, &targets, None, &mut optimizer).unwrap();
+        }
+
+        assert!(last_loss < first_loss * 0.5, "{} -> {}", first_loss, last_loss);
+    }
+
+    #[test]
+    fn test_sequential_rejects_softmax_layer_before_cross_entropy_on_probabilities() {
+        let mut model = Sequential::new()
+            .add(Flatten::new(Shape::from(IxDyn(&[2]))))
+            .add(Dense::new(2, None::<ReluActivation>, true))
+            .add(ActivationLayer::new(SoftmaxActivation::new()));
+        model.compile(SGD::new(0.1), CrossEntropyLoss::new());
+
+        let inputs = Tensor::new(vec![1.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let targets = Tensor::new(vec![1.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let mut loader = DataLoader::new(Dataset::new(inputs, targets), 2);
+
+        let result = model.fit_with_loader(&mut loader, 1);
+
+        assert!(matches!(result, Err(ModelError::TrainingError(_))));
+        assert_eq!(loader.epoch(), 0);
+    }
+
+    #[test]
+    fn test_sequential_rejects_sigmoid_layer_before_binary_losses_on_probabilities() {
+        let model = || {
+            Sequential::new()
+                .add(Flatten::new(Shape::from(IxDyn(&[2]))))
+                .add(Dense::new(2, None::<ReluActivation>, true))
+                .add(ActivationLayer::new(SigmoidActivation::new()))
+        };
+        let inputs = Tensor::new(vec![1.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let targets = Tensor::new(vec![1.0, 0.0, 0.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let mut loader = DataLoader::new(Dataset::new(inputs, targets), 2);
+
+        let mut bce = model();
+        bce.compile(SGD::new(0.1), BinaryCrossEntropy::new());
+        let result = bce.fit_with_loader(&mut loader, 1);
+        assert!(matches!(result, Err(ModelError::TrainingError(_))));
+
+        let mut focal = model();
+        focal.compile(SGD::new(0.1), FocalLoss::default());
+        let result = focal.fit_with_loader(&mut loader, 1);
+        assert!(matches!(result, Err(ModelError::TrainingError(_))));
+
+        assert_eq!(loader.epoch(), 0);
+    }
+
+    #[test]
+    fn test_sequential_train_one_batch_applies_sample_weights() {
+        let mut model = Sequential::new().add(Flatten::new(Shape::from(IxDyn(&[2])))).add(
+            Dense::new(2, None::<ReluActivation>, true).with_kernel_initializer(Constant::new(0.5)),
+        );
+        model.compile(SGD::new(0.01), MeanSquaredLoss::new());
+
+        let inputs = Tensor::new(vec![1.0, 1.0, 1.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let targets = Tensor::new(vec![0.0, 0.0, 1.0, 1.0], Shape::from(IxDyn(&[2, 2])));
+        let weights = Tensor::new(vec![2.0, 0.0], Shape::from(IxDyn(&[2])));
+        let mut optimizer: Box<dyn Optimizer> = Box::new(SGD::new(0.01));
+
+        // Only the first sample contributes, with a squared error of 1.0
+        let loss =
+            model.train_one_batch(&inputs, &targets, Some(&weights), &mut optimizer).unwrap();
+        assert!((loss - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sequential_train_one_epoch_uses_trailing_partial_batch() {
+        let mut model = Sequential::new().add(Flatten::new(Shape::from(IxDyn(&[2])))).add(
+            Dense::new(2, None::<ReluActivation>, true).with_kernel_initializer(Constant::new(0.5)),
+        );
+        model.compile(SGD::new(0.01), MeanSquaredLoss::new());
+
+        let batch = |size: usize| Batch {
+            inputs: Tensor::new(vec![1.0; size * 2], Shape::from(IxDyn(&[size, 2]))),
+            targets: Tensor::new(vec![1.0; size * 2], Shape::from(IxDyn(&[size, 2]))),
+            sample_weights: None,
+        };
+        let mut optimizer: Box<dyn Optimizer> = Box::new(SGD::new(0.01));
+        let mut samples_seen = 0;
+        let mut batches =
+            vec![batch(2), batch(2), batch(1)].into_iter().inspect(|b| samples_seen += b.len());
+
+        model.train_one_epoch(&mut batches, 3, &mut optimizer).unwrap();
+        drop(batches);
+
+        assert_eq!(samples_seen, 5);
+    }
+
+    #[test]
+    fn test_sequential_with_loader_runs_every_epoch() {
+        let mut model = Sequential::new().add(Flatten::new(Shape::from(IxDyn(&[2])))).add(
+            Dense::new(2, None::<ReluActivation>, true).with_kernel_initializer(Constant::new(0.5)),
+        );
+        model.compile(SGD::new(0.01), MeanSquaredLoss::new());
+
+        let inputs = Tensor::new(vec![1.0; 10], Shape::from(IxDyn(&[5, 2])));
+        let targets = Tensor::new([1.0, 0.0].repeat(5), Shape::from(IxDyn(&[5, 2])));
+        let mut loader =
+            DataLoader::new(Dataset::new(inputs, targets), 2).with_shuffle(true).with_seed(3);
+
+        model.fit_with_loader(&mut loader, 2).unwrap();
+        let loss = model.validate_with_loader(&mut loader).unwrap();
+        let accuracy = model.evaluate_with_loader(&mut loader).unwrap();
+
+        assert_eq!(loader.epoch(), 4);
+        assert!(loss.is_finite());
+        assert!((0.0..=1.0).contains(&accuracy));
+    }
+
+    #[test]
+    fn test_sequential_keeps_optimizer_when_fit_with_loader_fails() {
+        let mut model = Sequential::new()
+            .add(Flatten::new(Shape::from(IxDyn(&[2]))))
+            .add(Dense::new(2, None::<ReluActivation>, true));
+        model.compile(SGD::new(0.01), MeanSquaredLoss::new());
+        let empty = Dataset::new(
+            Tensor::new(vec![], Shape::from(IxDyn(&[0, 2]))),
+            Tensor::new(vec![], Shape::from(IxDyn(&[0, 2]))),
+        );
+
+        let mut loader = DataLoader::new(empty, 2);
+        assert!(model.fit_with_loader(&mut loader, 1).is_err());
+        assert!(model.optimizer.is_some());
+    }
+
+    #[test]
+    fn test_sequential_with_iterable_reads_steps_per_epoch_batches() {
+        let mut model = Sequential::new().add(Flatten::new(Shape::from(IxDyn(&[2])))).add(
+            Dense::new(2, None::<ReluActivation>, true).with_kernel_initializer(Constant::new(0.5)),
+        );
+        model.compile(SGD::new(0.01), MeanSquaredLoss::new());
+
+        let samples_read = Arc::new(AtomicUsize::new(0));
+        let counter = samples_read.clone();
+        let dataset = GeneratorDataset::new(move || {
+            let counter = counter.clone();
+            (0..5)
+                .inspect(move |_| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                })
+                .map(|_| {
+                    (
+                        Tensor::new(vec![1.0; 2], Shape::from(IxDyn(&[2]))),
+                        Tensor::new(vec![1.0, 0.0], Shape::from(IxDyn(&[2]))),
+                    )
+                })
+        });
+
+        model.fit_with_iterable(&dataset, 2, 2, 4).unwrap();
+
+        // Each epoch reads 4 batches of at most 2 samples, restarting the 5 samples after 3 batches
+        assert_eq!(samples_read.load(Ordering::Relaxed), 14);
+        assert!(model.optimizer.is_some());
+
+        let empty = GeneratorDataset::new(std::iter::empty);
+        assert!(model.fit_with_iterable(&empty, 1, 2, 4).is_err());
+        assert!(model.optimizer.is_some());
+
+        let result = model.fit_with_iterable(&dataset, 1, 2, 0);
+        assert!(matches!(result, Err(ModelError::TrainingError(_))));
+    }
 }
@@ -0,0 +1,284 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt::Debug;
+
+use ndarray::{Array2, Ix2};
+
+use super::tensor_ops::Tensor;
+
+/// A trait representing a regularizer that penalizes a tensor.
+pub trait Regularizer: Debug {
+    /// Computes the penalty for the given tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `tensor` - The tensor to penalize.
+    ///
+    /// # Returns
+    ///
+    /// The penalty as a `f32` value.
+    fn penalty(&self, tensor: &Tensor) -> f32;
+
+    /// Computes the gradient of the penalty with respect to the given tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `tensor` - The tensor to penalize.
+    ///
+    /// # Returns
+    ///
+    /// A tensor of the same shape containing the gradient of the penalty.
+    fn gradient(&self, tensor: &Tensor) -> Tensor;
+
+    /// Returns the name of the regularizer.
+    ///
+    /// # Returns
+    ///
+    /// A string slice containing the name of the regularizer.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>().split("::").last().unwrap_or("Unknown")
+    }
+
+    /// Returns the regularizer's configuration as a serializable format.
+    ///
+    /// # Returns
+    ///
+    /// A `serde_json::Value` containing the regularizer's configuration.
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+}
+
+/// A regularizer that applies an L1 penalty: `l1 * sum(|x|)`.
+#[derive(Debug)]
+pub struct L1 {
+    l1: f32,
+}
+
+impl L1 {
+    /// Creates a new `L1` regularizer.
+    ///
+    /// # Arguments
+    ///
+    /// * `l1` - The L1 regularization factor.
+    pub fn new(l1: f32) -> Self {
+        Self { l1 }
+    }
+}
+
+impl Regularizer for L1 {
+    fn penalty(&self, tensor: &Tensor) -> f32 {
+        self.l1 * tensor.data.iter().map(|x| x.abs()).sum::<f32>()
+    }
+
+    fn gradient(&self, tensor: &Tensor) -> Tensor {
+        let l1 = self.l1;
+        tensor.map(|x| if x == 0.0 { 0.0 } else { l1 * x.signum() })
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({ "l1": self.l1 })
+    }
+}
+
+/// A regularizer that applies an L2 penalty: `l2 * sum(x^2)`.
+#[derive(Debug)]
+pub struct L2 {
+    l2: f32,
+}
+
+impl L2 {
+    /// Creates a new `L2` regularizer.
+    ///
+    /// # Arguments
+    ///
+    /// * `l2` - The L2 regularization factor.
+    pub fn new(l2: f32) -> Self {
+        Self { l2 }
+    }
+}
+
+impl Regularizer for L2 {
+    fn penalty(&self, tensor: &Tensor) -> f32 {
+        self.l2 * tensor.data.iter().map(|x| x * x).sum::<f32>()
+    }
+
+    fn gradient(&self, tensor: &Tensor) -> Tensor {
+        tensor.mul_scalar(2.0 * self.l2)
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({ "l2": self.l2 })
+    }
+}
+
+/// A regularizer that applies both L1 and L2 penalties.
+#[derive(Debug)]
+pub struct L1L2 {
+    l1: L1,
+    l2: L2,
+}
+
+impl L1L2 {
+    /// Creates a new `L1L2` regularizer.
+    ///
+    /// # Arguments
+    ///
+    /// * `l1` - The L1 regularization factor.
+    /// * `l2` - The L2 regularization factor.
+    pub fn new(l1: f32, l2: f32) -> Self {
+        Self { l1: L1::new(l1), l2: L2::new(l2) }
+    }
+}
+
+impl Regularizer for L1L2 {
+    fn penalty(&self, tensor: &Tensor) -> f32 {
+        self.l1.penalty(tensor) + self.l2.penalty(tensor)
+    }
+
+    fn gradient(&self, tensor: &Tensor) -> Tensor {
+        self.l1.gradient(tensor).add(&self.l2.gradient(tensor))
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({ "l1": self.l1.l1, "l2": self.l2.l2 })
+    }
+}
+
+/// A regularizer that encourages the columns of a 2D weight matrix to be orthonormal.
+///
+/// The penalty is `factor * ||W^T W - I||_F^2`, where `W` has shape `[input_units, units]`.
+#[derive(Debug)]
+pub struct OrthogonalRegularizer {
+    factor: f32,
+}
+
+impl OrthogonalRegularizer {
+    /// Creates a new `OrthogonalRegularizer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - The regularization factor.
+    pub fn new(factor: f32) -> Self {
+        Self { factor }
+    }
+
+    /// Returns the weights as a matrix together with `W^T W - I`.
+    fn residual(tensor: &Tensor) -> (Array2<f32>, Array2<f32>) {
+        let matrix = tensor
+            .data
+            .clone()
+            .into_dimensionality::<Ix2>()
+            .expect("OrthogonalRegularizer requires a 2D tensor");
+        let cols = matrix.ncols();
+        let residual = matrix.t().dot(&matrix) - Array2::<f32>::eye(cols);
+        (matrix, residual)
+    }
+}
+
+impl Regularizer for OrthogonalRegularizer {
+    fn penalty(&self, tensor: &Tensor) -> f32 {
+        let (_, residual) = Self::residual(tensor);
+        self.factor * residual.iter().map(|x| x * x).sum::<f32>()
+    }
+
+    fn gradient(&self, tensor: &Tensor) -> Tensor {
+        let (matrix, residual) = Self::residual(tensor);
+        let gradient = matrix.dot(&residual) * (4.0 * self.factor);
+        Tensor { data: gradient.into_dyn(), device: tensor.device.clone() }
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({ "factor": self.factor })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{IxDyn, Shape};
+
+    use super::*;
+
+    /// Checks the analytic gradient of a regularizer against central finite differences.
+    fn assert_gradient_matches(regularizer: &dyn Regularizer, tensor: &Tensor) {
+        let gradient = regularizer.gradient(tensor);
+        let epsilon = 1e-3;
+
+        for i in 0..tensor.data.len() {
+            let mut plus = tensor.clone();
+            let mut minus = tensor.clone();
+            plus.data.as_slice_mut().unwrap()[i] += epsilon;
+            minus.data.as_slice_mut().unwrap()[i] -= epsilon;
+            let numeric =
+                (regularizer.penalty(&plus) - regularizer.penalty(&minus)) / (2.0 * epsilon);
+            let analytic = gradient.data.as_slice().unwrap()[i];
+            assert!(
+                (numeric - analytic).abs() < 1e-2,
+                "{}: numeric = {}, analytic = {}",
+                regularizer.name(),
+                numeric,
+                analytic
+            );
+        }
+    }
+
+    fn weights() -> Tensor {
+        Tensor::new(vec![0.5, -1.0, 0.25, 2.0, -0.75, 1.5], Shape::from(IxDyn(&[3, 2])))
+    }
+
+    #[test]
+    fn test_l1() {
+        let regularizer = L1::new(0.1);
+        assert!((regularizer.penalty(&weights()) - 0.6).abs() < 1e-6);
+        assert_gradient_matches(&regularizer, &weights());
+    }
+
+    #[test]
+    fn test_l2() {
+        let regularizer = L2::new(0.1);
+        assert!((regularizer.penalty(&weights()) - 0.8125).abs() < 1e-6);
+        assert_gradient_matches(&regularizer, &weights());
+    }
+
+    #[test]
+    fn test_l1l2() {
+        let regularizer = L1L2::new(0.1, 0.1);
+        assert!((regularizer.penalty(&weights()) - 1.4125).abs() < 1e-6);
+        assert_gradient_matches(&regularizer, &weights());
+    }
+
+    #[test]
+    fn test_orthogonal() {
+        let regularizer = OrthogonalRegularizer::new(0.1);
+        let identity = Tensor::new(vec![1.0, 0.0, 0.0, 1.0, 0.0, 0.0], Shape::from(IxDyn(&[3, 2])));
+        assert!(regularizer.penalty(&identity).abs() < 1e-6);
+        assert_gradient_matches(&regularizer, &weights());
+    }
+}
@@ -0,0 +1,146 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, BlackPortal ○
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this
+//    list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its
+//    contributors may be used to endorse or promote products derived from
+//    this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::sync::{Mutex, OnceLock};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// The global random number generator and the seed it was created from, if any.
+struct GlobalRng {
+    seed: Option<u64>,
+    rng: StdRng,
+}
+
+/// Returns the lazily initialized global random number generator.
+///
+/// Until `set_seed` is called, the generator is seeded from system entropy.
+fn global() -> &'static Mutex<GlobalRng> {
+    static GLOBAL_RNG: OnceLock<Mutex<GlobalRng>> = OnceLock::new();
+    GLOBAL_RNG.get_or_init(|| Mutex::new(GlobalRng { seed: None, rng: StdRng::from_entropy() }))
+}
+
+/// Sets the global seed used by every random operation in delta.
+///
+/// This reseeds the global generator used by `Tensor::random`, `Tensor::random_normal`,
+/// `Tensor::add_noise`, dataset shuffling and layer weight initialization. Running the same
+/// program with the same seed on the same machine produces bit-identical results.
+///
+/// # Arguments
+///
+/// * `seed` - The seed for the global random number generator.
+pub fn set_seed(seed: u64) {
+    let mut global = global().lock().expect("Global RNG lock poisoned");
+    global.seed = Some(seed);
+    global.rng = StdRng::seed_from_u64(seed);
+}
+
+/// Returns the global seed, if one was set with `set_seed`.
+///
+/// # Returns
+///
+/// The global seed, or `None` if the global generator is seeded from system entropy.
+pub fn get_seed() -> Option<u64> {
+    global().lock().expect("Global RNG lock poisoned").seed
+}
+
+/// Runs a closure with exclusive access to the global random number generator.
+///
+/// # Arguments
+///
+/// * `f` - The closure to run with the global generator.
+///
+/// # Returns
+///
+/// The value returned by the closure.
+pub fn with_global_rng<T, F>(f: F) -> T
+where
+    F: FnOnce(&mut StdRng) -> T,
+{
+    let mut global = global().lock().expect("Global RNG lock poisoned");
+    f(&mut global.rng)
+}
+
+/// Creates a new generator whose seed is drawn from the global generator.
+///
+/// Use this to give a component its own independent stream that is still reproducible
+/// when a global seed is set.
+///
+/// # Returns
+///
+/// A new `StdRng` seeded from the global generator.
+pub fn fork_global_rng() -> StdRng {
+    with_global_rng(|rng| StdRng::seed_from_u64(rng.gen()))
+}
+
+/// Creates a new generator from an explicit seed.
+///
+/// # Arguments
+///
+/// * `seed` - The seed for the generator.
+///
+/// # Returns
+///
+/// A new `StdRng` seeded with `seed`.
+pub fn rng_from_seed(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+
+    use super::*;
+
+    #[test]
+    #[serial]
+    fn test_set_seed() {
+        set_seed(42);
+        assert_eq!(get_seed(), Some(42));
+
+        set_seed(7);
+        assert_eq!(get_seed(), Some(7));
+    }
+
+    #[test]
+    fn test_rng_from_seed() {
+        let a: Vec<u32> = {
+            let mut rng = rng_from_seed(1);
+            (0..8).map(|_| rng.gen()).collect()
+        };
+        let b: Vec<u32> = {
+            let mut rng = rng_from_seed(1);
+            (0..8).map(|_| rng.gen()).collect()
+        };
+        let c: u32 = rng_from_seed(2).gen();
+
+        assert_eq!(a, b);
+        assert_ne!(a[0], c);
+    }
+}
@@ -32,10 +32,11 @@
 
 use image::{GenericImageView, ImageReader};
 use ndarray::{Array, ArrayD, Axis, Dimension, Ix2, IxDyn, Shape};
-use rand::{Rng, thread_rng};
+use rand::Rng;
 use rand_distr::{Distribution, Normal};
 use rayon::prelude::*;
 
+use super::rng::with_global_rng;
 use crate::devices::Device;
 #[cfg(all(target_os = "macos", feature = "metal"))]
 use crate::devices::osx_metal::{
@@ -95,7 +96,7 @@ pub fn ones(shape: Shape<IxDyn>, device: Device) -> Self {
         Self { data: Array::ones(shape), device }
     }
 
-    /// Creates a tensor filled with random values.
+    /// Creates a tensor filled with random values drawn from the global generator.
     ///
     /// # Arguments
     ///
@@ -105,7 +106,20 @@ pub fn ones(shape: Shape<IxDyn>, device: Device) -> Self {
     ///
     /// A tensor filled with random values.
     pub fn random(shape: Shape<IxDyn>) -> Self {
-        let mut rng = thread_rng();
+        with_global_rng(|rng| Self::random_with_rng(shape, rng))
+    }
+
+    /// Creates a tensor filled with random values drawn from the given generator.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - A vector representing the shape of the tensor.
+    /// * `rng` - The random number generator to sample from.
+    ///
+    /// # Returns
+    ///
+    /// A tensor filled with random values.
+    pub fn random_with_rng<R: Rng + ?Sized>(shape: Shape<IxDyn>, rng: &mut R) -> Self {
         let data: Vec<f32> = (0..shape.size()).map(|_| rng.gen::<f32>()).collect(); // Use size() method
         Self {
             data: Array::from_shape_vec(shape, data).expect("Invalid shape for random dataset"),
@@ -441,6 +455,30 @@ pub fn div(&self, other: &Tensor) -> Tensor {
         }
     }
 
+    /// Multiplies the tensor element-wise by another tensor.
+    ///
+    /// Unlike the `*` operator, which performs a dot product, this multiplies matching elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The tensor to multiply by.
+    ///
+    /// # Returns
+    ///
+    /// A new tensor containing the element-wise product.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shapes are incompatible and cannot be broadcast.
+    pub fn multiply(&self, other: &Tensor) -> Tensor {
+        let broadcasted_other = other
+            .data
+            .broadcast(self.data.raw_dim())
+            .expect("Shapes are incompatible for broadcasting");
+
+        Tensor { data: &self.data * &broadcasted_other, device: self.device.clone() }
+    }
+
     /// Applies a threshold to each element in the tensor.
     ///
     /// # Arguments
@@ -564,13 +602,22 @@ pub fn normalize(&self, min: f32, max: f32) -> Tensor {
         Tensor { data: normalized_data, device: self.device.clone() }
     }
 
-    /// Adds noise to the tensor.
+    /// Adds noise drawn from the global generator to the tensor.
     ///
     /// # Arguments
     ///
     /// * `noise_level` - The level of noise to add.
     pub fn add_noise(&mut self, noise_level: f32) {
-        let mut rng = thread_rng();
+        with_global_rng(|rng| self.add_noise_with_rng(noise_level, rng))
+    }
+
+    /// Adds noise drawn from the given generator to the tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise_level` - The level of noise to add.
+    /// * `rng` - The random number generator to sample from.
+    pub fn add_noise_with_rng<R: Rng + ?Sized>(&mut self, noise_level: f32, rng: &mut R) {
         self.data.mapv_inplace(|value| {
             let noise: f32 = rng.gen_range(-noise_level..noise_level);
             value + noise
@@ -774,6 +821,8 @@ pub fn split_at(&self, index: usize) -> (Tensor, Tensor) {
 
     /// Creates a tensor filled with random values sampled from a normal distribution.
     ///
+    /// The values are drawn from the global generator.
+    ///
     /// # Arguments
     ///
     /// * `shape` - The shape of the tensor.
@@ -784,11 +833,32 @@ pub fn split_at(&self, index: usize) -> (Tensor, Tensor) {
     ///
     /// A new tensor filled with random values sampled from the normal distribution.
     pub fn random_normal(shape: Shape<IxDyn>, mean: f32, stddev: f32) -> Self {
+        with_global_rng(|rng| Self::random_normal_with_rng(shape, mean, stddev, rng))
+    }
+
+    /// Creates a tensor filled with values sampled from a normal distribution using the given
+    /// generator.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - The shape of the tensor.
+    /// * `mean` - The mean of the normal distribution.
+    /// * `stddev` - The standard deviation of the normal distribution.
+    /// * `rng` - The random number generator to sample from.
+    ///
+    /// # Returns
+    ///
+    /// A new tensor filled with random values sampled from the normal distribution.
+    pub fn random_normal_with_rng<R: Rng + ?Sized>(
+        shape: Shape<IxDyn>,
+        mean: f32,
+        stddev: f32,
+        rng: &mut R,
+    ) -> Self {
         let normal = Normal::new(mean, stddev).expect("Failed to create normal distribution");
-        let mut rng = thread_rng();
 
         // Generate random values from the normal distribution
-        let data: Vec<f32> = (0..shape.size()).map(|_| normal.sample(&mut rng)).collect();
+        let data: Vec<f32> = (0..shape.size()).map(|_| normal.sample(rng)).collect();
 
         // Create a tensor from the generated data
         Tensor {
@@ -921,6 +991,7 @@ mod tests {
     use ndarray::IxDyn;
 
     use super::*;
+    use crate::deep_learning::rng::rng_from_seed;
 
     #[test]
     fn test_new() {
@@ -1081,6 +1152,14 @@ fn test_div() {
         assert_eq!(divided.data.shape(), &[2, 2]);
     }
 
+    #[test]
+    fn test_multiply() {
+        let tensor1 = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], Shape::from(IxDyn(&[2, 2])));
+        let tensor2 = Tensor::new(vec![2.0, 3.0], Shape::from(IxDyn(&[2])));
+        let product = tensor1.multiply(&tensor2);
+        assert_eq!(product.to_vec(), vec![2.0, 6.0, 6.0, 12.0]);
+    }
+
     #[test]
     fn test_flatten() {
         let data = vec![1.0, 2.0, 3.0, 4.0];
@@ -1171,6 +1250,33 @@ fn test_random_normal() {
         assert_eq!(tensor.data.shape(), &[2, 2]);
     }
 
+    #[test]
+    fn test_random_with_rng_is_reproducible() {
+        let shape = Shape::from(IxDyn(&[3, 4]));
+        let a = Tensor::random_with_rng(shape.clone(), &mut rng_from_seed(42));
+        let b = Tensor::random_with_rng(shape.clone(), &mut rng_from_seed(42));
+        let c = Tensor::random_with_rng(shape, &mut rng_from_seed(43));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_random_normal_with_rng_is_reproducible() {
+        let shape = Shape::from(IxDyn(&[3, 4]));
+        let a = Tensor::random_normal_with_rng(shape.clone(), 0.0, 1.0, &mut rng_from_seed(42));
+        let b = Tensor::random_normal_with_rng(shape, 0.0, 1.0, &mut rng_from_seed(42));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_add_noise_with_rng_is_reproducible() {
+        let mut a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], Shape::from(IxDyn(&[2, 2])));
+        let mut b = a.clone();
+        a.add_noise_with_rng(0.1, &mut rng_from_seed(42));
+        b.add_noise_with_rng(0.1, &mut rng_from_seed(42));
+        assert_eq!(a, b);
+    }
+
     #[test]
     fn test_sub() {
         let tensor1 = Tensor::new(vec![1.0, 2.0, 3.0], Shape::from(IxDyn(&[3])));
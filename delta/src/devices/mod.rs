@@ -58,9 +58,10 @@
 ///
 /// These variants are currently commented out in the codebase and will be introduced as the
 /// framework evolves to support additional hardware platforms.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub enum Device {
     /// The central processing unit (CPU).
+    #[default]
     Cpu,
 
     /// A Metal-based device for macOS.
@@ -85,12 +86,6 @@ pub enum Device {
     // WebGpu,
 }
 
-impl Default for Device {
-    fn default() -> Self {
-        Self::Cpu
-    }
-}
-
 impl fmt::Display for Device {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {